@@ -32,17 +32,14 @@ async fn main() -> Result<(), Loc<Error, ()>> {
 	//
 	// We won't be loading any external document here,
 	// so we use the `NoLoader` type.
-	let mut loader = NoLoader::<Value>::new();
+	let loader = NoLoader::<Value>::new();
 
 	let processed_context = context
-		.process::<context::Json<Value>, _>(&mut loader, None)
+		.process::<context::Json<Value>, _>(&loader, None)
 		.await?;
 
 	// Compaction.
-	let output: Value = input
-		.compact(&processed_context, &mut loader)
-		.await
-		.unwrap();
+	let output: Value = input.compact(&processed_context, &loader).await.unwrap();
 
 	println!("{}", serde_json::to_string_pretty(&output).unwrap());
 
@@ -25,11 +25,11 @@ async fn main() {
 	.unwrap();
 
 	// JSON document loader.
-	let mut loader = NoLoader::<Value>::new();
+	let loader = NoLoader::<Value>::new();
 
 	// Expansion.
 	let expanded_doc = doc
-		.expand::<context::Json<Value>, _>(&mut loader)
+		.expand::<context::Json<Value>, _>(&loader)
 		.await
 		.unwrap();
 
@@ -72,7 +72,7 @@ async fn main() {
 		.expect("unable to load the test suite");
 
 	let expanded_doc = doc
-		.expand::<context::Json<Value, Id>, _>(&mut loader)
+		.expand::<context::Json<Value, Id>, _>(&loader)
 		.await
 		.expect("expansion failed");
 
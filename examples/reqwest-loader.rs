@@ -34,7 +34,7 @@ type Id = Lexicon<Vocab>;
 
 #[tokio::main]
 async fn main() {
-	let mut loader = Loader::<Value>::new(|s| serde_json::from_str(s));
+	let loader = Loader::<Value>::new(|s| serde_json::from_str(s));
 
 	// The JSON-LD document to expand.
 	let doc = loader
@@ -46,7 +46,7 @@ async fn main() {
 
 	// Expansion.
 	let expanded_doc = doc
-		.expand::<context::Json<Value, Id>, _>(&mut loader)
+		.expand::<context::Json<Value, Id>, _>(&loader)
 		.await
 		.unwrap();
 
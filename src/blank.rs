@@ -1,5 +1,6 @@
-use crate::util;
+use crate::{util, Id, Reference};
 use generic_json::JsonBuild;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 
@@ -75,3 +76,108 @@ impl fmt::Display for BlankId {
 		self.0.fmt(f)
 	}
 }
+
+/// Blank node identifier generator.
+///
+/// Used to relabel blank nodes (see [`ExpandedDocument::relabel_blank_nodes`](crate::ExpandedDocument::relabel_blank_nodes)).
+/// Every call to [`BlankIdGenerator::next_blank_id`] must return a fresh identifier.
+///
+/// This trait is implemented by the [`flattening::Generator`](crate::flattening::Generator)
+/// producing sequential identifiers,
+/// by the [`Uuid`] generator (with the `uuid-generator` feature)
+/// and by the [`Prefixed`] generator wrapper.
+pub trait BlankIdGenerator {
+	/// Returns a fresh blank node identifier.
+	fn next_blank_id(&mut self) -> BlankId;
+}
+
+impl<G: BlankIdGenerator> BlankIdGenerator for &mut G {
+	#[inline(always)]
+	fn next_blank_id(&mut self) -> BlankId {
+		G::next_blank_id(self)
+	}
+}
+
+/// Generator wrapper prefixing every identifier generated by the inner generator.
+///
+/// For instance, wrapping a generator producing `_:b0`, `_:b1`, etc.
+/// with the prefix `doc-` produces `_:doc-b0`, `_:doc-b1`, etc.
+pub struct Prefixed<G> {
+	prefix: String,
+	generator: G,
+}
+
+impl<G> Prefixed<G> {
+	/// Creates a new prefixed generator.
+	#[inline(always)]
+	pub fn new(prefix: &str, generator: G) -> Self {
+		Self {
+			prefix: prefix.to_string(),
+			generator,
+		}
+	}
+
+	/// Returns the inner generator.
+	#[inline(always)]
+	pub fn into_inner(self) -> G {
+		self.generator
+	}
+}
+
+impl<G: BlankIdGenerator> BlankIdGenerator for Prefixed<G> {
+	#[inline(always)]
+	fn next_blank_id(&mut self) -> BlankId {
+		BlankId::new(&(self.prefix.clone() + self.generator.next_blank_id().name()))
+	}
+}
+
+/// Random UUID (version 4) blank node identifier generator.
+///
+/// Identifiers are of the form `_:{uuid}`, where `uuid` is hyphenated.
+#[cfg(feature = "uuid-generator")]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Uuid;
+
+#[cfg(feature = "uuid-generator")]
+impl BlankIdGenerator for Uuid {
+	#[inline(always)]
+	fn next_blank_id(&mut self) -> BlankId {
+		BlankId::new(&uuid::Uuid::new_v4().to_hyphenated().to_string())
+	}
+}
+
+/// Blank node relabeling.
+///
+/// Associates a fresh identifier, produced by the inner generator,
+/// to each relabeled blank node identifier,
+/// so that each occurrence of the same blank node is relabeled the same way.
+pub(crate) struct Relabeling<G> {
+	generator: G,
+	map: HashMap<BlankId, BlankId>,
+}
+
+impl<G: BlankIdGenerator> Relabeling<G> {
+	#[inline(always)]
+	pub fn new(generator: G) -> Self {
+		Self {
+			generator,
+			map: HashMap::new(),
+		}
+	}
+
+	/// Relabel the given reference if it is a blank node identifier.
+	pub fn relabel<T: Id>(&mut self, r: Reference<T>) -> Reference<T> {
+		match r {
+			Reference::Blank(id) => {
+				let generator = &mut self.generator;
+				Reference::Blank(
+					self.map
+						.entry(id)
+						.or_insert_with(|| generator.next_blank_id())
+						.clone(),
+				)
+			}
+			r => r,
+		}
+	}
+}
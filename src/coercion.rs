@@ -0,0 +1,112 @@
+use crate::{
+	object::{Literal, LiteralString, Value},
+	ErrorCode, Id,
+};
+use generic_json::Json;
+
+/// Result of a literal coercion hook.
+///
+/// See [`LiteralCoercions`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Coercion {
+	/// Keep the lexical form as is.
+	Keep,
+
+	/// Replace the lexical form with the given one.
+	Replace(String),
+
+	/// The lexical form is not valid for the datatype.
+	///
+	/// During expansion, an [`ErrorCode::InvalidTypedValue`] error is raised.
+	/// During RDF serialization, the literal is kept and a
+	/// [`Warning::InvalidLiteral`](crate::Warning::InvalidLiteral) warning is emitted.
+	Invalid,
+}
+
+/// Literal coercion hook, called on the lexical form of a literal.
+pub type CoercionHook = fn(&str) -> Coercion;
+
+/// Registry of per-datatype literal coercion hooks.
+///
+/// A hook is called on the lexical form of every string literal
+/// typed with its datatype,
+/// during expansion (see [`expansion::Options::coercions`](crate::expansion::Options::coercions))
+/// and RDF serialization (see [`rdf::Options::coercions`](crate::rdf::Options::coercions)).
+/// It can be used to normalize or validate domain specific literals
+/// such as `xsd:dateTime` or `xsd:anyURI` values.
+///
+/// The registry is built from `'static` data so that it can be
+/// shared by copy with the processing options.
+///
+/// # Example
+/// ```
+/// # use json_ld::{Coercion, LiteralCoercions};
+/// fn boolean(value: &str) -> Coercion {
+///   match value {
+///     "true" | "false" => Coercion::Keep,
+///     "1" => Coercion::Replace("true".to_string()),
+///     "0" => Coercion::Replace("false".to_string()),
+///     _ => Coercion::Invalid,
+///   }
+/// }
+///
+/// const COERCIONS: LiteralCoercions =
+///   LiteralCoercions::new(&[("http://www.w3.org/2001/XMLSchema#boolean", boolean)]);
+///
+/// let ty = "http://www.w3.org/2001/XMLSchema#boolean";
+/// assert_eq!(COERCIONS.coerce(ty, "true"), Coercion::Keep);
+/// assert_eq!(COERCIONS.coerce(ty, "1"), Coercion::Replace("true".to_string()));
+/// assert_eq!(COERCIONS.coerce(ty, "yes"), Coercion::Invalid);
+/// assert_eq!(COERCIONS.coerce("https://example.org/Type", "yes"), Coercion::Keep);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct LiteralCoercions {
+	hooks: &'static [(&'static str, CoercionHook)],
+}
+
+impl LiteralCoercions {
+	/// Creates a new registry from the given datatype IRIs and associated hooks.
+	#[inline(always)]
+	pub const fn new(hooks: &'static [(&'static str, CoercionHook)]) -> Self {
+		Self { hooks }
+	}
+
+	/// Checks if no hook is registered.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.hooks.is_empty()
+	}
+
+	/// Coerces the given lexical form of a literal typed with the given datatype IRI.
+	///
+	/// Returns [`Coercion::Keep`] if no hook is registered for the datatype.
+	pub fn coerce(&self, datatype: &str, value: &str) -> Coercion {
+		match self.hooks.iter().find(|(ty, _)| *ty == datatype) {
+			Some((_, hook)) => hook(value),
+			None => Coercion::Keep,
+		}
+	}
+
+	/// Coerces the given expanded value, if it is a typed string literal.
+	pub(crate) fn coerce_value<J: Json, T: Id>(
+		&self,
+		value: &mut Value<J, T>,
+	) -> Result<(), ErrorCode> {
+		if let Value::Literal(Literal::String(s), Some(ty)) = value {
+			match self.coerce(ty.as_iri().as_str(), s.as_str()) {
+				Coercion::Keep => (),
+				Coercion::Replace(new_value) => *s = LiteralString::Inferred(new_value),
+				Coercion::Invalid => return Err(ErrorCode::InvalidTypedValue),
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl Default for LiteralCoercions {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new(&[])
+	}
+}
@@ -1,4 +1,4 @@
-use super::{Options, TypeLangValue};
+use super::{Options, TermPreference, TypeLangValue};
 use crate::{
 	context::inverse::{Inversible, LangSelection, Selection, TypeSelection},
 	object,
@@ -6,10 +6,54 @@ use crate::{
 	Context, Error, ErrorCode, Id, Indexed, Nullable, Object, ProcessingMode, Value,
 };
 use generic_json::{JsonClone, JsonHash};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Key of the compacted IRIs memoized during a compaction run.
+///
+/// Includes the options affecting the compaction of an IRI without value.
+#[derive(PartialEq, Eq, Hash)]
+pub(crate) struct CompactIriKey<T: Id> {
+	var: Term<T>,
+	vocab: bool,
+	reverse: bool,
+	processing_mode: ProcessingMode,
+
+	/// Address of the term preference function.
+	///
+	/// Functions sharing the same address have the same code,
+	/// and the same function having different addresses only causes a cache miss.
+	term_preference: Option<usize>,
+}
+
+/// Compacted form of the IRIs compacted without value against a context.
+///
+/// A new memo is created for each compaction run
+/// (see [`ContextMutProxy::inversible`](crate::ContextMutProxy::inversible)),
+/// and dropped with it.
+pub(crate) struct CompactIriMemo<T: Id>(Mutex<HashMap<CompactIriKey<T>, Option<String>>>);
+
+impl<T: Id> CompactIriMemo<T> {
+	pub fn new() -> Self {
+		Self(Mutex::new(HashMap::new()))
+	}
+
+	/// Returns the memoized compacted form of an IRI, if any.
+	fn get(&self, key: &CompactIriKey<T>) -> Option<Option<String>> {
+		self.0.lock().unwrap().get(key).cloned()
+	}
+
+	/// Memoizes the compacted form of an IRI.
+	fn insert(&self, key: CompactIriKey<T>, compacted: Option<String>) {
+		self.0.lock().unwrap().insert(key, compacted);
+	}
+}
 
 /// Compact the given term without considering any value.
 ///
 /// Calls [`compact_iri_full`] with `None` for `value`.
+/// The result is memoized in the compaction run memo of `active_context`.
 pub(crate) fn compact_iri<'a, J: JsonHash, T: 'a + Id, C: Context<T>>(
 	active_context: Inversible<T, &C>,
 	var: &Term<T>,
@@ -17,7 +61,32 @@ pub(crate) fn compact_iri<'a, J: JsonHash, T: 'a + Id, C: Context<T>>(
 	reverse: bool,
 	options: Options,
 ) -> Result<Option<String>, Error> {
-	compact_iri_full::<J, T, C, Object<J, T>>(active_context, var, None, vocab, reverse, options)
+	if var.is_null() {
+		return Ok(None);
+	}
+
+	let key = CompactIriKey {
+		var: var.clone(),
+		vocab,
+		reverse,
+		processing_mode: options.processing_mode,
+		term_preference: options.term_preference.map(|f| f as usize),
+	};
+
+	if let Some(compacted) = active_context.memo().get(&key) {
+		return Ok(compacted);
+	}
+
+	let compacted = compact_iri_full::<J, T, C, Object<J, T>>(
+		active_context.clone(),
+		var,
+		None,
+		vocab,
+		reverse,
+		options,
+	)?;
+	active_context.memo().insert(key, compacted.clone());
+	Ok(compacted)
 }
 
 /// Compact the given term considering the given value object.
@@ -109,7 +178,7 @@ pub(crate) fn compact_iri_full<'a, J: JsonHash, T: 'a + Id, C: Context<T>, N: ob
 											Value::Literal(_, Some(ty)) => {
 												item_type = Some(Type::Ref(ty.clone()))
 											}
-											Value::Literal(_, None) => {
+											Value::Literal(_, None) | Value::Invalid(_) => {
 												item_lang_dir = Some(Nullable::Null)
 											}
 											Value::Json(_) => item_type = Some(Type::Json),
@@ -277,7 +346,7 @@ pub(crate) fn compact_iri_full<'a, J: JsonHash, T: 'a + Id, C: Context<T>, N: ob
 										&id.clone().into_term(),
 										true,
 										false,
-										options,
+										options.clone(),
 									)?;
 									if let Some(def) =
 										active_context.get(compacted_iri.as_ref().unwrap())
@@ -333,7 +402,10 @@ pub(crate) fn compact_iri_full<'a, J: JsonHash, T: 'a + Id, C: Context<T>, N: ob
 			};
 
 			if let Some(term) = entry.select(&containers, &selection) {
-				return Ok(Some(term.into()));
+				return Ok(Some(match options.term_preference {
+					Some(preference) => preferred_term(*active_context, term, preference),
+					None => term.into(),
+				}));
 			}
 		}
 
@@ -379,7 +451,7 @@ pub(crate) fn compact_iri_full<'a, J: JsonHash, T: 'a + Id, C: Context<T>, N: ob
 						// and value is null, set compact IRI to candidate.
 						let candidate_def = active_context.get(&candidate);
 						if (compact_iri.is_empty()
-							|| (candidate.len() <= compact_iri.len() && candidate < compact_iri))
+							|| is_preferred(&candidate, &compact_iri, &options))
 							&& (candidate_def.is_none()
 								|| (candidate_def.is_some()
 									&& candidate_def
@@ -425,3 +497,45 @@ pub(crate) fn compact_iri_full<'a, J: JsonHash, T: 'a + Id, C: Context<T>, N: ob
 	// Finally, return var as is.
 	Ok(Some(var.as_str().into()))
 }
+
+/// Selects the preferred term among the terms equivalent to the given term.
+///
+/// Equivalent terms have the same IRI mapping, container,
+/// type, language and direction mappings, and no scoped context.
+fn preferred_term<T: Id, C: Context<T>>(
+	active_context: &C,
+	term: &str,
+	preference: TermPreference,
+) -> String {
+	let selected = match active_context.get(term) {
+		Some(selected) if selected.context.is_none() => selected,
+		_ => return term.into(),
+	};
+
+	let mut result = term;
+	for (key, definition) in active_context.definitions() {
+		if definition.value == selected.value
+			&& definition.reverse_property == selected.reverse_property
+			&& definition.container == selected.container
+			&& definition.typ == selected.typ
+			&& definition.language == selected.language
+			&& definition.direction == selected.direction
+			&& definition.index == selected.index
+			&& definition.nest == selected.nest
+			&& definition.context.is_none()
+			&& preference(key, result) == Ordering::Less
+		{
+			result = key
+		}
+	}
+
+	result.into()
+}
+
+/// Checks if the given compact IRI candidate is preferred over the current compact IRI.
+fn is_preferred(candidate: &str, compact_iri: &str, options: &Options) -> bool {
+	match options.term_preference {
+		Some(preference) => preference(candidate, compact_iri) == Ordering::Less,
+		None => candidate.len() <= compact_iri.len() && candidate < compact_iri,
+	}
+}
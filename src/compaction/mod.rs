@@ -1,4 +1,5 @@
 //! Compaction algorithm and related types.
+use crate::future::{BoxFuture, FutureExt, JsonMaybeSendSync, MaybeSend, MaybeSync};
 use crate::{
 	context::{
 		self,
@@ -8,14 +9,16 @@ use crate::{
 	object,
 	syntax::{ContainerType, Keyword, Term},
 	util::{AsAnyJson, AsJson, JsonFrom},
-	ContextMut, Error, Id, Indexed, Loc, Object, ProcessingMode, Value,
+	ContextMut, Error, ExtensionKeywords, Id, Indexed, Loc, Node, Object, ProcessingMode, Value,
 };
-use futures::future::{BoxFuture, FutureExt};
-use generic_json::{JsonBuild, JsonClone, JsonHash, JsonMut, JsonSendSync};
+use generic_json::{JsonBuild, JsonClone, JsonHash, JsonMut};
+use indexmap::IndexSet;
+use iref::IriBuf;
+use std::cmp::Ordering;
 use std::collections::HashSet;
 
 /// JSON type that can be used by the compaction algorithm.
-pub trait JsonSrc = JsonClone + JsonHash + JsonSendSync;
+pub trait JsonSrc = JsonClone + JsonHash + JsonMaybeSendSync;
 
 mod iri;
 mod node;
@@ -34,8 +37,38 @@ fn optional_string<K: JsonBuild>(s: Option<String>, meta: K::MetaData) -> K {
 	}
 }
 
+/// Term selection strategy.
+///
+/// See [`Options::term_preference`].
+pub type TermPreference = fn(&str, &str) -> Ordering;
+
+/// `@context` entry of a compacted document.
+///
+/// See [`Options::context_entry`].
+#[derive(Clone, Debug)]
+pub enum ContextEntry {
+	/// The context used for compaction is embedded in the document.
+	Embedded,
+
+	/// The `@context` entry is the given context IRI.
+	Iri(IriBuf),
+
+	/// The document has no `@context` entry.
+	///
+	/// This is useful when the context is transmitted separately,
+	/// for instance in an HTTP `Link` header.
+	Omitted,
+}
+
+impl Default for ContextEntry {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::Embedded
+	}
+}
+
 /// Compaction options.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct Options {
 	/// JSON-LD processing mode.
 	pub processing_mode: ProcessingMode,
@@ -45,11 +78,224 @@ pub struct Options {
 
 	/// If set to `true`, arrays with just one element are replaced with that element during compaction.
 	/// If set to `false`, all arrays will remain arrays even if they have just one element.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{compaction, context, ContextBuilder, Document, NoLoader};
+	/// # use iref::Iri;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .build();
+	///
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "https://schema.org/name": "Alice"
+	/// });
+	///
+	/// let options = compaction::Options { compact_arrays: false, ..Default::default() };
+	/// let compacted: Value = doc
+	///   .compact_with(None, &context, &NoLoader::<Value>::new(), options, |_| (), |_| ())
+	///   .await
+	///   .unwrap();
+	///
+	/// assert_eq!(compacted["@graph"], json!([{
+	///   "@id": "https://example.org/alice",
+	///   "name": [ "Alice" ]
+	/// }]));
+	/// # }
+	/// ```
 	pub compact_arrays: bool,
 
+	/// If set to `true`, a single top-level node object is emitted as is.
+	/// If set to `false`, the top-level node objects are always wrapped in a `@graph` entry,
+	/// even if there is only one.
+	///
+	/// This is the `omitGraph` flag of the JSON-LD API.
+	/// It applies to compaction and flattening.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{compaction, context, ContextBuilder, Document, NoLoader};
+	/// # use iref::Iri;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .build();
+	///
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "https://schema.org/name": "Alice"
+	/// });
+	///
+	/// let options = compaction::Options { omit_graph: false, ..Default::default() };
+	/// let compacted: Value = doc
+	///   .compact_with(None, &context, &NoLoader::<Value>::new(), options, |_| (), |_| ())
+	///   .await
+	///   .unwrap();
+	///
+	/// assert_eq!(compacted["@graph"], json!([{
+	///   "@id": "https://example.org/alice",
+	///   "name": "Alice"
+	/// }]));
+	/// # }
+	/// ```
+	pub omit_graph: bool,
+
+	/// `@context` entry of the compacted document.
+	///
+	/// Applies to compaction and flattening.
+	/// By default, the context used for compaction is embedded.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{compaction, context, ContextBuilder, Document, NoLoader};
+	/// # use iref::{Iri, IriBuf};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .build();
+	///
+	/// let doc = json!({ "https://schema.org/name": "Alice" });
+	/// let loader = NoLoader::<Value>::new();
+	///
+	/// let options = compaction::Options {
+	///   context_entry: compaction::ContextEntry::Iri(IriBuf::new("https://example.org/context.jsonld").unwrap()),
+	///   ..Default::default()
+	/// };
+	/// let compacted: Value = doc
+	///   .compact_with(None, &context, &loader, options, |_| (), |_| ())
+	///   .await
+	///   .unwrap();
+	/// assert_eq!(compacted, json!({
+	///   "@context": "https://example.org/context.jsonld",
+	///   "name": "Alice"
+	/// }));
+	///
+	/// let options = compaction::Options {
+	///   context_entry: compaction::ContextEntry::Omitted,
+	///   ..Default::default()
+	/// };
+	/// let compacted: Value = doc
+	///   .compact_with(None, &context, &loader, options, |_| (), |_| ())
+	///   .await
+	///   .unwrap();
+	/// assert_eq!(compacted, json!({ "name": "Alice" }));
+	/// # }
+	/// ```
+	pub context_entry: ContextEntry,
+
 	/// If set to `true`, properties are processed by lexical order.
 	/// If `false`, order is not considered in processing.
+	///
+	/// When compacting or flattening a [`Document`](crate::Document),
+	/// the entries of every object of the output are also sorted by key,
+	/// whatever the order preserved by the output JSON type,
+	/// so that the output is stable across runs.
 	pub ordered: bool,
+
+	/// Term selection strategy.
+	///
+	/// When several terms equally compact an IRI
+	/// (same IRI mapping, container, type, language and direction mappings,
+	/// and no scoped context),
+	/// or when several compact IRIs can be created from prefixes,
+	/// the preferred one according to this function is selected.
+	/// A term is preferred over another if the function returns [`Ordering::Less`].
+	///
+	/// If `None`, the standard selection applies:
+	/// the shortest term, then the lexicographically least term is preferred.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{compaction, context, ContextBuilder, Document, NoLoader};
+	/// # use iref::Iri;
+	/// # use serde_json::{json, Value};
+	/// # use std::cmp::Ordering;
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .term("fullName", Iri::new("https://schema.org/name").unwrap())
+	///   .term("s", Iri::new("https://schema.org/").unwrap())
+	///   .term("schema", Iri::new("https://schema.org/").unwrap())
+	///   .build();
+	///
+	/// let doc = json!({
+	///   "https://schema.org/name": "Alice",
+	///   "https://schema.org/email": "alice@example.org"
+	/// });
+	///
+	/// // Prefer longer terms.
+	/// fn longest(a: &str, b: &str) -> Ordering {
+	///   b.len().cmp(&a.len()).then_with(|| a.cmp(b))
+	/// }
+	///
+	/// let options = compaction::Options { term_preference: Some(longest), ..Default::default() };
+	/// let compacted: Value = doc
+	///   .compact_with(None, &context, &NoLoader::<Value>::new(), options, |_| (), |_| ())
+	///   .await
+	///   .unwrap();
+	///
+	/// assert_eq!(compacted["fullName"], "Alice");
+	/// assert_eq!(compacted["schema:email"], "alice@example.org");
+	/// # }
+	/// ```
+	pub term_preference: Option<TermPreference>,
+
+	/// Handling of entries whose key is a non-standard keyword.
+	///
+	/// Only the entries preserved by the expansion algorithm
+	/// (see [`ExtensionAction::Preserve`](crate::ExtensionAction::Preserve)) can be compacted.
+	/// They are written back unless their keyword is not preserved by this registry.
+	pub extensions: ExtensionKeywords,
+
+	/// Enables the experimental JSON-LD-star `@annotation` syntax
+	/// when the input document is expanded before compaction.
+	///
+	/// See [`expansion::Options::rdf_star`](crate::expansion::Options::rdf_star).
+	/// Annotations of already expanded objects are always compacted.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{compaction, context, ContextBuilder, Document, NoLoader};
+	/// # use iref::Iri;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("knows", Iri::new("https://example.org/knows").unwrap())
+	///   .term("certainty", Iri::new("https://example.org/certainty").unwrap())
+	///   .build();
+	///
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "https://example.org/knows": {
+	///     "@id": "https://example.org/bob",
+	///     "@annotation": { "https://example.org/certainty": 0.8 }
+	///   }
+	/// });
+	///
+	/// let options = compaction::Options { rdf_star: true, ..Default::default() };
+	/// let compacted: Value = doc
+	///   .compact_with(None, &context, &NoLoader::<Value>::new(), options, |_| (), |_| ())
+	///   .await
+	///   .unwrap();
+	///
+	/// assert_eq!(compacted["knows"], json!({
+	///   "@id": "https://example.org/bob",
+	///   "@annotation": { "certainty": 0.8 }
+	/// }));
+	/// # }
+	/// ```
+	#[cfg(feature = "rdf-star")]
+	pub rdf_star: bool,
 }
 
 impl From<Options> for context::ProcessingOptions {
@@ -66,6 +312,9 @@ impl From<crate::expansion::Options> for Options {
 		Options {
 			processing_mode: options.processing_mode,
 			ordered: options.ordered,
+			extensions: options.extensions,
+			#[cfg(feature = "rdf-star")]
+			rdf_star: options.rdf_star,
 			..Options::default()
 		}
 	}
@@ -77,7 +326,13 @@ impl Default for Options {
 			processing_mode: ProcessingMode::default(),
 			compact_to_relative: true,
 			compact_arrays: true,
+			omit_graph: true,
+			context_entry: ContextEntry::default(),
 			ordered: false,
+			term_preference: None,
+			extensions: ExtensionKeywords::default(),
+			#[cfg(feature = "rdf-star")]
+			rdf_star: false,
 		}
 	}
 }
@@ -94,17 +349,17 @@ pub trait Compact<J: JsonSrc, T: Id> {
 		active_context: Inversible<T, &'a C>,
 		type_scoped_context: Inversible<T, &'a C>,
 		active_property: Option<&'a str>,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: Options,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
 		J: 'a,
 		T: 'a,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData;
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData;
 
 	/// Compact a JSON-LD document into a `K` JSON value with the provided options.
 	///
@@ -114,17 +369,17 @@ pub trait Compact<J: JsonSrc, T: Id> {
 	fn compact_with<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
 		&'a self,
 		active_context: Inversible<T, &'a C>,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: Options,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
-		Self: Sync,
-		T: 'a + Sync + Send,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+		Self: MaybeSync,
+		T: 'a + MaybeSync + MaybeSend,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 	{
 		async move {
 			self.compact_full(
@@ -145,16 +400,16 @@ pub trait Compact<J: JsonSrc, T: Id> {
 	fn compact<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
 		&'a self,
 		active_context: Inversible<T, &'a C>,
-		loader: &'a mut L,
+		loader: &'a L,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
-		Self: Sync,
-		T: 'a + Sync + Send,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+		Self: MaybeSync,
+		T: 'a + MaybeSync + MaybeSend,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 	{
 		self.compact_with(active_context, loader, Options::default(), meta)
 	}
@@ -174,39 +429,55 @@ pub trait CompactIndexed<J: JsonSrc, T: Id> {
 		active_context: Inversible<T, &'a C>,
 		type_scoped_context: Inversible<T, &'a C>,
 		active_property: Option<&'a str>,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: Options,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
 		J: 'a,
 		T: 'a,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData;
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData;
 }
 
-impl<J: JsonSrc, T: Sync + Send + Id, V: Sync + Send + CompactIndexed<J, T>> Compact<J, T>
-	for Indexed<V>
+impl<
+		J: JsonSrc,
+		T: MaybeSync + MaybeSend + Id,
+		V: MaybeSync + MaybeSend + CompactIndexed<J, T>,
+	> Compact<J, T> for Indexed<V>
 {
 	fn compact_full<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
 		&'a self,
 		active_context: Inversible<T, &'a C>,
 		type_scoped_context: Inversible<T, &'a C>,
 		active_property: Option<&'a str>,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: Options,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
 		J: 'a,
 		T: 'a,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 	{
+		#[cfg(feature = "rdf-star")]
+		if !self.annotation().is_empty() {
+			return compact_annotated(
+				self,
+				active_context,
+				type_scoped_context,
+				active_property,
+				loader,
+				options,
+				meta,
+			);
+		}
+
 		self.inner().compact_indexed(
 			self.index(),
 			active_context,
@@ -219,8 +490,8 @@ impl<J: JsonSrc, T: Sync + Send + Id, V: Sync + Send + CompactIndexed<J, T>> Com
 	}
 }
 
-impl<J: JsonSrc, T: Sync + Send + Id, N: object::Any<J, T> + Sync + Send> CompactIndexed<J, T>
-	for N
+impl<J: JsonSrc, T: MaybeSync + MaybeSend + Id, N: object::Any<J, T> + MaybeSync + MaybeSend>
+	CompactIndexed<J, T> for N
 {
 	fn compact_indexed<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
 		&'a self,
@@ -228,17 +499,17 @@ impl<J: JsonSrc, T: Sync + Send + Id, N: object::Any<J, T> + Sync + Send> Compac
 		active_context: Inversible<T, &'a C>,
 		type_scoped_context: Inversible<T, &'a C>,
 		active_property: Option<&'a str>,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: Options,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
 		J: 'a,
 		T: 'a,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 	{
 		match self.as_ref() {
 			object::Ref::Value(value) => async move {
@@ -294,7 +565,8 @@ impl<J: JsonSrc, T: Sync + Send + Id, N: object::Any<J, T> + Sync + Send> Compac
 										*active_context.as_ref(),
 										loader,
 										active_property_definition.base_url(),
-										context::ProcessingOptions::from(options).with_override(),
+										context::ProcessingOptions::from(options.clone())
+											.with_override(),
 									)
 									.await
 									.map_err(Loc::unwrap)?
@@ -329,7 +601,7 @@ impl<J: JsonSrc, T: Sync + Send + Id, N: object::Any<J, T> + Sync + Send> Compac
 						active_context.as_ref(),
 						loader,
 						false,
-						options,
+						options.clone(),
 						meta.clone(),
 					)
 					.await?;
@@ -379,6 +651,111 @@ impl<J: JsonSrc, T: Sync + Send + Id, N: object::Any<J, T> + Sync + Send> Compac
 	}
 }
 
+/// Compact an object with annotations (JSON-LD-star).
+///
+/// The object is compacted into a JSON object, to which the compacted
+/// annotations are added with an `@annotation` entry.
+#[cfg(feature = "rdf-star")]
+fn compact_annotated<
+	'a,
+	J: JsonSrc,
+	K: JsonFrom<J>,
+	T: MaybeSync + MaybeSend + Id,
+	V: MaybeSync + MaybeSend + CompactIndexed<J, T>,
+	C: ContextMut<T>,
+	L: Loader,
+	M,
+>(
+	object: &'a Indexed<V>,
+	active_context: Inversible<T, &'a C>,
+	type_scoped_context: Inversible<T, &'a C>,
+	active_property: Option<&'a str>,
+	loader: &'a L,
+	options: Options,
+	meta: M,
+) -> BoxFuture<'a, Result<K, Error>>
+where
+	J: 'a,
+	T: 'a,
+	C: MaybeSync + MaybeSend,
+	C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+	L: MaybeSync + MaybeSend,
+	M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+{
+	async move {
+		let mut result: K = object
+			.inner()
+			.compact_indexed(
+				object.index(),
+				active_context.clone(),
+				type_scoped_context.clone(),
+				active_property,
+				loader,
+				options.clone(),
+				meta.clone(),
+			)
+			.await?;
+
+		if !result.is_object() {
+			// The object has been compacted into a scalar (e.g. using a type coercion).
+			// Compact it again without the active property definition.
+			result = object
+				.inner()
+				.compact_indexed(
+					object.index(),
+					active_context.clone(),
+					type_scoped_context,
+					None,
+					loader,
+					options.clone(),
+					meta.clone(),
+				)
+				.await?;
+		}
+
+		if !result.is_object() {
+			// Native value.
+			let alias = compact_iri::<J, _, _>(
+				active_context.clone(),
+				&Term::Keyword(Keyword::Value),
+				true,
+				false,
+				options.clone(),
+			)?;
+
+			let mut map = K::Object::default();
+			map.insert(K::new_key(alias.unwrap().as_str(), meta(None)), result);
+			result = K::object(map, meta(None))
+		}
+
+		let mut annotation = Vec::with_capacity(object.annotation().len());
+		for item in object.annotation() {
+			annotation.push(
+				item.compact_full(
+					active_context.clone(),
+					active_context.clone(),
+					Some(crate::syntax::ANNOTATION),
+					loader,
+					options.clone(),
+					meta.clone(),
+				)
+				.await?,
+			)
+		}
+
+		add_value(
+			result.as_object_mut().unwrap(),
+			crate::syntax::ANNOTATION,
+			K::array(annotation.into_iter().collect(), meta(None)),
+			!options.compact_arrays,
+			|| meta(None),
+		);
+
+		Ok(result)
+	}
+	.boxed()
+}
+
 /// Default value of `as_array` is false.
 fn add_value<K: JsonBuild + JsonMut>(
 	map: &mut K::Object,
@@ -435,7 +812,7 @@ where
 			Literal::String(s) => s.as_json_with(meta(None)),
 		},
 		Value::LangString(str) => K::string(str.as_str().into(), meta(None)),
-		Value::Json(json) => json.as_json_with(meta),
+		Value::Json(json) | Value::Invalid(json) => json.as_json_with(meta),
 	}
 }
 
@@ -443,8 +820,9 @@ fn compact_collection_with<
 	'a,
 	J: 'a + JsonSrc,
 	K: JsonFrom<J>,
-	T: 'a + Sync + Send + Id,
-	O: 'a + Send + Iterator<Item = &'a Indexed<Object<J, T>>>,
+	T: 'a + MaybeSync + MaybeSend + Id,
+	N: 'a + MaybeSync + MaybeSend + CompactIndexed<J, T>,
+	O: 'a + MaybeSend + Iterator<Item = &'a Indexed<N>>,
 	C: ContextMut<T>,
 	L: Loader,
 	M: 'a,
@@ -453,15 +831,15 @@ fn compact_collection_with<
 	active_context: Inversible<T, &'a C>,
 	type_scoped_context: Inversible<T, &'a C>,
 	active_property: Option<&'a str>,
-	loader: &'a mut L,
+	loader: &'a L,
 	options: Options,
 	meta: M,
 ) -> BoxFuture<'a, Result<K, Error>>
 where
-	C: Sync + Send,
-	C::LocalContext: Send + Sync + From<L::Output>,
-	L: Sync + Send,
-	M: Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	C: MaybeSync + MaybeSend,
+	C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+	L: MaybeSync + MaybeSend,
+	M: MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 {
 	async move {
 		let mut result = Vec::new();
@@ -473,7 +851,7 @@ where
 					type_scoped_context.clone(),
 					active_property,
 					loader,
-					options,
+					options.clone(),
 					meta.clone(),
 				)
 				.await?;
@@ -510,22 +888,80 @@ where
 	.boxed()
 }
 
-impl<J: JsonSrc, T: Sync + Send + Id> Compact<J, T> for HashSet<Indexed<Object<J, T>>> {
+impl<J: JsonSrc, T: MaybeSync + MaybeSend + Id> Compact<J, T> for HashSet<Indexed<Object<J, T>>> {
+	fn compact_full<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
+		&'a self,
+		active_context: Inversible<T, &'a C>,
+		type_scoped_context: Inversible<T, &'a C>,
+		active_property: Option<&'a str>,
+		loader: &'a L,
+		options: Options,
+		meta: M,
+	) -> BoxFuture<'a, Result<K, Error>>
+	where
+		T: 'a,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	{
+		compact_collection_with(
+			self.iter(),
+			active_context,
+			type_scoped_context,
+			active_property,
+			loader,
+			options,
+			meta,
+		)
+	}
+}
+
+impl<J: JsonSrc, T: MaybeSync + MaybeSend + Id> Compact<J, T> for IndexSet<Indexed<Object<J, T>>> {
+	fn compact_full<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
+		&'a self,
+		active_context: Inversible<T, &'a C>,
+		type_scoped_context: Inversible<T, &'a C>,
+		active_property: Option<&'a str>,
+		loader: &'a L,
+		options: Options,
+		meta: M,
+	) -> BoxFuture<'a, Result<K, Error>>
+	where
+		T: 'a,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	{
+		compact_collection_with(
+			self.iter(),
+			active_context,
+			type_scoped_context,
+			active_property,
+			loader,
+			options,
+			meta,
+		)
+	}
+}
+
+impl<J: JsonSrc, T: MaybeSync + MaybeSend + Id> Compact<J, T> for Vec<Indexed<Node<J, T>>> {
 	fn compact_full<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
 		&'a self,
 		active_context: Inversible<T, &'a C>,
 		type_scoped_context: Inversible<T, &'a C>,
 		active_property: Option<&'a str>,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: Options,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
 		T: 'a,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 	{
 		compact_collection_with(
 			self.iter(),
@@ -1,17 +1,22 @@
 use super::{add_value, compact_iri, compact_property, optional_string, JsonSrc, Options};
+use crate::future::{MaybeSend, MaybeSync};
 use crate::{
 	context::{self, Inversible, Loader, Local},
-	syntax::{Container, ContainerType, Keyword, Term, Type},
+	syntax::{is_keyword_like, Container, ContainerType, Keyword, Term, Type},
 	util::{AsAnyJson, JsonFrom},
 	ContextMut, Error, Id, Loc, Node, ProcessingMode, Reference,
 };
 use cc_traits::Len;
 
 /// Compact the given indexed node.
+#[cfg_attr(
+	feature = "tracing",
+	tracing::instrument(level = "debug", skip_all, fields(id = node.id().map(|id| id.as_str())))
+)]
 pub async fn compact_indexed_node_with<
 	J: JsonSrc,
 	K: JsonFrom<J>,
-	T: Sync + Send + Id,
+	T: MaybeSync + MaybeSend + Id,
 	C: ContextMut<T>,
 	L: Loader,
 	M,
@@ -21,15 +26,15 @@ pub async fn compact_indexed_node_with<
 	mut active_context: Inversible<T, &C>,
 	type_scoped_context: Inversible<T, &C>,
 	active_property: Option<&str>,
-	loader: &mut L,
+	loader: &L,
 	options: Options,
 	meta: M,
 ) -> Result<K, Error>
 where
-	C: Sync + Send,
-	C::LocalContext: Send + Sync + From<L::Output>,
-	L: Sync + Send,
-	M: Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	C: MaybeSync + MaybeSend,
+	C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+	L: MaybeSync + MaybeSend,
+	M: MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 {
 	// If active context has a previous context, the active context is not propagated.
 	// If element does not contain an @value entry, and element does not consist of
@@ -55,7 +60,7 @@ where
 							*active_context.as_ref(),
 							loader,
 							active_property_definition.base_url(),
-							context::ProcessingOptions::from(options).with_override(),
+							context::ProcessingOptions::from(options.clone()).with_override(),
 						)
 						.await
 						.map_err(Loc::unwrap)?
@@ -81,7 +86,7 @@ where
 				&ty.clone().into_term(),
 				true,
 				false,
-				options,
+				options.clone(),
 			)?;
 			compacted_types.push(compacted_ty)
 		}
@@ -93,7 +98,7 @@ where
 			{
 				if let Some(local_context) = &term_definition.context {
 					let processing_options =
-						context::ProcessingOptions::from(options).without_propagation();
+						context::ProcessingOptions::from(options.clone()).without_propagation();
 					active_context = Inversible::new(
 						local_context
 							.process_with(
@@ -165,7 +170,7 @@ where
 		// If expanded value is a string, then initialize compacted value by IRI
 		// compacting expanded value with vocab set to false.
 		let compacted_value =
-			compact_iri::<J, _, _>(active_context.as_ref(), &id, false, false, options)?;
+			compact_iri::<J, _, _>(active_context.as_ref(), &id, false, false, options.clone())?;
 
 		// Initialize alias by IRI compacting expanded property.
 		let alias = compact_iri::<J, _, _>(
@@ -173,7 +178,7 @@ where
 			&Term::Keyword(Keyword::Id),
 			true,
 			false,
-			options,
+			options.clone(),
 		)?;
 
 		// Add an entry alias to result whose value is set to compacted value and continue
@@ -191,7 +196,7 @@ where
 		&node.types,
 		active_context.as_ref(),
 		type_scoped_context.clone(),
-		options,
+		options.clone(),
 		meta.clone(),
 	)?;
 
@@ -209,7 +214,7 @@ where
 							*active_context.as_ref(),
 							loader,
 							active_property_definition.base_url(),
-							context::ProcessingOptions::from(options).with_override(),
+							context::ProcessingOptions::from(options.clone()).with_override(),
 						)
 						.await
 						.map_err(Loc::unwrap)?
@@ -228,7 +233,7 @@ where
 				active_context.as_ref(),
 				loader,
 				true,
-				options,
+				options.clone(),
 				meta.clone(),
 			)
 			.await?;
@@ -267,7 +272,7 @@ where
 				&Term::Keyword(Keyword::Reverse),
 				true,
 				false,
-				options,
+				options.clone(),
 			)?;
 
 			// Set the value of the alias entry of result to compacted value.
@@ -302,7 +307,7 @@ where
 				&Term::Keyword(Keyword::Index),
 				true,
 				false,
-				options,
+				options.clone(),
 			)?;
 
 			// Add an entry alias to result whose value is set to expanded value and continue with the next expanded property.
@@ -321,13 +326,19 @@ where
 			active_context.as_ref(),
 			loader,
 			false,
-			options,
+			options.clone(),
 			meta.clone(),
 		)
 		.await?
 	}
 
 	for (expanded_property, expanded_value) in expanded_entries {
+		if let Reference::Invalid(key) = expanded_property {
+			if is_keyword_like(key) && !options.extensions.preserves(key) {
+				continue;
+			}
+		}
+
 		compact_property::<J, K, _, _, _, _, _, _>(
 			&mut result,
 			expanded_property.clone().into(),
@@ -335,7 +346,7 @@ where
 			active_context.as_ref(),
 			loader,
 			false,
-			options,
+			options.clone(),
 			meta.clone(),
 		)
 		.await?
@@ -362,7 +373,7 @@ where
 fn compact_types<
 	J: JsonSrc,
 	K: JsonFrom<J>,
-	T: Sync + Send + Id,
+	T: MaybeSync + MaybeSend + Id,
 	C: ContextMut<T>,
 	M: Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 >(
@@ -385,7 +396,7 @@ fn compact_types<
 					&types[0].clone().into_term(),
 					true,
 					false,
-					options,
+					options.clone(),
 				)?,
 				meta(None),
 			)
@@ -399,8 +410,13 @@ fn compact_types<
 				let ty = ty.clone().into_term();
 
 				// Set term by IRI compacting expanded type using type-scoped context for active context.
-				let compacted_ty =
-					compact_iri::<J, _, _>(type_scoped_context.clone(), &ty, true, false, options)?;
+				let compacted_ty = compact_iri::<J, _, _>(
+					type_scoped_context.clone(),
+					&ty,
+					true,
+					false,
+					options.clone(),
+				)?;
 
 				// Append term, to compacted value.
 				compacted_value.push(optional_string(compacted_ty, meta(None)))
@@ -415,7 +431,7 @@ fn compact_types<
 			&Term::Keyword(Keyword::Type),
 			true,
 			false,
-			options,
+			options.clone(),
 		)?
 		.unwrap();
 
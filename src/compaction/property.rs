@@ -2,6 +2,7 @@ use super::{
 	add_value, compact_collection_with, compact_iri, compact_iri_with, value_value, Compact,
 	CompactIndexed, JsonSrc, Options,
 };
+use crate::future::{MaybeSend, MaybeSync};
 use crate::{
 	context::{Inversible, Loader},
 	object,
@@ -15,7 +16,7 @@ use generic_json::{JsonBuild, JsonClone, JsonHash, JsonIntoMut, JsonMut, ValueMu
 async fn compact_property_list<
 	J: JsonClone + JsonHash,
 	K: JsonFrom<J>,
-	T: Sync + Send + Id,
+	T: MaybeSync + MaybeSend + Id,
 	C: ContextMut<T>,
 	L: Loader,
 	M,
@@ -27,16 +28,16 @@ async fn compact_property_list<
 	as_array: bool,
 	item_active_property: &str,
 	active_context: Inversible<T, &C>,
-	loader: &mut L,
+	loader: &L,
 	options: Options,
 	meta: M,
 ) -> Result<(), Error>
 where
 	J: JsonSrc,
-	C: Sync + Send,
-	C::LocalContext: Send + Sync + From<L::Output>,
-	L: Sync + Send,
-	M: Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	C: MaybeSync + MaybeSend,
+	C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+	L: MaybeSync + MaybeSend,
+	M: MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 {
 	// If expanded item is a list object:
 	let mut compacted_item: K = compact_collection_with(
@@ -45,7 +46,7 @@ where
 		active_context.clone(),
 		Some(item_active_property),
 		loader,
-		options,
+		options.clone(),
 		meta.clone(),
 	)
 	.await?;
@@ -69,7 +70,7 @@ where
 			&Term::Keyword(Keyword::List),
 			true,
 			false,
-			options,
+			options.clone(),
 		)?;
 		let mut compacted_item_list_object = K::Object::default();
 		compacted_item_list_object.insert(
@@ -116,7 +117,7 @@ where
 async fn compact_property_graph<
 	J: JsonSrc,
 	K: JsonFrom<J>,
-	T: Sync + Send + Id,
+	T: MaybeSync + MaybeSend + Id,
 	C: ContextMut<T>,
 	L: Loader,
 	M,
@@ -128,15 +129,15 @@ async fn compact_property_graph<
 	as_array: bool,
 	item_active_property: &str,
 	active_context: Inversible<T, &C>,
-	loader: &mut L,
+	loader: &L,
 	options: Options,
 	meta: M,
 ) -> Result<(), Error>
 where
-	C: Sync + Send,
-	C::LocalContext: Send + Sync + From<L::Output>,
-	L: Sync + Send,
-	M: Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	C: MaybeSync + MaybeSend,
+	C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+	L: MaybeSync + MaybeSend,
+	M: MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 {
 	// If expanded item is a graph object
 	let mut compacted_item: K = node
@@ -148,7 +149,7 @@ where
 			active_context.clone(),
 			Some(item_active_property),
 			loader,
-			options,
+			options.clone(),
 			meta.clone(),
 		)
 		.await?;
@@ -264,7 +265,7 @@ where
 			&Term::Keyword(Keyword::Graph),
 			true,
 			false,
-			options,
+			options.clone(),
 		)?
 		.unwrap();
 		let mut map = K::Object::default();
@@ -281,7 +282,7 @@ where
 				&Term::Keyword(Keyword::Id),
 				false,
 				false,
-				options,
+				options.clone(),
 			)?
 			.unwrap();
 			let value = compact_iri::<J, _, _>(
@@ -289,7 +290,7 @@ where
 				&id.clone().into_term(),
 				false,
 				false,
-				options,
+				options.clone(),
 			)?;
 			map.insert(
 				K::new_key(key.as_str(), meta(None)),
@@ -435,26 +436,26 @@ pub async fn compact_property<
 	'a,
 	J: JsonSrc,
 	K: JsonFrom<J>,
-	T: 'a + Sync + Send + Id,
-	N: 'a + object::Any<J, T> + Sync + Send,
+	T: 'a + MaybeSync + MaybeSend + Id,
+	N: 'a + object::Any<J, T> + MaybeSync + MaybeSend,
 	O: IntoIterator<Item = &'a Indexed<N>>,
 	C: ContextMut<T>,
 	L: Loader,
-	M: Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	M: MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 >(
 	result: &mut K::Object,
 	expanded_property: Term<T>,
 	expanded_value: O,
 	active_context: Inversible<T, &C>,
-	loader: &mut L,
+	loader: &L,
 	inside_reverse: bool,
 	options: Options,
 	meta: M,
 ) -> Result<(), Error>
 where
-	C: Sync + Send,
-	C::LocalContext: Send + Sync + From<L::Output>,
-	L: Sync + Send,
+	C: MaybeSync + MaybeSend,
+	C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+	L: MaybeSync + MaybeSend,
 {
 	let mut is_empty = true;
 
@@ -469,7 +470,7 @@ where
 			expanded_item,
 			true,
 			inside_reverse,
-			options,
+			options.clone(),
 		)?;
 
 		// If the term definition for `item_active_property` in the active context
@@ -502,7 +503,7 @@ where
 						item_active_property.as_str(),
 						active_context.clone(),
 						loader,
-						options,
+						options.clone(),
 						meta.clone(),
 					)
 					.await?
@@ -517,7 +518,7 @@ where
 						item_active_property.as_str(),
 						active_context.clone(),
 						loader,
-						options,
+						options.clone(),
 						meta.clone(),
 					)
 					.await?
@@ -529,7 +530,7 @@ where
 							active_context.clone(),
 							Some(item_active_property.as_str()),
 							loader,
-							options,
+							options.clone(),
 							meta.clone(),
 						)
 						.await?;
@@ -573,7 +574,7 @@ where
 							&Term::Keyword(container_type.into()),
 							true,
 							false,
-							options,
+							options.clone(),
 						)?;
 
 						// Initialize `index_key` to the value of index mapping in
@@ -614,7 +615,7 @@ where
 									&Term::Ref(Reference::Invalid(index_key.to_string())),
 									true,
 									false,
-									options,
+									options.clone(),
 								)?;
 
 								// Set `map_key` to the first value of
@@ -752,7 +753,7 @@ where
 											active_context.clone(),
 											Some(item_active_property.as_str()),
 											loader,
-											options,
+											options.clone(),
 											meta.clone(),
 										)
 										.await?
@@ -772,7 +773,7 @@ where
 									&Term::Keyword(Keyword::None),
 									true,
 									false,
-									options,
+									options.clone(),
 								)?;
 								key.unwrap()
 							}
@@ -810,7 +811,7 @@ where
 			&Indexed::new(Object::Node(Node::new()), None),
 			true,
 			inside_reverse,
-			options,
+			options.clone(),
 		)?;
 
 		// If the term definition for `item_active_property` in the active context
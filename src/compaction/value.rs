@@ -1,4 +1,5 @@
 use super::{compact_iri, JsonSrc, Options};
+use crate::future::{MaybeSend, MaybeSync};
 use crate::{
 	context::{self, Inversible, Loader, Local},
 	syntax::{Container, ContainerType, Keyword, Term, Type},
@@ -10,7 +11,7 @@ use crate::{
 pub async fn compact_indexed_value_with<
 	J: JsonSrc,
 	K: JsonFrom<J>,
-	T: Sync + Send + Id,
+	T: MaybeSync + MaybeSend + Id,
 	C: ContextMut<T>,
 	L: Loader,
 	M,
@@ -19,15 +20,15 @@ pub async fn compact_indexed_value_with<
 	index: Option<&str>,
 	active_context: Inversible<T, &C>,
 	active_property: Option<&str>,
-	loader: &mut L,
+	loader: &L,
 	options: Options,
 	meta: M,
 ) -> Result<K, Error>
 where
-	C: Sync + Send,
-	C::LocalContext: Send + Sync + From<L::Output>,
-	L: Sync + Send,
-	M: Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+	C: MaybeSync + MaybeSend,
+	C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+	L: MaybeSync + MaybeSend,
+	M: MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 {
 	// If the term definition for active property in active context has a local context:
 	let mut active_context = active_context.into_borrowed();
@@ -40,7 +41,7 @@ where
 							*active_context.as_ref(),
 							loader,
 							active_property_definition.base_url(),
-							context::ProcessingOptions::from(options).with_override(),
+							context::ProcessingOptions::from(options.clone()).with_override(),
 						)
 						.await
 						.map_err(Loc::unwrap)?
@@ -113,6 +114,8 @@ where
 		(index.is_some() && container_mapping.contains(ContainerType::Index)) || index.is_none();
 
 	match value {
+		// Invalid values are written back as is.
+		Value::Invalid(json) => return Ok(json.as_json_with(meta)),
 		Value::Literal(lit, ty) => {
 			use crate::object::value::Literal;
 			if ty.as_ref().map(Type::Ref) == type_mapping && remove_index {
@@ -129,7 +132,7 @@ where
 								&Term::Keyword(Keyword::Value),
 								true,
 								false,
-								options,
+								options.clone(),
 							)?;
 							result.insert(
 								K::new_key(compact_key.as_ref().unwrap().as_str(), meta(None)),
@@ -144,7 +147,7 @@ where
 					&Term::Keyword(Keyword::Value),
 					true,
 					false,
-					options,
+					options.clone(),
 				)?;
 				match lit {
 					Literal::Null => {
@@ -179,14 +182,14 @@ where
 						&Term::Keyword(Keyword::Type),
 						true,
 						false,
-						options,
+						options.clone(),
 					)?;
 					let compact_ty = compact_iri::<J, _, _>(
 						active_context.as_ref(),
 						&Term::Ref(Reference::Id(ty.clone())),
 						true,
 						false,
-						options,
+						options.clone(),
 					)?;
 					result.insert(
 						K::new_key(compact_key.as_ref().unwrap().as_str(), meta(None)),
@@ -214,7 +217,7 @@ where
 					&Term::Keyword(Keyword::Value),
 					true,
 					false,
-					options,
+					options.clone(),
 				)?;
 				result.insert(
 					K::new_key(compact_key.as_ref().unwrap().as_str(), meta(None)),
@@ -227,7 +230,7 @@ where
 						&Term::Keyword(Keyword::Language),
 						true,
 						false,
-						options,
+						options.clone(),
 					)?;
 					result.insert(
 						K::new_key(compact_key.as_ref().unwrap().as_str(), meta(None)),
@@ -241,7 +244,7 @@ where
 						&Term::Keyword(Keyword::Direction),
 						true,
 						false,
-						options,
+						options.clone(),
 					)?;
 					result.insert(
 						K::new_key(compact_key.as_ref().unwrap().as_str(), meta(None)),
@@ -259,7 +262,7 @@ where
 					&Term::Keyword(Keyword::Value),
 					true,
 					false,
-					options,
+					options.clone(),
 				)?;
 				result.insert(
 					K::new_key(compact_key.as_ref().unwrap().as_str(), meta(None)),
@@ -271,14 +274,14 @@ where
 					&Term::Keyword(Keyword::Type),
 					true,
 					false,
-					options,
+					options.clone(),
 				)?;
 				let compact_ty = compact_iri::<J, _, _>(
 					active_context.as_ref(),
 					&Term::Keyword(Keyword::Json),
 					true,
 					false,
-					options,
+					options.clone(),
 				)?;
 				result.insert(
 					K::new_key(compact_key.as_ref().unwrap().as_str(), meta(None)),
@@ -0,0 +1,161 @@
+use super::{processing::is_gen_delim, ContextMut, TermDefinition};
+use crate::{
+	syntax::{Term, Type},
+	Direction, Id, Reference,
+};
+use iref::{Iri, IriBuf};
+use langtag::LanguageTagBuf;
+use std::marker::PhantomData;
+
+/// Context builder.
+///
+/// Builds an active context in code, without writing and processing a JSON context.
+/// Terms are defined in order, each definition replacing any previous definition of the same term.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, syntax::Type, ContextBuilder, Document, NoLoader};
+/// # use iref::Iri;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let context: context::Json<Value> = ContextBuilder::new()
+///   .vocab(Iri::new("https://schema.org/").unwrap())
+///   .term("name", Iri::new("https://schema.org/name").unwrap())
+///   .typed_term("homepage", Iri::new("https://schema.org/url").unwrap(), Type::Id)
+///   .language("en")
+///   .build();
+///
+/// let doc = json!({ "name": "Alice", "homepage": "https://alice.example.org/", "knows": "Bob" });
+/// let expanded = doc
+///   .expand_with(None, &context, &NoLoader::<Value>::new(), Default::default())
+///   .await
+///   .unwrap();
+///
+/// let node = expanded.into_iter().next().unwrap().into_inner().into_node().unwrap();
+/// assert_eq!(node.properties().len(), 3);
+/// # }
+/// ```
+pub struct ContextBuilder<C, T = IriBuf> {
+	context: C,
+	id: PhantomData<T>,
+}
+
+impl<T: Id, C: ContextMut<T>> ContextBuilder<C, T> {
+	/// Creates a new builder starting from a newly-initialized active context,
+	/// without base IRI.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::from_context(C::new(None))
+	}
+
+	/// Creates a new builder starting from a newly-initialized active context,
+	/// with the given base IRI.
+	#[inline(always)]
+	pub fn with_base_iri(base_iri: Iri) -> Self {
+		Self::from_context(C::new(Some(base_iri)))
+	}
+
+	/// Creates a new builder extending the given active context.
+	#[inline(always)]
+	pub fn from_context(context: C) -> Self {
+		Self {
+			context,
+			id: PhantomData,
+		}
+	}
+
+	/// Sets the base IRI.
+	#[inline(always)]
+	pub fn base(mut self, base_iri: Iri) -> Self {
+		self.context.set_base_iri(Some(base_iri));
+		self
+	}
+
+	/// Sets the vocabulary mapping.
+	#[inline(always)]
+	pub fn vocab(mut self, vocab: Iri) -> Self {
+		self.context
+			.set_vocabulary(Some(Term::Ref(Reference::Id(T::from_iri(vocab)))));
+		self
+	}
+
+	/// Sets the default language.
+	///
+	/// Malformed language tags are kept as they are.
+	pub fn language(mut self, language: &str) -> Self {
+		let language = match LanguageTagBuf::parse_copy(language) {
+			Ok(tag) => tag.into(),
+			Err(_) => language.to_string().into(),
+		};
+
+		self.context.set_default_language(Some(language));
+		self
+	}
+
+	/// Sets the default base direction.
+	#[inline(always)]
+	pub fn direction(mut self, direction: Direction) -> Self {
+		self.context.set_default_base_direction(Some(direction));
+		self
+	}
+
+	/// Defines the given term with the given definition.
+	#[inline(always)]
+	pub fn define(mut self, term: &str, definition: TermDefinition<T, C>) -> Self {
+		self.context.set(term, Some(definition));
+		self
+	}
+
+	/// Defines the given term as an alias of the given IRI.
+	///
+	/// As for simple term definitions, the term can be used as a prefix
+	/// if the IRI ends with a gen-delim character (such as `/` or `#`).
+	#[inline(always)]
+	pub fn term(self, term: &str, iri: Iri) -> Self {
+		let prefix = !term.contains(':')
+			&& !term.contains('/')
+			&& iri
+				.as_str()
+				.chars()
+				.last()
+				.map(is_gen_delim)
+				.unwrap_or(false);
+
+		self.define(
+			term,
+			TermDefinition {
+				value: Some(Term::Ref(Reference::Id(T::from_iri(iri)))),
+				prefix,
+				..TermDefinition::default()
+			},
+		)
+	}
+
+	/// Defines the given term as an alias of the given IRI,
+	/// whose values have the given type.
+	#[inline(always)]
+	pub fn typed_term(self, term: &str, iri: Iri, typ: Type<T>) -> Self {
+		self.define(
+			term,
+			TermDefinition {
+				value: Some(Term::Ref(Reference::Id(T::from_iri(iri)))),
+				typ: Some(typ),
+				..TermDefinition::default()
+			},
+		)
+	}
+
+	/// Returns the built context.
+	#[inline(always)]
+	pub fn build(self) -> C {
+		self.context
+	}
+}
+
+impl<T: Id, C: ContextMut<T>> Default for ContextBuilder<C, T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
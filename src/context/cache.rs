@@ -0,0 +1,152 @@
+use super::ProcessingOptions;
+use iref::{Iri, IriBuf};
+use std::collections::HashMap;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::Mutex;
+
+/// Key of a processed context in a [`Cache`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Key {
+	/// IRI of the remote context.
+	url: IriBuf,
+
+	/// Base IRI of the active context the remote context is processed on.
+	base_iri: Option<IriBuf>,
+
+	/// Options the remote context is processed with.
+	options: ProcessingOptions,
+}
+
+impl Key {
+	fn new(url: Iri, base_iri: Option<Iri>, options: ProcessingOptions) -> Self {
+		Self {
+			url: url.into(),
+			base_iri: base_iri.map(IriBuf::from),
+			options: ProcessingOptions {
+				concurrent_context_loading: false,
+				..options
+			},
+		}
+	}
+}
+
+/// Cache of processed remote contexts.
+///
+/// Processing a remote context such as `https://schema.org` is expensive.
+/// When many documents share the same remote contexts,
+/// this cache lets them be processed only once:
+/// processed contexts are stored by context IRI, base IRI and processing options,
+/// and reused for every document expanded with an active context attached to the cache
+/// (see [`Json::with_cache`](super::Json::with_cache)).
+///
+/// Only remote contexts processed on top of a newly-initialized active context,
+/// such as the top-level `@context` of a document, are cached.
+/// Remote contexts processed on top of other definitions are always processed.
+/// Remote contexts whose processing raises warnings are not cached either,
+/// so that the warnings are reported for every document.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Cache}, Document, MockLoader};
+/// # use iref::Iri;
+/// # use serde_json::{json, Value};
+/// # use std::sync::Arc;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let context_url = Iri::new("https://example.org/context.jsonld").unwrap();
+/// let loader = MockLoader::new()
+///   .with(context_url, json!({ "@context": { "name": "https://schema.org/name" } }));
+///
+/// let cache = Arc::new(Cache::new());
+/// let active_context = context::Json::<Value>::new(None).with_cache(cache.clone());
+///
+/// for name in ["Alice", "Bob"] {
+///   let doc = json!({ "@context": "https://example.org/context.jsonld", "name": name });
+///   doc.expand_with(None, &active_context, &loader, Default::default()).await.unwrap();
+/// }
+///
+/// assert_eq!(loader.request_count(context_url), 1);
+/// assert_eq!(cache.hits(), 1);
+/// # }
+/// ```
+pub struct Cache<C> {
+	entries: Mutex<HashMap<Key, C>>,
+	hits: AtomicUsize,
+	misses: AtomicUsize,
+}
+
+impl<C> Cache<C> {
+	/// Creates a new empty cache.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			entries: Mutex::new(HashMap::new()),
+			hits: AtomicUsize::new(0),
+			misses: AtomicUsize::new(0),
+		}
+	}
+
+	/// Returns the number of cached contexts.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.entries.lock().unwrap().len()
+	}
+
+	/// Checks if the cache is empty.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.entries.lock().unwrap().is_empty()
+	}
+
+	/// Number of remote contexts found in the cache.
+	#[inline(always)]
+	pub fn hits(&self) -> usize {
+		self.hits.load(atomic::Ordering::SeqCst)
+	}
+
+	/// Number of remote contexts not found in the cache, and processed.
+	#[inline(always)]
+	pub fn misses(&self) -> usize {
+		self.misses.load(atomic::Ordering::SeqCst)
+	}
+
+	/// Removes every processed context from the cache.
+	#[inline(always)]
+	pub fn clear(&self) {
+		self.entries.lock().unwrap().clear()
+	}
+
+	/// Returns the processed context of the given remote context,
+	/// processed on top of a newly-initialized active context with the given base IRI
+	/// and the given options.
+	pub fn get(&self, url: Iri, base_iri: Option<Iri>, options: ProcessingOptions) -> Option<C>
+	where
+		C: Clone,
+	{
+		let key = Key::new(url, base_iri, options);
+
+		let context = self.entries.lock().unwrap().get(&key).cloned();
+		match context {
+			Some(_) => self.hits.fetch_add(1, atomic::Ordering::SeqCst),
+			None => self.misses.fetch_add(1, atomic::Ordering::SeqCst),
+		};
+
+		context
+	}
+
+	/// Stores the processed context of the given remote context,
+	/// processed on top of a newly-initialized active context with the given base IRI
+	/// and the given options.
+	pub fn insert(&self, url: Iri, base_iri: Option<Iri>, options: ProcessingOptions, context: C) {
+		let key = Key::new(url, base_iri, options);
+
+		self.entries.lock().unwrap().insert(key, context);
+	}
+}
+
+impl<C> Default for Cache<C> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
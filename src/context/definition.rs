@@ -1,56 +1,242 @@
-use super::Context;
+use super::{Context, ContextChain};
 use crate::{
-	lang::LenientLanguageTagBuf,
-	syntax::{Container, Term, Type},
+	lang::{LenientLanguageTag, LenientLanguageTagBuf},
+	loader,
+	syntax::{Container, ContainerType, Term, Type},
 	Direction, Id, Nullable,
 };
 use iref::{Iri, IriBuf};
 // use langtag::LanguageTagBuf;
 
-// A term definition.
+/// Term definition.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Context, TermDefinition}, syntax::{ContainerType, Type}, ContextBuilder};
+/// # use iref::{Iri, IriBuf};
+/// # use serde_json::Value;
+/// let mut steps = TermDefinition::<IriBuf, context::Json<Value>>::default();
+/// steps.container.add(ContainerType::List);
+///
+/// let context: context::Json<Value> = ContextBuilder::new()
+///   .typed_term("knows", Iri::new("https://schema.org/knows").unwrap(), Type::Id)
+///   .define("steps", steps)
+///   .build();
+///
+/// let knows = context.get("knows").unwrap();
+/// assert!(knows.type_mapping() == Some(&Type::Id));
+/// assert!(!knows.is_list() && !knows.is_reverse());
+/// assert!(context.get("steps").unwrap().is_list());
+/// ```
 #[derive(Clone)]
 pub struct TermDefinition<T: Id, C: Context<T>> {
-	// IRI mapping.
+	/// IRI mapping.
 	pub value: Option<Term<T>>,
 
-	// Prefix flag.
+	/// Prefix flag.
 	pub prefix: bool,
 
-	// Protected flag.
+	/// Protected flag.
 	pub protected: bool,
 
-	// Reverse property flag.
+	/// Reverse property flag.
 	pub reverse_property: bool,
 
-	// Optional base URL.
+	/// Optional base URL.
 	pub base_url: Option<IriBuf>,
 
-	// Optional context.
+	/// Optional context.
 	pub context: Option<C::LocalContext>,
 
-	// Container mapping.
+	/// Container mapping.
 	pub container: Container,
 
-	// Optional direction mapping.
+	/// Optional direction mapping.
 	pub direction: Option<Nullable<Direction>>,
 
-	// Optional index mapping.
+	/// Optional index mapping.
 	pub index: Option<String>,
 
-	// Optional language mapping.
+	/// Optional language mapping.
 	pub language: Option<Nullable<LenientLanguageTagBuf>>,
 
-	// Optional nest value.
+	/// Optional nest value.
 	pub nest: Option<String>,
 
-	// Optional type mapping.
+	/// Optional type mapping.
 	pub typ: Option<Type<T>>,
+
+	/// Context that introduced or last redefined the term, if known.
+	///
+	/// This is ignored when comparing term definitions.
+	pub origin: Option<TermOrigin>,
 }
 
 impl<T: Id, C: Context<T>> TermDefinition<T, C> {
+	/// IRI mapping of the term.
+	#[inline(always)]
+	pub fn value(&self) -> Option<&Term<T>> {
+		self.value.as_ref()
+	}
+
+	/// Checks if the term can be used as a prefix in compact IRIs.
+	#[inline(always)]
+	pub fn is_prefix(&self) -> bool {
+		self.prefix
+	}
+
+	/// Checks if the term is protected.
+	#[inline(always)]
+	pub fn is_protected(&self) -> bool {
+		self.protected
+	}
+
+	/// Checks if the term is a reverse property (defined with `@reverse`).
+	#[inline(always)]
+	pub fn is_reverse(&self) -> bool {
+		self.reverse_property
+	}
+
+	/// Base URL of the term definition, used to process its scoped context.
 	pub fn base_url(&self) -> Option<Iri> {
 		self.base_url.as_ref().map(|iri| iri.as_iri())
 	}
+
+	/// Scoped context of the term.
+	#[inline(always)]
+	pub fn context(&self) -> Option<&C::LocalContext> {
+		self.context.as_ref()
+	}
+
+	/// Container mapping of the term.
+	#[inline(always)]
+	pub fn container(&self) -> Container {
+		self.container
+	}
+
+	/// Checks if the container mapping of the term includes the given container type.
+	#[inline(always)]
+	pub fn has_container(&self, c: ContainerType) -> bool {
+		self.container.contains(c)
+	}
+
+	/// Checks if the container mapping includes `@set`.
+	#[inline(always)]
+	pub fn is_set(&self) -> bool {
+		self.has_container(ContainerType::Set)
+	}
+
+	/// Checks if the container mapping is `@list`.
+	#[inline(always)]
+	pub fn is_list(&self) -> bool {
+		self.has_container(ContainerType::List)
+	}
+
+	/// Checks if the container mapping includes `@index`.
+	#[inline(always)]
+	pub fn is_index_map(&self) -> bool {
+		self.has_container(ContainerType::Index)
+	}
+
+	/// Checks if the container mapping includes `@language`.
+	#[inline(always)]
+	pub fn is_language_map(&self) -> bool {
+		self.has_container(ContainerType::Language)
+	}
+
+	/// Checks if the container mapping includes `@graph`.
+	#[inline(always)]
+	pub fn is_graph(&self) -> bool {
+		self.has_container(ContainerType::Graph)
+	}
+
+	/// Checks if the container mapping includes `@id`.
+	#[inline(always)]
+	pub fn is_id_map(&self) -> bool {
+		self.has_container(ContainerType::Id)
+	}
+
+	/// Checks if the container mapping includes `@type`.
+	#[inline(always)]
+	pub fn is_type_map(&self) -> bool {
+		self.has_container(ContainerType::Type)
+	}
+
+	/// Type mapping of the term.
+	#[inline(always)]
+	pub fn type_mapping(&self) -> Option<&Type<T>> {
+		self.typ.as_ref()
+	}
+
+	/// Language mapping of the term.
+	///
+	/// Returns `Some(Nullable::Null)` if the term is explicitly defined
+	/// without language (`"@language": null`).
+	#[inline(always)]
+	pub fn language_mapping(&self) -> Option<Nullable<LenientLanguageTag>> {
+		self.language
+			.as_ref()
+			.map(|language| language.as_ref().map(|tag| tag.as_ref()))
+	}
+
+	/// Direction mapping of the term.
+	///
+	/// Returns `Some(Nullable::Null)` if the term is explicitly defined
+	/// without base direction (`"@direction": null`).
+	#[inline(always)]
+	pub fn direction_mapping(&self) -> Option<Nullable<Direction>> {
+		self.direction
+	}
+
+	/// Index mapping of the term, used with the `@index` container.
+	#[inline(always)]
+	pub fn index(&self) -> Option<&str> {
+		self.index.as_deref()
+	}
+
+	/// Nest value of the term.
+	#[inline(always)]
+	pub fn nest(&self) -> Option<&str> {
+		self.nest.as_deref()
+	}
+
+	/// Context that introduced or last redefined the term.
+	///
+	/// Only set for terms defined by context processing.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Context, Local}, MockLoader};
+	/// # use iref::{Iri, IriBuf};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let schema = Iri::new("https://example.org/schema.jsonld").unwrap();
+	/// let loader = MockLoader::new()
+	///   .with(schema, json!({ "@context": { "name": "https://schema.org/name" } }));
+	/// let local_context = json!([
+	///   "https://example.org/schema.jsonld",
+	///   { "knows": "https://schema.org/knows" }
+	/// ]);
+	///
+	/// let processed: context::Json<Value> = Local::<IriBuf>::process_with(
+	///   &local_context,
+	///   &context::Json::new(None),
+	///   &loader,
+	///   None,
+	///   context::ProcessingOptions::default()
+	/// ).await.unwrap().into_inner();
+	///
+	/// let name = processed.get("name").unwrap().origin().unwrap();
+	/// assert_eq!(name.context_iri(), Some(schema));
+	/// let knows = processed.get("knows").unwrap().origin().unwrap();
+	/// assert!(knows.is_inline());
+	/// # }
+	/// ```
+	#[inline(always)]
+	pub fn origin(&self) -> Option<&TermOrigin> {
+		self.origin.as_ref()
+	}
 }
 
 impl<T: Id, C: Context<T>> Default for TermDefinition<T, C> {
@@ -68,6 +254,7 @@ impl<T: Id, C: Context<T>> Default for TermDefinition<T, C> {
 			nest: None,
 			index: None,
 			container: Container::new(),
+			origin: None,
 		}
 	}
 }
@@ -90,3 +277,53 @@ impl<T: Id, C: Context<T>> PartialEq for TermDefinition<T, C> {
 }
 
 impl<T: Id, C: Context<T>> Eq for TermDefinition<T, C> {}
+
+/// Origin of a term definition.
+///
+/// See [`TermDefinition::origin`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TermOrigin {
+	/// Remote contexts being processed when the term was defined.
+	chain: ContextChain,
+
+	/// Document containing the definition.
+	source: Option<loader::Id>,
+}
+
+impl TermOrigin {
+	/// Creates a new term origin from the chain of remote contexts being processed
+	/// and the identifier of the document containing the definition.
+	#[inline(always)]
+	pub fn new(chain: ContextChain, source: Option<loader::Id>) -> Self {
+		Self { chain, source }
+	}
+
+	/// IRI of the remote context that defined the term,
+	/// or `None` if the term was defined by an inline context.
+	#[inline(always)]
+	pub fn context_iri(&self) -> Option<Iri> {
+		self.chain.urls().last().map(|url| url.as_iri())
+	}
+
+	/// Checks if the term was defined by an inline context,
+	/// such as the processed local context itself or an embedded context.
+	#[inline(always)]
+	pub fn is_inline(&self) -> bool {
+		self.chain.urls().is_empty()
+	}
+
+	/// Chain of remote contexts that led to the definition,
+	/// from the first loaded context to the one defining the term.
+	#[inline(always)]
+	pub fn chain(&self) -> &ContextChain {
+		&self.chain
+	}
+
+	/// Loader identifier of the document containing the definition, if any.
+	///
+	/// For inline contexts, this is the document in which the context is embedded.
+	#[inline(always)]
+	pub fn source(&self) -> Option<loader::Id> {
+		self.source
+	}
+}
@@ -0,0 +1,184 @@
+use super::{Context, TermDefinition};
+use crate::{lang::LenientLanguageTag, syntax::Term, Direction, Id};
+use iref::Iri;
+use std::collections::BTreeSet;
+
+/// Change of a context setting, from an old value to a new value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Change<V> {
+	/// Value in the old context.
+	pub old: V,
+
+	/// Value in the new context.
+	pub new: V,
+}
+
+impl<V: PartialEq> Change<V> {
+	/// Returns the change between the given values, if they differ.
+	#[inline(always)]
+	fn between(old: V, new: V) -> Option<Self> {
+		if old == new {
+			None
+		} else {
+			Some(Self { old, new })
+		}
+	}
+}
+
+/// Term definition changed between two contexts.
+pub struct TermChange<'a, T: Id, C: Context<T>> {
+	/// Defined term.
+	pub term: &'a str,
+
+	/// Definition in the old context.
+	pub old: &'a TermDefinition<T, C>,
+
+	/// Definition in the new context.
+	pub new: &'a TermDefinition<T, C>,
+}
+
+impl<'a, T: Id, C: Context<T>> TermChange<'a, T, C> {
+	/// Checks if the definition itself changed,
+	/// regardless of the protection of the term.
+	#[inline(always)]
+	pub fn is_redefined(&self) -> bool {
+		self.old != self.new
+	}
+
+	/// Checks if the term protection changed.
+	#[inline(always)]
+	pub fn is_protection_changed(&self) -> bool {
+		self.old.protected != self.new.protected
+	}
+}
+
+/// Differences between two contexts.
+///
+/// Returned by the [`diff`] function.
+pub struct Diff<'a, T: Id, C: Context<T>> {
+	/// Terms defined only in the new context, sorted by term.
+	pub added: Vec<(&'a str, &'a TermDefinition<T, C>)>,
+
+	/// Terms defined only in the old context, sorted by term.
+	pub removed: Vec<(&'a str, &'a TermDefinition<T, C>)>,
+
+	/// Terms defined in both contexts with a different definition or protection,
+	/// sorted by term.
+	pub changed: Vec<TermChange<'a, T, C>>,
+
+	/// Change of the base IRI.
+	pub base_iri: Option<Change<Option<Iri<'a>>>>,
+
+	/// Change of the vocabulary mapping.
+	pub vocabulary: Option<Change<Option<&'a Term<T>>>>,
+
+	/// Change of the default language.
+	pub default_language: Option<Change<Option<LenientLanguageTag<'a>>>>,
+
+	/// Change of the default base direction.
+	pub default_base_direction: Option<Change<Option<Direction>>>,
+}
+
+impl<'a, T: Id, C: Context<T>> Diff<'a, T, C> {
+	/// Checks if both contexts are the same.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty()
+			&& self.removed.is_empty()
+			&& self.changed.is_empty()
+			&& self.base_iri.is_none()
+			&& self.vocabulary.is_none()
+			&& self.default_language.is_none()
+			&& self.default_base_direction.is_none()
+	}
+
+	/// Checks if the new context may expand documents differently than the old context,
+	/// or reject documents accepted by the old context.
+	///
+	/// This is the case if a term is removed or redefined,
+	/// if a term becomes protected,
+	/// or if the base IRI, vocabulary mapping, default language or base direction changed.
+	/// Adding terms and removing protections are not breaking changes.
+	pub fn is_breaking(&self) -> bool {
+		!self.removed.is_empty()
+			|| self.changed.iter().any(|change| {
+				change.is_redefined() || (!change.old.protected && change.new.protected)
+			}) || self.base_iri.is_some()
+			|| self.vocabulary.is_some()
+			|| self.default_language.is_some()
+			|| self.default_base_direction.is_some()
+	}
+}
+
+/// Computes the differences between an old and a new version of a context.
+///
+/// Term definitions are compared ignoring their protection,
+/// which is compared separately (see [`TermChange`]).
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Context}, syntax::Type, ContextBuilder};
+/// # use iref::Iri;
+/// # use serde_json::Value;
+/// let schema = Iri::new("https://schema.org/").unwrap();
+/// let name = Iri::new("https://schema.org/name").unwrap();
+///
+/// let v1: context::Json<Value> = ContextBuilder::new()
+///   .vocab(schema)
+///   .term("name", name)
+///   .term("email", Iri::new("https://schema.org/email").unwrap())
+///   .build();
+///
+/// let mut protected_name = v1.get("name").unwrap().clone();
+/// protected_name.protected = true;
+/// let v2: context::Json<Value> = ContextBuilder::new()
+///   .vocab(schema)
+///   .define("name", protected_name)
+///   .typed_term("homepage", Iri::new("https://schema.org/url").unwrap(), Type::Id)
+///   .build();
+///
+/// let diff = context::diff(&v1, &v2);
+/// assert_eq!(diff.added.iter().map(|(term, _)| *term).collect::<Vec<_>>(), ["homepage"]);
+/// assert_eq!(diff.removed.iter().map(|(term, _)| *term).collect::<Vec<_>>(), ["email"]);
+/// assert_eq!(diff.changed.len(), 1);
+/// assert!(diff.changed[0].is_protection_changed());
+/// assert!(!diff.changed[0].is_redefined());
+/// assert!(diff.vocabulary.is_none());
+/// assert!(diff.is_breaking());
+/// ```
+pub fn diff<'a, T: Id, C: Context<T>>(old: &'a C, new: &'a C) -> Diff<'a, T, C> {
+	let terms: BTreeSet<&'a str> = old
+		.definitions()
+		.chain(new.definitions())
+		.map(|(term, _)| term.as_str())
+		.collect();
+
+	let mut added = Vec::new();
+	let mut removed = Vec::new();
+	let mut changed = Vec::new();
+
+	for term in terms {
+		match (old.get(term), new.get(term)) {
+			(Some(old), Some(new)) => {
+				if old != new || old.protected != new.protected {
+					changed.push(TermChange { term, old, new })
+				}
+			}
+			(Some(old), None) => removed.push((term, old)),
+			(None, Some(new)) => added.push((term, new)),
+			(None, None) => (),
+		}
+	}
+
+	Diff {
+		added,
+		removed,
+		changed,
+		base_iri: Change::between(old.base_iri(), new.base_iri()),
+		vocabulary: Change::between(old.vocabulary(), new.vocabulary()),
+		default_language: Change::between(old.default_language(), new.default_language()),
+		default_base_direction: Change::between(
+			old.default_base_direction(),
+			new.default_base_direction(),
+		),
+	}
+}
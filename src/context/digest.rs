@@ -0,0 +1,154 @@
+use super::{Context, TermDefinition};
+use crate::{
+	rdf::Digest,
+	syntax::{Keyword, Term, Type},
+	util::{canonical_json, write_canonical_string},
+	Id,
+};
+use sha2::{Digest as _, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Writes an optional string, as a JSON string or `null`.
+fn write_opt(out: &mut String, s: Option<&str>) {
+	match s {
+		Some(s) => write_canonical_string(s, out),
+		None => out.push_str("null"),
+	}
+}
+
+/// Writes an optional nullable value, omitting it if it is not set.
+fn write_nullable(out: &mut String, key: &str, value: Option<Option<&str>>) {
+	if let Some(value) = value {
+		write!(out, " {}:", key).unwrap();
+		write_opt(out, value)
+	}
+}
+
+fn term_str<T: Id>(term: &Term<T>) -> Option<&str> {
+	match term {
+		Term::Null => None,
+		term => Some(term.as_str()),
+	}
+}
+
+fn type_str<T: Id>(ty: &Type<T>) -> &str {
+	match ty {
+		Type::Id => "@id",
+		Type::Json => "@json",
+		Type::None => "@none",
+		Type::Vocab => "@vocab",
+		Type::Ref(t) => t.as_iri().into_str(),
+	}
+}
+
+fn write_definition<T: Id, C: Context<T>>(
+	out: &mut String,
+	term: &str,
+	definition: &TermDefinition<T, C>,
+) {
+	out.push_str("term ");
+	write_canonical_string(term, out);
+
+	if let Some(value) = &definition.value {
+		out.push_str(" value:");
+		write_opt(out, term_str(value))
+	}
+
+	if definition.prefix {
+		out.push_str(" prefix")
+	}
+
+	if definition.protected {
+		out.push_str(" protected")
+	}
+
+	if definition.reverse_property {
+		out.push_str(" reverse")
+	}
+
+	if let Some(base_url) = &definition.base_url {
+		out.push_str(" base:");
+		write_canonical_string(base_url.as_str(), out)
+	}
+
+	if let Some(context) = &definition.context {
+		out.push_str(" context:");
+		out.push_str(&canonical_json(context))
+	}
+
+	for c in definition.container.iter() {
+		write!(out, " container:{}", Keyword::from(*c).into_str()).unwrap()
+	}
+
+	let direction = definition
+		.direction
+		.map(|dir| dir.option().map(|dir| dir.to_string()));
+	write_nullable(out, "direction", direction.as_ref().map(Option::as_deref));
+
+	if let Some(index) = &definition.index {
+		out.push_str(" index:");
+		write_canonical_string(index, out)
+	}
+
+	let language = definition
+		.language
+		.as_ref()
+		.map(|tag| tag.as_ref().option().map(|tag| tag.as_str()));
+	write_nullable(out, "language", language);
+
+	if let Some(nest) = &definition.nest {
+		out.push_str(" nest:");
+		write_canonical_string(nest, out)
+	}
+
+	if let Some(ty) = &definition.typ {
+		out.push_str(" type:");
+		write_canonical_string(type_str(ty), out)
+	}
+
+	out.push('\n')
+}
+
+pub(super) fn digest<T: Id, C: Context<T>>(context: &C) -> Digest {
+	let mut out = String::new();
+
+	out.push_str("original_base_url:");
+	write_opt(
+		&mut out,
+		context.original_base_url().map(|iri| iri.into_str()),
+	);
+	out.push_str("\nbase_iri:");
+	write_opt(&mut out, context.base_iri().map(|iri| iri.into_str()));
+	out.push_str("\nvocab:");
+	write_opt(&mut out, context.vocabulary().and_then(term_str));
+	out.push_str("\nlanguage:");
+	write_opt(
+		&mut out,
+		context.default_language().as_ref().map(|tag| tag.as_str()),
+	);
+	out.push_str("\ndirection:");
+	write_opt(
+		&mut out,
+		context
+			.default_base_direction()
+			.map(|dir| dir.to_string())
+			.as_deref(),
+	);
+	out.push('\n');
+
+	if let Some(previous) = context.previous_context() {
+		out.push_str("previous:");
+		for byte in &digest(previous) {
+			write!(out, "{:02x}", byte).unwrap()
+		}
+		out.push('\n')
+	}
+
+	let definitions: BTreeMap<_, _> = context.definitions().collect();
+	for (term, definition) in definitions {
+		write_definition(&mut out, term, definition)
+	}
+
+	Sha256::digest(out.as_bytes()).into()
+}
@@ -0,0 +1,329 @@
+use super::{ContextMut, TermDefinition};
+use crate::{
+	object::Value,
+	syntax::{ContainerType, Term, Type},
+	ExpandedDocument, Id, Indexed, Node, Object, Reference,
+};
+use generic_json::JsonHash;
+use iref::Iri;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Context inference options.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InferOptions {
+	/// Define a prefix for each namespace of the document IRIs.
+	pub prefixes: bool,
+
+	/// Coerce the values of properties that are always node references,
+	/// or literals of a single datatype.
+	pub type_coercion: bool,
+
+	/// Use the `@list` container for properties whose values are always lists,
+	/// and the `@set` container for properties with multiple values.
+	pub containers: bool,
+}
+
+impl Default for InferOptions {
+	fn default() -> Self {
+		Self {
+			prefixes: true,
+			type_coercion: true,
+			containers: true,
+		}
+	}
+}
+
+/// Well-known prefixes, used to name the namespaces of the document.
+const WELL_KNOWN_PREFIXES: &[(&str, &str)] = &[
+	("as", "https://www.w3.org/ns/activitystreams#"),
+	("dc", "http://purl.org/dc/elements/1.1/"),
+	("dcterms", "http://purl.org/dc/terms/"),
+	("foaf", "http://xmlns.com/foaf/0.1/"),
+	("owl", "http://www.w3.org/2002/07/owl#"),
+	("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+	("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+	("schema", "http://schema.org/"),
+	("schema", "https://schema.org/"),
+	("sec", "https://w3id.org/security#"),
+	("skos", "http://www.w3.org/2004/02/skos/core#"),
+	("xsd", "http://www.w3.org/2001/XMLSchema#"),
+];
+
+/// Values of a property in an expanded document.
+#[derive(Default)]
+struct PropertyUsage {
+	/// Number of node references (node objects with only an `@id`).
+	references: usize,
+
+	/// Number of literals, by datatype.
+	literals: HashMap<Option<String>, usize>,
+
+	/// Number of lists.
+	lists: usize,
+
+	/// Number of other values.
+	others: usize,
+
+	/// Whether the property has multiple values on some node.
+	multiple: bool,
+}
+
+impl PropertyUsage {
+	/// Type coercion of the property, if all its values are node references
+	/// or literals with the same datatype.
+	fn coercion<T: Id>(&self) -> Option<Type<T>> {
+		if self.lists > 0 || self.others > 0 {
+			return None;
+		}
+
+		if self.references > 0 {
+			if self.literals.is_empty() {
+				Some(Type::Id)
+			} else {
+				None
+			}
+		} else if self.literals.len() == 1 {
+			self.literals
+				.keys()
+				.next()
+				.unwrap()
+				.as_ref()
+				.map(|datatype| Type::Ref(T::from_iri(Iri::new(datatype).unwrap())))
+		} else {
+			None
+		}
+	}
+
+	/// Container of the property.
+	fn container(&self) -> Option<ContainerType> {
+		if self.lists > 0 {
+			if self.references == 0 && self.literals.is_empty() && self.others == 0 {
+				Some(ContainerType::List)
+			} else {
+				None
+			}
+		} else if self.multiple {
+			Some(ContainerType::Set)
+		} else {
+			None
+		}
+	}
+}
+
+/// IRIs of an expanded document and the usage of its properties.
+#[derive(Default)]
+struct Usage {
+	properties: BTreeMap<String, PropertyUsage>,
+	classes: BTreeSet<String>,
+}
+
+impl Usage {
+	fn object<J: JsonHash, T: Id>(&mut self, object: &Indexed<Object<J, T>>) {
+		match object.inner() {
+			Object::Node(node) => self.node(node),
+			Object::List(items) => {
+				for item in items {
+					self.object(item)
+				}
+			}
+			Object::Value(_) => (),
+		}
+	}
+
+	fn node<J: JsonHash, T: Id>(&mut self, node: &Node<J, T>) {
+		for ty in node.types() {
+			if let Reference::Id(ty) = ty {
+				self.classes.insert(ty.as_iri().as_str().to_string());
+			}
+		}
+
+		for (prop, objects) in node.properties() {
+			if let Reference::Id(prop) = prop {
+				let usage = self
+					.properties
+					.entry(prop.as_iri().as_str().to_string())
+					.or_default();
+				usage.multiple |= objects.len() > 1;
+				for object in objects {
+					match object.inner() {
+						Object::Node(node) if node.is_empty() && node.id().is_some() => {
+							usage.references += 1
+						}
+						Object::Value(Value::Literal(_, ty)) => {
+							*usage
+								.literals
+								.entry(ty.as_ref().map(|ty| ty.as_iri().as_str().to_string()))
+								.or_default() += 1
+						}
+						Object::List(_) => usage.lists += 1,
+						_ => usage.others += 1,
+					}
+				}
+			}
+
+			for object in objects {
+				self.object(object)
+			}
+		}
+
+		for (_, nodes) in node.reverse_properties() {
+			for node in nodes {
+				self.node(node.inner())
+			}
+		}
+
+		if let Some(graph) = node.graph() {
+			for object in graph {
+				self.object(object)
+			}
+		}
+
+		if let Some(included) = node.included() {
+			for node in included {
+				self.node(node.inner())
+			}
+		}
+	}
+}
+
+/// Splits the given IRI into its namespace and local name,
+/// after the last `#` or `/`.
+fn split_iri(iri: &str) -> Option<(&str, &str)> {
+	let i = iri.rfind(['#', '/'])?;
+	let (namespace, name) = iri.split_at(i + 1);
+	if name.is_empty() || name.starts_with('@') || name.contains(':') {
+		None
+	} else {
+		Some((namespace, name))
+	}
+}
+
+/// Infers a compaction context from the given expanded document.
+///
+/// This is a heuristic, useful when no context is published for the document:
+///   - each property and type IRI is given a term named after its local name
+///     (the part after the last `#` or `/`), unless multiple IRIs share the same local name;
+///   - properties whose values are always node references are coerced to `@id`,
+///     and properties whose values are always literals of the same datatype
+///     are coerced to this datatype;
+///   - properties whose values are always lists use the `@list` container,
+///     and properties with multiple values use the `@set` container;
+///   - each namespace of the properties and types is given a prefix,
+///     named after a well-known prefix (such as `schema` or `foaf`) if possible,
+///     so that IRIs without term can be compacted into compact IRIs.
+///
+/// Each step can be disabled with the given [`InferOptions`].
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, InferOptions}, Document, NoLoader};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let doc = json!({
+///   "@type": "https://schema.org/Person",
+///   "https://schema.org/name": "Alice",
+///   "https://schema.org/knows": { "@id": "https://example.org/bob" }
+/// });
+/// let expanded = doc.expand::<context::Json<Value>, _>(&NoLoader::<Value>::new()).await.unwrap();
+///
+/// let context: context::Json<Value> = context::infer_context(&expanded, InferOptions::default());
+/// assert_eq!(context.into_local_json(), json!({
+///   "Person": "https://schema.org/Person",
+///   "knows": { "@id": "https://schema.org/knows", "@type": "@id" },
+///   "name": "https://schema.org/name",
+///   "schema": "https://schema.org/"
+/// }));
+/// # }
+/// ```
+pub fn infer_context<J: JsonHash, T: Id, C: ContextMut<T>>(
+	doc: &ExpandedDocument<J, T>,
+	options: InferOptions,
+) -> C {
+	let mut usage = Usage::default();
+	for object in doc {
+		usage.object(object)
+	}
+
+	let iris = usage
+		.properties
+		.keys()
+		.chain(usage.classes.iter())
+		.collect::<BTreeSet<_>>();
+
+	let mut names: HashMap<&str, usize> = HashMap::new();
+	for iri in &iris {
+		if let Some((_, name)) = split_iri(iri) {
+			*names.entry(name).or_default() += 1
+		}
+	}
+
+	let mut context = C::new(None);
+	for iri in &iris {
+		let name = match split_iri(iri) {
+			Some((_, name)) if names[name] == 1 => name,
+			_ => continue,
+		};
+
+		let mut definition = TermDefinition::<T, C> {
+			value: Some(Term::Ref(Reference::Id(T::from_iri(
+				Iri::new(iri).unwrap(),
+			)))),
+			..TermDefinition::default()
+		};
+
+		if let Some(property) = usage.properties.get(iri.as_str()) {
+			if options.type_coercion {
+				definition.typ = property.coercion();
+			}
+
+			if options.containers {
+				if let Some(container) = property.container() {
+					definition.container.add(container);
+				}
+			}
+		}
+
+		context.set(name, Some(definition));
+	}
+
+	if options.prefixes {
+		let namespaces = iris
+			.iter()
+			.filter_map(|iri| split_iri(iri).map(|(namespace, _)| namespace))
+			.collect::<BTreeSet<_>>();
+
+		let mut count = 0;
+		for namespace in namespaces {
+			let prefix = WELL_KNOWN_PREFIXES
+				.iter()
+				.find(|(_, iri)| *iri == namespace)
+				.map(|(prefix, _)| prefix.to_string())
+				.filter(|prefix| !context.contains(prefix));
+
+			let prefix = match prefix {
+				Some(prefix) => prefix,
+				None => loop {
+					count += 1;
+					let prefix = format!("ns{}", count);
+					if !context.contains(&prefix) {
+						break prefix;
+					}
+				},
+			};
+
+			context.set(
+				&prefix,
+				Some(TermDefinition {
+					value: Some(Term::Ref(Reference::Id(T::from_iri(
+						Iri::new(namespace).unwrap(),
+					)))),
+					prefix: true,
+					..TermDefinition::default()
+				}),
+			);
+		}
+	}
+
+	context
+}
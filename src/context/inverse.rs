@@ -1,9 +1,13 @@
-use super::Context;
+use super::{Context, ContextMut, ContextMutProxy};
 use crate::{
+	compaction::{self, CompactIriMemo},
 	lang::{LenientLanguageTag, LenientLanguageTagBuf},
 	syntax::{Container, Term, Type},
-	Direction, Id, Nullable,
+	util::AsJson,
+	Direction, Error, Id, Indexed, Nullable, Object, Reference,
 };
+use generic_json::{Json, JsonClone, JsonHash};
+use iref::Iri;
 use mown::Mown;
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
@@ -13,12 +17,43 @@ use std::{cmp::Ordering, collections::HashMap, fmt};
 ///
 /// This type keeps an inversion of the underlying context which is computed
 /// when [`inverse`](Inversible::inverse) is called and reset when the context is mutably accessed.
+///
+/// An `Inversible` context can be given to [`Document::compact`](crate::Document::compact)
+/// in place of the context itself, so that the inverse context is computed only once
+/// for every document compacted against it.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Inversible}, ContextBuilder, ContextMutProxy, Document, NoLoader};
+/// # use iref::Iri;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let context: context::Json<Value> = ContextBuilder::new()
+///   .term("name", Iri::new("https://schema.org/name").unwrap())
+///   .build();
+/// let context = Inversible::new(context);
+///
+/// let loader = NoLoader::<Value>::new();
+/// for name in ["Alice", "Bob"] {
+///   let doc = json!({ "https://schema.org/name": name });
+///   let compacted = doc.compact(&context, &loader).await.unwrap();
+///   assert_eq!(compacted["name"], name);
+/// }
+///
+/// // The inverse context is shared by every compaction.
+/// assert!(std::ptr::eq(context.inversible().inverse(), context.inversible().inverse()));
+/// # }
+/// ```
 pub struct Inversible<T: Id, C> {
 	/// Underlying context.
 	context: C,
 
 	/// Inverse context.
 	inverse: Arc<OnceCell<InverseContext<T>>>,
+
+	/// IRIs compacted against the context during the current compaction run.
+	memo: Arc<CompactIriMemo<T>>,
 }
 
 impl<T: Id, C: Clone> Clone for Inversible<T, C> {
@@ -27,6 +62,7 @@ impl<T: Id, C: Clone> Clone for Inversible<T, C> {
 		Inversible {
 			context: self.context.clone(),
 			inverse: self.inverse.clone(),
+			memo: self.memo.clone(),
 		}
 	}
 }
@@ -44,18 +80,22 @@ impl<T: Id, C> std::ops::DerefMut for Inversible<T, C> {
 	#[inline]
 	fn deref_mut(&mut self) -> &mut C {
 		self.inverse = Arc::new(OnceCell::new());
+		self.memo = Arc::new(CompactIriMemo::new());
 		&mut self.context
 	}
 }
 
 impl<T: Id, C> Inversible<T, C> {
+	/// Wraps the given context.
 	pub fn new(context: C) -> Inversible<T, C> {
 		Inversible {
 			context,
 			inverse: Arc::new(OnceCell::new()),
+			memo: Arc::new(CompactIriMemo::new()),
 		}
 	}
 
+	/// Returns the inverse context, computing it on the first call.
 	pub fn inverse(&self) -> &InverseContext<T>
 	where
 		C: std::ops::Deref,
@@ -65,19 +105,113 @@ impl<T: Id, C> Inversible<T, C> {
 			.get_or_init(|| InverseContext::from(&*self.context))
 	}
 
+	/// Returns the IRIs compacted against the context during the current compaction run.
+	pub(crate) fn memo(&self) -> &CompactIriMemo<T> {
+		&self.memo
+	}
+
+	/// Compacts the given IRI using the definitions of the context.
+	///
+	/// If `vocab` is `true`, the IRI is compacted as a property or type,
+	/// using terms, compact IRIs and the vocabulary mapping.
+	/// Otherwise it is compacted as a node identifier, relative to the base IRI.
+	/// If `reverse` is `true`, terms defining reverse properties are selected.
+	/// If a `value` is given, it is used to select a term
+	/// with a matching container, type mapping or language mapping.
+	///
+	/// Compaction uses the default [`compaction::Options`](crate::compaction::Options).
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Inversible}, ContextBuilder};
+	/// # use iref::Iri;
+	/// # use serde_json::Value;
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .term("schema", Iri::new("https://schema.org/").unwrap())
+	///   .build();
+	/// let context = Inversible::new(&context);
+	///
+	/// let name = Iri::new("https://schema.org/name").unwrap();
+	/// let email = Iri::new("https://schema.org/email").unwrap();
+	/// assert_eq!(context.compact_iri(name, None, true, false).unwrap(), "name");
+	/// assert_eq!(context.compact_iri(email, None, true, false).unwrap(), "schema:email");
+	/// ```
+	pub fn compact_iri<J: JsonHash>(
+		&self,
+		iri: Iri,
+		value: Option<&Indexed<Object<J, T>>>,
+		vocab: bool,
+		reverse: bool,
+	) -> Result<String, Error>
+	where
+		C: std::ops::Deref,
+		C::Target: Context<T, LocalContext = J>,
+	{
+		compaction::compact_iri_full(
+			self.as_deref(),
+			&Term::Ref(Reference::Id(T::from_iri(iri))),
+			value,
+			vocab,
+			reverse,
+			compaction::Options::default(),
+		)
+		.map(Option::unwrap)
+	}
+
+	/// Borrows the dereferenced context, sharing the inverse context.
+	fn as_deref(&self) -> Inversible<T, &C::Target>
+	where
+		C: std::ops::Deref,
+	{
+		Inversible {
+			context: &*self.context,
+			inverse: self.inverse.clone(),
+			memo: self.memo.clone(),
+		}
+	}
+
 	pub fn into_owned<'a>(self) -> Inversible<T, Mown<'a, C>> {
 		Inversible {
 			context: Mown::Owned(self.context),
 			inverse: self.inverse,
+			memo: self.memo,
+		}
+	}
+}
+
+impl<T: Id, C: ContextMut<T>> ContextMutProxy<T> for Inversible<T, C> {
+	type Target = C;
+
+	#[inline(always)]
+	fn deref(&self) -> &C {
+		&self.context
+	}
+
+	/// Shares the inverse context, with a new compaction run memo.
+	#[inline(always)]
+	fn inversible(&self) -> Inversible<T, &C> {
+		Inversible {
+			context: &self.context,
+			inverse: self.inverse.clone(),
+			memo: Arc::new(CompactIriMemo::new()),
 		}
 	}
 }
 
+impl<J: JsonClone, K: Json, T: Id, C: AsJson<J, K>> AsJson<J, K> for Inversible<T, C> {
+	#[inline(always)]
+	fn as_json_with(&self, meta: impl Clone + Fn(Option<&J::MetaData>) -> K::MetaData) -> K {
+		self.context.as_json_with(meta)
+	}
+}
+
 impl<'a, T: Id, C> Inversible<T, &'a C> {
 	pub fn into_borrowed(self) -> Inversible<T, Mown<'a, C>> {
 		Inversible {
 			context: Mown::Borrowed(self.context),
 			inverse: self.inverse,
+			memo: self.memo,
 		}
 	}
 }
@@ -87,14 +221,21 @@ impl<'a, T: Id, C> Inversible<T, Mown<'a, C>> {
 		Inversible {
 			context: self.context.as_ref(),
 			inverse: self.inverse.clone(),
+			memo: self.memo.clone(),
 		}
 	}
 }
 
+/// Type mapping preferred when selecting a term in an [`InverseContext`].
 #[derive(Clone, PartialEq, Eq)]
 pub enum TypeSelection<T: Id> {
+	/// Reverse property.
 	Reverse,
+
+	/// Any type mapping.
 	Any,
+
+	/// Given type mapping (`Type::None` for terms without type mapping).
 	Type(Type<T>),
 }
 
@@ -148,9 +289,16 @@ struct InverseLang {
 	map: HashMap<LangDir, String>,
 }
 
+/// Language and direction mappings preferred when selecting a term in an [`InverseContext`].
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum LangSelection<'a> {
+	/// Any language and direction mapping.
 	Any,
+
+	/// Given language and direction mappings.
+	///
+	/// `Nullable::Null` selects terms whose language mapping is `null`,
+	/// and `Nullable::Some((None, None))` terms without language nor direction mapping.
 	Lang(Nullable<(Option<LenientLanguageTag<'a>>, Option<Direction>)>),
 }
 
@@ -272,13 +420,24 @@ impl<T: Id> InverseDefinition<T> {
 	}
 }
 
+/// Inverse context.
+///
+/// Maps IRIs to the terms defining them,
+/// indexed by container, type mapping and language mapping.
+/// It is used by the compaction algorithm to select the best term for an IRI.
 pub struct InverseContext<T: Id> {
 	map: HashMap<Term<T>, InverseDefinition<T>>,
 }
 
+/// Preferred type or language mappings when selecting a term in an [`InverseContext`].
 pub enum Selection<'a, T: Id> {
+	/// Any term.
 	Any,
+
+	/// Preferred type mappings, in order of preference.
 	Type(Vec<TypeSelection<T>>),
+
+	/// Preferred language and direction mappings, in order of preference.
 	Lang(Vec<LangSelection<'a>>),
 }
 
@@ -326,6 +485,9 @@ impl<T: Id> InverseContext<T> {
 		self.map.get_mut(term).unwrap()
 	}
 
+	/// Selects the term to use for the given IRI mapping,
+	/// with one of the given containers (in order of preference),
+	/// and the given preferred type or language mappings.
 	pub fn select(
 		&self,
 		var: &Term<T>,
@@ -337,6 +499,45 @@ impl<T: Id> InverseContext<T> {
 			None => None,
 		}
 	}
+
+	/// Selects the best term to compact the given IRI,
+	/// with one of the given containers (in order of preference),
+	/// and the given preferred type or language mappings.
+	///
+	/// This is the term selection used by the compaction algorithm.
+	/// Returns `None` if no term of the context matches.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, inverse::{InverseContext, Selection, TypeSelection}}, syntax::{Container, Type}, ContextBuilder};
+	/// # use iref::Iri;
+	/// # use serde_json::Value;
+	/// let url = Iri::new("https://schema.org/url").unwrap();
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("url", url)
+	///   .typed_term("homepage", url, Type::Id)
+	///   .build();
+	///
+	/// let inverse = InverseContext::from(&context);
+	/// let id = Selection::Type(vec![TypeSelection::Type(Type::Id), TypeSelection::Any]);
+	/// let none = Selection::Type(vec![TypeSelection::Type(Type::None), TypeSelection::Any]);
+	/// assert_eq!(inverse.select_term(url, &[Container::None], &id), Some("homepage"));
+	/// assert_eq!(inverse.select_term(url, &[Container::None], &none), Some("url"));
+	/// assert_eq!(inverse.select_term(url, &[Container::List], &none), None);
+	/// ```
+	#[inline(always)]
+	pub fn select_term(
+		&self,
+		iri: Iri,
+		containers: &[Container],
+		selection: &Selection<T>,
+	) -> Option<&str> {
+		self.select(
+			&Term::Ref(Reference::Id(T::from_iri(iri))),
+			containers,
+			selection,
+		)
+	}
 }
 
 impl<T: Id> Default for InverseContext<T> {
@@ -1,5 +1,5 @@
+use crate::future::{BoxFuture, FutureExt};
 use crate::{loader, Error, ErrorCode};
-use futures::future::{BoxFuture, FutureExt};
 use generic_json::Json;
 use iref::{Iri, IriBuf};
 
@@ -69,15 +69,15 @@ pub trait Loader {
 	}
 
 	/// Returns the IRI with the given identifier, if any.
-	fn iri(&self, id: crate::loader::Id) -> Option<Iri<'_>>;
+	fn iri(&self, id: crate::loader::Id) -> Option<IriBuf>;
 
 	fn load_context<'a>(
-		&'a mut self,
+		&'a self,
 		url: Iri,
 	) -> BoxFuture<'a, Result<RemoteContext<Self::Output>, Error>>;
 }
 
-impl<L: Send + Sync + crate::Loader> Loader for L
+impl<L: crate::Loader> Loader for L
 where
 	<L::Document as Json>::Object: IntoIterator,
 {
@@ -87,32 +87,48 @@ where
 		self.id(iri)
 	}
 
-	fn iri(&self, id: crate::loader::Id) -> Option<Iri<'_>> {
+	fn iri(&self, id: crate::loader::Id) -> Option<IriBuf> {
 		self.iri(id)
 	}
 
 	fn load_context<'a>(
-		&'a mut self,
+		&'a self,
 		url: Iri,
 	) -> BoxFuture<'a, Result<RemoteContext<L::Document>, Error>> {
 		let url = IriBuf::from(url);
-		async move {
-			match self.load(url.as_iri()).await {
-				Ok(remote_doc) => {
-					let (doc, source, url) = remote_doc.into_parts();
-					if let generic_json::Value::Object(obj) = doc.into() {
-						for (key, value) in obj {
-							if &*key == "@context" {
-								return Ok(RemoteContext::from_parts(url, source, value));
+		instrumented!(
+			async move {
+				match self
+					.load_with_profile(url.as_iri(), crate::loader::Profile::Context)
+					.await
+				{
+					Ok(remote_doc) => {
+						let (doc, source, url) = remote_doc.into_parts();
+						if let generic_json::Value::Object(obj) = doc.into() {
+							for (key, value) in obj {
+								if &*key == "@context" {
+									return Ok(RemoteContext::from_parts(url, source, value));
+								}
 							}
 						}
-					}
 
-					Err(ErrorCode::InvalidRemoteContext.into())
+						Err(ErrorCode::InvalidRemoteContext.into())
+					}
+					Err(e)
+						if e.code().is_limit_exceeded()
+							|| e.code() == ErrorCode::Cancelled
+							|| e.code() == ErrorCode::ForbiddenOrigin
+							|| e.code() == ErrorCode::IntegrityCheckFailed
+							|| e.code() == ErrorCode::LoadingDocumentTimeout =>
+					{
+						Err(e)
+					}
+					Err(_) => Err(ErrorCode::LoadingRemoteContextFailed.into()),
 				}
-				Err(_) => Err(ErrorCode::LoadingRemoteContextFailed.into()),
-			}
-		}
+			},
+			"load_context",
+			url = %url
+		)
 		.boxed()
 	}
 }
@@ -0,0 +1,140 @@
+use super::{ContextMut, TermDefinition};
+use crate::{Error, ErrorCode, Id};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Policy used to resolve conflicts when merging contexts.
+///
+/// See [`ContextMut::merge`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MergePolicy {
+	/// Keep the definition of the context being merged into.
+	KeepLeft,
+
+	/// Keep the definition of the merged context,
+	/// unless it redefines a protected term.
+	KeepRight,
+
+	/// Fail with a [`ErrorCode::ConflictingDefinitions`] error.
+	Error,
+}
+
+/// Term or context setting defined differently by two merged contexts.
+///
+/// This is the source of the errors returned by [`ContextMut::merge`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Conflict {
+	key: String,
+}
+
+impl Conflict {
+	/// Conflicting term, or context setting keyword (such as `@vocab`).
+	#[inline(always)]
+	pub fn key(&self) -> &str {
+		&self.key
+	}
+}
+
+impl fmt::Display for Conflict {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "conflicting definitions of `{}`", self.key)
+	}
+}
+
+impl std::error::Error for Conflict {}
+
+fn conflict(code: ErrorCode, key: &str) -> Error {
+	Error::with_source(
+		code,
+		Conflict {
+			key: key.to_string(),
+		},
+	)
+}
+
+/// Resolves a conflict between two values of a context setting.
+fn merge_setting<V: PartialEq>(
+	left: Option<V>,
+	right: Option<V>,
+	policy: MergePolicy,
+	keyword: &str,
+) -> Result<Option<V>, Error> {
+	match (left, right) {
+		(Some(left), Some(right)) if left != right => match policy {
+			MergePolicy::KeepLeft => Ok(Some(left)),
+			MergePolicy::KeepRight => Ok(Some(right)),
+			MergePolicy::Error => Err(conflict(ErrorCode::ConflictingDefinitions, keyword)),
+		},
+		(Some(left), _) => Ok(Some(left)),
+		(None, right) => Ok(right),
+	}
+}
+
+pub(super) fn merge<T: Id, C: ContextMut<T>>(
+	context: &mut C,
+	other: &C,
+	policy: MergePolicy,
+) -> Result<(), Error> {
+	let vocabulary = merge_setting(
+		context.vocabulary().cloned(),
+		other.vocabulary().cloned(),
+		policy,
+		"@vocab",
+	)?;
+	let default_language = merge_setting(
+		context.default_language().map(|tag| tag.cloned()),
+		other.default_language().map(|tag| tag.cloned()),
+		policy,
+		"@language",
+	)?;
+	let default_base_direction = merge_setting(
+		context.default_base_direction(),
+		other.default_base_direction(),
+		policy,
+		"@direction",
+	)?;
+
+	// Definitions are taken out of a copy of `other`,
+	// since term definitions are not `Clone` for every local context type.
+	let mut right_definitions = other.clone();
+	let terms: BTreeSet<&String> = other.definitions().map(|(term, _)| term).collect();
+	let mut definitions: Vec<(&str, TermDefinition<T, C>)> = Vec::new();
+	for term in terms {
+		let right = other.get(term).unwrap();
+		let mut take = || right_definitions.set(term, None).unwrap();
+		match context.get(term) {
+			Some(left) if left == right => {
+				// Same definition, possibly with a different protection.
+				if !left.protected && right.protected && policy != MergePolicy::KeepLeft {
+					definitions.push((term, take()))
+				}
+			}
+			Some(left) => match policy {
+				MergePolicy::KeepLeft => (),
+				MergePolicy::KeepRight if left.protected => {
+					return Err(conflict(ErrorCode::ProtectedTermRedefinition, term))
+				}
+				MergePolicy::KeepRight => definitions.push((term, take())),
+				MergePolicy::Error => {
+					let code = if left.protected {
+						ErrorCode::ProtectedTermRedefinition
+					} else {
+						ErrorCode::ConflictingDefinitions
+					};
+
+					return Err(conflict(code, term));
+				}
+			},
+			None => definitions.push((term, take())),
+		}
+	}
+
+	context.set_vocabulary(vocabulary);
+	context.set_default_language(default_language);
+	context.set_default_base_direction(default_base_direction);
+	for (term, definition) in definitions {
+		context.set(term, Some(definition));
+	}
+
+	Ok(())
+}
@@ -0,0 +1,160 @@
+use super::ContextMut;
+use crate::{object::Value, syntax::Term, ExpandedDocument, Id, Indexed, Node, Object, Reference};
+use generic_json::JsonHash;
+use std::collections::HashSet;
+
+/// IRIs used by an expanded document.
+struct Usage {
+	/// IRIs compacted relative to the vocabulary:
+	/// properties, reverse properties, types and datatypes.
+	vocab: HashSet<String>,
+
+	/// Every IRI of the document, including node identifiers.
+	all: HashSet<String>,
+}
+
+impl Usage {
+	fn new() -> Self {
+		Self {
+			vocab: HashSet::new(),
+			all: HashSet::new(),
+		}
+	}
+
+	fn insert_vocab<T: Id>(&mut self, r: &Reference<T>) {
+		if let Reference::Id(id) = r {
+			self.vocab.insert(id.as_iri().as_str().to_string());
+			self.all.insert(id.as_iri().as_str().to_string());
+		}
+	}
+
+	fn insert<T: Id>(&mut self, r: &Reference<T>) {
+		if let Reference::Id(id) = r {
+			self.all.insert(id.as_iri().as_str().to_string());
+		}
+	}
+
+	fn object<J: JsonHash, T: Id>(&mut self, object: &Indexed<Object<J, T>>) {
+		match object.inner() {
+			Object::Value(Value::Literal(_, Some(ty))) => {
+				self.vocab.insert(ty.as_iri().as_str().to_string());
+				self.all.insert(ty.as_iri().as_str().to_string());
+			}
+			Object::Value(_) => (),
+			Object::Node(node) => self.node(node),
+			Object::List(items) => {
+				for item in items {
+					self.object(item)
+				}
+			}
+		}
+	}
+
+	fn node<J: JsonHash, T: Id>(&mut self, node: &Node<J, T>) {
+		if let Some(id) = node.id() {
+			self.insert(id)
+		}
+
+		for ty in node.types() {
+			self.insert_vocab(ty)
+		}
+
+		for (prop, objects) in node.properties() {
+			self.insert_vocab(prop);
+			for object in objects {
+				self.object(object)
+			}
+		}
+
+		for (prop, nodes) in node.reverse_properties() {
+			self.insert_vocab(prop);
+			for node in nodes {
+				self.node(node.inner())
+			}
+		}
+
+		if let Some(graph) = node.graph() {
+			for object in graph {
+				self.object(object)
+			}
+		}
+
+		if let Some(included) = node.included() {
+			for node in included {
+				self.node(node.inner())
+			}
+		}
+	}
+}
+
+/// Returns a copy of the given context keeping only the terms
+/// that may be needed to compact the given expanded document.
+///
+/// The following term definitions are kept:
+///   - keyword aliases;
+///   - terms whose IRI is used as a property, reverse property, type or datatype;
+///   - prefixes of an IRI used in the document, which can be used to build compact IRIs.
+///
+/// Other term definitions are removed.
+/// The base IRI, vocabulary mapping, default language and base direction are kept.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, ContextBuilder, Document, NoLoader};
+/// # use iref::Iri;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let context: context::Json<Value> = ContextBuilder::new()
+///   .term("schema", Iri::new("https://schema.org/").unwrap())
+///   .term("name", Iri::new("https://schema.org/name").unwrap())
+///   .term("email", Iri::new("https://schema.org/email").unwrap())
+///   .term("foaf", Iri::new("http://xmlns.com/foaf/0.1/").unwrap())
+///   .build();
+///
+/// let doc = json!({ "name": "Alice", "schema:birthDate": "1970-01-01" });
+/// let expanded = doc
+///   .expand_with(None, &context, &NoLoader::<Value>::new(), Default::default())
+///   .await
+///   .unwrap();
+///
+/// let minimal = context::minimize(&context, &expanded);
+/// assert_eq!(minimal.into_local_json(), json!({
+///   "name": "https://schema.org/name",
+///   "schema": "https://schema.org/"
+/// }));
+/// # }
+/// ```
+pub fn minimize<J: JsonHash, T: Id, C: ContextMut<T>>(
+	context: &C,
+	doc: &ExpandedDocument<J, T>,
+) -> C {
+	let mut usage = Usage::new();
+	for object in doc {
+		usage.object(object)
+	}
+
+	let unused: Vec<String> = context
+		.definitions()
+		.filter(|(_, definition)| match &definition.value {
+			Some(Term::Keyword(_)) => false,
+			Some(Term::Ref(Reference::Id(id))) => {
+				let iri = id.as_iri();
+				!usage.vocab.contains(iri.as_str())
+					&& !(definition.prefix
+						&& usage.all.iter().any(|used| {
+							used.len() > iri.as_str().len() && used.starts_with(iri.as_str())
+						}))
+			}
+			_ => true,
+		})
+		.map(|(term, _)| term.clone())
+		.collect();
+
+	let mut result = context.clone();
+	for term in unused {
+		result.set(&term, None);
+	}
+
+	result
+}
@@ -1,31 +1,82 @@
 //! Context processing algorithm and related types.
 
+mod builder;
+mod cache;
 mod definition;
+mod diff;
+mod digest;
+mod infer;
 pub mod inverse;
 mod loader;
+mod merge;
+mod minimize;
 mod processing;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod validation;
 
+use crate::future::{BoxFuture, FutureExt, JsonMaybeSendSync, MaybeSend, MaybeSync};
 use crate::{
 	lang::{LenientLanguageTag, LenientLanguageTagBuf},
-	syntax::Term,
-	util::{AsJson, JsonFrom},
-	Direction, Error, Id, Loc, ProcessingMode, Warning,
+	syntax::{Keyword, Term, Type},
+	util::{AsAnyJson, AsJson, JsonFrom},
+	Direction, Error, ErrorCode, Id, Loc, Nullable, ProcessingMode, Reference, Warning,
+	WarningPolicy,
 };
-use futures::{future::BoxFuture, FutureExt};
-use generic_json::{JsonClone, JsonSendSync};
+use cc_traits::MapInsert;
+use generic_json::{JsonBuild, JsonClone};
 use iref::{Iri, IriBuf};
 // use langtag::{LanguageTag, LanguageTagBuf};
 use std::collections::HashMap;
+use std::sync::Arc;
 
+pub use builder::*;
+pub use cache::*;
 pub use definition::*;
+pub use diff::*;
+pub use infer::*;
 pub use inverse::{InverseContext, Inversible};
 pub use loader::*;
+pub use merge::{Conflict, MergePolicy};
+pub use minimize::*;
 use processing::*;
+pub use processing::{ContextChain, ProcessingStack};
+pub use validation::*;
 
-pub trait JsonContext = JsonSendSync + JsonClone;
+pub trait JsonContext = JsonMaybeSendSync + JsonClone;
 
 /// Options of the Context Processing Algorithm.
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Local, ProcessingOptions}, ErrorCode, MockLoader, Warning};
+/// # use iref::{Iri, IriBuf};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let schema = Iri::new("https://example.org/schema.jsonld").unwrap();
+/// let loader = MockLoader::new()
+///   .with(schema, json!({ "@context": { "name": "https://schema.org/name" } }));
+/// let local_context = json!({ "@import": "https://example.org/schema.jsonld" });
+/// let active_context = context::Json::<Value>::new(None);
+///
+/// // Report imported contexts.
+/// let options = ProcessingOptions { report_imports: true, ..Default::default() };
+/// let processed = Local::<IriBuf>::process_with(&local_context, &active_context, &loader, None, options)
+///   .await
+///   .unwrap();
+/// assert_eq!(*processed.warnings()[0], Warning::ImportedContext(schema.into()));
+///
+/// // Forbid imports.
+/// let options = ProcessingOptions { allow_import: false, ..Default::default() };
+/// let error = Local::<IriBuf>::process_with(&local_context, &active_context, &loader, None, options)
+///   .await
+///   .err()
+///   .unwrap();
+/// assert_eq!(error.code(), ErrorCode::ForbiddenImport);
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ProcessingOptions {
 	/// The processing mode
 	pub processing_mode: ProcessingMode,
@@ -35,6 +86,74 @@ pub struct ProcessingOptions {
 
 	/// Propagate the processed context.
 	pub propagate: bool,
+
+	/// Load the remote contexts referenced by a context array concurrently.
+	///
+	/// The loaded contexts are still processed in the order of the array,
+	/// so the result does not depend on the order in which they are loaded.
+	/// However, every remote context of the array is loaded,
+	/// even if processing fails before reaching it.
+	///
+	/// Default is `false`.
+	pub concurrent_context_loading: bool,
+
+	/// Maximum number of nested remote contexts,
+	/// i.e. remote contexts loaded while processing another remote context.
+	///
+	/// Exceeding this depth raises a [`ErrorCode::ContextOverflow`] error.
+	/// Default is `32`.
+	pub max_context_depth: usize,
+
+	/// Maximum number of remote contexts dereferenced while processing a local context,
+	/// including nested remote contexts, imported contexts and scoped contexts.
+	///
+	/// Contexts found in the context cache are not counted.
+	/// Exceeding this budget raises a [`ErrorCode::ContextOverflow`] error.
+	/// Default is `256`.
+	pub max_remote_contexts: usize,
+
+	/// Allow contexts to import other contexts with `@import`.
+	///
+	/// If `false`, any `@import` entry raises a [`ErrorCode::ForbiddenImport`] error.
+	/// Default is `true`.
+	pub allow_import: bool,
+
+	/// Maximum number of nested remote contexts in which `@import` is allowed.
+	///
+	/// With `Some(0)`, only the processed local context itself can use `@import`,
+	/// and not the remote contexts it references.
+	/// Imports found deeper raise a [`ErrorCode::ForbiddenImport`] error.
+	/// Default is `None` (no limit other than [`max_context_depth`](Self::max_context_depth)).
+	pub max_import_depth: Option<usize>,
+
+	/// Report each context imported with `@import`
+	/// with a [`Warning::ImportedContext`] processing warning.
+	///
+	/// Default is `false`.
+	pub report_imports: bool,
+
+	/// Skip invalid term definitions instead of failing.
+	///
+	/// Each term whose definition fails is left undefined,
+	/// and reported with a [`Warning::InvalidTermDefinition`] processing warning.
+	/// Other errors are still fatal.
+	/// Default is `false`.
+	///
+	/// See [`validate_context`] to collect all the errors of a context.
+	pub skip_invalid_terms: bool,
+
+	/// Validation of the language tags of the context (`@language` entries).
+	///
+	/// In the [`Reject`](crate::StrictLanguageTags::Reject) mode, a malformed default language
+	/// raises an [`ErrorCode::InvalidDefaultLanguage`] error, and a malformed language mapping
+	/// raises an [`ErrorCode::InvalidLanguageMapping`] error.
+	/// Default is [`StrictLanguageTags::Disabled`](crate::StrictLanguageTags::Disabled).
+	pub strict_language_tags: crate::StrictLanguageTags,
+
+	/// Suppress, collect or escalate processing warnings according to their severity.
+	///
+	/// Default is to collect every warning.
+	pub warning_policy: WarningPolicy,
 }
 
 impl ProcessingOptions {
@@ -69,6 +188,15 @@ impl Default for ProcessingOptions {
 			processing_mode: ProcessingMode::default(),
 			override_protected: false,
 			propagate: true,
+			concurrent_context_loading: false,
+			max_context_depth: 32,
+			max_remote_contexts: 256,
+			allow_import: true,
+			max_import_depth: None,
+			report_imports: false,
+			skip_invalid_terms: false,
+			strict_language_tags: crate::StrictLanguageTags::Disabled,
+			warning_policy: WarningPolicy::new(),
 		}
 	}
 }
@@ -123,6 +251,186 @@ pub trait Context<T: Id = IriBuf>: Clone {
 	fn definitions<'a>(
 		&'a self,
 	) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>>;
+
+	/// Expands the given term, compact IRI, blank node identifier or IRI
+	/// using the definitions of this context.
+	///
+	/// If `document_relative` is `true`, relative IRIs are resolved against the base IRI.
+	/// If `vocab` is `true`, terms and vocabulary-relative IRIs are expanded
+	/// using the term definitions and vocabulary mapping,
+	/// as done for keys and `@type` values.
+	/// Values that cannot be expanded are returned as [`Term::Ref`]
+	/// of an invalid [`Reference`](crate::Reference::Invalid),
+	/// and values having the form of a keyword as [`Term::Null`].
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Context}, ContextBuilder};
+	/// # use iref::Iri;
+	/// # use serde_json::Value;
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .base(Iri::new("https://example.org/").unwrap())
+	///   .vocab(Iri::new("https://schema.org/").unwrap())
+	///   .term("foaf", Iri::new("http://xmlns.com/foaf/0.1/").unwrap())
+	///   .build();
+	///
+	/// assert_eq!(context.expand_iri("name", false, true).as_str(), "https://schema.org/name");
+	/// assert_eq!(context.expand_iri("foaf:knows", false, true).as_str(), "http://xmlns.com/foaf/0.1/knows");
+	/// assert_eq!(context.expand_iri("alice", true, false).as_str(), "https://example.org/alice");
+	/// ```
+	fn expand_iri(&self, value: &str, document_relative: bool, vocab: bool) -> Term<T> {
+		let mut warnings: Vec<Loc<Warning, ()>> = Vec::new();
+		crate::expansion::expand_iri(
+			None,
+			self,
+			value,
+			&(),
+			document_relative,
+			vocab,
+			&mut warnings,
+		)
+	}
+
+	/// Returns an iterator over the prefix definitions of the context,
+	/// i.e. the terms that can be used as prefix of compact IRIs.
+	fn prefixes<'a>(
+		&'a self,
+	) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>>
+	where
+		T: 'a,
+	{
+		Box::new(
+			self.definitions()
+				.filter(|(_, definition)| definition.prefix),
+		)
+	}
+
+	/// Returns an iterator over the term definitions of the context
+	/// that are not prefix definitions.
+	fn terms<'a>(
+		&'a self,
+	) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>>
+	where
+		T: 'a,
+	{
+		Box::new(
+			self.definitions()
+				.filter(|(_, definition)| !definition.prefix),
+		)
+	}
+
+	/// Resolves the given compact IRI (such as `foaf:name`)
+	/// using the prefix definitions of the context.
+	///
+	/// Returns `None` if the given value is not a compact IRI,
+	/// or if its prefix is not defined as a prefix.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Context}, ContextBuilder};
+	/// # use iref::{Iri, IriBuf};
+	/// # use serde_json::Value;
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .term("foaf", Iri::new("http://xmlns.com/foaf/0.1/").unwrap())
+	///   .term("name", Iri::new("http://xmlns.com/foaf/0.1/name").unwrap())
+	///   .build();
+	///
+	/// assert_eq!(context.prefixes().map(|(term, _)| term.as_str()).collect::<Vec<_>>(), ["foaf"]);
+	/// assert_eq!(context.terms().map(|(term, _)| term.as_str()).collect::<Vec<_>>(), ["name"]);
+	///
+	/// let name = Iri::new("http://xmlns.com/foaf/0.1/name").unwrap();
+	/// assert_eq!(context.resolve_curie("foaf:name"), Some(IriBuf::from(name)));
+	/// assert_eq!(context.resolve_curie("name:foaf"), None);
+	/// assert_eq!(context.resolve_curie("http://xmlns.com/foaf/0.1/name"), None);
+	/// ```
+	fn resolve_curie(&self, curie: &str) -> Option<T> {
+		let (prefix, suffix) = curie.split_once(':')?;
+		if prefix.is_empty() || prefix == "_" || suffix.starts_with("//") {
+			return None;
+		}
+
+		let definition = self.get(prefix).filter(|definition| definition.prefix)?;
+		match &definition.value {
+			Some(Term::Ref(Reference::Id(id))) => {
+				let iri = format!("{}{}", id.as_iri().as_str(), suffix);
+				Iri::new(&iri).ok().map(T::from_iri)
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns an iterator over the protected terms of the context.
+	///
+	/// Protected terms cannot be redefined, and prevent the context from being
+	/// nullified, unless protection is overridden.
+	fn protected_terms<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = &'a String>>
+	where
+		T: 'a,
+	{
+		Box::new(
+			self.definitions()
+				.filter(|(_, definition)| definition.protected)
+				.map(|(term, _)| term),
+		)
+	}
+
+	/// Checks if the given term is defined and protected.
+	#[inline]
+	fn is_protected(&self, term: &str) -> bool {
+		self.get(term)
+			.map(|definition| definition.protected)
+			.unwrap_or(false)
+	}
+
+	/// Checks if the context has a protected term definition.
+	#[inline]
+	fn has_protected_terms(&self) -> bool {
+		self.protected_terms().next().is_some()
+	}
+
+	/// Cache of processed remote contexts attached to this context, if any.
+	///
+	/// Remote contexts processed on top of this context are looked up in this cache.
+	/// By default, contexts have no cache.
+	#[inline(always)]
+	fn cache(&self) -> Option<&Arc<Cache<Self>>> {
+		None
+	}
+
+	/// Computes a stable hash of the semantic content of this context.
+	///
+	/// The digest covers the base IRI, vocabulary mapping, default language and direction,
+	/// every term definition (in term order, so independently of the underlying map
+	/// iteration order) and, recursively, the previous context.
+	/// The attached [`Cache`] is not taken into account.
+	///
+	/// Two contexts with the same digest expand and compact documents the same way,
+	/// which makes the digest suitable as a cache key.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Context}, ContextBuilder};
+	/// # use iref::Iri;
+	/// # use serde_json::Value;
+	/// let a: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .term("knows", Iri::new("https://schema.org/knows").unwrap())
+	///   .build();
+	/// let b: context::Json<Value> = ContextBuilder::new()
+	///   .term("knows", Iri::new("https://schema.org/knows").unwrap())
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .build();
+	/// let c: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("http://xmlns.com/foaf/0.1/name").unwrap())
+	///   .term("knows", Iri::new("https://schema.org/knows").unwrap())
+	///   .build();
+	///
+	/// assert_eq!(a.digest(), b.digest());
+	/// assert_ne!(a.digest(), c.digest());
+	/// ```
+	fn digest(&self) -> crate::rdf::Digest {
+		digest::digest(self)
+	}
 }
 
 /// Mutable JSON-LD context.
@@ -148,6 +456,183 @@ pub trait ContextMut<T: Id = IriBuf>: Context<T> {
 
 	/// Sets the previous context.
 	fn set_previous_context(&mut self, previous: Self);
+
+	/// Attaches the given cache of processed remote contexts to the context.
+	///
+	/// By default, the cache is ignored.
+	#[inline(always)]
+	fn set_cache(&mut self, _cache: Option<Arc<Cache<Self>>>) {}
+
+	/// Removes the definition of the given term, and returns it.
+	///
+	/// As in context processing, protected terms cannot be removed:
+	/// if the term is protected, a
+	/// [`ErrorCode::ProtectedTermRedefinition`]
+	/// error is returned and the context is left unchanged.
+	/// Use [`remove_protected`](ContextMut::remove_protected) to override the protection.
+	fn remove(&mut self, term: &str) -> Result<Option<TermDefinition<T, Self>>, Error> {
+		if self.is_protected(term) {
+			Err(ErrorCode::ProtectedTermRedefinition.into())
+		} else {
+			Ok(self.set(term, None))
+		}
+	}
+
+	/// Removes the definition of the given term, even if it is protected, and returns it.
+	///
+	/// This mirrors the `override_protected` processing option.
+	#[inline]
+	fn remove_protected(&mut self, term: &str) -> Option<TermDefinition<T, Self>> {
+		self.set(term, None)
+	}
+
+	/// Resets the context to a newly-initialized active context,
+	/// as done when processing a `null` local context.
+	///
+	/// The original base URL and the attached cache are preserved.
+	/// If the context has protected terms, an
+	/// [`ErrorCode::InvalidContextNullification`]
+	/// error is returned and the context is left unchanged.
+	/// Use [`clear_with_override`](ContextMut::clear_with_override) to override the protection.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Context, ContextMut}, ContextBuilder, ErrorCode};
+	/// # use iref::Iri;
+	/// # use serde_json::Value;
+	/// let mut context: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .term("knows", Iri::new("http://xmlns.com/foaf/0.1/knows").unwrap())
+	///   .build();
+	///
+	/// let mut name = context.remove("name").ok().flatten().unwrap();
+	/// name.protected = true;
+	/// context.set("name", Some(name));
+	/// assert_eq!(context.protected_terms().collect::<Vec<_>>(), ["name"]);
+	///
+	/// assert_eq!(context.remove("name").err().unwrap().code(), ErrorCode::ProtectedTermRedefinition);
+	/// assert_eq!(context.clear().unwrap_err().code(), ErrorCode::InvalidContextNullification);
+	///
+	/// context.clear_with_override();
+	/// assert!(!context.has_protected_terms());
+	/// assert!(!context.contains("knows"));
+	/// ```
+	fn clear(&mut self) -> Result<(), Error> {
+		if self.has_protected_terms() {
+			Err(ErrorCode::InvalidContextNullification.into())
+		} else {
+			self.clear_with_override();
+			Ok(())
+		}
+	}
+
+	/// Resets the context to a newly-initialized active context,
+	/// even if it has protected terms.
+	///
+	/// This mirrors the `override_protected` processing option.
+	/// The original base URL and the attached cache are preserved.
+	fn clear_with_override(&mut self) {
+		let mut result = Self::new(self.original_base_url());
+		result.set_cache(self.cache().cloned());
+		*self = result;
+	}
+
+	/// Processes the given local context on top of this context,
+	/// and replaces this context with the result.
+	///
+	/// Only the given local context is processed:
+	/// this is equivalent to processing the concatenation of all the local contexts
+	/// applied so far, without processing them again.
+	/// The warnings emitted during processing are returned.
+	///
+	/// On error, this context is left unchanged.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Context, ContextMut}, NoLoader};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let loader = NoLoader::<Value>::new();
+	/// let mut context = context::Json::<Value>::new(None);
+	///
+	/// let update = json!({ "name": "https://schema.org/name" });
+	/// context.apply_local(&update, &loader, None, Default::default()).await.unwrap();
+	///
+	/// let update = json!({ "email": "https://schema.org/email" });
+	/// context.apply_local(&update, &loader, None, Default::default()).await.unwrap();
+	///
+	/// assert!(context.contains("name"));
+	/// assert!(context.contains("email"));
+	/// # }
+	/// ```
+	fn apply_local<'a, J: Local<T>, L: Loader + MaybeSend + MaybeSync>(
+		&'a mut self,
+		local_context: &'a J,
+		loader: &'a L,
+		base_url: Option<Iri<'a>>,
+		options: ProcessingOptions,
+	) -> BoxFuture<'a, ApplyResult<J>>
+	where
+		Self: MaybeSend + MaybeSync,
+		Self::LocalContext: From<L::Output> + From<J>,
+		L::Output: Into<J>,
+		T: MaybeSend + MaybeSync,
+	{
+		async move {
+			let (result, warnings) = local_context
+				.process_full(&*self, ProcessingStack::new(), loader, base_url, options)
+				.await?
+				.into_parts();
+			*self = result;
+			Ok(warnings)
+		}
+		.boxed()
+	}
+
+	/// Merges the term definitions and settings of the given context into this context.
+	///
+	/// The vocabulary mapping, default language and default base direction
+	/// of `other` are used when this context does not define them.
+	/// The base IRI of this context is left unchanged.
+	///
+	/// When both contexts define the same term or setting differently,
+	/// the conflict is resolved according to the given `policy`.
+	/// As in context processing, a protected term of this context
+	/// cannot be redefined by `other`:
+	/// unless the policy is [`MergePolicy::KeepLeft`],
+	/// a [`ErrorCode::ProtectedTermRedefinition`]
+	/// error is returned.
+	/// The source of the returned errors is a [`Conflict`] giving the conflicting term.
+	///
+	/// On error, this context is left unchanged.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context::{self, Context, ContextMut, MergePolicy}, ContextBuilder, ErrorCode};
+	/// # use iref::Iri;
+	/// # use serde_json::Value;
+	/// let mut context: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .build();
+	///
+	/// let foaf: context::Json<Value> = ContextBuilder::new()
+	///   .term("name", Iri::new("http://xmlns.com/foaf/0.1/name").unwrap())
+	///   .term("knows", Iri::new("http://xmlns.com/foaf/0.1/knows").unwrap())
+	///   .build();
+	///
+	/// let error = context.merge(&foaf, MergePolicy::Error).unwrap_err();
+	/// assert_eq!(error.code(), ErrorCode::ConflictingDefinitions);
+	/// assert!(!context.contains("knows"));
+	///
+	/// context.merge(&foaf, MergePolicy::KeepLeft).unwrap();
+	/// assert!(context.contains("knows"));
+	/// let name = context.get("name").unwrap().value.as_ref().unwrap();
+	/// assert_eq!(name.as_str(), "https://schema.org/name");
+	/// ```
+	fn merge(&mut self, other: &Self, policy: MergePolicy) -> Result<(), Error> {
+		merge::merge(self, other, policy)
+	}
 }
 
 /// Trait for types that are or wrap a mutable context.
@@ -159,43 +644,72 @@ pub trait ContextMutProxy<T: Id = IriBuf> {
 
 	/// Returns a reference to the mutable context.
 	fn deref(&self) -> &Self::Target;
+
+	/// Returns the context wrapped for compaction.
+	///
+	/// By default, the inverse context is computed again for each call.
+	/// An [`Inversible`] context shares its inverse context instead.
+	///
+	/// Each compaction run calls this method once,
+	/// and memoizes the IRIs it compacts in the returned wrapper,
+	/// so that they are dropped at the end of the run.
+	#[inline(always)]
+	fn inversible(&self) -> Inversible<T, &Self::Target> {
+		Inversible::new(self.deref())
+	}
 }
 
 /// Context processing result.
 pub type ProcessingResult<'s, J, C> =
 	Result<Processed<'s, J, C>, Loc<Error, <J as generic_json::Json>::MetaData>>;
 
+/// Result of [`ContextMut::apply_local`], giving the warnings emitted during processing.
+pub type ApplyResult<J> = Result<
+	Vec<Loc<Warning, <J as generic_json::Json>::MetaData>>,
+	Loc<Error, <J as generic_json::Json>::MetaData>,
+>;
+
 /// Local context used for context expansion.
 ///
 /// Local contexts can be seen as "abstract contexts" that can be processed to enrich an
 /// existing active context.
-pub trait Local<T: Id = IriBuf>: JsonSendSync {
+pub trait Local<T: Id = IriBuf>: JsonMaybeSendSync {
 	/// Process the local context with specific options.
-	fn process_full<'a, 's: 'a, C: ContextMut<T> + Send + Sync, L: Loader + Send + Sync>(
+	fn process_full<
+		'a,
+		's: 'a,
+		C: ContextMut<T> + MaybeSend + MaybeSync,
+		L: Loader + MaybeSend + MaybeSync,
+	>(
 		&'s self,
 		active_context: &'a C,
 		stack: ProcessingStack,
-		loader: &'a mut L,
+		loader: &'a L,
 		base_url: Option<Iri<'a>>,
 		options: ProcessingOptions,
 	) -> BoxFuture<'a, ProcessingResult<'s, Self, C>>
 	where
 		C::LocalContext: From<L::Output> + From<Self>,
 		L::Output: Into<Self>,
-		T: Send + Sync;
+		T: MaybeSend + MaybeSync;
 
 	/// Process the local context with specific options.
-	fn process_with<'a, 's: 'a, C: ContextMut<T> + Send + Sync, L: Loader + Send + Sync>(
+	fn process_with<
+		'a,
+		's: 'a,
+		C: ContextMut<T> + MaybeSend + MaybeSync,
+		L: Loader + MaybeSend + MaybeSync,
+	>(
 		&'s self,
 		active_context: &'a C,
-		loader: &'a mut L,
+		loader: &'a L,
 		base_url: Option<Iri<'a>>,
 		options: ProcessingOptions,
 	) -> BoxFuture<'a, ProcessingResult<'s, Self, C>>
 	where
 		C::LocalContext: From<L::Output> + From<Self>,
 		L::Output: Into<Self>,
-		T: Send + Sync,
+		T: MaybeSend + MaybeSync,
 	{
 		self.process_full(
 			active_context,
@@ -208,15 +722,20 @@ pub trait Local<T: Id = IriBuf>: JsonSendSync {
 
 	/// Process the local context with the given initial active context with the default options:
 	/// `is_remote` is `false`, `override_protected` is `false` and `propagate` is `true`.
-	fn process<'a, 's: 'a, C: ContextMut<T> + Default + Send + Sync, L: Loader + Send + Sync>(
+	fn process<
+		'a,
+		's: 'a,
+		C: ContextMut<T> + Default + MaybeSend + MaybeSync,
+		L: Loader + MaybeSend + MaybeSync,
+	>(
 		&'s self,
-		loader: &'a mut L,
+		loader: &'a L,
 		base_url: Option<Iri<'a>>,
 	) -> BoxFuture<'a, ProcessingResult<'s, Self, C>>
 	where
 		C::LocalContext: From<L::Output> + From<Self>,
 		L::Output: Into<Self>,
-		T: Send + Sync,
+		T: MaybeSend + MaybeSync,
 	{
 		async move {
 			let active_context = C::default();
@@ -351,6 +870,12 @@ impl<'a, L: generic_json::Json, C> Processed<'a, L, C> {
 		self.processed
 	}
 
+	/// Consumes the wrapper and returns the processed context
+	/// with the warnings emitted during processing.
+	pub fn into_parts(self) -> (C, Vec<Loc<Warning, L::MetaData>>) {
+		(self.processed, self.warnings)
+	}
+
 	/// Clone the referenced local context and return
 	/// a `Processed` context that owns the original local context.
 	pub fn owned(self) -> ProcessedOwned<L, C>
@@ -397,17 +922,60 @@ impl<'a, J: JsonClone, K: JsonFrom<J>, L: generic_json::Json + AsJson<J, K>, C>
 	}
 }
 
-#[derive(Clone, PartialEq, Eq)]
+/// Processed context, with JSON local contexts.
+///
+/// Term definitions and the previous context are shared between clones,
+/// so cloning an active context (as done for each type-scoped and property-scoped
+/// context during expansion) does not copy its definitions.
+/// They are copied on write, the first time a shared context is modified.
+///
+/// With the `serde` feature, processed contexts implement `Serialize` and `Deserialize`,
+/// so that they can be stored or sent and restored without being processed again.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Context, ContextMut}, ContextBuilder};
+/// # use iref::Iri;
+/// # use serde_json::Value;
+/// let context: context::Json<Value> = ContextBuilder::new()
+///   .term("name", Iri::new("https://schema.org/name").unwrap())
+///   .build();
+///
+/// let mut scoped = context.clone();
+/// assert!(scoped.shares_definitions(&context));
+///
+/// scoped.set("name", None);
+/// assert!(!scoped.shares_definitions(&context));
+/// assert!(context.get("name").is_some());
+/// ```
+#[derive(Clone)]
 pub struct Json<J: JsonContext, T: Id = IriBuf> {
 	original_base_url: Option<IriBuf>,
 	base_iri: Option<IriBuf>,
 	vocabulary: Option<Term<T>>,
 	default_language: Option<LenientLanguageTagBuf>,
 	default_base_direction: Option<Direction>,
-	previous_context: Option<Box<Self>>,
-	definitions: HashMap<String, TermDefinition<T, Self>>,
+	previous_context: Option<Arc<Self>>,
+	definitions: Arc<HashMap<String, TermDefinition<T, Self>>>,
+	cache: Option<Arc<Cache<Self>>>,
 }
 
+impl<J: JsonContext, T: Id> PartialEq for Json<J, T> {
+	/// Compares the contexts, ignoring their attached cache.
+	fn eq(&self, other: &Self) -> bool {
+		self.original_base_url == other.original_base_url
+			&& self.base_iri == other.base_iri
+			&& self.vocabulary == other.vocabulary
+			&& self.default_language == other.default_language
+			&& self.default_base_direction == other.default_base_direction
+			&& self.previous_context == other.previous_context
+			&& (Arc::ptr_eq(&self.definitions, &other.definitions)
+				|| self.definitions == other.definitions)
+	}
+}
+
+impl<J: JsonContext, T: Id> Eq for Json<J, T> {}
+
 impl<J: JsonContext, T: Id> Json<J, T> {
 	pub fn new(base_iri: Option<Iri>) -> Self {
 		Self {
@@ -417,9 +985,27 @@ impl<J: JsonContext, T: Id> Json<J, T> {
 			default_language: None,
 			default_base_direction: None,
 			previous_context: None,
-			definitions: HashMap::new(),
+			definitions: Arc::new(HashMap::new()),
+			cache: None,
 		}
 	}
+
+	/// Attaches the given cache of processed remote contexts to the context,
+	/// so that the remote contexts processed on top of it are processed only once.
+	///
+	/// See [`Cache`].
+	#[inline(always)]
+	pub fn with_cache(mut self, cache: Arc<Cache<Self>>) -> Self {
+		self.cache = Some(cache);
+		self
+	}
+
+	/// Checks if this context shares its term definitions with the given context,
+	/// meaning that none of them has been modified since one was cloned from the other.
+	#[inline(always)]
+	pub fn shares_definitions(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.definitions, &other.definitions)
+	}
 }
 
 impl<J: JsonContext, T: Id> ContextMutProxy<T> for Json<J, T> {
@@ -439,7 +1025,8 @@ impl<J: JsonContext, T: Id> Default for Json<J, T> {
 			default_language: None,
 			default_base_direction: None,
 			previous_context: None,
-			definitions: HashMap::new(),
+			definitions: Arc::new(HashMap::new()),
+			cache: None,
 		}
 	}
 }
@@ -490,6 +1077,10 @@ impl<J: JsonContext, T: Id> Context<T> for Json<J, T> {
 	) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>> {
 		Box::new(self.definitions.iter())
 	}
+
+	fn cache(&self) -> Option<&Arc<Cache<Self>>> {
+		self.cache.as_ref()
+	}
 }
 
 impl<J: JsonContext, T: Id> ContextMut<T> for Json<J, T> {
@@ -499,8 +1090,14 @@ impl<J: JsonContext, T: Id> ContextMut<T> for Json<J, T> {
 		definition: Option<TermDefinition<T, Self>>,
 	) -> Option<TermDefinition<T, Self>> {
 		match definition {
-			Some(def) => self.definitions.insert(term.to_string(), def),
-			None => self.definitions.remove(term),
+			Some(def) => Arc::make_mut(&mut self.definitions).insert(term.to_string(), def),
+			None => {
+				if self.definitions.contains_key(term) {
+					Arc::make_mut(&mut self.definitions).remove(term)
+				} else {
+					None
+				}
+			}
 		}
 	}
 
@@ -527,6 +1124,240 @@ impl<J: JsonContext, T: Id> ContextMut<T> for Json<J, T> {
 	}
 
 	fn set_previous_context(&mut self, previous: Self) {
-		self.previous_context = Some(Box::new(previous))
+		self.previous_context = Some(Arc::new(previous))
+	}
+
+	fn set_cache(&mut self, cache: Option<Arc<Cache<Self>>>) {
+		self.cache = cache
+	}
+}
+
+impl<J: JsonContext, T: Id> Json<J, T> {
+	/// Converts the processed context into an equivalent local context,
+	/// that is a JSON `@context` object.
+	///
+	/// The local context defines the terms (sorted by name), `@base`, `@vocab`,
+	/// `@language` and `@direction` of this context.
+	/// Definitions that can be written as simple term definitions are,
+	/// other definitions are written as expanded term definitions,
+	/// with their type, container, language, direction, scoped context,
+	/// nest value, index mapping, prefix flag and protection.
+	/// Processing the result on top of a newly-initialized active context
+	/// with the same base IRI gives back this context.
+	///
+	/// The metadata of the returned JSON value is the default metadata.
+	/// Use the [`AsJson`] implementation to convert the context
+	/// into another JSON type or with other metadata.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, syntax::Type, ContextBuilder};
+	/// # use iref::Iri;
+	/// # use serde_json::{json, Value};
+	/// let context: context::Json<Value> = ContextBuilder::new()
+	///   .vocab(Iri::new("https://schema.org/").unwrap())
+	///   .term("name", Iri::new("https://schema.org/name").unwrap())
+	///   .typed_term("homepage", Iri::new("https://schema.org/url").unwrap(), Type::Id)
+	///   .build();
+	///
+	/// assert_eq!(context.into_local_json(), json!({
+	///   "@vocab": "https://schema.org/",
+	///   "homepage": { "@id": "https://schema.org/url", "@type": "@id" },
+	///   "name": "https://schema.org/name"
+	/// }));
+	/// ```
+	pub fn into_local_json(self) -> J
+	where
+		J: JsonFrom<J>,
+		J::MetaData: Default,
+	{
+		self.as_json()
+	}
+}
+
+/// Checks if the given definition of the given term
+/// can be written as a simple term definition.
+fn is_simple_definition<T: Id, C: Context<T>>(
+	term: &str,
+	definition: &TermDefinition<T, C>,
+) -> bool {
+	let prefix = match &definition.value {
+		Some(value) => !term.contains(':') && !term.contains('/') && is_gen_delim_or_blank(value),
+		None => false,
+	};
+
+	definition.prefix == prefix
+		&& !definition.protected
+		&& !definition.reverse_property
+		&& definition.typ.is_none()
+		&& definition.container.is_empty()
+		&& definition.language.is_none()
+		&& definition.direction.is_none()
+		&& definition.context.is_none()
+		&& definition.nest.is_none()
+		&& definition.index.is_none()
+}
+
+/// Converts a type mapping into a JSON value.
+fn type_as_json<K: JsonBuild, T: Id>(typ: &Type<T>, meta: K::MetaData) -> K {
+	match typ {
+		Type::Ref(id) => id.as_iri().as_str().as_json_with(meta),
+		Type::Id => Keyword::Id.as_json_with(meta),
+		Type::Json => Keyword::Json.as_json_with(meta),
+		Type::None => Keyword::None.as_json_with(meta),
+		Type::Vocab => Keyword::Vocab.as_json_with(meta),
+	}
+}
+
+impl<J: JsonContext, K: JsonFrom<J>, T: Id> AsJson<J, K> for Json<J, T> {
+	fn as_json_with(&self, meta: impl Clone + Fn(Option<&J::MetaData>) -> K::MetaData) -> K {
+		let mut obj = K::Object::default();
+
+		if self.base_iri != self.original_base_url {
+			let base = match &self.base_iri {
+				Some(base_iri) => base_iri.as_str().as_json_with(meta(None)),
+				None => K::null(meta(None)),
+			};
+
+			obj.insert(K::new_key(Keyword::Base.into_str(), meta(None)), base);
+		}
+
+		if let Some(vocab) = &self.vocabulary {
+			obj.insert(
+				K::new_key(Keyword::Vocab.into_str(), meta(None)),
+				vocab.as_json_with(meta(None)),
+			);
+		}
+
+		if let Some(language) = &self.default_language {
+			obj.insert(
+				K::new_key(Keyword::Language.into_str(), meta(None)),
+				language.as_json_with(meta(None)),
+			);
+		}
+
+		if let Some(direction) = &self.default_base_direction {
+			obj.insert(
+				K::new_key(Keyword::Direction.into_str(), meta(None)),
+				direction.as_json_with(meta(None)),
+			);
+		}
+
+		let mut terms: Vec<_> = self.definitions.iter().collect();
+		terms.sort_by_key(|(term, _)| *term);
+		for (term, definition) in terms {
+			let value = if is_simple_definition(term, definition) {
+				match &definition.value {
+					Some(value) => value.as_json_with(meta(None)),
+					None => K::null(meta(None)),
+				}
+			} else {
+				let mut def = K::Object::default();
+
+				let id_key = if definition.reverse_property {
+					Keyword::Reverse
+				} else {
+					Keyword::Id
+				};
+				let id = match &definition.value {
+					Some(value) => value.as_json_with(meta(None)),
+					None => K::null(meta(None)),
+				};
+				def.insert(K::new_key(id_key.into_str(), meta(None)), id);
+
+				if let Some(typ) = &definition.typ {
+					def.insert(
+						K::new_key(Keyword::Type.into_str(), meta(None)),
+						type_as_json(typ, meta(None)),
+					);
+				}
+
+				if !definition.container.is_empty() {
+					let container = if definition.container.len() == 1 {
+						Keyword::from(*definition.container.iter().next().unwrap())
+							.as_json_with(meta(None))
+					} else {
+						K::array(
+							definition
+								.container
+								.iter()
+								.map(|c| Keyword::from(*c).as_json_with(meta(None)))
+								.collect(),
+							meta(None),
+						)
+					};
+
+					def.insert(
+						K::new_key(Keyword::Container.into_str(), meta(None)),
+						container,
+					);
+				}
+
+				if let Some(language) = &definition.language {
+					let language = match language {
+						Nullable::Some(language) => language.as_json_with(meta(None)),
+						Nullable::Null => K::null(meta(None)),
+					};
+
+					def.insert(
+						K::new_key(Keyword::Language.into_str(), meta(None)),
+						language,
+					);
+				}
+
+				if let Some(direction) = &definition.direction {
+					let direction = match direction {
+						Nullable::Some(direction) => direction.as_json_with(meta(None)),
+						Nullable::Null => K::null(meta(None)),
+					};
+
+					def.insert(
+						K::new_key(Keyword::Direction.into_str(), meta(None)),
+						direction,
+					);
+				}
+
+				if let Some(context) = &definition.context {
+					def.insert(
+						K::new_key(Keyword::Context.into_str(), meta(None)),
+						context.as_json_with(meta.clone()),
+					);
+				}
+
+				if let Some(nest) = &definition.nest {
+					def.insert(
+						K::new_key(Keyword::Nest.into_str(), meta(None)),
+						nest.as_json_with(meta(None)),
+					);
+				}
+
+				if let Some(index) = &definition.index {
+					def.insert(
+						K::new_key(Keyword::Index.into_str(), meta(None)),
+						index.as_json_with(meta(None)),
+					);
+				}
+
+				if definition.prefix {
+					def.insert(
+						K::new_key(Keyword::Prefix.into_str(), meta(None)),
+						true.as_json_with(meta(None)),
+					);
+				}
+
+				if definition.protected {
+					def.insert(
+						K::new_key(Keyword::Protected.into_str(), meta(None)),
+						true.as_json_with(meta(None)),
+					);
+				}
+
+				K::object(def, meta(None))
+			};
+
+			obj.insert(K::new_key(term.as_str(), meta(None)), value);
+		}
+
+		K::object(obj, meta(None))
 	}
 }
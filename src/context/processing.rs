@@ -1,22 +1,24 @@
 use super::{
 	Context, ContextMut, JsonContext, Loader, Local, Processed, ProcessingOptions,
-	ProcessingResult, TermDefinition,
+	ProcessingResult, RemoteContext, TermDefinition, TermOrigin,
 };
+use crate::future::{BoxFuture, FutureExt, MaybeSend, MaybeSync};
 use crate::{
 	expansion, loader,
 	syntax::{is_keyword, is_keyword_like, ContainerType, Keyword, Term, Type},
 	util::as_array,
-	BlankId, Direction, Error, ErrorCode, Id, Loc, Nullable, ProcessingMode, Reference, Warning,
+	BlankId, Direction, Error, ErrorCode, Id, Loc, Nullable, ProcessingMode, Reference,
+	SendWarningHandler, Warning,
 };
 use cc_traits::{Get, GetKeyValue, Len, MapIter};
-use futures::future::{BoxFuture, FutureExt};
 use generic_json::{Json, Key, ValueRef};
 use iref::{Iri, IriBuf, IriRef};
-use langtag::LanguageTagBuf;
 use mown::Mown;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
+use std::fmt;
 use std::future::Future;
+use std::sync::atomic::{self, AtomicUsize};
 use std::sync::Arc;
 
 /// Local JSON-LD context.
@@ -233,18 +235,23 @@ where
 
 impl<J: JsonContext, T: Id> Local<T> for J {
 	/// Load a local context.
-	fn process_full<'a, 's: 'a, C: ContextMut<T> + Send + Sync, L: Loader + Send + Sync>(
+	fn process_full<
+		'a,
+		's: 'a,
+		C: ContextMut<T> + MaybeSend + MaybeSync,
+		L: Loader + MaybeSend + MaybeSync,
+	>(
 		&'s self,
 		active_context: &'a C,
 		stack: ProcessingStack,
-		loader: &'a mut L,
+		loader: &'a L,
 		base_url: Option<Iri<'a>>,
 		options: ProcessingOptions,
 	) -> BoxFuture<'a, ProcessingResult<'s, J, C>>
 	where
 		C::LocalContext: From<L::Output> + From<Self>,
 		L::Output: Into<Self>,
-		T: Send + Sync,
+		T: MaybeSend + MaybeSync,
 	{
 		async move {
 			let mut warnings = Vec::new();
@@ -258,21 +265,78 @@ impl<J: JsonContext, T: Id> Local<T> for J {
 				&mut warnings,
 			)
 			.await?;
+			let warnings = options.warning_policy.apply(warnings)?;
 			Ok(Processed::with_warnings(self, processed, warnings))
 		}
 		.boxed()
 	}
 }
 
-/// Checks if the given context has a protected definition.
-pub fn has_protected_items<T: Id, C: Context<T>>(active_context: &C) -> bool {
-	for (_, definition) in active_context.definitions() {
-		if definition.protected {
-			return true;
+/// Concurrently loads the remote contexts referenced by the given local context.
+///
+/// Contexts already in the processing stack are not loaded.
+/// Only the successfully loaded contexts are returned:
+/// the others are loaded again when they are processed,
+/// so that errors are reported in the order of the array.
+async fn load_remote_contexts<J: JsonContext, L: Loader + MaybeSend + MaybeSync>(
+	local_context: &J,
+	remote_contexts: &ProcessingStack,
+	loader: &L,
+	base_url: Option<Iri<'_>>,
+	max_remote_contexts: usize,
+) -> HashMap<IriBuf, RemoteContext<J>>
+where
+	L::Output: Into<J>,
+{
+	let mut urls: Vec<IriBuf> = Vec::new();
+	for context in as_array(local_context).0 {
+		if let ValueRef::String(context_str) = context.as_value_ref() {
+			let context_str: &str = context_str.as_ref();
+			if let Some(url) = IriRef::new(context_str)
+				.ok()
+				.and_then(|iri_ref| resolve_iri(iri_ref, base_url))
+			{
+				if !remote_contexts.cycle(url.as_iri()) && !urls.iter().any(|u| *u == url) {
+					urls.push(url)
+				}
+			}
 		}
 	}
 
-	false
+	// Do not load more contexts than the remaining budget.
+	urls.truncate(max_remote_contexts.saturating_sub(remote_contexts.loaded_count()));
+
+	if urls.len() < 2 {
+		return HashMap::new();
+	}
+
+	// Errors are dropped as soon as possible since they cannot be sent between threads.
+	let loaded = futures::future::join_all(urls.iter().map(|url| {
+		futures::FutureExt::map(loader.load_context(url.as_iri()), |loaded| {
+			loaded.ok().map(RemoteContext::cast::<J>)
+		})
+	}))
+	.await;
+
+	urls.into_iter()
+		.zip(loaded)
+		.filter_map(|(url, loaded)| Some((url, loaded?)))
+		.collect()
+}
+
+/// Checks if the given context is a newly-initialized active context.
+fn is_newly_initialized<T: Id, C: Context<T>>(context: &C) -> bool {
+	context.definitions().next().is_none()
+		&& context.vocabulary().is_none()
+		&& context.default_language().is_none()
+		&& context.default_base_direction().is_none()
+		&& context.previous_context().is_none()
+		&& context.base_iri() == context.original_base_url()
+}
+
+/// Checks if the given context has a protected definition.
+pub fn has_protected_items<T: Id, C: Context<T>>(active_context: &C) -> bool {
+	active_context.has_protected_terms()
 }
 
 /// Resolve `iri_ref` against the given base IRI.
@@ -293,12 +357,20 @@ struct StackNode {
 
 	/// URL of the last loaded context.
 	url: IriBuf,
+
+	/// Number of frames, including this one.
+	depth: usize,
 }
 
 impl StackNode {
 	/// Create a new stack frame registering the load of the given context URL.
 	fn new(previous: Option<Arc<StackNode>>, url: IriBuf) -> StackNode {
-		StackNode { previous, url }
+		let depth = previous.as_ref().map(|p| p.depth).unwrap_or(0) + 1;
+		StackNode {
+			previous,
+			url,
+			depth,
+		}
 	}
 
 	/// Checks if this frame or any parent holds the given URL.
@@ -317,15 +389,40 @@ impl StackNode {
 /// Context processing stack.
 ///
 /// Contains the list of the loaded contexts to detect loops.
+/// It also counts the remote contexts dereferenced since its creation,
+/// this count being shared by all the copies of the stack.
 #[derive(Clone)]
 pub struct ProcessingStack {
 	head: Option<Arc<StackNode>>,
+	loaded: Arc<AtomicUsize>,
+
+	/// Chain of the remote contexts leading to the remote context being processed.
+	///
+	/// It is computed once when entering the remote context,
+	/// and shared by the origins of all the terms it defines.
+	current: ContextChain,
+
+	/// Whether cycles are errors (`validate scoped context` flag of the specification).
+	///
+	/// Cycles are ignored when processing scoped contexts during term definition.
+	validate: bool,
 }
 
 impl ProcessingStack {
 	/// Creates a new empty processing stack.
 	pub fn new() -> ProcessingStack {
-		ProcessingStack { head: None }
+		ProcessingStack {
+			head: None,
+			loaded: Arc::new(AtomicUsize::new(0)),
+			current: ContextChain::default(),
+			validate: true,
+		}
+	}
+
+	/// Returns the same stack, ignoring cycles instead of reporting them.
+	fn without_validation(mut self) -> ProcessingStack {
+		self.validate = false;
+		self
 	}
 
 	/// Checks if the stack is empty.
@@ -333,6 +430,42 @@ impl ProcessingStack {
 		self.head.is_none()
 	}
 
+	/// Number of contexts in the stack,
+	/// i.e. the number of remote contexts including each other up to the last loaded one.
+	pub fn depth(&self) -> usize {
+		self.head.as_ref().map(|head| head.depth).unwrap_or(0)
+	}
+
+	/// Number of remote contexts dereferenced since the creation of the stack.
+	pub fn loaded_count(&self) -> usize {
+		self.loaded.load(atomic::Ordering::SeqCst)
+	}
+
+	/// Registers the dereferencing of a remote context,
+	/// and returns the new number of dereferenced contexts.
+	fn count_load(&self) -> usize {
+		self.loaded.fetch_add(1, atomic::Ordering::SeqCst) + 1
+	}
+
+	/// Returns the chain of contexts in the stack,
+	/// from the first loaded context to the last.
+	pub fn chain(&self) -> ContextChain {
+		ContextChain::from_node(self.head.as_ref())
+	}
+
+	/// Returns the chain of contexts leading to the remote context being processed.
+	///
+	/// This is empty when processing a local context given inline.
+	fn current_chain(&self) -> ContextChain {
+		self.current.clone()
+	}
+
+	/// Returns the same stack, entering the last loaded context.
+	fn entered(mut self) -> ProcessingStack {
+		self.current = self.chain();
+		self
+	}
+
 	/// Checks if the given URL is already in the stack.
 	///
 	/// This is used for loop detection.
@@ -365,25 +498,139 @@ impl Default for ProcessingStack {
 	}
 }
 
+/// Chain of remote contexts loading each other.
+///
+/// This is the source of the [`ErrorCode::ContextOverflow`] errors
+/// raised by context processing, listing the contexts being processed
+/// from the first loaded context to the one exceeding the limits,
+/// or to the context loaded again in case of cycle (see [`ContextChain::is_cycle`]).
+///
+/// It is also the source of the [`ErrorCode::CyclicIriMapping`] errors,
+/// listing the remote contexts being processed
+/// up to the one containing the cyclic term definitions
+/// (empty if they are in an inline context).
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, ContextChain, Local, ProcessingOptions}, ErrorCode, MockLoader};
+/// # use iref::{Iri, IriBuf};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let a = Iri::new("https://example.org/a.jsonld").unwrap();
+/// let b = Iri::new("https://example.org/b.jsonld").unwrap();
+/// let c = Iri::new("https://example.org/c.jsonld").unwrap();
+/// let loader = MockLoader::new()
+///   .with(a, json!({ "@context": "https://example.org/b.jsonld" }))
+///   .with(b, json!({ "@context": "https://example.org/c.jsonld" }))
+///   .with(c, json!({ "@context": { "name": "https://schema.org/name" } }));
+///
+/// let options = ProcessingOptions { max_context_depth: 2, ..Default::default() };
+/// let error = Local::<IriBuf>::process_with(&json!(a.as_str()), &context::Json::<Value>::new(None), &loader, None, options)
+///   .await
+///   .err()
+///   .unwrap()
+///   .unwrap();
+///
+/// assert_eq!(error.code(), ErrorCode::ContextOverflow);
+/// let chain = std::error::Error::source(&error).unwrap().downcast_ref::<ContextChain>().unwrap();
+/// assert_eq!(chain.urls(), [a, b, c]);
+/// assert!(!chain.is_cycle());
+///
+/// // `c` including `a` again.
+/// let loader = MockLoader::new()
+///   .with(a, json!({ "@context": "https://example.org/b.jsonld" }))
+///   .with(b, json!({ "@context": "https://example.org/c.jsonld" }))
+///   .with(c, json!({ "@context": "https://example.org/a.jsonld" }));
+///
+/// let error = Local::<IriBuf>::process_with(&json!(a.as_str()), &context::Json::<Value>::new(None), &loader, None, Default::default())
+///   .await
+///   .err()
+///   .unwrap()
+///   .unwrap();
+///
+/// assert_eq!(error.code(), ErrorCode::ContextOverflow);
+/// let chain = std::error::Error::source(&error).unwrap().downcast_ref::<ContextChain>().unwrap();
+/// assert_eq!(chain.urls(), [a, b, c, a]);
+/// assert!(chain.is_cycle());
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ContextChain {
+	urls: Arc<[IriBuf]>,
+}
+
+impl ContextChain {
+	/// Builds the chain ending with the given stack frame.
+	fn from_node(mut node: Option<&Arc<StackNode>>) -> Self {
+		let mut urls = Vec::new();
+		while let Some(n) = node {
+			urls.push(n.url.clone());
+			node = n.previous.as_ref();
+		}
+
+		urls.reverse();
+		ContextChain { urls: urls.into() }
+	}
+
+	/// URLs of the contexts, from the first loaded context to the last.
+	#[inline(always)]
+	pub fn urls(&self) -> &[IriBuf] {
+		&self.urls
+	}
+
+	/// Checks if the last context of the chain is also loaded before in the chain.
+	#[inline(always)]
+	pub fn is_cycle(&self) -> bool {
+		match self.urls.split_last() {
+			Some((last, previous)) => previous.contains(last),
+			None => false,
+		}
+	}
+
+	/// Adds the given URL at the end of the chain.
+	fn with(self, url: Iri) -> Self {
+		let mut urls = self.urls.to_vec();
+		urls.push(url.into());
+		ContextChain { urls: urls.into() }
+	}
+}
+
+impl fmt::Display for ContextChain {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, url) in self.urls.iter().enumerate() {
+			if i > 0 {
+				write!(f, " -> ")?;
+			}
+
+			write!(f, "{}", url)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl std::error::Error for ContextChain {}
+
 // This function tries to follow the recommended context proessing algorithm.
 // See `https://www.w3.org/TR/json-ld11-api/#context-processing-algorithm`.
 //
 // The recommended default value for `remote_contexts` is the empty set,
 // `false` for `override_protected`, and `true` for `propagate`.
-fn process_context<
+pub(super) fn process_context<
 	'a,
 	J: JsonContext,
-	T: Id + Send + Sync,
-	C: ContextMut<T> + Send + Sync,
-	L: Loader + Send + Sync,
+	T: Id + MaybeSend + MaybeSync,
+	C: ContextMut<T> + MaybeSend + MaybeSync,
+	L: Loader + MaybeSend + MaybeSync,
 >(
 	active_context: &'a C,
 	local_context: &'a J,
-	mut remote_contexts: ProcessingStack,
-	loader: &'a mut L,
+	remote_contexts: ProcessingStack,
+	loader: &'a L,
 	base_url: Option<Iri>,
 	mut options: ProcessingOptions,
-	warnings: &'a mut Vec<Loc<Warning, J::MetaData>>,
+	warnings: &'a mut dyn SendWarningHandler<J::MetaData>,
 ) -> BoxFuture<'a, Result<C, Loc<Error, J::MetaData>>>
 where
 	C::LocalContext: From<L::Output> + From<J>,
@@ -422,6 +669,20 @@ where
 			result.set_previous_context(active_context.clone());
 		}
 
+		// Load the remote contexts of the array ahead of processing, if enabled.
+		let mut loaded_contexts = if options.concurrent_context_loading {
+			load_remote_contexts::<J, _>(
+				local_context,
+				&remote_contexts,
+				loader,
+				base_url,
+				options.max_remote_contexts,
+			)
+			.await
+		} else {
+			HashMap::new()
+		};
+
 		// 4) If local context is not an array, set it to an array containing only local context.
 		let (local_context, _) = as_array(local_context);
 
@@ -446,6 +707,7 @@ where
 						// `base_iri` and `original_base_url` to the value of `original_base_url` in
 						// active context, ...
 						result = C::new(active_context.original_base_url());
+						result.set_cache(active_context.cache().cloned());
 
 						// ... and, if `propagate` is `false`, `previous_context` in `result` to the
 						// previous value of `result`.
@@ -491,33 +753,111 @@ where
 					// If the document has no top-level map with an @context entry, an invalid remote
 					// context has been detected and processing is aborted.
 					// Set loaded context to the value of that entry.
+					// Remote contexts processed on top of a newly-initialized active context
+					// outside of any other remote context are looked up in the cache, if any.
+					// A context including itself, directly or indirectly, would make the context
+					// overflow: this is reported as soon as the cycle is detected, with the chain
+					// of contexts forming it.
+					// Cycles are ignored when validating scoped contexts.
+					//
+					// Each item of the array is processed with its own copy of remote contexts,
+					// so that sibling contexts are not considered as including each other.
+					let mut remote_contexts = remote_contexts.clone();
+					if remote_contexts.validate && remote_contexts.cycle(context_iri.as_iri()) {
+						return Err(Error::with_source(
+							ErrorCode::ContextOverflow,
+							remote_contexts.chain().with(context_iri.as_iri()),
+						)
+						.located(source, context.metadata().clone()));
+					}
+
+					let cache = active_context
+						.cache()
+						.filter(|_| remote_contexts.is_empty() && is_newly_initialized(&result));
 					if remote_contexts.push(context_iri.as_iri()) {
-						let context_document = loader
-							.load_context(context_iri.as_iri())
-							.await
-							.map_err(|e| e.located(source, context.metadata().clone()))?
-							.cast::<J>();
-						let loaded_context = context_document.context();
+						if remote_contexts.depth() > options.max_context_depth {
+							return Err(Error::with_source(
+								ErrorCode::ContextOverflow,
+								remote_contexts.chain(),
+							)
+							.located(source, context.metadata().clone()));
+						}
 
 						// Set result to the result of recursively calling this algorithm, passing result
 						// for active context, loaded context for local context, the documentUrl of context
 						// document for base URL, and a copy of remote contexts.
 						let new_options = ProcessingOptions {
-							processing_mode: options.processing_mode,
 							override_protected: false,
 							propagate: true,
+							..options
+						};
+
+						let cached = cache.and_then(|cache| {
+							cache.get(context_iri.as_iri(), result.base_iri(), new_options)
+						});
+
+						if let Some(cached) = cached {
+							result = cached;
+							result.set_cache(active_context.cache().cloned());
+							continue;
+						}
+
+						if remote_contexts.count_load() > options.max_remote_contexts {
+							return Err(Error::with_source(
+								ErrorCode::ContextOverflow,
+								remote_contexts.chain(),
+							)
+							.located(source, context.metadata().clone()));
+						}
+
+						let context_document = match loaded_contexts.remove(&context_iri) {
+							Some(context_document) => context_document,
+							None => loader
+								.load_context(context_iri.as_iri())
+								.await
+								.map_err(|e| e.located(source, context.metadata().clone()))?
+								.cast::<J>(),
 						};
+						let loaded_context = context_document.context();
+
+						// Cached contexts are processed without cache,
+						// so that they do not reference the cache themselves.
+						let base_iri = result.base_iri().map(IriBuf::from);
+						if cache.is_some() {
+							result.set_cache(None)
+						}
 
-						result = loaded_context
-							.process_full(
+						let (processed, remote_warnings) = instrumented!(
+							loaded_context.process_full(
 								&result,
-								remote_contexts.clone(),
+								remote_contexts.clone().entered(),
 								loader,
 								Some(context_document.url()),
 								new_options,
-							)
-							.await?
-							.into_inner();
+							),
+							"process_context",
+							iri = %context_iri
+						)
+						.await?
+						.into_parts();
+						result = processed;
+
+						if let Some(cache) = cache {
+							if remote_warnings.is_empty() {
+								cache.insert(
+									context_iri.as_iri(),
+									base_iri.as_ref().map(IriBuf::as_iri),
+									new_options,
+									result.clone(),
+								);
+							}
+
+							result.set_cache(Some(cache.clone()))
+						}
+
+						for warning in remote_warnings {
+							warnings.handle(warning)
+						}
 						// result = process_context(&result, loaded_context, remote_contexts, loader, Some(context_document.url()), new_options).await?
 					}
 				}
@@ -554,6 +894,11 @@ where
 								.located(source, import_value.metadata().clone()));
 						}
 
+						if !options.allow_import {
+							return Err(ErrorCode::ForbiddenImport
+								.located(source, import_value.metadata().clone()));
+						}
+
 						if let Some(import_value_str) = import_value.as_str() {
 							// 5.6.3) Initialize import to the result of resolving the value of
 							// @import.
@@ -567,7 +912,33 @@ where
 									.located(source, import_value.metadata().clone()));
 							};
 
+							if let Some(max_import_depth) = options.max_import_depth {
+								if remote_contexts.depth() > max_import_depth {
+									return Err(Error::with_source(
+										ErrorCode::ForbiddenImport,
+										remote_contexts.chain().with(import.as_iri()),
+									)
+									.located(source, import_value.metadata().clone()));
+								}
+							}
+
+							if options.report_imports {
+								warnings.handle(Loc::new(
+									Warning::ImportedContext(import.clone()),
+									source,
+									import_value.metadata().clone(),
+								));
+							}
+
 							// 5.6.4) Dereference import.
+							if remote_contexts.count_load() > options.max_remote_contexts {
+								return Err(Error::with_source(
+									ErrorCode::ContextOverflow,
+									remote_contexts.chain().with(import.as_iri()),
+								)
+								.located(source, import_value.metadata().clone()));
+							}
+
 							let import_context_document = loader
 								.load_context(import.as_iri())
 								.await
@@ -708,12 +1079,16 @@ where
 						} else if let Some(str_value) = value.as_str() {
 							// 5.9.3) Otherwise, if value is string, the default language of result is
 							// set to value.
-							match LanguageTagBuf::parse_copy(str_value) {
+							match options.strict_language_tags.parse(str_value) {
 								Ok(lang) => result.set_default_language(Some(lang.into())),
+								Err(_) if options.strict_language_tags.rejects() => {
+									return Err(ErrorCode::InvalidDefaultLanguage
+										.located(source, value.metadata().clone()))
+								}
 								Err(err) => {
 									// If value is not well-formed according to section 2.2.9 of [BCP47],
 									// processors SHOULD issue a warning.
-									warnings.push(Loc::new(
+									warnings.handle(Loc::new(
 										Warning::MalformedLanguageTag(str_value.to_string(), err),
 										source,
 										value.metadata().clone(),
@@ -778,21 +1153,40 @@ where
 						match key {
 							"@base" | "@direction" | "@import" | "@language" | "@propagate"
 							| "@protected" | "@version" | "@vocab" => (),
-							_ => define(
-								&mut result,
-								&context,
-								key,
-								key_metadata,
-								&mut defined,
-								remote_contexts.clone(),
-								loader,
-								base_url,
-								protected,
-								options,
-								warnings,
-							)
-							.await
-							.map_err(|e| e.located(source, key_metadata.clone()))?,
+							_ => {
+								let definition = define(
+									&mut result,
+									&context,
+									key,
+									key_metadata,
+									&mut defined,
+									remote_contexts.clone(),
+									loader,
+									base_url,
+									protected,
+									options,
+									warnings,
+								)
+								.await;
+
+								if let Err(e) = definition {
+									if options.skip_invalid_terms {
+										// Terms left half-defined by the failed definition
+										// are defined again from scratch.
+										defined.retain(|_, done| *done);
+										warnings.handle(Loc::new(
+											Warning::InvalidTermDefinition(
+												key.to_string(),
+												e.code(),
+											),
+											source,
+											key_metadata.clone(),
+										));
+									} else {
+										return Err(e.located(source, key_metadata.clone()));
+									}
+								}
+							}
 						}
 					}
 				}
@@ -810,12 +1204,12 @@ where
 	.boxed()
 }
 
-fn is_gen_delim(c: char) -> bool {
+pub(super) fn is_gen_delim(c: char) -> bool {
 	matches!(c, ':' | '/' | '?' | '#' | '[' | ']' | '@')
 }
 
 // Checks if the input term is an IRI ending with a gen-delim character, or a blank node identifier.
-fn is_gen_delim_or_blank<T: Id>(t: &Term<T>) -> bool {
+pub(super) fn is_gen_delim_or_blank<T: Id>(t: &Term<T>) -> bool {
 	match t {
 		Term::Ref(Reference::Blank(_)) => true,
 		Term::Ref(Reference::Id(id)) => {
@@ -855,9 +1249,9 @@ fn contains_between_boundaries(id: &str, c: char) -> bool {
 pub fn define<
 	'a,
 	J: JsonContext,
-	T: Id + Send + Sync,
-	C: ContextMut<T> + Send + Sync,
-	L: Loader + Send + Sync,
+	T: Id + MaybeSend + MaybeSync,
+	C: ContextMut<T> + MaybeSend + MaybeSync,
+	L: Loader + MaybeSend + MaybeSync,
 >(
 	active_context: &'a mut C,
 	local_context: &'a LocalContextObject<'a, J::Object>,
@@ -865,14 +1259,14 @@ pub fn define<
 	term_metadata: &'a J::MetaData,
 	defined: &'a mut HashMap<String, bool>,
 	remote_contexts: ProcessingStack,
-	loader: &'a mut L,
+	loader: &'a L,
 	base_url: Option<Iri<'a>>,
 	protected: bool,
 	options: ProcessingOptions,
-	warnings: &'a mut Vec<Loc<Warning, J::MetaData>>,
+	warnings: &'a mut dyn SendWarningHandler<J::MetaData>,
 ) -> BoxFuture<'a, Result<(), Error>>
 where
-	C::LocalContext: From<L::Output> + From<J> + Send + Sync,
+	C::LocalContext: From<L::Output> + From<J> + MaybeSend + MaybeSync,
 	L::Output: Into<J>,
 {
 	let source = loader.id_opt(base_url);
@@ -882,7 +1276,11 @@ where
 			// that the term definition has already been created), return.
 			Some(true) => Ok(()),
 			// Otherwise, if the value is false, a cyclic IRI mapping error has been detected and processing is aborted.
-			Some(false) => Err(ErrorCode::CyclicIriMapping.into()),
+			// The error source is the chain of remote contexts being processed.
+			Some(false) => Err(Error::with_source(
+				ErrorCode::CyclicIriMapping,
+				remote_contexts.current_chain(),
+			)),
 			None => {
 				if term.is_empty() {
 					return Err(ErrorCode::InvalidTermDefinition.into());
@@ -934,7 +1332,7 @@ where
 							// If term has the form of a keyword (i.e., it matches the ABNF rule "@"1*ALPHA
 							// from [RFC5234]), return; processors SHOULD generate a warning.
 							if is_keyword_like(term) {
-								warnings.push(Loc::new(
+								warnings.handle(Loc::new(
 									Warning::KeywordLikeTerm(term.to_string()),
 									source,
 									term_metadata.clone(),
@@ -972,6 +1370,7 @@ where
 					// `false`, `protected` to `protected`, and `reverse_property` to `false`.
 					let mut definition = TermDefinition::<T, C> {
 						protected,
+						origin: Some(TermOrigin::new(remote_contexts.current_chain(), source)),
 						..Default::default()
 					};
 
@@ -1050,7 +1449,7 @@ where
 							// If the value associated with the @reverse entry is a string having
 							// the form of a keyword, return; processors SHOULD generate a warning.
 							if is_keyword_like(reverse_value) {
-								warnings.push(Loc::new(
+								warnings.handle(Loc::new(
 									Warning::KeywordLikeValue(reverse_value.into()),
 									source,
 									reverse_value_metadata.clone(),
@@ -1147,7 +1546,7 @@ where
 									// keyword, but has the form of a keyword, return;
 									// processors SHOULD generate a warning.
 									if is_keyword_like(id_value) && !is_keyword(id_value) {
-										warnings.push(Loc::new(
+										warnings.handle(Loc::new(
 											Warning::KeywordLikeValue(id_value.into()),
 											source,
 											id_value_metadata.clone(),
@@ -1463,14 +1862,16 @@ where
 						process_context(
 							active_context,
 							&*context,
-							remote_contexts.clone(),
+							remote_contexts.clone().without_validation(),
 							loader,
 							base_url,
 							options.with_override(),
 							warnings,
 						)
 						.await
-						.map_err(|_| Error::from(ErrorCode::InvalidScopedContext))?;
+						.map_err(|e| {
+							Error::with_source(ErrorCode::InvalidScopedContext, e.unwrap())
+						})?;
 
 						// Set the local context of definition to context, and base URL to base URL.
 						definition.context = Some(C::LocalContext::from((*context).clone()));
@@ -1492,10 +1893,13 @@ where
 								ValueRef::Null => Nullable::Null,
 								ValueRef::String(lang_str) => {
 									let lang_str: &str = lang_str.as_ref();
-									match LanguageTagBuf::parse_copy(lang_str) {
+									match options.strict_language_tags.parse(lang_str) {
 										Ok(lang) => Nullable::Some(lang.into()),
+										Err(_) if options.strict_language_tags.rejects() => {
+											return Err(ErrorCode::InvalidLanguageMapping.into())
+										}
 										Err(err) => {
-											warnings.push(Loc::new(
+											warnings.handle(Loc::new(
 												Warning::MalformedLanguageTag(
 													lang_str.to_string(),
 													err,
@@ -1635,9 +2039,9 @@ fn invalid_iri<T: Id, M: Clone>(
 	value: String,
 	source: Option<loader::Id>,
 	metadata: &M,
-	warnings: &mut Vec<Loc<Warning, M>>,
+	warnings: &mut dyn SendWarningHandler<M>,
 ) -> Term<T> {
-	warnings.push(Loc::new(
+	warnings.handle(Loc::new(
 		Warning::MalformedIri(value.clone()),
 		source,
 		metadata.clone(),
@@ -1649,9 +2053,9 @@ fn invalid_iri<T: Id, M: Clone>(
 fn expand_iri<
 	'a,
 	J: JsonContext,
-	T: Id + Send + Sync,
-	C: ContextMut<T> + Send + Sync,
-	L: Loader + Send + Sync,
+	T: Id + MaybeSend + MaybeSync,
+	C: ContextMut<T> + MaybeSend + MaybeSync,
+	L: Loader + MaybeSend + MaybeSync,
 >(
 	active_context: &'a mut C,
 	value: &str,
@@ -1662,10 +2066,10 @@ fn expand_iri<
 	local_context: &'a LocalContextObject<'a, J::Object>,
 	defined: &'a mut HashMap<String, bool>,
 	remote_contexts: ProcessingStack,
-	loader: &'a mut L,
+	loader: &'a L,
 	options: ProcessingOptions,
-	warnings: &'a mut Vec<Loc<Warning, J::MetaData>>,
-) -> impl 'a + Send + Future<Output = Result<Term<T>, Error>>
+	warnings: &'a mut dyn SendWarningHandler<J::MetaData>,
+) -> impl 'a + MaybeSend + Future<Output = Result<Term<T>, Error>>
 where
 	C::LocalContext: From<L::Output> + From<J>,
 	L::Output: Into<J>,
@@ -1678,7 +2082,7 @@ where
 			// If value has the form of a keyword, a processor SHOULD generate a warning and return
 			// null.
 			if is_keyword_like(value.as_ref()) {
-				warnings.push(Loc::new(
+				warnings.handle(Loc::new(
 					Warning::KeywordLikeValue(value),
 					source,
 					metadata.clone(),
@@ -0,0 +1,372 @@
+use super::{Context, ContextMut, Json, JsonContext, TermDefinition};
+use crate::{
+	syntax::{ContainerType, Keyword, Term, Type},
+	BlankId, Direction, Id, Nullable, Reference,
+};
+use iref::{Iri, IriBuf};
+use serde::{
+	de::{self, Deserialize, Deserializer, MapAccess, Visitor},
+	ser::{Serialize, SerializeMap, Serializer},
+};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+
+fn term_from_str<T: Id>(s: &str) -> Term<T> {
+	match Keyword::try_from(s) {
+		Ok(keyword) => Term::Keyword(keyword),
+		Err(_) => Term::Ref(reference_from_str(s)),
+	}
+}
+
+fn reference_from_str<T: Id>(s: &str) -> Reference<T> {
+	if let Ok(blank) = BlankId::try_from(s) {
+		Reference::Blank(blank)
+	} else if let Ok(iri) = Iri::new(s) {
+		Reference::Id(T::from_iri(iri))
+	} else {
+		Reference::Invalid(s.to_string())
+	}
+}
+
+/// Term serialized as a string, or `null` for [`Term::Null`].
+struct SerTerm<'a, T: Id>(&'a Term<T>);
+
+impl<'a, T: Id> Serialize for SerTerm<'a, T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self.0 {
+			Term::Null => serializer.serialize_unit(),
+			term => serializer.serialize_str(term.as_str()),
+		}
+	}
+}
+
+fn deserialize_term<'de, T: Id, A: MapAccess<'de>>(map: &mut A) -> Result<Term<T>, A::Error> {
+	Ok(match map.next_value::<Option<String>>()? {
+		Some(s) => term_from_str(&s),
+		None => Term::Null,
+	})
+}
+
+fn deserialize_iri<'de, A: MapAccess<'de>>(map: &mut A) -> Result<IriBuf, A::Error> {
+	let s: String = map.next_value()?;
+	IriBuf::new(&s).map_err(|_| de::Error::custom(format!("invalid IRI `{}`", s)))
+}
+
+fn deserialize_direction<'de, A: MapAccess<'de>>(map: &mut A) -> Result<Direction, A::Error> {
+	let s: String = map.next_value()?;
+	Direction::try_from(s.as_str())
+		.map_err(|_| de::Error::custom(format!("invalid base direction `{}`", s)))
+}
+
+fn type_as_str<T: Id>(ty: &Type<T>) -> &str {
+	match ty {
+		Type::Id => "@id",
+		Type::Json => "@json",
+		Type::None => "@none",
+		Type::Vocab => "@vocab",
+		Type::Ref(t) => t.as_iri().into_str(),
+	}
+}
+
+fn type_from_str<T: Id, E: de::Error>(s: &str) -> Result<Type<T>, E> {
+	match s {
+		"@id" => Ok(Type::Id),
+		"@json" => Ok(Type::Json),
+		"@none" => Ok(Type::None),
+		"@vocab" => Ok(Type::Vocab),
+		_ => match Iri::new(s) {
+			Ok(iri) => Ok(Type::Ref(T::from_iri(iri))),
+			Err(_) => Err(E::custom(format!("invalid type mapping `{}`", s))),
+		},
+	}
+}
+
+/// Serializes a term definition as a map.
+///
+/// Entries with a default value are omitted.
+/// Nullable mappings are serialized as `null` when explicitly unset.
+impl<T: Id, C: Context<T>> Serialize for TermDefinition<T, C>
+where
+	C::LocalContext: Serialize,
+{
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+
+		if let Some(value) = &self.value {
+			map.serialize_entry("value", &SerTerm(value))?
+		}
+
+		if self.prefix {
+			map.serialize_entry("prefix", &true)?
+		}
+
+		if self.protected {
+			map.serialize_entry("protected", &true)?
+		}
+
+		if self.reverse_property {
+			map.serialize_entry("reverse", &true)?
+		}
+
+		if let Some(base_url) = &self.base_url {
+			map.serialize_entry("baseUrl", base_url.as_str())?
+		}
+
+		if let Some(context) = &self.context {
+			map.serialize_entry("context", context)?
+		}
+
+		if !self.container.is_empty() {
+			let container: Vec<&str> = self
+				.container
+				.iter()
+				.map(|c| Keyword::from(*c).into_str())
+				.collect();
+			map.serialize_entry("container", &container)?
+		}
+
+		if let Some(direction) = &self.direction {
+			map.serialize_entry("direction", &direction.option().map(|dir| dir.to_string()))?
+		}
+
+		if let Some(index) = &self.index {
+			map.serialize_entry("index", index)?
+		}
+
+		if let Some(language) = &self.language {
+			map.serialize_entry(
+				"language",
+				&language.as_ref().option().map(|tag| tag.as_str()),
+			)?
+		}
+
+		if let Some(nest) = &self.nest {
+			map.serialize_entry("nest", nest)?
+		}
+
+		if let Some(ty) = &self.typ {
+			map.serialize_entry("type", type_as_str(ty))?
+		}
+
+		map.end()
+	}
+}
+
+struct TermDefinitionVisitor<T, C>(PhantomData<(T, C)>);
+
+impl<'de, T: Id, C: Context<T>> Visitor<'de> for TermDefinitionVisitor<T, C>
+where
+	C::LocalContext: Deserialize<'de>,
+{
+	type Value = TermDefinition<T, C>;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a term definition")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut definition = TermDefinition::default();
+
+		while let Some(key) = map.next_key::<String>()? {
+			match key.as_str() {
+				"value" => definition.value = Some(deserialize_term(&mut map)?),
+				"prefix" => definition.prefix = map.next_value()?,
+				"protected" => definition.protected = map.next_value()?,
+				"reverse" => definition.reverse_property = map.next_value()?,
+				"baseUrl" => definition.base_url = Some(deserialize_iri(&mut map)?),
+				"context" => definition.context = Some(map.next_value()?),
+				"container" => {
+					let container: Vec<String> = map.next_value()?;
+					for c in container {
+						match ContainerType::try_from(c.as_str()) {
+							Ok(c) => {
+								if !definition.container.add(c) {
+									return Err(de::Error::custom("invalid container mapping"));
+								}
+							}
+							Err(_) => {
+								return Err(de::Error::custom(format!("invalid container `{}`", c)))
+							}
+						}
+					}
+				}
+				"direction" => {
+					definition.direction = Some(match map.next_value::<Option<String>>()? {
+						Some(s) => {
+							Nullable::Some(Direction::try_from(s.as_str()).map_err(|_| {
+								de::Error::custom(format!("invalid base direction `{}`", s))
+							})?)
+						}
+						None => Nullable::Null,
+					})
+				}
+				"index" => definition.index = Some(map.next_value()?),
+				"language" => {
+					definition.language = Some(match map.next_value::<Option<String>>()? {
+						Some(tag) => Nullable::Some(tag.into()),
+						None => Nullable::Null,
+					})
+				}
+				"nest" => definition.nest = Some(map.next_value()?),
+				"type" => {
+					let ty: String = map.next_value()?;
+					definition.typ = Some(type_from_str(&ty)?)
+				}
+				_ => return Err(de::Error::unknown_field(&key, TERM_DEFINITION_FIELDS)),
+			}
+		}
+
+		Ok(definition)
+	}
+}
+
+const TERM_DEFINITION_FIELDS: &[&str] = &[
+	"value",
+	"prefix",
+	"protected",
+	"reverse",
+	"baseUrl",
+	"context",
+	"container",
+	"direction",
+	"index",
+	"language",
+	"nest",
+	"type",
+];
+
+impl<'de, T: Id, C: Context<T>> Deserialize<'de> for TermDefinition<T, C>
+where
+	C::LocalContext: Deserialize<'de>,
+{
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_map(TermDefinitionVisitor(PhantomData))
+	}
+}
+
+/// Serializes a processed context as a map.
+///
+/// The attached [`Cache`](super::Cache), if any, and the term definitions
+/// [origins](TermDefinition::origin) are not serialized.
+/// Term definitions are sorted by term, so that the same context
+/// is always serialized the same way.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Local}, NoLoader};
+/// # use iref::IriBuf;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let local_context = json!({
+///   "@vocab": "https://schema.org/",
+///   "knows": { "@type": "@id", "@container": "@set", "@context": { "name": "https://schema.org/name" } },
+///   "label": { "@id": "https://schema.org/name", "@language": null }
+/// });
+/// let processed: context::Json<Value> = Local::<IriBuf>::process_with(
+///   &local_context,
+///   &context::Json::new(None),
+///   &NoLoader::<Value>::new(),
+///   None,
+///   context::ProcessingOptions::default()
+/// ).await.unwrap().into_inner();
+///
+/// let snapshot = serde_json::to_value(&processed).unwrap();
+/// assert_eq!(snapshot["definitions"]["knows"]["type"], json!("@id"));
+///
+/// let restored: context::Json<Value> = serde_json::from_value(snapshot).unwrap();
+/// assert!(restored == processed);
+/// # }
+/// ```
+impl<J: JsonContext + Serialize, T: Id> Serialize for Json<J, T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+
+		if let Some(url) = &self.original_base_url {
+			map.serialize_entry("originalBaseUrl", url.as_str())?
+		}
+
+		if let Some(iri) = &self.base_iri {
+			map.serialize_entry("baseIri", iri.as_str())?
+		}
+
+		if let Some(vocab) = &self.vocabulary {
+			map.serialize_entry("vocab", &SerTerm(vocab))?
+		}
+
+		if let Some(language) = &self.default_language {
+			map.serialize_entry("language", language.as_str())?
+		}
+
+		if let Some(direction) = &self.default_base_direction {
+			map.serialize_entry("direction", &direction.to_string())?
+		}
+
+		if let Some(previous) = &self.previous_context {
+			map.serialize_entry("previousContext", &**previous)?
+		}
+
+		let definitions: BTreeMap<_, _> = self.definitions.iter().collect();
+		map.serialize_entry("definitions", &definitions)?;
+
+		map.end()
+	}
+}
+
+struct JsonVisitor<J, T>(PhantomData<(J, T)>);
+
+impl<'de, J: JsonContext + Deserialize<'de>, T: Id> Visitor<'de> for JsonVisitor<J, T> {
+	type Value = Json<J, T>;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a processed context")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut context = Json::default();
+
+		while let Some(key) = map.next_key::<String>()? {
+			match key.as_str() {
+				"originalBaseUrl" => context.original_base_url = Some(deserialize_iri(&mut map)?),
+				"baseIri" => context.base_iri = Some(deserialize_iri(&mut map)?),
+				"vocab" => context.vocabulary = Some(deserialize_term(&mut map)?),
+				"language" => {
+					let tag: String = map.next_value()?;
+					context.default_language = Some(tag.into())
+				}
+				"direction" => {
+					context.default_base_direction = Some(deserialize_direction(&mut map)?)
+				}
+				"previousContext" => context.set_previous_context(map.next_value()?),
+				"definitions" => {
+					let definitions: BTreeMap<String, TermDefinition<T, Json<J, T>>> =
+						map.next_value()?;
+					for (term, definition) in definitions {
+						context.set(&term, Some(definition));
+					}
+				}
+				_ => return Err(de::Error::unknown_field(&key, CONTEXT_FIELDS)),
+			}
+		}
+
+		Ok(context)
+	}
+}
+
+const CONTEXT_FIELDS: &[&str] = &[
+	"originalBaseUrl",
+	"baseIri",
+	"vocab",
+	"language",
+	"direction",
+	"previousContext",
+	"definitions",
+];
+
+impl<'de, J: JsonContext + Deserialize<'de>, T: Id> Deserialize<'de> for Json<J, T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_map(JsonVisitor(PhantomData))
+	}
+}
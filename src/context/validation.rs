@@ -0,0 +1,118 @@
+use super::{processing::process_context, Json, JsonContext, Loader, ProcessingOptions};
+use crate::future::{BoxFuture, FutureExt, MaybeSend, MaybeSync};
+use crate::{Error, Loc, Warning};
+use iref::IriBuf;
+
+/// Result of the validation of a local context.
+///
+/// Returned by the [`validate_context`] function.
+pub struct Validation<M> {
+	errors: Vec<Loc<Error, M>>,
+	warnings: Vec<Loc<Warning, M>>,
+}
+
+impl<M> Validation<M> {
+	/// Checks if the context is valid, meaning that it has no errors.
+	///
+	/// A valid context may still have warnings.
+	#[inline(always)]
+	pub fn is_valid(&self) -> bool {
+		self.errors.is_empty()
+	}
+
+	/// Errors found in the context, in processing order.
+	#[inline(always)]
+	pub fn errors(&self) -> &[Loc<Error, M>] {
+		&self.errors
+	}
+
+	/// Warnings emitted while processing the context.
+	#[inline(always)]
+	pub fn warnings(&self) -> &[Loc<Warning, M>] {
+		&self.warnings
+	}
+}
+
+/// Validates a local context, collecting all its errors and warnings.
+///
+/// The context is processed on top of an empty active context,
+/// with the [`skip_invalid_terms`](ProcessingOptions::skip_invalid_terms) option set:
+/// each invalid term definition (including in scoped and remote contexts)
+/// is reported as an error of the [`Validation`], and processing continues.
+/// Processing stops at the first error that is not related to a term definition
+/// (such as an invalid `@vocab` or a remote context that cannot be loaded),
+/// which is reported last.
+///
+/// This is meant for context authors, to lint `@context` documents.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, ErrorCode, NoLoader};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let local_context = json!({
+///   "@vocab": "https://schema.org/",
+///   "name": { "@id": true },
+///   "knows": { "@type": "@id" },
+///   "tags": { "@container": "@bag" }
+/// });
+///
+/// let validation = context::validate_context(
+///   &local_context,
+///   &NoLoader::<Value>::new(),
+///   context::ProcessingOptions::default()
+/// ).await;
+///
+/// assert!(!validation.is_valid());
+/// let codes: Vec<_> = validation.errors().iter().map(|e| e.code()).collect();
+/// assert_eq!(codes, [ErrorCode::InvalidIriMapping, ErrorCode::InvalidContainerMapping]);
+/// # }
+/// ```
+pub fn validate_context<'a, J: JsonContext, L: Loader + MaybeSend + MaybeSync>(
+	local_context: &'a J,
+	loader: &'a L,
+	options: ProcessingOptions,
+) -> BoxFuture<'a, Validation<J::MetaData>>
+where
+	J: From<L::Output>,
+	L::Output: Into<J>,
+{
+	async move {
+		let options = ProcessingOptions {
+			skip_invalid_terms: true,
+			..options
+		};
+
+		let mut warnings = Vec::new();
+		let result = process_context::<J, IriBuf, Json<J, IriBuf>, L>(
+			&Json::<J, IriBuf>::new(None),
+			local_context,
+			Default::default(),
+			loader,
+			None,
+			options,
+			&mut warnings,
+		)
+		.await;
+
+		let mut errors = Vec::new();
+		let warnings = warnings
+			.into_iter()
+			.filter_map(|warning| match warning.into_parts() {
+				(Warning::InvalidTermDefinition(_, code), source, metadata) => {
+					errors.push(Loc::new(Error::new(code), source, metadata));
+					None
+				}
+				(warning, source, metadata) => Some(Loc::new(warning, source, metadata)),
+			})
+			.collect();
+
+		if let Err(e) = result {
+			errors.push(e)
+		}
+
+		Validation { errors, warnings }
+	}
+	.boxed()
+}
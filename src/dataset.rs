@@ -0,0 +1,358 @@
+//! RDF datasets of JSON-LD nodes.
+//!
+//! A [`Dataset`] explicitly represents the graphs of an expanded document:
+//! a default graph and a set of named graphs, each containing nodes indexed by identifier.
+//! In an expanded document, named graphs are represented by node objects with a
+//! `@graph` entry, and nodes are not required to have an identifier.
+//! A dataset can be created from an expanded document with
+//! [`ExpandedDocument::into_dataset`] and exported back into an expanded document with
+//! [`Dataset::into_expanded_document`].
+//!
+//! Unlike the [`flattening`](crate::flattening) algorithm, nodes are not flattened:
+//! only nodes appearing at the top level of a graph are indexed.
+use crate::{flattening::Generator, ExpandedDocument, Id, Indexed, Node, Object, Reference};
+use generic_json::JsonHash;
+use indexmap::IndexSet;
+use std::collections::{hash_map, HashMap};
+
+/// Graph of a [`Dataset`].
+///
+/// Maps each node identifier to its node object.
+pub struct Graph<J: JsonHash, T: Id> {
+	nodes: HashMap<Reference<T>, Indexed<Node<J, T>>>,
+}
+
+impl<J: JsonHash, T: Id> Default for Graph<J, T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<J: JsonHash, T: Id> Graph<J, T> {
+	/// Creates a new empty graph.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			nodes: HashMap::new(),
+		}
+	}
+
+	/// Returns the number of nodes in the graph.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	/// Checks if the graph is empty.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+
+	/// Checks if the graph contains a node with the given identifier.
+	#[inline(always)]
+	pub fn contains(&self, id: &Reference<T>) -> bool {
+		self.nodes.contains_key(id)
+	}
+
+	/// Get the node with the given identifier.
+	#[inline(always)]
+	pub fn get(&self, id: &Reference<T>) -> Option<&Indexed<Node<J, T>>> {
+		self.nodes.get(id)
+	}
+
+	/// Get a mutable reference to the node with the given identifier.
+	#[inline(always)]
+	pub fn get_mut(&mut self, id: &Reference<T>) -> Option<&mut Indexed<Node<J, T>>> {
+		self.nodes.get_mut(id)
+	}
+
+	/// Inserts the given node in the graph.
+	///
+	/// Returns the node previously associated to the same identifier, if any.
+	///
+	/// # Panics
+	///
+	/// Panics if the node has no identifier.
+	#[inline(always)]
+	pub fn insert(&mut self, node: Indexed<Node<J, T>>) -> Option<Indexed<Node<J, T>>> {
+		let id = node.id().expect("node has no identifier").clone();
+		self.nodes.insert(id, node)
+	}
+
+	/// Removes the node with the given identifier from the graph, and returns it.
+	#[inline(always)]
+	pub fn remove(&mut self, id: &Reference<T>) -> Option<Indexed<Node<J, T>>> {
+		self.nodes.remove(id)
+	}
+
+	/// Returns an iterator over the nodes of the graph.
+	#[inline(always)]
+	pub fn iter(&self) -> hash_map::Values<Reference<T>, Indexed<Node<J, T>>> {
+		self.nodes.values()
+	}
+
+	/// Inserts the given node in the graph,
+	/// merging it with the node with the same identifier, if any.
+	fn merge(&mut self, node: Indexed<Node<J, T>>) {
+		let id = node.id().unwrap().clone();
+		match self.nodes.entry(id) {
+			hash_map::Entry::Occupied(mut entry) => merge_node(entry.get_mut(), node),
+			hash_map::Entry::Vacant(entry) => {
+				entry.insert(node);
+			}
+		}
+	}
+}
+
+impl<J: JsonHash, T: Id> IntoIterator for Graph<J, T> {
+	type Item = Indexed<Node<J, T>>;
+	type IntoIter = hash_map::IntoValues<Reference<T>, Indexed<Node<J, T>>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.nodes.into_values()
+	}
+}
+
+impl<'a, J: JsonHash, T: Id> IntoIterator for &'a Graph<J, T> {
+	type Item = &'a Indexed<Node<J, T>>;
+	type IntoIter = hash_map::Values<'a, Reference<T>, Indexed<Node<J, T>>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// Dataset.
+///
+/// Default graph and named graphs of nodes.
+pub struct Dataset<J: JsonHash, T: Id> {
+	default_graph: Graph<J, T>,
+	named_graphs: HashMap<Reference<T>, Graph<J, T>>,
+}
+
+impl<J: JsonHash, T: Id> Default for Dataset<J, T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<J: JsonHash, T: Id> Dataset<J, T> {
+	/// Creates a new empty dataset.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			default_graph: Graph::new(),
+			named_graphs: HashMap::new(),
+		}
+	}
+
+	/// Returns a reference to the default graph.
+	#[inline(always)]
+	pub fn default_graph(&self) -> &Graph<J, T> {
+		&self.default_graph
+	}
+
+	/// Returns a mutable reference to the default graph.
+	#[inline(always)]
+	pub fn default_graph_mut(&mut self) -> &mut Graph<J, T> {
+		&mut self.default_graph
+	}
+
+	/// Returns a reference to the given graph.
+	///
+	/// If `name` is `None`, the default graph is returned.
+	#[inline(always)]
+	pub fn graph(&self, name: Option<&Reference<T>>) -> Option<&Graph<J, T>> {
+		match name {
+			Some(name) => self.named_graphs.get(name),
+			None => Some(&self.default_graph),
+		}
+	}
+
+	/// Returns a mutable reference to the given graph.
+	///
+	/// If `name` is `None`, the default graph is returned.
+	#[inline(always)]
+	pub fn graph_mut(&mut self, name: Option<&Reference<T>>) -> Option<&mut Graph<J, T>> {
+		match name {
+			Some(name) => self.named_graphs.get_mut(name),
+			None => Some(&mut self.default_graph),
+		}
+	}
+
+	/// Returns an iterator over the named graphs of the dataset.
+	#[inline(always)]
+	pub fn named_graphs(&self) -> hash_map::Iter<Reference<T>, Graph<J, T>> {
+		self.named_graphs.iter()
+	}
+
+	/// Inserts a named graph in the dataset.
+	///
+	/// Returns the graph previously associated to the same name, if any.
+	#[inline(always)]
+	pub fn insert_graph(&mut self, name: Reference<T>, graph: Graph<J, T>) -> Option<Graph<J, T>> {
+		self.named_graphs.insert(name, graph)
+	}
+
+	/// Removes the given named graph from the dataset, and returns it.
+	#[inline(always)]
+	pub fn remove_graph(&mut self, name: &Reference<T>) -> Option<Graph<J, T>> {
+		self.named_graphs.remove(name)
+	}
+
+	/// Inserts the given node in the given graph,
+	/// creating the graph if it does not already exist.
+	///
+	/// If `graph_name` is `None`, the node is inserted in the default graph.
+	/// Returns the node previously associated to the same identifier in this graph, if any.
+	///
+	/// # Panics
+	///
+	/// Panics if the node has no identifier.
+	#[inline(always)]
+	pub fn insert(
+		&mut self,
+		graph_name: Option<Reference<T>>,
+		node: Indexed<Node<J, T>>,
+	) -> Option<Indexed<Node<J, T>>> {
+		self.graph_entry(graph_name).insert(node)
+	}
+
+	/// Removes the node with the given identifier from the given graph, and returns it.
+	///
+	/// If `graph_name` is `None`, the node is removed from the default graph.
+	#[inline(always)]
+	pub fn remove(
+		&mut self,
+		graph_name: Option<&Reference<T>>,
+		id: &Reference<T>,
+	) -> Option<Indexed<Node<J, T>>> {
+		self.graph_mut(graph_name)
+			.and_then(|graph| graph.remove(id))
+	}
+
+	/// Returns an iterator over the nodes of every graph,
+	/// with the name of their graph (`None` for the default graph).
+	pub fn iter(&self) -> impl Iterator<Item = (Option<&Reference<T>>, &Indexed<Node<J, T>>)> {
+		self.default_graph.iter().map(|node| (None, node)).chain(
+			self.named_graphs
+				.iter()
+				.flat_map(|(name, graph)| graph.iter().map(move |node| (Some(name), node))),
+		)
+	}
+
+	/// Creates a dataset from the given expanded document.
+	///
+	/// Top-level node objects are added to the default graph,
+	/// and the content of `@graph` entries is added to the corresponding named graphs.
+	/// Nodes with the same identifier in the same graph are merged.
+	/// Nodes without identifier, and named graphs without name,
+	/// are assigned a fresh blank node identifier produced by the given `generator`.
+	/// Top-level value objects and lists are ignored.
+	pub fn from_expanded_document(
+		document: ExpandedDocument<J, T>,
+		generator: &mut Generator,
+	) -> Self {
+		let mut dataset = Self::new();
+		for object in document {
+			dataset.add_object(None, object, generator)
+		}
+
+		dataset
+	}
+
+	/// Turns this dataset into an expanded document.
+	///
+	/// Each named graph is attached as a `@graph` entry to the node of the
+	/// default graph with the same identifier, which is created if necessary.
+	pub fn into_expanded_document(self) -> ExpandedDocument<J, T> {
+		let mut default_graph = self.default_graph;
+		for (name, graph) in self.named_graphs {
+			let objects: IndexSet<_> = graph.into_iter().map(Indexed::cast).collect();
+			if default_graph.get(&name).is_none() {
+				default_graph.insert(Indexed::new(Node::with_id(name.clone()), None));
+			}
+
+			let node = default_graph.get_mut(&name).unwrap();
+			match node.graph_mut() {
+				Some(graph) => graph.extend(objects),
+				None => node.set_graph(Some(objects)),
+			}
+		}
+
+		let objects = default_graph.into_iter().map(Indexed::cast);
+		ExpandedDocument::new(objects, Vec::new())
+	}
+
+	/// Returns the given graph, creating it if it does not already exist.
+	fn graph_entry(&mut self, name: Option<Reference<T>>) -> &mut Graph<J, T> {
+		match name {
+			Some(name) => self.named_graphs.entry(name).or_default(),
+			None => &mut self.default_graph,
+		}
+	}
+
+	/// Adds the given top-level object to the given graph.
+	fn add_object(
+		&mut self,
+		graph_name: Option<Reference<T>>,
+		object: Indexed<Object<J, T>>,
+		generator: &mut Generator,
+	) {
+		let (object, index) = object.into_parts();
+		if let Object::Node(mut node) = object {
+			if node.id.is_none() {
+				node.id = Some(Reference::Blank(generator.generate(None)))
+			}
+
+			if let Some(graph) = node.graph.take() {
+				let name = node.id.clone();
+				self.graph_entry(name.clone());
+				for object in graph {
+					self.add_object(name.clone(), object, generator)
+				}
+			}
+
+			if index.is_some() || !node.is_empty() {
+				self.graph_entry(graph_name)
+					.merge(Indexed::new(node, index))
+			}
+		}
+	}
+}
+
+/// Merge `node` into `target`.
+fn merge_node<J: JsonHash, T: Id>(target: &mut Indexed<Node<J, T>>, node: Indexed<Node<J, T>>) {
+	let (node, index) = node.into_parts();
+	if target.index().is_none() {
+		target.set_index(index)
+	}
+
+	for ty in node.types {
+		if !target.types.contains(&ty) {
+			target.types.push(ty)
+		}
+	}
+
+	if let Some(included) = node.included {
+		match target.included_mut() {
+			Some(target_included) => target_included.extend(included),
+			None => target.set_included(Some(included)),
+		}
+	}
+
+	for (property, values) in node.properties {
+		for value in values {
+			target.insert_unique(property.clone(), value)
+		}
+	}
+
+	for (property, nodes) in node.reverse_properties {
+		target.insert_all_reverse(property, nodes.into_iter())
+	}
+}
@@ -0,0 +1,250 @@
+use super::{signature, Graphs};
+use crate::{Error, ExpandedDocument, Id, Indexed, Node, Reference};
+use generic_json::{JsonClone, JsonHash};
+use std::collections::{HashMap, HashSet};
+
+/// Checks if the two given expanded documents are isomorphic.
+///
+/// Two documents are isomorphic if they are structurally equal
+/// (after flattening) up to a bijection between their blank node identifiers.
+/// As in [`expanded_diff`](super::expanded_diff), the values of a property
+/// are compared as sets.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, Document, NoLoader, diff};
+/// # use serde_json::Value;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let a: Value = serde_json::from_str(r#"{
+///   "@id": "_:a",
+///   "https://example.com/knows": { "@id": "_:b", "https://example.com/knows": { "@id": "_:a" } }
+/// }"#).unwrap();
+///
+/// let b: Value = serde_json::from_str(r#"{
+///   "@id": "_:x",
+///   "https://example.com/knows": { "@id": "_:y", "https://example.com/knows": { "@id": "_:x" } }
+/// }"#).unwrap();
+///
+/// let c: Value = serde_json::from_str(r#"{
+///   "@id": "_:x",
+///   "https://example.com/knows": { "@id": "_:y", "https://example.com/knows": { "@id": "_:y" } }
+/// }"#).unwrap();
+///
+/// let loader = NoLoader::<Value>::new();
+/// let a = a.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+/// let b = b.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+/// let c = c.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+///
+/// assert!(diff::is_isomorphic(&a, &b).unwrap());
+/// assert!(!diff::is_isomorphic(&a, &c).unwrap());
+/// # }
+/// ```
+pub fn is_isomorphic<J: JsonHash + JsonClone, T: Id>(
+	a: &ExpandedDocument<J, T>,
+	b: &ExpandedDocument<J, T>,
+) -> Result<bool, Error> {
+	let a = Graphs::new(a)?;
+	let b = Graphs::new(b)?;
+
+	if a.0.len() != b.0.len() {
+		return Ok(false);
+	}
+
+	let mut a_sizes: Vec<_> = a.0.values().map(HashMap::len).collect();
+	let mut b_sizes: Vec<_> = b.0.values().map(HashMap::len).collect();
+	a_sizes.sort_unstable();
+	b_sizes.sort_unstable();
+	if a_sizes != b_sizes {
+		return Ok(false);
+	}
+
+	let a_nodes = mentions(&a);
+	let b_nodes = mentions(&b);
+
+	let a_keys = keys(&a, &a_nodes);
+	let b_keys = keys(&b, &b_nodes);
+	if a_keys.len() != b_keys.len() {
+		return Ok(false);
+	}
+
+	// Each blank node of `a` can only be matched with a blank node of `b`
+	// mentioned by nodes with the same signatures.
+	let mut candidates = Vec::with_capacity(a_keys.len());
+	for (id, key) in &a_keys {
+		let ids: Vec<_> = b_keys
+			.iter()
+			.filter(|(_, other_key)| *other_key == key)
+			.map(|(other_id, _)| other_id)
+			.collect();
+
+		if ids.is_empty() {
+			return Ok(false);
+		}
+
+		candidates.push((id, ids))
+	}
+
+	candidates.sort_by_key(|(_, ids)| ids.len());
+
+	let mut search = Search {
+		a: &a,
+		b: &b,
+		a_nodes: &a_nodes,
+		mapping: HashMap::new(),
+		used: HashSet::new(),
+	};
+
+	Ok(search.run(&candidates))
+}
+
+/// Flattened node, with the blank node identifiers it mentions
+/// (including the name of its graph).
+struct Mention<'a, J: JsonHash, T: Id> {
+	graph_name: &'a Option<Reference<T>>,
+	node: &'a Indexed<Node<J, T>>,
+	blank_ids: HashSet<Reference<T>>,
+}
+
+fn mentions<J: JsonHash + JsonClone, T: Id>(graphs: &Graphs<J, T>) -> Vec<Mention<J, T>> {
+	let mut result = Vec::new();
+	for (graph_name, graph) in &graphs.0 {
+		for node in graph.values() {
+			let mut blank_ids = HashSet::new();
+			let mut collect = |r: Reference<T>| {
+				if let Reference::Blank(_) = &r {
+					blank_ids.insert(r.clone());
+				}
+
+				r
+			};
+
+			graph_name.clone().map(&mut collect);
+			node.clone().map_references(&mut collect);
+
+			result.push(Mention {
+				graph_name,
+				node,
+				blank_ids,
+			})
+		}
+	}
+
+	result
+}
+
+/// Multiset of signatures of the nodes mentioning a blank node.
+type Key<J, T> = HashMap<(Option<Reference<T>>, Indexed<Node<J, T>>), usize>;
+
+/// Computes the key of every blank node.
+///
+/// Blank graph names are included even if their graph is empty.
+fn keys<J: JsonHash + JsonClone, T: Id>(
+	graphs: &Graphs<J, T>,
+	nodes: &[Mention<J, T>],
+) -> HashMap<Reference<T>, Key<J, T>> {
+	let mut keys: HashMap<_, Key<J, T>> = graphs
+		.0
+		.keys()
+		.filter_map(|graph_name| match graph_name {
+			Some(Reference::Blank(_)) => Some((graph_name.clone().unwrap(), HashMap::new())),
+			_ => None,
+		})
+		.collect();
+
+	for mention in nodes {
+		let signature = signature(mention.graph_name, mention.node);
+		for id in &mention.blank_ids {
+			*keys
+				.entry(id.clone())
+				.or_default()
+				.entry(signature.clone())
+				.or_default() += 1
+		}
+	}
+
+	keys
+}
+
+/// Backtracking search of a blank node bijection.
+struct Search<'a, J: JsonHash, T: Id> {
+	a: &'a Graphs<J, T>,
+	b: &'a Graphs<J, T>,
+	a_nodes: &'a [Mention<'a, J, T>],
+	mapping: HashMap<Reference<T>, Reference<T>>,
+	used: HashSet<&'a Reference<T>>,
+}
+
+impl<'a, J: JsonHash + JsonClone, T: Id> Search<'a, J, T> {
+	fn run(&mut self, candidates: &[(&Reference<T>, Vec<&'a Reference<T>>)]) -> bool {
+		match candidates.split_first() {
+			Some(((id, ids), rest)) => {
+				for other_id in ids {
+					if self.used.contains(other_id) {
+						continue;
+					}
+
+					self.mapping.insert((*id).clone(), (*other_id).clone());
+					self.used.insert(other_id);
+
+					if self.is_consistent(id) && self.run(rest) {
+						return true;
+					}
+
+					self.used.remove(other_id);
+				}
+
+				self.mapping.remove(*id);
+				false
+			}
+			None => self.is_complete(),
+		}
+	}
+
+	fn relabel(&self, r: Reference<T>) -> Reference<T> {
+		self.mapping.get(&r).cloned().unwrap_or(r)
+	}
+
+	/// Checks that every node mentioning `id` whose blank nodes are all mapped
+	/// is mapped to a node of `b`.
+	fn is_consistent(&self, id: &Reference<T>) -> bool {
+		self.a_nodes
+			.iter()
+			.filter(|mention| {
+				mention.blank_ids.contains(id)
+					&& mention
+						.blank_ids
+						.iter()
+						.all(|id| self.mapping.contains_key(id))
+			})
+			.all(|mention| {
+				let graph_name = mention.graph_name.clone().map(|r| self.relabel(r));
+				let node = mention
+					.node
+					.clone()
+					.map_references(&mut |r| self.relabel(r));
+				self.b
+					.0
+					.get(&graph_name)
+					.and_then(|graph| graph.get(node.id().unwrap()))
+					== Some(&node)
+			})
+	}
+
+	/// Checks that the relabeled `a` is equal to `b`.
+	fn is_complete(&self) -> bool {
+		self.a.0.iter().all(|(graph_name, graph)| {
+			let graph_name = graph_name.clone().map(|r| self.relabel(r));
+			match self.b.0.get(&graph_name) {
+				Some(other_graph) => {
+					graph.len() == other_graph.len()
+						&& graph.values().all(|node| {
+							let node = node.clone().map_references(&mut |r| self.relabel(r));
+							other_graph.get(node.id().unwrap()) == Some(&node)
+						})
+				}
+				None => false,
+			}
+		})
+	}
+}
@@ -0,0 +1,486 @@
+//! Structural diff between expanded documents.
+//!
+//! Documents are compared node by node, after flattening.
+//! The values of a property are compared as sets,
+//! so the order of values (except in lists) is irrelevant.
+//!
+//! Since blank node identifiers are local to each document,
+//! the blank nodes of the second document are first matched with the blank nodes
+//! of the first document, either because they have the same content,
+//! or because they have the same identifier.
+//! Matched blank nodes are then compared as if they had the same identifier.
+//!
+//! A [`Diff`] can be turned into a [`Patch`] that can be applied
+//! to the first document to obtain the second one.
+//!
+//! The [`is_isomorphic`] function checks that two documents are equal
+//! up to a renaming of their blank nodes.
+use crate::{
+	flattening::{Generator, NodeMap},
+	BlankId, Error, ExpandedDocument, Id, Indexed, Node, Object, Reference,
+};
+use generic_json::{JsonClone, JsonHash};
+use std::collections::{HashMap, HashSet};
+
+mod isomorphism;
+mod patch;
+
+pub use isomorphism::*;
+pub use patch::*;
+
+/// Difference between two expanded documents.
+///
+/// List of every node that has been added, removed or changed,
+/// ordered by graph and identifier.
+/// It is created by the [`expanded_diff`] function.
+pub struct Diff<J: JsonHash, T: Id>(Vec<NodeDiff<J, T>>);
+
+impl<J: JsonHash, T: Id> Diff<J, T> {
+	/// Returns the number of added, removed or changed nodes.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Checks if the documents are identical.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns an iterator over the node differences.
+	#[inline(always)]
+	pub fn iter(&self) -> std::slice::Iter<NodeDiff<J, T>> {
+		self.0.iter()
+	}
+}
+
+impl<J: JsonHash, T: Id> IntoIterator for Diff<J, T> {
+	type Item = NodeDiff<J, T>;
+	type IntoIter = std::vec::IntoIter<NodeDiff<J, T>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<'a, J: JsonHash, T: Id> IntoIterator for &'a Diff<J, T> {
+	type Item = &'a NodeDiff<J, T>;
+	type IntoIter = std::slice::Iter<'a, NodeDiff<J, T>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// Difference on a single node.
+pub struct NodeDiff<J: JsonHash, T: Id> {
+	graph: Option<Reference<T>>,
+	id: Reference<T>,
+	change: NodeChange<J, T>,
+}
+
+impl<J: JsonHash, T: Id> NodeDiff<J, T> {
+	/// Graph of the node.
+	///
+	/// Returns `None` if the node belongs to the default graph.
+	#[inline(always)]
+	pub fn graph(&self) -> Option<&Reference<T>> {
+		self.graph.as_ref()
+	}
+
+	/// Identifier of the node.
+	#[inline(always)]
+	pub fn id(&self) -> &Reference<T> {
+		&self.id
+	}
+
+	/// Change on the node.
+	#[inline(always)]
+	pub fn change(&self) -> &NodeChange<J, T> {
+		&self.change
+	}
+
+	/// Turns this difference into the node graph, identifier and change.
+	#[inline(always)]
+	pub fn into_parts(self) -> (Option<Reference<T>>, Reference<T>, NodeChange<J, T>) {
+		(self.graph, self.id, self.change)
+	}
+}
+
+/// Change on a node.
+pub enum NodeChange<J: JsonHash, T: Id> {
+	/// The node only appears in the second document.
+	Added(Indexed<Node<J, T>>),
+
+	/// The node only appears in the first document.
+	Removed(Indexed<Node<J, T>>),
+
+	/// The node appears in both documents, with different types or properties.
+	Changed(NodeChanges<J, T>),
+}
+
+/// Changes on the types and properties of a node.
+pub struct NodeChanges<J: JsonHash, T: Id> {
+	added_types: Vec<Reference<T>>,
+	removed_types: Vec<Reference<T>>,
+	properties: Vec<PropertyChange<J, T>>,
+}
+
+impl<J: JsonHash, T: Id> NodeChanges<J, T> {
+	/// Types only present in the second document.
+	#[inline(always)]
+	pub fn added_types(&self) -> &[Reference<T>] {
+		&self.added_types
+	}
+
+	/// Types only present in the first document.
+	#[inline(always)]
+	pub fn removed_types(&self) -> &[Reference<T>] {
+		&self.removed_types
+	}
+
+	/// Changed properties, ordered by property.
+	#[inline(always)]
+	pub fn properties(&self) -> &[PropertyChange<J, T>] {
+		&self.properties
+	}
+
+	fn is_empty(&self) -> bool {
+		self.added_types.is_empty() && self.removed_types.is_empty() && self.properties.is_empty()
+	}
+}
+
+/// Changes on the values of a property.
+///
+/// A property is added if it has no removed values,
+/// removed if it has no added values,
+/// and changed if it has both.
+pub struct PropertyChange<J: JsonHash, T: Id> {
+	property: Reference<T>,
+	added: Vec<Indexed<Object<J, T>>>,
+	removed: Vec<Indexed<Object<J, T>>>,
+}
+
+impl<J: JsonHash, T: Id> PropertyChange<J, T> {
+	/// Property.
+	#[inline(always)]
+	pub fn property(&self) -> &Reference<T> {
+		&self.property
+	}
+
+	/// Values only present in the second document.
+	#[inline(always)]
+	pub fn added(&self) -> &[Indexed<Object<J, T>>] {
+		&self.added
+	}
+
+	/// Values only present in the first document.
+	#[inline(always)]
+	pub fn removed(&self) -> &[Indexed<Object<J, T>>] {
+		&self.removed
+	}
+}
+
+/// Computes the difference between the documents `a` and `b`.
+///
+/// Blank node identifiers of `a` are preserved.
+/// Blank nodes of `b` are given the identifier of the blank node of `a` they are
+/// matched with, or a fresh identifier if they have no match but their identifier
+/// is already used in `a`.
+/// Nodes without identifier are given fresh blank node identifiers.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, Document, NoLoader, diff};
+/// # use serde_json::Value;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let a: Value = serde_json::from_str(r#"{
+///   "@id": "https://example.com/a",
+///   "https://example.com/p": [ "x", { "https://example.com/q": 1 } ]
+/// }"#).unwrap();
+///
+/// let b: Value = serde_json::from_str(r#"{
+///   "@id": "https://example.com/a",
+///   "https://example.com/p": [ { "https://example.com/q": 1 }, "y" ]
+/// }"#).unwrap();
+///
+/// let loader = NoLoader::<Value>::new();
+/// let a = a.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+/// let b = b.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+///
+/// let diff = diff::expanded_diff(&a, &b).unwrap();
+/// assert_eq!(diff.len(), 1);
+///
+/// let node_diff = diff.iter().next().unwrap();
+/// assert_eq!(node_diff.id().as_str(), "https://example.com/a");
+/// match node_diff.change() {
+///   diff::NodeChange::Changed(changes) => {
+///     let property = &changes.properties()[0];
+///     assert_eq!(property.added()[0].as_str(), Some("y"));
+///     assert_eq!(property.removed()[0].as_str(), Some("x"));
+///   }
+///   _ => panic!("unexpected change")
+/// }
+/// # }
+/// ```
+pub fn expanded_diff<J: JsonHash + JsonClone, T: Id>(
+	a: &ExpandedDocument<J, T>,
+	b: &ExpandedDocument<J, T>,
+) -> Result<Diff<J, T>, Error> {
+	let a = Graphs::new(a)?;
+	let mut b = Graphs::new(b)?;
+	b.match_blank_nodes(&a);
+
+	let mut graph_names: Vec<_> = a.0.keys().chain(b.0.keys()).cloned().collect();
+	graph_names.sort_by(|x, y| cmp_graph_names(x.as_ref(), y.as_ref()));
+	graph_names.dedup();
+
+	let mut result = Vec::new();
+	for graph_name in graph_names {
+		let empty = HashMap::new();
+		let a_graph = a.0.get(&graph_name).unwrap_or(&empty);
+		let b_graph = b.0.get(&graph_name).unwrap_or(&empty);
+
+		let mut ids: Vec<_> = a_graph.keys().chain(b_graph.keys()).collect();
+		ids.sort_by(|x, y| x.as_str().cmp(y.as_str()));
+		ids.dedup();
+
+		for id in ids {
+			let change = match (a_graph.get(id), b_graph.get(id)) {
+				(Some(a_node), Some(b_node)) => {
+					let changes = node_changes(a_node, b_node);
+					if changes.is_empty() {
+						continue;
+					}
+
+					NodeChange::Changed(changes)
+				}
+				(Some(a_node), None) => NodeChange::Removed(a_node.clone()),
+				(None, Some(b_node)) => NodeChange::Added(b_node.clone()),
+				(None, None) => unreachable!(),
+			};
+
+			result.push(NodeDiff {
+				graph: graph_name.clone(),
+				id: id.clone(),
+				change,
+			})
+		}
+	}
+
+	Ok(Diff(result))
+}
+
+fn cmp_graph_names<T: Id>(
+	a: Option<&Reference<T>>,
+	b: Option<&Reference<T>>,
+) -> std::cmp::Ordering {
+	a.map(Reference::as_str).cmp(&b.map(Reference::as_str))
+}
+
+fn node_changes<J: JsonHash + JsonClone, T: Id>(
+	a: &Node<J, T>,
+	b: &Node<J, T>,
+) -> NodeChanges<J, T> {
+	let added_types = b
+		.types()
+		.iter()
+		.filter(|ty| !a.types().contains(ty))
+		.cloned()
+		.collect();
+	let removed_types = a
+		.types()
+		.iter()
+		.filter(|ty| !b.types().contains(ty))
+		.cloned()
+		.collect();
+
+	let mut properties: Vec<_> = a
+		.properties()
+		.iter()
+		.map(|(prop, _)| prop)
+		.chain(b.properties().iter().map(|(prop, _)| prop))
+		.collect();
+	properties.sort_by(|x, y| x.as_str().cmp(y.as_str()));
+	properties.dedup();
+
+	let mut property_changes = Vec::new();
+	for property in properties {
+		let a_values: HashSet<_> = a.get(property).collect();
+		let b_values: HashSet<_> = b.get(property).collect();
+
+		let added: Vec<_> = b_values
+			.difference(&a_values)
+			.map(|v| (*v).clone())
+			.collect();
+		let removed: Vec<_> = a_values
+			.difference(&b_values)
+			.map(|v| (*v).clone())
+			.collect();
+
+		if !added.is_empty() || !removed.is_empty() {
+			property_changes.push(PropertyChange {
+				property: property.clone(),
+				added,
+				removed,
+			})
+		}
+	}
+
+	NodeChanges {
+		added_types,
+		removed_types,
+		properties: property_changes,
+	}
+}
+
+/// Flattened nodes of a graph, indexed by identifier.
+type Graph<J, T> = HashMap<Reference<T>, Indexed<Node<J, T>>>;
+
+/// Flattened nodes of a document, indexed by graph and identifier.
+struct Graphs<J: JsonHash, T: Id>(HashMap<Option<Reference<T>>, Graph<J, T>>);
+
+impl<J: JsonHash + JsonClone, T: Id> Graphs<J, T> {
+	/// Flattens the given document, preserving its blank node identifiers.
+	fn new(document: &ExpandedDocument<J, T>) -> Result<Self, Error> {
+		let mut ids = HashSet::new();
+		for object in document {
+			object.clone().map_references(&mut |r| {
+				if let Reference::Blank(id) = &r {
+					ids.insert(id.clone());
+				}
+
+				r
+			});
+		}
+
+		let mut generator = Generator::preserving(ids);
+		let mut node_map = NodeMap::new();
+		for object in document {
+			node_map.extend_with(&mut generator, object.clone())?;
+		}
+
+		let (default_graph, named_graphs) = node_map.into_parts();
+		let mut graphs = HashMap::new();
+		graphs.insert(None, nodes(default_graph.into_nodes()));
+		for (graph_name, graph) in named_graphs {
+			graphs.insert(Some(graph_name), nodes(graph.into_nodes()));
+		}
+
+		Ok(Self(graphs))
+	}
+
+	/// Returns the identifiers of every blank node, in every graph.
+	fn blank_ids(&self) -> impl Iterator<Item = (&Option<Reference<T>>, &Reference<T>)> {
+		self.0.iter().flat_map(|(graph_name, graph)| {
+			graph
+				.keys()
+				.filter(|id| matches!(id, Reference::Blank(_)))
+				.map(move |id| (graph_name, id))
+		})
+	}
+
+	/// Relabels the blank nodes of this document after the blank nodes of `other`
+	/// they are matched with.
+	fn match_blank_nodes(&mut self, other: &Self) {
+		let mut other_signatures: HashMap<_, Vec<&Reference<T>>> = HashMap::new();
+		for (graph_name, id) in other.blank_ids() {
+			other_signatures
+				.entry(signature(graph_name, &other.0[graph_name][id]))
+				.or_default()
+				.push(id)
+		}
+
+		let mut blank_ids: Vec<_> = self.blank_ids().collect();
+		blank_ids.sort_by(|(g1, x), (g2, y)| {
+			cmp_graph_names(g1.as_ref(), g2.as_ref()).then_with(|| x.as_str().cmp(y.as_str()))
+		});
+
+		let mut mapping: HashMap<Reference<T>, Reference<T>> = HashMap::new();
+		let mut used = HashSet::new();
+
+		// Match blank nodes with the same content.
+		for (graph_name, id) in &blank_ids {
+			if mapping.contains_key(id) {
+				continue;
+			}
+
+			if let Some(candidates) =
+				other_signatures.get_mut(&signature(graph_name, &self.0[*graph_name][*id]))
+			{
+				candidates.retain(|candidate| !used.contains(*candidate));
+				if let Some(other_id) = candidates.pop() {
+					used.insert(other_id);
+					mapping.insert((*id).clone(), other_id.clone());
+				}
+			}
+		}
+
+		// Match blank nodes with the same identifier.
+		let other_ids: HashSet<_> = other.blank_ids().map(|(_, id)| id).collect();
+		for (_, id) in &blank_ids {
+			if !mapping.contains_key(id) && other_ids.contains(id) && !used.contains(id) {
+				used.insert(id);
+				mapping.insert((*id).clone(), (*id).clone());
+			}
+		}
+
+		// Relabel remaining blank nodes clashing with blank nodes of `other`.
+		let all_ids = self
+			.blank_ids()
+			.chain(other.blank_ids())
+			.filter_map(|(_, id)| match id {
+				Reference::Blank(id) => Some(id.clone()),
+				_ => None,
+			});
+		let mut generator = Generator::preserving(all_ids.collect::<Vec<BlankId>>());
+		for (_, id) in &blank_ids {
+			if !mapping.contains_key(id) && other_ids.contains(id) {
+				mapping.insert((*id).clone(), Reference::Blank(generator.generate(None)));
+			}
+		}
+
+		let graphs = std::mem::take(&mut self.0);
+		let mut relabel = |r: Reference<T>| mapping.get(&r).cloned().unwrap_or(r);
+		for (graph_name, graph) in graphs {
+			let graph_name = graph_name.map(&mut relabel);
+			let graph = graph
+				.into_values()
+				.map(|node| {
+					let node = node.map_references(&mut relabel);
+					(node.id().unwrap().clone(), node)
+				})
+				.collect();
+			self.0.insert(graph_name, graph);
+		}
+	}
+}
+
+/// Index the given nodes by identifier, ignoring nodes with no other field than `@id`.
+fn nodes<J: JsonHash, T: Id, I: Iterator<Item = Indexed<Node<J, T>>>>(nodes: I) -> Graph<J, T> {
+	nodes
+		.filter(|node| node.index().is_some() || !node.is_empty())
+		.map(|node| (node.id().unwrap().clone(), node))
+		.collect()
+}
+
+/// Computes the signature of a blank node,
+/// which is its content (and graph) where every blank node identifier is erased.
+fn signature<J: JsonHash + JsonClone, T: Id>(
+	graph_name: &Option<Reference<T>>,
+	node: &Indexed<Node<J, T>>,
+) -> (Option<Reference<T>>, Indexed<Node<J, T>>) {
+	let mut erase = |r: Reference<T>| match r {
+		Reference::Blank(_) => Reference::Blank(BlankId::new("")),
+		r => r,
+	};
+
+	(
+		graph_name.clone().map(&mut erase),
+		node.clone().map_references(&mut erase),
+	)
+}
@@ -0,0 +1,360 @@
+use super::{Diff, Graphs, NodeChange};
+use crate::{
+	object::node::Properties, Error, ExpandedDocument, Id, Indexed, Node, Object, Reference,
+};
+use generic_json::{JsonClone, JsonHash};
+use indexmap::IndexSet;
+
+/// Patch operation.
+///
+/// Every operation targets a node of a given graph
+/// (the default graph if `graph` is `None`).
+pub enum Operation<J: JsonHash, T: Id> {
+	/// Add a node.
+	///
+	/// If a node with the same identifier already exists,
+	/// the two nodes are merged.
+	AddNode {
+		graph: Option<Reference<T>>,
+		node: Indexed<Node<J, T>>,
+	},
+
+	/// Remove a node.
+	RemoveNode {
+		graph: Option<Reference<T>>,
+		id: Reference<T>,
+	},
+
+	/// Add a type to a node.
+	AddType {
+		graph: Option<Reference<T>>,
+		id: Reference<T>,
+		ty: Reference<T>,
+	},
+
+	/// Remove a type from a node.
+	RemoveType {
+		graph: Option<Reference<T>>,
+		id: Reference<T>,
+		ty: Reference<T>,
+	},
+
+	/// Add a value to a property of a node.
+	AddValue {
+		graph: Option<Reference<T>>,
+		id: Reference<T>,
+		property: Reference<T>,
+		value: Indexed<Object<J, T>>,
+	},
+
+	/// Remove a value from a property of a node.
+	RemoveValue {
+		graph: Option<Reference<T>>,
+		id: Reference<T>,
+		property: Reference<T>,
+		value: Indexed<Object<J, T>>,
+	},
+}
+
+/// Patch.
+///
+/// List of operations to apply to an expanded document.
+/// A patch can be built from the [`Diff`] between two documents,
+/// in which case applying it to the first document yields the second document.
+pub struct Patch<J: JsonHash, T: Id>(Vec<Operation<J, T>>);
+
+impl<J: JsonHash, T: Id> Default for Patch<J, T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<J: JsonHash, T: Id> Patch<J, T> {
+	/// Creates a new empty patch.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self(Vec::new())
+	}
+
+	/// Returns the number of operations.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Checks if the patch has no operation.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Adds an operation at the end of the patch.
+	#[inline(always)]
+	pub fn push(&mut self, operation: Operation<J, T>) {
+		self.0.push(operation)
+	}
+
+	/// Returns an iterator over the operations of the patch.
+	#[inline(always)]
+	pub fn iter(&self) -> std::slice::Iter<Operation<J, T>> {
+		self.0.iter()
+	}
+}
+
+impl<J: JsonHash + JsonClone, T: Id> Patch<J, T> {
+	/// Applies the patch to the given document, and returns the patched document.
+	///
+	/// The document is flattened first, preserving its blank node identifiers,
+	/// so that the patch can refer to them.
+	/// Operations targeting an absent node or value are ignored,
+	/// except for `AddType` and `AddValue`, which create the node.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, NoLoader, diff};
+	/// # use serde_json::Value;
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let a: Value = serde_json::from_str(r#"{
+	///   "@id": "https://example.com/a",
+	///   "https://example.com/p": [ "x", { "https://example.com/q": 1 } ]
+	/// }"#).unwrap();
+	///
+	/// let b: Value = serde_json::from_str(r#"{
+	///   "@id": "https://example.com/a",
+	///   "https://example.com/p": [ { "https://example.com/q": 2 }, "y" ]
+	/// }"#).unwrap();
+	///
+	/// let loader = NoLoader::<Value>::new();
+	/// let a = a.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	/// let b = b.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	///
+	/// let patch: diff::Patch<_, _> = diff::expanded_diff(&a, &b).unwrap().into();
+	/// let patched = patch.apply(&a).unwrap();
+	/// assert!(diff::expanded_diff(&patched, &b).unwrap().is_empty());
+	/// # }
+	/// ```
+	pub fn apply(
+		&self,
+		document: &ExpandedDocument<J, T>,
+	) -> Result<ExpandedDocument<J, T>, Error> {
+		let mut graphs = Graphs::new(document)?;
+
+		for operation in &self.0 {
+			match operation {
+				Operation::AddNode { graph, node } => {
+					let target = declare_node(&mut graphs, graph, node.id().unwrap());
+					merge_node(target, node.clone())
+				}
+				Operation::RemoveNode { graph, id } => {
+					if let Some(graph) = graphs.0.get_mut(graph) {
+						graph.remove(id);
+					}
+				}
+				Operation::AddType { graph, id, ty } => {
+					let node = declare_node(&mut graphs, graph, id);
+					if !node.types.contains(ty) {
+						node.types.push(ty.clone())
+					}
+				}
+				Operation::RemoveType { graph, id, ty } => {
+					if let Some(node) = get_node_mut(&mut graphs, graph, id) {
+						node.types.retain(|t| t != ty)
+					}
+				}
+				Operation::AddValue {
+					graph,
+					id,
+					property,
+					value,
+				} => declare_node(&mut graphs, graph, id)
+					.insert_unique(property.clone(), value.clone()),
+				Operation::RemoveValue {
+					graph,
+					id,
+					property,
+					value,
+				} => {
+					if let Some(node) = get_node_mut(&mut graphs, graph, id) {
+						remove_value(node, property, value)
+					}
+				}
+			}
+		}
+
+		Ok(into_document(graphs))
+	}
+}
+
+impl<J: JsonHash, T: Id> From<Vec<Operation<J, T>>> for Patch<J, T> {
+	#[inline(always)]
+	fn from(operations: Vec<Operation<J, T>>) -> Self {
+		Self(operations)
+	}
+}
+
+impl<J: JsonHash, T: Id> From<Diff<J, T>> for Patch<J, T> {
+	fn from(diff: Diff<J, T>) -> Self {
+		let mut patch = Self::new();
+
+		for node_diff in diff {
+			let (graph, id, change) = node_diff.into_parts();
+			match change {
+				NodeChange::Added(node) => patch.push(Operation::AddNode { graph, node }),
+				NodeChange::Removed(_) => patch.push(Operation::RemoveNode { graph, id }),
+				NodeChange::Changed(changes) => {
+					for ty in changes.removed_types {
+						patch.push(Operation::RemoveType {
+							graph: graph.clone(),
+							id: id.clone(),
+							ty,
+						})
+					}
+
+					for ty in changes.added_types {
+						patch.push(Operation::AddType {
+							graph: graph.clone(),
+							id: id.clone(),
+							ty,
+						})
+					}
+
+					for property_change in changes.properties {
+						for value in property_change.removed {
+							patch.push(Operation::RemoveValue {
+								graph: graph.clone(),
+								id: id.clone(),
+								property: property_change.property.clone(),
+								value,
+							})
+						}
+
+						for value in property_change.added {
+							patch.push(Operation::AddValue {
+								graph: graph.clone(),
+								id: id.clone(),
+								property: property_change.property.clone(),
+								value,
+							})
+						}
+					}
+				}
+			}
+		}
+
+		patch
+	}
+}
+
+impl<J: JsonHash, T: Id> IntoIterator for Patch<J, T> {
+	type Item = Operation<J, T>;
+	type IntoIter = std::vec::IntoIter<Operation<J, T>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<'a, J: JsonHash, T: Id> IntoIterator for &'a Patch<J, T> {
+	type Item = &'a Operation<J, T>;
+	type IntoIter = std::slice::Iter<'a, Operation<J, T>>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/// Get the given node, creating it if it does not exist.
+fn declare_node<'a, J: JsonHash, T: Id>(
+	graphs: &'a mut Graphs<J, T>,
+	graph: &Option<Reference<T>>,
+	id: &Reference<T>,
+) -> &'a mut Indexed<Node<J, T>> {
+	graphs
+		.0
+		.entry(graph.clone())
+		.or_default()
+		.entry(id.clone())
+		.or_insert_with(|| Indexed::new(Node::with_id(id.clone()), None))
+}
+
+fn get_node_mut<'a, J: JsonHash, T: Id>(
+	graphs: &'a mut Graphs<J, T>,
+	graph: &Option<Reference<T>>,
+	id: &Reference<T>,
+) -> Option<&'a mut Indexed<Node<J, T>>> {
+	graphs.0.get_mut(graph).and_then(|graph| graph.get_mut(id))
+}
+
+/// Merge the types and properties of `node` into `target`.
+fn merge_node<J: JsonHash, T: Id>(target: &mut Indexed<Node<J, T>>, node: Indexed<Node<J, T>>) {
+	let (node, index) = node.into_parts();
+	if index.is_some() {
+		target.set_index(index)
+	}
+
+	for ty in node.types {
+		if !target.types.contains(&ty) {
+			target.types.push(ty)
+		}
+	}
+
+	for (property, values) in node.properties {
+		for value in values {
+			target.insert_unique(property.clone(), value)
+		}
+	}
+}
+
+fn remove_value<J: JsonHash, T: Id>(
+	node: &mut Node<J, T>,
+	property: &Reference<T>,
+	value: &Indexed<Object<J, T>>,
+) {
+	let properties = std::mem::replace(&mut node.properties, Properties::new());
+	for (p, values) in properties {
+		let values: Vec<_> = if p == *property {
+			values.into_iter().filter(|v| v != value).collect()
+		} else {
+			values
+		};
+
+		if !values.is_empty() {
+			node.insert_all(p, values.into_iter())
+		}
+	}
+}
+
+/// Turns the given flattened graphs back into an expanded document.
+///
+/// Each named graph is attached to the node of the default graph
+/// with the same identifier.
+fn into_document<J: JsonHash, T: Id>(mut graphs: Graphs<J, T>) -> ExpandedDocument<J, T> {
+	let mut default_graph = graphs.0.remove(&None).unwrap_or_default();
+
+	for (graph_name, graph) in graphs.0 {
+		let graph_name = graph_name.unwrap();
+		let objects: IndexSet<_> = graph
+			.into_values()
+			.filter(|node| node.index().is_some() || !node.is_empty())
+			.map(Indexed::cast)
+			.collect();
+
+		if !objects.is_empty() {
+			default_graph
+				.entry(graph_name.clone())
+				.or_insert_with(|| Indexed::new(Node::with_id(graph_name), None))
+				.set_graph(Some(objects))
+		}
+	}
+
+	let objects = default_graph
+		.into_values()
+		.filter(|node| node.index().is_some() || !node.is_empty())
+		.map(Indexed::cast);
+
+	ExpandedDocument::new(objects, Vec::new())
+}
@@ -1,32 +1,70 @@
+use crate::future::{BoxFuture, FutureExt, MaybeSend, MaybeSync};
 use crate::{
 	compaction,
 	context::{self, Loader},
-	expansion, loader,
-	util::{AsJson, JsonFrom},
-	Context, ContextMut, ContextMutProxy, Error, Id, Indexed, Loc, Object, Warning,
+	dataset::Dataset,
+	expansion, flattening, loader, rdf, skolemization,
+	util::{AsAnyJson, AsJson, JsonFrom},
+	BlankIdGenerator, Context, ContextMut, ContextMutProxy, DeduplicateWarnings, Error, Id,
+	Indexed, Loc, Node, Object, Reference, Relabeling, Stats, Warning, WarningHandler,
 };
 use cc_traits::Len;
-use futures::future::{BoxFuture, FutureExt};
-use generic_json::{Json, JsonClone, JsonHash};
+use generic_json::{Json, JsonBuild, JsonClone, JsonHash, JsonIntoMut};
+use indexmap::IndexSet;
 use iref::{Iri, IriBuf};
-use std::collections::HashSet;
+use mown::Mown;
+use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
+use std::time::Instant;
 
 /// Result of the document expansion algorithm.
 ///
-/// It is just an alias for a set of (indexed) objects.
+/// This is a set of (indexed) objects that preserves the order of the top-level objects
+/// of the input document: iterating over the document yields the objects in the order
+/// in which they appear in the input, duplicates being removed.
+/// Note that the objects of a top-level `@graph` are not ordered.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, Document, NoLoader};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let doc = json!([
+///   { "@id": "https://example.org/c", "https://schema.org/name": "C" },
+///   { "@id": "https://example.org/a", "https://schema.org/name": "A" },
+///   { "@id": "https://example.org/b", "https://schema.org/name": "B" },
+///   { "@id": "https://example.org/a", "https://schema.org/name": "A" }
+/// ]);
+/// let expanded = doc.expand::<context::Json<Value>, _>(&NoLoader::<Value>::new()).await.unwrap();
+///
+/// let ids: Vec<_> = expanded.iter().map(|object| object.id().unwrap().as_str()).collect();
+/// assert_eq!(ids, ["https://example.org/c", "https://example.org/a", "https://example.org/b"]);
+///
+/// let first = expanded.get_index(0).unwrap();
+/// assert!(expanded.contains(first));
+/// # }
+/// ```
 pub struct ExpandedDocument<J: JsonHash, T: Id> {
-	objects: HashSet<Indexed<Object<J, T>>>,
+	objects: IndexSet<Indexed<Object<J, T>>>,
 	warnings: Vec<Loc<Warning, J::MetaData>>,
+	stats: Option<Stats>,
 }
 
 impl<J: JsonHash, T: Id> ExpandedDocument<J, T> {
+	/// Creates a new expanded document from the given objects, in order.
+	///
+	/// Duplicate objects are only kept once, at their first position.
 	#[inline(always)]
-	pub fn new(
-		objects: HashSet<Indexed<Object<J, T>>>,
+	pub fn new<O: IntoIterator<Item = Indexed<Object<J, T>>>>(
+		objects: O,
 		warnings: Vec<Loc<Warning, J::MetaData>>,
 	) -> Self {
-		Self { objects, warnings }
+		Self {
+			objects: objects.into_iter().collect(),
+			warnings,
+			stats: None,
+		}
 	}
 
 	#[inline(always)]
@@ -44,13 +82,310 @@ impl<J: JsonHash, T: Id> ExpandedDocument<J, T> {
 		&self.warnings
 	}
 
+	/// Returns the statistics of the expansion that produced this document,
+	/// if they have been collected.
+	///
+	/// See the [`collect_stats`](expansion::Options::collect_stats) expansion option.
+	#[inline(always)]
+	pub fn stats(&self) -> Option<&Stats> {
+		self.stats.as_ref()
+	}
+
+	/// Sets the statistics of the expansion that produced this document.
 	#[inline(always)]
-	pub fn iter(&self) -> std::collections::hash_set::Iter<'_, Indexed<Object<J, T>>> {
+	pub fn set_stats(&mut self, stats: Option<Stats>) {
+		self.stats = stats
+	}
+
+	/// Returns an iterator over the top-level objects, in order.
+	#[inline(always)]
+	pub fn iter(&self) -> indexmap::set::Iter<'_, Indexed<Object<J, T>>> {
 		self.objects.iter()
 	}
+
+	/// Checks if the document contains the given top-level object.
+	#[inline(always)]
+	pub fn contains(&self, object: &Indexed<Object<J, T>>) -> bool {
+		self.objects.contains(object)
+	}
+
+	/// Returns the top-level object at the given position, if any.
+	#[inline(always)]
+	pub fn get_index(&self, index: usize) -> Option<&Indexed<Object<J, T>>> {
+		self.objects.get_index(index)
+	}
+
+	/// Returns the position of the given top-level object, if any.
+	#[inline(always)]
+	pub fn index_of(&self, object: &Indexed<Object<J, T>>) -> Option<usize> {
+		self.objects.get_index_of(object)
+	}
+
+	/// Indexes the node objects of this document by identifier.
+	///
+	/// Every node object with an identifier is listed under its identifier,
+	/// at any depth: top-level nodes and nodes nested in properties,
+	/// reverse properties, lists, graphs and included blocks.
+	/// The same identifier may be associated to more than one node object,
+	/// for instance when a node is described in one place and referenced in another.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, NoLoader, Reference};
+	/// # use iref::IriBuf;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@context": { "knows": { "@id": "https://schema.org/knows", "@type": "@id" } },
+	///   "@id": "https://example.org/alice",
+	///   "knows": [
+	///     { "@id": "https://example.org/bob", "knows": "https://example.org/alice" },
+	///     { "@id": "_:carol" }
+	///   ]
+	/// });
+	///
+	/// let loader = NoLoader::<Value>::new();
+	/// let expanded = doc.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	/// let index = expanded.index_by_id();
+	///
+	/// let alice = Reference::Id(IriBuf::new("https://example.org/alice").unwrap());
+	/// let bob = Reference::Id(IriBuf::new("https://example.org/bob").unwrap());
+	/// assert_eq!(index[&alice].len(), 2);
+	/// assert_eq!(index[&bob].len(), 1);
+	/// assert_eq!(index.len(), 3);
+	/// # }
+	/// ```
+	pub fn index_by_id(&self) -> HashMap<&Reference<T>, Vec<&Node<J, T>>> {
+		let mut index = HashMap::new();
+		index_objects(&self.objects, &mut index);
+		index
+	}
+
+	/// Adds a top-level object at the end of the document.
+	///
+	/// Returns `false` if the document already contains the object,
+	/// in which case it keeps its original position.
+	#[inline(always)]
+	pub fn insert(&mut self, object: Indexed<Object<J, T>>) -> bool {
+		self.objects.insert(object)
+	}
+
+	/// Removes the given top-level object, preserving the order of the others.
+	///
+	/// Returns `true` if the document contained the object.
+	#[inline(always)]
+	pub fn remove(&mut self, object: &Indexed<Object<J, T>>) -> bool {
+		self.objects.shift_remove(object)
+	}
+
+	/// Merge the given expanded documents into a single document.
+	///
+	/// Nodes with the same identifier are merged together,
+	/// and clashing blank node identifiers are relabeled using the given `generator`.
+	/// The result is flattened, and the warnings of every document are preserved.
+	/// See [`flattening::merge`] for more details.
+	pub fn merge<D: IntoIterator<Item = Self>>(
+		documents: D,
+		generator: &mut flattening::Generator,
+	) -> Result<Self, Error> {
+		let mut warnings = Vec::new();
+		let node_map = flattening::merge(
+			documents.into_iter().map(|document| {
+				warnings.extend(document.warnings);
+				document.objects
+			}),
+			generator,
+		)?;
+
+		let objects = node_map.flatten(false).into_iter().map(Indexed::cast);
+
+		Ok(Self::new(objects, warnings))
+	}
+
+	/// Skolemize the document.
+	///
+	/// Every blank node identifier is replaced by a Skolem IRI
+	/// with the same scheme and authority as `base_iri`.
+	/// See the [`skolemization`] module for more details.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, NoLoader};
+	/// # use serde_json::Value;
+	/// # use iref::Iri;
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc: Value = serde_json::from_str(r#"{ "@id": "_:b0", "http://example.com/p": "v" }"#).unwrap();
+	/// let loader = NoLoader::<Value>::new();
+	/// let expanded = doc.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	///
+	/// let skolemized = expanded.skolemize(Iri::new("https://example.com/").unwrap());
+	/// let node = skolemized.iter().next().unwrap().as_node().unwrap();
+	/// assert_eq!(node.id().unwrap().as_str(), "https://example.com/.well-known/genid/b0");
+	///
+	/// let deskolemized = skolemized.deskolemize(None);
+	/// let node = deskolemized.iter().next().unwrap().as_node().unwrap();
+	/// assert_eq!(node.id().unwrap().as_str(), "_:b0");
+	/// # }
+	/// ```
+	pub fn skolemize(self, base_iri: Iri) -> Self {
+		let objects = self
+			.objects
+			.into_iter()
+			.map(|object| skolemization::skolemize(object, base_iri));
+
+		Self {
+			stats: self.stats,
+			..Self::new(objects, self.warnings)
+		}
+	}
+
+	/// Deskolemize the document.
+	///
+	/// Every Skolem IRI is replaced by a blank node identifier.
+	/// If `base_iri` is given, only the Skolem IRIs with the same scheme
+	/// and authority are replaced.
+	/// See the [`skolemization`] module for more details.
+	pub fn deskolemize(self, base_iri: Option<Iri>) -> Self {
+		let objects = self
+			.objects
+			.into_iter()
+			.map(|object| skolemization::deskolemize(object, base_iri));
+
+		Self {
+			stats: self.stats,
+			..Self::new(objects, self.warnings)
+		}
+	}
+
+	/// Turns this document into a [`Dataset`].
+	///
+	/// See [`Dataset::from_expanded_document`] for more details.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, NoLoader, flattening::Generator};
+	/// # use serde_json::Value;
+	/// # use iref::IriBuf;
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc: Value = serde_json::from_str(r#"{
+	///   "@id": "https://example.com/graph",
+	///   "@graph": [
+	///     { "@id": "https://example.com/a", "https://example.com/p": "x" },
+	///     { "@id": "https://example.com/b", "https://example.com/p": "y" }
+	///   ]
+	/// }"#).unwrap();
+	///
+	/// let loader = NoLoader::<Value>::new();
+	/// let expanded = doc.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	///
+	/// let mut dataset = expanded.into_dataset(&mut Generator::new());
+	/// assert!(dataset.default_graph().is_empty());
+	///
+	/// let name = json_ld::Reference::Id(IriBuf::new("https://example.com/graph").unwrap());
+	/// assert_eq!(dataset.graph(Some(&name)).unwrap().len(), 2);
+	///
+	/// let a = json_ld::Reference::Id(IriBuf::new("https://example.com/a").unwrap());
+	/// assert!(dataset.remove(Some(&name), &a).is_some());
+	///
+	/// let expanded = dataset.into_expanded_document();
+	/// assert_eq!(expanded.len(), 1);
+	/// # }
+	/// ```
+	#[inline(always)]
+	pub fn into_dataset(self, generator: &mut flattening::Generator) -> Dataset<J, T> {
+		Dataset::from_expanded_document(self, generator)
+	}
+
+	/// Relabel every blank node of the document using the given `generator`.
+	///
+	/// Each blank node identifier is replaced by a fresh identifier,
+	/// the same for every occurrence of the identifier in the document.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, NoLoader, Prefixed, flattening::Generator};
+	/// # use serde_json::Value;
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc: Value = serde_json::from_str(r#"{ "@id": "_:foo", "http://example.com/p": "v" }"#).unwrap();
+	/// let loader = NoLoader::<Value>::new();
+	/// let expanded = doc.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	///
+	/// let relabeled = expanded.relabel_blank_nodes(Prefixed::new("doc-", Generator::new()));
+	/// let node = relabeled.iter().next().unwrap().as_node().unwrap();
+	/// assert_eq!(node.id().unwrap().as_str(), "_:doc-b0");
+	/// # }
+	/// ```
+	pub fn relabel_blank_nodes<G: BlankIdGenerator>(self, generator: G) -> Self {
+		let mut relabeling = Relabeling::new(generator);
+		let objects = self
+			.objects
+			.into_iter()
+			.map(|object| object.map_references(&mut |r| relabeling.relabel(r)));
+
+		Self {
+			stats: self.stats,
+			..Self::new(objects, self.warnings)
+		}
+	}
+}
+
+impl<J: JsonHash + JsonClone, T: Id> ExpandedDocument<J, T> {
+	/// Converts this document into RDF quads.
+	///
+	/// Blank node identifiers are relabeled using the given `generator`.
+	/// See [`rdf::to_rdf`] for more details.
+	#[inline(always)]
+	pub fn to_rdf(
+		&self,
+		generator: &mut flattening::Generator,
+		options: rdf::Options,
+	) -> Result<rdf::Quads<T>, Error> {
+		rdf::to_rdf(self.objects.iter().cloned(), generator, options)
+	}
+
+	/// Computes a content hash of this document.
+	///
+	/// The document is converted into RDF quads with the default options,
+	/// and hashed using [`rdf::hash`].
+	/// The hash does not depend on the order of nodes and values,
+	/// nor on blank node identifiers.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, NoLoader};
+	/// # use serde_json::Value;
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let a: Value = serde_json::from_str(r#"{
+	///   "@id": "_:a",
+	///   "https://example.com/name": "A",
+	///   "https://example.com/age": 42
+	/// }"#).unwrap();
+	///
+	/// let b: Value = serde_json::from_str(r#"{
+	///   "https://example.com/age": 42,
+	///   "https://example.com/name": "A"
+	/// }"#).unwrap();
+	///
+	/// let loader = NoLoader::<Value>::new();
+	/// let a = a.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	/// let b = b.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	///
+	/// assert_eq!(a.hash().unwrap(), b.hash().unwrap());
+	/// # }
+	/// ```
+	pub fn hash(&self) -> Result<rdf::Digest, Error> {
+		let mut generator = flattening::Generator::new();
+		let quads = self.to_rdf(&mut generator, rdf::Options::default())?;
+		Ok(rdf::hash(quads))
+	}
 }
 
-impl<J: compaction::JsonSrc, T: Sync + Send + Id> compaction::Compact<J, T>
+impl<J: compaction::JsonSrc, T: MaybeSync + MaybeSend + Id> compaction::Compact<J, T>
 	for ExpandedDocument<J, T>
 {
 	fn compact_full<'a, K: JsonFrom<J>, C: ContextMut<T>, L: Loader, M>(
@@ -58,16 +393,16 @@ impl<J: compaction::JsonSrc, T: Sync + Send + Id> compaction::Compact<J, T>
 		active_context: context::Inversible<T, &'a C>,
 		type_scoped_context: context::Inversible<T, &'a C>,
 		active_property: Option<&'a str>,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: compaction::Options,
 		meta: M,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
 		T: 'a,
-		C: Sync + Send,
-		C::LocalContext: Send + Sync + From<L::Output>,
-		L: Sync + Send,
-		M: 'a + Send + Sync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
+		C: MaybeSync + MaybeSend,
+		C::LocalContext: MaybeSend + MaybeSync + From<L::Output>,
+		L: MaybeSync + MaybeSend,
+		M: 'a + MaybeSend + MaybeSync + Clone + Fn(Option<&J::MetaData>) -> K::MetaData,
 	{
 		self.objects.compact_full(
 			active_context,
@@ -80,8 +415,19 @@ impl<J: compaction::JsonSrc, T: Sync + Send + Id> compaction::Compact<J, T>
 	}
 }
 
+impl<J: JsonHash + JsonClone, T: Id> Clone for ExpandedDocument<J, T> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		Self {
+			objects: self.objects.clone(),
+			warnings: self.warnings.clone(),
+			stats: self.stats,
+		}
+	}
+}
+
 impl<J: JsonHash, T: Id> IntoIterator for ExpandedDocument<J, T> {
-	type IntoIter = std::collections::hash_set::IntoIter<Indexed<Object<J, T>>>;
+	type IntoIter = indexmap::set::IntoIter<Indexed<Object<J, T>>>;
 	type Item = Indexed<Object<J, T>>;
 
 	#[inline(always)]
@@ -91,7 +437,7 @@ impl<J: JsonHash, T: Id> IntoIterator for ExpandedDocument<J, T> {
 }
 
 impl<'a, J: JsonHash, T: Id> IntoIterator for &'a ExpandedDocument<J, T> {
-	type IntoIter = std::collections::hash_set::Iter<'a, Indexed<Object<J, T>>>;
+	type IntoIter = indexmap::set::Iter<'a, Indexed<Object<J, T>>>;
 	type Item = &'a Indexed<Object<J, T>>;
 
 	#[inline(always)]
@@ -111,6 +457,12 @@ pub type ExpansionError<J> = Loc<Error, <J as Json>::MetaData>;
 
 pub type ExpansionResult<T, J> = Result<ExpandedDocument<J, T>, ExpansionError<J>>;
 
+/// Expansion result, borrowing the document if it is already expanded.
+///
+/// See [`Document::expand_borrowed_with`].
+pub type BorrowedExpansionResult<'a, T, J> =
+	Result<Mown<'a, ExpandedDocument<J, T>>, ExpansionError<J>>;
+
 /// JSON-LD document.
 ///
 /// This trait represent a JSON-LD document that can be expanded into an [`ExpandedDocument`]
@@ -134,17 +486,44 @@ pub trait Document<T: Id> {
 		&'a self,
 		base_url: Option<Iri>,
 		context: &'a C,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: expansion::Options,
 	) -> BoxFuture<'a, ExpansionResult<T, Self::Json>>
 	where
 		Self::Json: expansion::JsonExpand,
-		T: 'a + Send + Sync,
-		C: Send + Sync,
+		T: 'a + MaybeSend + MaybeSync,
+		C: MaybeSend + MaybeSync,
 		C::LocalContext: From<L::Output> + From<Self::Json>,
-		L: Send + Sync,
+		L: MaybeSend + MaybeSync,
 		L::Output: Into<Self::Json>; // TODO get rid of this bound?
 
+	/// Expand the document as [`expand_with`](`Document::expand_with`) does,
+	/// but borrow it instead if it is already expanded.
+	///
+	/// The compaction, flattening and RDF serialization methods use this method,
+	/// so that an [`ExpandedDocument`] is processed without being copied first.
+	fn expand_borrowed_with<'a, C: 'a + ContextMut<T>, L: 'a + Loader>(
+		&'a self,
+		base_url: Option<Iri>,
+		context: &'a C,
+		loader: &'a L,
+		options: expansion::Options,
+	) -> BoxFuture<'a, BorrowedExpansionResult<'a, T, Self::Json>>
+	where
+		Self::Json: expansion::JsonExpand,
+		T: 'a + MaybeSend + MaybeSync,
+		C: MaybeSend + MaybeSync,
+		C::LocalContext: From<L::Output> + From<Self::Json>,
+		L: MaybeSend + MaybeSync,
+		L::Output: Into<Self::Json>,
+	{
+		futures::FutureExt::map(
+			self.expand_with(base_url, context, loader, options),
+			|result| result.map(Mown::Owned),
+		)
+		.boxed()
+	}
+
 	/// Expand the document.
 	///
 	/// Uses the given initial context and the given document loader.
@@ -175,24 +554,24 @@ pub trait Document<T: Id> {
 	///     }
 	///   ]
 	/// }").unwrap();
-	/// let mut loader = NoLoader::<Value>::new();
-	/// let expanded_doc = task::block_on(doc.expand::<context::Json<Value>, _>(&mut loader))?;
+	/// let loader = NoLoader::<Value>::new();
+	/// let expanded_doc = task::block_on(doc.expand::<context::Json<Value>, _>(&loader))?;
 	/// # Ok(())
 	/// # }
 	/// ```
 	#[inline(always)]
 	fn expand<'a, C: 'a + ContextMut<T>, L: Loader>(
 		&'a self,
-		loader: &'a mut L,
+		loader: &'a L,
 	) -> BoxFuture<'a, ExpansionResult<T, Self::Json>>
 	where
-		Self: Send + Sync,
+		Self: MaybeSend + MaybeSync,
 		Self::Json: expansion::JsonExpand,
-		C: Send + Sync,
+		C: MaybeSend + MaybeSync,
 		C::LocalContext: From<L::Output> + From<Self::Json>,
-		L: Send + Sync,
+		L: MaybeSend + MaybeSync,
 		L::Output: Into<Self::Json>,
-		T: 'a + Send + Sync,
+		T: 'a + MaybeSend + MaybeSync,
 	{
 		async move {
 			let context = C::new(self.base_url());
@@ -217,101 +596,103 @@ pub trait Document<T: Id> {
 		&'a self,
 		base_url: Option<Iri<'a>>,
 		context: &'a C,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: compaction::Options,
 		meta_context: M1,
 		meta_document: M2,
 	) -> BoxFuture<'a, Result<K, Error>>
 	where
-		Self: Sync,
+		Self: MaybeSync,
 		Self::Json: expansion::JsonExpand + compaction::JsonSrc,
-		T: 'a + Send + Sync,
+		T: 'a + MaybeSend + MaybeSync,
 		K: JsonFrom<<C::Target as Context<T>>::LocalContext>,
-		C: AsJson<<C::Target as Context<T>>::LocalContext, K> + Send + Sync,
+		C: AsJson<<C::Target as Context<T>>::LocalContext, K> + MaybeSend + MaybeSync,
 		<C::Target as Context<T>>::LocalContext:
 			compaction::JsonSrc + From<L::Output> + From<Self::Json>,
-		C::Target: Send + Sync,
-		L: 'a + Send + Sync,
+		C::Target: MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
 		M1: 'a
 			+ Clone
-			+ Send
-			+ Sync
+			+ MaybeSend
+			+ MaybeSync
 			+ Fn(Option<&<<C::Target as Context<T>>::LocalContext as Json>::MetaData>) -> K::MetaData,
-		M2: 'a + Clone + Send + Sync + Fn(Option<&<Self::Json as Json>::MetaData>) -> K::MetaData,
+		M2: 'a
+			+ Clone
+			+ MaybeSend
+			+ MaybeSync
+			+ Fn(Option<&<Self::Json as Json>::MetaData>) -> K::MetaData,
 		L::Output: Into<Self::Json>,
 	{
-		use compaction::Compact;
-		async move {
-			let json_context = context.as_json_with(meta_context);
-			let context = context::Inversible::new(context.deref());
-			let expanded = self
-				.expand_with(base_url, &C::Target::new(base_url), loader, options.into())
-				.await
-				.map_err(Loc::unwrap)?;
-
-			let compacted: K = if expanded.len() == 1 && options.compact_arrays {
-				expanded
-					.into_iter()
-					.next()
-					.unwrap()
-					.compact_full(
-						context.clone(),
-						context.clone(),
-						None,
-						loader,
-						options,
-						meta_document.clone(),
-					)
-					.await?
-			} else {
-				expanded
-					.compact_full(
-						context.clone(),
-						context.clone(),
-						None,
-						loader,
-						options,
-						meta_document.clone(),
-					)
-					.await?
-			};
-
-			let (mut map, metadata) = match compacted.into_parts() {
-				(generic_json::Value::Array(items), metadata) => {
-					let mut map = K::Object::default();
-					if !items.is_empty() {
-						use crate::syntax::{Keyword, Term};
-						let key = crate::compaction::compact_iri::<Self::Json, _, _>(
-							context.clone(),
-							&Term::Keyword(Keyword::Graph),
-							true,
-							false,
-							options,
-						)?;
-						map.insert(
-							K::new_key(&key.unwrap(), meta_document(None)),
-							K::array(items, metadata),
-						);
-					}
-
-					(map, meta_document(None))
-				}
-				(generic_json::Value::Object(map), metadata) => (map, metadata),
-				_ => {
-					// This should never be triggered unless some user
-					// uses a custom faulty `Compact` implementation.
-					panic!("invalid compact document")
-				}
-			};
-
-			if !map.is_empty()
-				&& !json_context.is_null()
-				&& !json_context.is_empty_array_or_object()
-			{
-				map.insert(K::new_key("@context", meta_document(None)), json_context);
-			}
+		compact_document(
+			self,
+			base_url,
+			context,
+			loader,
+			options,
+			meta_context,
+			meta_document,
+			None,
+		)
+	}
 
-			Ok(K::object(map, metadata))
+	/// Compact the document with a custom base URL, context, document loader and options,
+	/// as [`compact_with`](Document::compact_with) does,
+	/// also returning processing statistics.
+	///
+	/// See [`Stats`] for the collected statistics.
+	/// Collecting them requires a clock (the processing times are measured with [`Instant`]),
+	/// which is not available on every target, such as `wasm32-unknown-unknown`.
+	/// Use [`compact_with`](Document::compact_with) there.
+	///
+	/// The `meta_context` parameter is a function to convert the metadata
+	/// associated to the input context (JSON representation) to `K::MetaData`.
+	/// The `meta_document` parameter is another conversion function for the
+	/// metadata attached to the document.
+	fn compact_with_stats<'a, K: JsonFrom<Self::Json>, C: ContextMutProxy<T>, L: Loader, M1, M2>(
+		&'a self,
+		base_url: Option<Iri<'a>>,
+		context: &'a C,
+		loader: &'a L,
+		options: compaction::Options,
+		meta_context: M1,
+		meta_document: M2,
+	) -> BoxFuture<'a, Result<(K, Stats), Error>>
+	where
+		Self: MaybeSync,
+		Self::Json: expansion::JsonExpand + compaction::JsonSrc,
+		T: 'a + MaybeSend + MaybeSync,
+		K: JsonFrom<<C::Target as Context<T>>::LocalContext>,
+		C: AsJson<<C::Target as Context<T>>::LocalContext, K> + MaybeSend + MaybeSync,
+		<C::Target as Context<T>>::LocalContext:
+			compaction::JsonSrc + From<L::Output> + From<Self::Json>,
+		C::Target: MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
+		M1: 'a
+			+ Clone
+			+ MaybeSend
+			+ MaybeSync
+			+ Fn(Option<&<<C::Target as Context<T>>::LocalContext as Json>::MetaData>) -> K::MetaData,
+		M2: 'a
+			+ Clone
+			+ MaybeSend
+			+ MaybeSync
+			+ Fn(Option<&<Self::Json as Json>::MetaData>) -> K::MetaData,
+		L::Output: Into<Self::Json>,
+	{
+		async move {
+			let mut stats = Stats::default();
+			let compacted = compact_document(
+				self,
+				base_url,
+				context,
+				loader,
+				options,
+				meta_context,
+				meta_document,
+				Some(&mut stats),
+			)
+			.await?;
+			Ok((compacted, stats))
 		}
 		.boxed()
 	}
@@ -321,18 +702,18 @@ pub trait Document<T: Id> {
 	fn compact<'a, C: ContextMutProxy<T> + AsJson<Self::Json, Self::Json>, L: Loader>(
 		&'a self,
 		context: &'a C,
-		loader: &'a mut L,
+		loader: &'a L,
 	) -> BoxFuture<'a, Result<Self::Json, Error>>
 	where
-		Self: Sync,
+		Self: MaybeSync,
 		Self::Json:
 			JsonFrom<Self::Json> + expansion::JsonExpand + compaction::JsonSrc + From<L::Output>,
 		<Self::Json as Json>::MetaData: Default,
-		T: 'a + Send + Sync,
+		T: 'a + MaybeSend + MaybeSync,
 		C::Target: Context<T, LocalContext = Self::Json>,
-		C: Send + Sync,
-		C::Target: Send + Sync,
-		L: 'a + Send + Sync,
+		C: MaybeSend + MaybeSync,
+		C::Target: MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
 		L::Output: Into<Self::Json>,
 	{
 		self.compact_with(
@@ -344,54 +725,795 @@ pub trait Document<T: Id> {
 			|m| m.cloned().unwrap_or_default(),
 		)
 	}
-}
-
-/// Default JSON document implementation.
-impl<J: Json, T: Id> Document<T> for J {
-	type Json = Self;
 
-	/// Returns `None`.
+	/// Compact the document against the given unprocessed context.
 	///
-	/// Use [`RemoteDocument`] to attach a base URL to a `JsonValue` document.
-	#[inline(always)]
-	fn base_url(&self) -> Option<Iri> {
-		None
+	/// The `context` is a local context, such as a context definition or a context IRI,
+	/// as it would appear in a `@context` entry.
+	/// It is processed on a newly-initialized active context using the given loader
+	/// and the document [`base_url`](`Document::base_url`), then the document is compacted
+	/// with [`compact`](`Document::compact`).
+	/// The `context` is used as is for the `@context` entry of the compacted document.
+	///
+	/// The `context` can also be an array of contexts (context definitions and/or IRIs),
+	/// processed in sequence as `"@context": [a, b]` would be.
+	/// The array is reproduced in the `@context` entry.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{Document, StaticLoader};
+	/// # use iref::{Iri, IriBuf};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let loader = StaticLoader::<Value>::default().with(
+	///   Iri::new("https://example.org/context.jsonld").unwrap(),
+	///   r#"{ "@context": { "name": "https://schema.org/name" } }"#
+	/// );
+	///
+	/// let doc = json!({ "https://schema.org/name": "Alice" });
+	/// let context = json!("https://example.org/context.jsonld");
+	/// let compacted = Document::<IriBuf>::compact_with_context(&doc, &context, &loader)
+	///   .await
+	///   .unwrap();
+	///
+	/// assert_eq!(compacted, json!({
+	///   "@context": "https://example.org/context.jsonld",
+	///   "name": "Alice"
+	/// }));
+	///
+	/// // Array of contexts.
+	/// let doc = json!({
+	///   "https://schema.org/name": "Alice",
+	///   "https://schema.org/knows": { "@id": "https://example.org/bob" }
+	/// });
+	/// let context = json!([
+	///   "https://example.org/context.jsonld",
+	///   { "knows": { "@id": "https://schema.org/knows", "@type": "@id" } }
+	/// ]);
+	/// let compacted = Document::<IriBuf>::compact_with_context(&doc, &context, &loader)
+	///   .await
+	///   .unwrap();
+	///
+	/// assert_eq!(compacted, json!({
+	///   "@context": [
+	///     "https://example.org/context.jsonld",
+	///     { "knows": { "@id": "https://schema.org/knows", "@type": "@id" } }
+	///   ],
+	///   "name": "Alice",
+	///   "knows": "https://example.org/bob"
+	/// }));
+	/// # }
+	/// ```
+	fn compact_with_context<'a, L: Loader>(
+		&'a self,
+		context: &'a Self::Json,
+		loader: &'a L,
+	) -> BoxFuture<'a, Result<Self::Json, Error>>
+	where
+		Self: MaybeSync,
+		Self::Json: JsonFrom<Self::Json>
+			+ context::JsonContext
+			+ expansion::JsonExpand
+			+ compaction::JsonSrc
+			+ From<L::Output>,
+		<Self::Json as Json>::MetaData: Default,
+		T: 'a + MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
+		L::Output: Into<Self::Json>,
+	{
+		self.compact_with_context_and_options(context, loader, compaction::Options::default())
 	}
 
-	#[inline(always)]
-	fn expand_with<'a, C: ContextMut<T>, L: Loader>(
+	/// Compact the document against the given unprocessed context, with the given options.
+	///
+	/// The `context` is processed as with [`compact_with_context`](`Document::compact_with_context`),
+	/// in the processing mode of the given `options`,
+	/// then the document is compacted with [`compact_with`](`Document::compact_with`).
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{compaction, Document, NoLoader};
+	/// # use iref::IriBuf;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({ "https://schema.org/knows": [{ "@id": "https://example.org/bob" }] });
+	/// let context = json!({ "knows": { "@id": "https://schema.org/knows", "@type": "@id" } });
+	///
+	/// let options = compaction::Options { compact_arrays: false, ..Default::default() };
+	/// let compacted = Document::<IriBuf>::compact_with_context_and_options(
+	///   &doc,
+	///   &context,
+	///   &NoLoader::<Value>::new(),
+	///   options
+	/// )
+	/// .await
+	/// .unwrap();
+	///
+	/// assert_eq!(compacted, json!({
+	///   "@context": { "knows": { "@id": "https://schema.org/knows", "@type": "@id" } },
+	///   "@graph": [{ "knows": ["https://example.org/bob"] }]
+	/// }));
+	/// # }
+	/// ```
+	fn compact_with_context_and_options<'a, L: Loader>(
 		&'a self,
-		base_url: Option<Iri>,
-		context: &'a C,
-		loader: &'a mut L,
-		options: expansion::Options,
-	) -> BoxFuture<'a, ExpansionResult<T, Self>>
+		context: &'a Self::Json,
+		loader: &'a L,
+		options: compaction::Options,
+	) -> BoxFuture<'a, Result<Self::Json, Error>>
 	where
-		Self: expansion::JsonExpand,
-		C: Send + Sync,
-		C::LocalContext: From<L::Output> + From<Self>,
-		L: Send + Sync,
-		L::Output: Into<Self>,
-		T: 'a + Send + Sync,
+		Self: MaybeSync,
+		Self::Json: JsonFrom<Self::Json>
+			+ context::JsonContext
+			+ expansion::JsonExpand
+			+ compaction::JsonSrc
+			+ From<L::Output>,
+		<Self::Json as Json>::MetaData: Default,
+		T: 'a + MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
+		L::Output: Into<Self::Json>,
 	{
-		let base_url = base_url.map(IriBuf::from);
+		use context::Local;
+		async move {
+			let processed = context
+				.process_with(
+					&context::Json::<Self::Json, T>::new(self.base_url()),
+					loader,
+					self.base_url(),
+					options.clone().into(),
+				)
+				.await
+				.map_err(Loc::unwrap)?;
+
+			self.compact_with(
+				self.base_url(),
+				&processed,
+				loader,
+				options,
+				|m| m.cloned().unwrap_or_default(),
+				|m| m.cloned().unwrap_or_default(),
+			)
+			.await
+		}
+		.boxed()
+	}
 
+	/// Compact the document against the context at the given IRI.
+	///
+	/// The context is loaded with the given loader and processed
+	/// as with [`compact_with_context`](`Document::compact_with_context`).
+	/// The `@context` entry of the compacted document is the context IRI,
+	/// not the context definition.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{Document, StaticLoader};
+	/// # use iref::{Iri, IriBuf};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let context_iri = Iri::new("https://example.org/context.jsonld").unwrap();
+	/// let loader = StaticLoader::<Value>::default().with(
+	///   context_iri,
+	///   r#"{ "@context": { "knows": { "@id": "https://schema.org/knows", "@type": "@id" } } }"#
+	/// );
+	///
+	/// let doc = json!({ "https://schema.org/knows": { "@id": "https://example.org/bob" } });
+	/// let compacted = Document::<IriBuf>::compact_with_context_iri(&doc, context_iri, &loader)
+	///   .await
+	///   .unwrap();
+	///
+	/// assert_eq!(compacted, json!({
+	///   "@context": "https://example.org/context.jsonld",
+	///   "knows": "https://example.org/bob"
+	/// }));
+	/// # }
+	/// ```
+	fn compact_with_context_iri<'a, L: Loader>(
+		&'a self,
+		iri: Iri<'a>,
+		loader: &'a L,
+	) -> BoxFuture<'a, Result<Self::Json, Error>>
+	where
+		Self: MaybeSync,
+		Self::Json: JsonFrom<Self::Json>
+			+ context::JsonContext
+			+ expansion::JsonExpand
+			+ compaction::JsonSrc
+			+ From<L::Output>,
+		<Self::Json as Json>::MetaData: Default,
+		T: 'a + MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
+		L::Output: Into<Self::Json>,
+	{
 		async move {
-			let mut warnings = Vec::new();
-			let objects =
-				expansion::expand(context, self, base_url, loader, options, &mut warnings).await?;
-			Ok(ExpandedDocument::new(objects, warnings))
+			let context: Self::Json = AsAnyJson::as_json(iri.as_str());
+			self.compact_with_context(&context, loader).await
 		}
 		.boxed()
 	}
-}
 
-/// Remote JSON-LD document.
+	/// Flatten the document with a custom base URL, context, document loader and options.
+	///
+	/// The document is expanded then flattened.
+	/// If a `context` is given, the flattened document is then compacted against it.
+	/// The `meta_context` and `meta_document` parameters are metadata conversion functions,
+	/// just like for [`compact_with`](`Document::compact_with`).
+	fn flatten_with<'a, K: JsonFrom<Self::Json>, C: ContextMutProxy<T>, L: Loader, M1, M2>(
+		&'a self,
+		base_url: Option<Iri<'a>>,
+		context: Option<&'a C>,
+		loader: &'a L,
+		options: compaction::Options,
+		meta_context: M1,
+		meta_document: M2,
+	) -> BoxFuture<'a, Result<K, Error>>
+	where
+		Self: MaybeSync,
+		Self::Json: expansion::JsonExpand + compaction::JsonSrc,
+		T: 'a + MaybeSend + MaybeSync,
+		K: JsonFrom<<C::Target as Context<T>>::LocalContext>,
+		C: AsJson<<C::Target as Context<T>>::LocalContext, K> + MaybeSend + MaybeSync,
+		<C::Target as Context<T>>::LocalContext:
+			compaction::JsonSrc + From<L::Output> + From<Self::Json>,
+		C::Target: MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
+		M1: 'a
+			+ Clone
+			+ MaybeSend
+			+ MaybeSync
+			+ Fn(Option<&<<C::Target as Context<T>>::LocalContext as Json>::MetaData>) -> K::MetaData,
+		M2: 'a
+			+ Clone
+			+ MaybeSend
+			+ MaybeSync
+			+ Fn(Option<&<Self::Json as Json>::MetaData>) -> K::MetaData,
+		L::Output: Into<Self::Json>,
+	{
+		use compaction::Compact;
+		async move {
+			let initial_context = C::Target::new(base_url);
+			let expanded = self
+				.expand_borrowed_with(base_url, &initial_context, loader, options.clone().into())
+				.await
+				.map_err(Loc::unwrap)?;
+
+			let mut generator = flattening::Generator::new();
+			let flattened = match expanded {
+				Mown::Owned(expanded) => {
+					flattening::flatten(expanded, &mut generator, options.ordered)?
+				}
+				Mown::Borrowed(expanded) => {
+					flattening::flatten(expanded.iter().cloned(), &mut generator, options.ordered)?
+				}
+			};
+
+			match context {
+				Some(context) => {
+					let json_context = context.as_json_with(meta_context);
+					let context = context.inversible();
+					let compacted: K = flattened
+						.compact_full(
+							context.clone(),
+							context.clone(),
+							None,
+							loader,
+							options.clone(),
+							meta_document.clone(),
+						)
+						.await?;
+
+					compacted_document::<Self::Json, _, _, _, _>(
+						compacted,
+						json_context,
+						context,
+						options,
+						meta_document,
+					)
+				}
+				None => Ok(flattened.as_json_with(meta_document)),
+			}
+		}
+		.boxed()
+	}
+
+	/// Flatten the document.
+	///
+	/// If a `context` is given, the flattened document is compacted against it.
+	///
+	/// # Example
+	/// ```
+	/// # fn main() -> Result<(), json_ld::Loc<json_ld::Error, ()>> {
+	/// use async_std::task;
+	/// use json_ld::{context::{self, Local}, Document, NoLoader};
+	/// use serde_json::Value;
+	///
+	/// let doc: Value = serde_json::from_str("{
+	///   \"@context\": {
+	///     \"name\": \"http://xmlns.com/foaf/0.1/name\",
+	///     \"knows\": \"http://xmlns.com/foaf/0.1/knows\"
+	///   },
+	///   \"@id\": \"http://timothee.haudebourg.net/\",
+	///   \"name\": \"Timothée Haudebourg\",
+	///   \"knows\": [
+	///     {
+	///       \"name\": \"Amélie Barbe\"
+	///     }
+	///   ]
+	/// }").unwrap();
+	/// let context: Value = serde_json::from_str("{
+	///   \"name\": \"http://xmlns.com/foaf/0.1/name\"
+	/// }").unwrap();
+	///
+	/// let loader = NoLoader::<Value>::new();
+	/// let processed_context = task::block_on(context.process::<context::Json<Value>, _>(&loader, None))?;
+	/// let flattened_doc = task::block_on(doc.flatten(Some(&processed_context), &loader)).unwrap();
+	/// assert_eq!(flattened_doc["@graph"].as_array().unwrap().len(), 2);
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[inline(always)]
+	fn flatten<'a, C: ContextMutProxy<T> + AsJson<Self::Json, Self::Json>, L: Loader>(
+		&'a self,
+		context: Option<&'a C>,
+		loader: &'a L,
+	) -> BoxFuture<'a, Result<Self::Json, Error>>
+	where
+		Self: MaybeSync,
+		Self::Json:
+			JsonFrom<Self::Json> + expansion::JsonExpand + compaction::JsonSrc + From<L::Output>,
+		<Self::Json as Json>::MetaData: Default,
+		T: 'a + MaybeSend + MaybeSync,
+		C::Target: Context<T, LocalContext = Self::Json>,
+		C: MaybeSend + MaybeSync,
+		C::Target: MaybeSend + MaybeSync,
+		L: 'a + MaybeSend + MaybeSync,
+		L::Output: Into<Self::Json>,
+	{
+		self.flatten_with(
+			self.base_url(),
+			context,
+			loader,
+			compaction::Options::default(),
+			|m| m.cloned().unwrap_or_default(),
+			|m| m.cloned().unwrap_or_default(),
+		)
+	}
+
+	/// Serialize the document into RDF quads with a custom base URL, initial context,
+	/// document loader and options.
+	///
+	/// The document is first expanded then converted into RDF using the
+	/// [`rdf::to_rdf`] function.
+	fn to_rdf_with<'a, C: 'a + ContextMut<T>, L: 'a + Loader>(
+		&'a self,
+		base_url: Option<Iri<'a>>,
+		context: &'a C,
+		loader: &'a L,
+		options: rdf::Options,
+	) -> BoxFuture<'a, Result<rdf::Quads<T>, Error>>
+	where
+		Self: MaybeSync,
+		Self::Json: expansion::JsonExpand,
+		T: 'a + MaybeSend + MaybeSync,
+		C: MaybeSend + MaybeSync,
+		C::LocalContext: From<L::Output> + From<Self::Json>,
+		L: MaybeSend + MaybeSync,
+		L::Output: Into<Self::Json>,
+	{
+		async move {
+			let expanded = self
+				.expand_borrowed_with(base_url, context, loader, options.into())
+				.await
+				.map_err(Loc::unwrap)?;
+
+			let mut generator = flattening::Generator::new();
+			match expanded {
+				Mown::Owned(expanded) => rdf::to_rdf(expanded, &mut generator, options),
+				Mown::Borrowed(expanded) => expanded.to_rdf(&mut generator, options),
+			}
+		}
+		.boxed()
+	}
+
+	/// Serialize the document into RDF quads.
+	///
+	/// Uses the given initial context and the given document loader.
+	/// The default implementation is equivalent to [`to_rdf_with`](`Document::to_rdf_with`), but
+	/// uses the document [`base_url`](`Document::base_url`), with the default
+	/// options.
+	///
+	/// # Example
+	/// ```
+	/// # fn main() -> Result<(), json_ld::Error> {
+	/// use async_std::task;
+	/// use json_ld::{Document, context, NoLoader};
+	/// use serde_json::Value;
+	///
+	/// let doc: Value = serde_json::from_str("{
+	///   \"@context\": {
+	///     \"name\": \"http://xmlns.com/foaf/0.1/name\"
+	///   },
+	///   \"@id\": \"http://timothee.haudebourg.net/\",
+	///   \"name\": \"Timothée Haudebourg\"
+	/// }").unwrap();
+	/// let loader = NoLoader::<Value>::new();
+	/// let quads: Vec<_> = task::block_on(doc.to_rdf::<context::Json<Value>, _>(&loader))?.collect();
+	/// assert_eq!(quads.len(), 1);
+	/// assert_eq!(quads[0].object().as_literal().unwrap().lexical_form(), "Timothée Haudebourg");
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[inline(always)]
+	fn to_rdf<'a, C: 'a + ContextMut<T>, L: 'a + Loader>(
+		&'a self,
+		loader: &'a L,
+	) -> BoxFuture<'a, Result<rdf::Quads<T>, Error>>
+	where
+		Self: MaybeSend + MaybeSync,
+		Self::Json: expansion::JsonExpand,
+		T: 'a + MaybeSend + MaybeSync,
+		C: MaybeSend + MaybeSync,
+		C::LocalContext: From<L::Output> + From<Self::Json>,
+		L: MaybeSend + MaybeSync,
+		L::Output: Into<Self::Json>,
+	{
+		async move {
+			let context = C::new(self.base_url());
+			self.to_rdf_with(self.base_url(), &context, loader, rdf::Options::default())
+				.await
+		}
+		.boxed()
+	}
+}
+
+/// Indexes the node objects found in the given objects by identifier.
+fn index_objects<'a, J: JsonHash, T: Id>(
+	objects: impl IntoIterator<Item = &'a Indexed<Object<J, T>>>,
+	index: &mut HashMap<&'a Reference<T>, Vec<&'a Node<J, T>>>,
+) {
+	for object in objects {
+		match object.inner() {
+			Object::Value(_) => (),
+			Object::List(items) => index_objects(items, index),
+			Object::Node(node) => index_node(node, index),
+		}
+	}
+}
+
+/// Indexes the given node object and its nested node objects by identifier.
+fn index_node<'a, J: JsonHash, T: Id>(
+	node: &'a Node<J, T>,
+	index: &mut HashMap<&'a Reference<T>, Vec<&'a Node<J, T>>>,
+) {
+	if let Some(id) = node.id() {
+		index.entry(id).or_default().push(node)
+	}
+
+	if let Some(graph) = node.graph() {
+		index_objects(graph, index)
+	}
+
+	for included in node.included_nodes() {
+		index_node(included, index)
+	}
+
+	for (_, values) in node.properties() {
+		index_objects(values, index)
+	}
+
+	for (_, nodes) in node.reverse_properties() {
+		for node in nodes {
+			index_node(node, index)
+		}
+	}
+}
+
+/// Turns a compacted document into a JSON object,
+/// wrapping it into a `@graph` entry if it is an array,
+/// and adding the given JSON context.
+fn compacted_document<J: JsonHash, K: JsonFrom<J>, T: Id, C: Context<T>, M>(
+	compacted: K,
+	json_context: K,
+	context: context::Inversible<T, &C>,
+	options: compaction::Options,
+	meta_document: M,
+) -> Result<K, Error>
+where
+	M: Fn(Option<&J::MetaData>) -> K::MetaData,
+{
+	// Unless `@graph` can be omitted, a single top-level node is wrapped in `@graph`.
+	let compacted = if options.omit_graph {
+		compacted
+	} else {
+		match compacted.into_parts() {
+			(generic_json::Value::Object(map), metadata) if !map.is_empty() => K::array(
+				std::iter::once(K::object(map, metadata)).collect(),
+				meta_document(None),
+			),
+			(value, metadata) => K::new(value, metadata),
+		}
+	};
+
+	let (mut map, metadata) = match compacted.into_parts() {
+		(generic_json::Value::Array(items), metadata) => {
+			let mut map = K::Object::default();
+			if !items.is_empty() {
+				use crate::syntax::{Keyword, Term};
+				let key = crate::compaction::compact_iri::<J, _, _>(
+					context,
+					&Term::Keyword(Keyword::Graph),
+					true,
+					false,
+					options.clone(),
+				)?;
+				map.insert(
+					K::new_key(&key.unwrap(), meta_document(None)),
+					K::array(items, metadata),
+				);
+			}
+
+			(map, meta_document(None))
+		}
+		(generic_json::Value::Object(map), metadata) => (map, metadata),
+		_ => {
+			// This should never be triggered unless some user
+			// uses a custom faulty `Compact` implementation.
+			panic!("invalid compact document")
+		}
+	};
+
+	if !map.is_empty() {
+		match options.context_entry {
+			compaction::ContextEntry::Embedded => {
+				if !json_context.is_null() && !json_context.is_empty_array_or_object() {
+					map.insert(K::new_key("@context", meta_document(None)), json_context);
+				}
+			}
+			compaction::ContextEntry::Iri(iri) => {
+				map.insert(
+					K::new_key("@context", meta_document(None)),
+					K::string(iri.as_str().into(), meta_document(None)),
+				);
+			}
+			compaction::ContextEntry::Omitted => (),
+		}
+	}
+
+	let document = K::object(map, metadata);
+	if options.ordered {
+		Ok(sort_entries(document))
+	} else {
+		Ok(document)
+	}
+}
+
+/// Recursively sorts the entries of every object in the given value by key.
+fn sort_entries<K: JsonBuild + JsonIntoMut>(value: K) -> K {
+	match value.into_parts() {
+		(generic_json::Value::Array(items), metadata) => {
+			K::array(items.into_iter().map(sort_entries).collect(), metadata)
+		}
+		(generic_json::Value::Object(map), metadata) => {
+			let mut entries: Vec<_> = map.into_iter().collect();
+			entries.sort_by(|(a, _), (b, _)| str::cmp(a, b));
+			K::object(
+				entries
+					.into_iter()
+					.map(|(key, value)| (key, sort_entries(value)))
+					.collect(),
+				metadata,
+			)
+		}
+		(value, metadata) => K::new(value, metadata),
+	}
+}
+
+/// Default JSON document implementation.
+impl<J: Json, T: Id> Document<T> for J {
+	type Json = Self;
+
+	/// Returns `None`.
+	///
+	/// Use [`RemoteDocument`] to attach a base URL to a `JsonValue` document.
+	#[inline(always)]
+	fn base_url(&self) -> Option<Iri> {
+		None
+	}
+
+	#[inline(always)]
+	fn expand_with<'a, C: ContextMut<T>, L: Loader>(
+		&'a self,
+		base_url: Option<Iri>,
+		context: &'a C,
+		loader: &'a L,
+		options: expansion::Options,
+	) -> BoxFuture<'a, ExpansionResult<T, Self>>
+	where
+		Self: expansion::JsonExpand,
+		C: MaybeSend + MaybeSync,
+		C::LocalContext: From<L::Output> + From<Self>,
+		L: MaybeSend + MaybeSync,
+		L::Output: Into<Self>,
+		T: 'a + MaybeSend + MaybeSync,
+	{
+		let base_url = base_url.map(IriBuf::from);
+
+		async move {
+			if options.deduplicate_warnings {
+				let mut warnings = DeduplicateWarnings::new();
+				let (objects, stats) =
+					expand_document(context, self, base_url, loader, options, &mut warnings)
+						.await?;
+				let mut document = ExpandedDocument::new(objects, warnings.into_warnings());
+				document.set_stats(stats);
+				Ok(document)
+			} else {
+				let mut warnings = Vec::new();
+				let (objects, stats) =
+					expand_document(context, self, base_url, loader, options, &mut warnings)
+						.await?;
+				let mut document = ExpandedDocument::new(objects, warnings);
+				document.set_stats(stats);
+				Ok(document)
+			}
+		}
+		.boxed()
+	}
+}
+
+/// Compacts the given document,
+/// as [`Document::compact_with`] does.
+///
+/// If a statistics collector is given, it is filled with the statistics
+/// collected during expansion and the time spent compacting.
+#[allow(clippy::too_many_arguments)]
+fn compact_document<
+	'a,
+	D: Document<T> + ?Sized,
+	T: Id,
+	K: JsonFrom<D::Json>,
+	C: ContextMutProxy<T>,
+	L: Loader,
+	M1,
+	M2,
+>(
+	document: &'a D,
+	base_url: Option<Iri<'a>>,
+	context: &'a C,
+	loader: &'a L,
+	options: compaction::Options,
+	meta_context: M1,
+	meta_document: M2,
+	stats: Option<&'a mut Stats>,
+) -> BoxFuture<'a, Result<K, Error>>
+where
+	D: MaybeSync,
+	D::Json: expansion::JsonExpand + compaction::JsonSrc,
+	T: 'a + MaybeSend + MaybeSync,
+	K: JsonFrom<<C::Target as Context<T>>::LocalContext>,
+	C: AsJson<<C::Target as Context<T>>::LocalContext, K> + MaybeSend + MaybeSync,
+	<C::Target as Context<T>>::LocalContext: compaction::JsonSrc + From<L::Output> + From<D::Json>,
+	C::Target: MaybeSend + MaybeSync,
+	L: 'a + MaybeSend + MaybeSync,
+	M1: 'a
+		+ Clone
+		+ MaybeSend
+		+ MaybeSync
+		+ Fn(Option<&<<C::Target as Context<T>>::LocalContext as Json>::MetaData>) -> K::MetaData,
+	M2: 'a
+		+ Clone
+		+ MaybeSend
+		+ MaybeSync
+		+ Fn(Option<&<D::Json as Json>::MetaData>) -> K::MetaData,
+	L::Output: Into<D::Json>,
+{
+	use compaction::Compact;
+	async move {
+		let json_context = context.as_json_with(meta_context);
+		let context = context.inversible();
+		let expansion_options = expansion::Options {
+			collect_stats: stats.is_some(),
+			..options.clone().into()
+		};
+		let initial_context = C::Target::new(base_url);
+		let expanded = document
+			.expand_borrowed_with(base_url, &initial_context, loader, expansion_options)
+			.await
+			.map_err(Loc::unwrap)?;
+
+		let start = stats.as_ref().map(|_| Instant::now());
+		let compacted: K = if expanded.len() == 1 && options.compact_arrays {
+			expanded
+				.iter()
+				.next()
+				.unwrap()
+				.compact_full(
+					context.clone(),
+					context.clone(),
+					None,
+					loader,
+					options.clone(),
+					meta_document.clone(),
+				)
+				.await?
+		} else {
+			expanded
+				.compact_full(
+					context.clone(),
+					context.clone(),
+					None,
+					loader,
+					options.clone(),
+					meta_document.clone(),
+				)
+				.await?
+		};
+
+		let compacted = compacted_document::<D::Json, _, _, _, _>(
+			compacted,
+			json_context,
+			context,
+			options,
+			meta_document,
+		)?;
+
+		if let (Some(stats), Some(start)) = (stats, start) {
+			*stats = expanded.stats().cloned().unwrap_or_default();
+			stats.compaction_time = start.elapsed();
+		}
+
+		Ok(compacted)
+	}
+	.boxed()
+}
+
+/// Expands the given document,
+/// collecting statistics if requested by the `collect_stats` option.
+async fn expand_document<
+	J: expansion::JsonExpand,
+	T: Id,
+	C: ContextMut<T>,
+	L: Loader,
+	W: WarningHandler<J::MetaData> + MaybeSend,
+>(
+	context: &C,
+	document: &J,
+	base_url: Option<IriBuf>,
+	loader: &L,
+	options: expansion::Options,
+	warnings: &mut W,
+) -> Result<(IndexSet<Indexed<Object<J, T>>>, Option<Stats>), Loc<Error, J::MetaData>>
+where
+	T: MaybeSend + MaybeSync,
+	C: MaybeSend + MaybeSync,
+	C::LocalContext: From<L::Output> + From<J>,
+	L: MaybeSend + MaybeSync,
+	L::Output: Into<J>,
+{
+	if options.collect_stats {
+		let (objects, stats) =
+			expansion::expand_with_stats(context, document, base_url, loader, options, warnings)
+				.await?;
+		Ok((objects, Some(stats)))
+	} else {
+		let objects =
+			expansion::expand(context, document, base_url, loader, options, warnings).await?;
+		Ok((objects, None))
+	}
+}
+
+/// Remote JSON-LD document.
 ///
 /// Represent a document located at a given base URL.
 /// This is the result of loading a document with [`Loader::load`](`crate::Loader::load`).
 /// It is a simple wrapper that [`Deref`] to the underlying document while remembering its
-/// base URL.
+/// base URL, along with the metadata of the fetch:
+/// its [content type](RemoteDocument::content_type),
+/// linked [context URL](RemoteDocument::context_url)
+/// and [profile](RemoteDocument::profile).
 ///
 /// # Example
 /// ```
@@ -416,10 +1538,19 @@ impl<J: Json, T: Id> Document<T> for J {
 #[derive(Clone)]
 pub struct RemoteDocument<D> {
 	/// Base URL of the document.
-	base_url: IriBuf,
+	pub(crate) base_url: IriBuf,
 
 	/// Document id.
-	source: loader::Id,
+	pub(crate) source: loader::Id,
+
+	/// Media type of the document, without parameters.
+	content_type: Option<String>,
+
+	/// URL of the context linked by the document, using the `Link` HTTP header.
+	context_url: Option<IriBuf>,
+
+	/// Value of the `profile` parameter of the document media type.
+	profile: Option<String>,
 
 	/// Document contents.
 	doc: D,
@@ -432,14 +1563,119 @@ impl<D> RemoteDocument<D> {
 		RemoteDocument {
 			base_url,
 			source,
+			content_type: None,
+			context_url: None,
+			profile: None,
 			doc,
 		}
 	}
 
+	/// Sets the media type of the document.
+	#[inline(always)]
+	pub fn with_content_type(mut self, content_type: Option<String>) -> Self {
+		self.content_type = content_type;
+		self
+	}
+
+	/// Sets the URL of the context linked by the document.
+	#[inline(always)]
+	pub fn with_context_url(mut self, context_url: Option<IriBuf>) -> Self {
+		self.context_url = context_url;
+		self
+	}
+
+	/// Sets the profile of the document media type.
+	#[inline(always)]
+	pub fn with_profile(mut self, profile: Option<String>) -> Self {
+		self.profile = profile;
+		self
+	}
+
+	/// Media type of the document (without parameters), if known.
+	#[inline(always)]
+	pub fn content_type(&self) -> Option<&str> {
+		self.content_type.as_deref()
+	}
+
+	/// URL of the context linked by the document, if any.
+	///
+	/// When a document is served with the `application/json` media type,
+	/// its context can be given by a `Link` HTTP header with the
+	/// `http://www.w3.org/ns/json-ld#context` relation type.
+	#[inline(always)]
+	pub fn context_url(&self) -> Option<Iri> {
+		self.context_url.as_ref().map(IriBuf::as_iri)
+	}
+
+	/// Value of the `profile` parameter of the document media type, if any.
+	#[inline(always)]
+	pub fn profile(&self) -> Option<&str> {
+		self.profile.as_deref()
+	}
+
+	/// Checks if the given profile is listed in the `profile` parameter
+	/// of the document media type.
+	///
+	/// The `profile` parameter is a space separated list of profile IRIs.
+	#[inline(always)]
+	pub fn has_profile(&self, profile: loader::Profile) -> bool {
+		self.profile
+			.as_deref()
+			.map(|p| {
+				p.split_whitespace()
+					.any(|iri| iri == profile.iri().as_str())
+			})
+			.unwrap_or(false)
+	}
+
 	pub fn source(&self) -> loader::Id {
 		self.source
 	}
 
+	/// Returns a reference to the document contents.
+	#[inline(always)]
+	pub fn document(&self) -> &D {
+		&self.doc
+	}
+
+	/// Returns a mutable reference to the document contents.
+	#[inline(always)]
+	pub fn document_mut(&mut self) -> &mut D {
+		&mut self.doc
+	}
+
+	/// URL of the document.
+	///
+	/// This is the final URL of the document, after redirections,
+	/// which is used as base URL when the document is expanded.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, Loader, Object, StaticLoader};
+	/// # use iref::Iri;
+	/// # use serde_json::Value;
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let url = Iri::new("https://example.org/dir/doc.jsonld").unwrap();
+	/// let loader = StaticLoader::<Value>::default()
+	///   .with(url, r#"{ "@id": "node", "https://example.org/name": "Alice" }"#);
+	///
+	/// let doc = loader.load(url).await.unwrap();
+	/// assert_eq!(doc.document_url(), url);
+	///
+	/// // Relative IRIs are resolved against the document URL.
+	/// let expanded = doc.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+	/// let object = expanded.into_iter().next().unwrap();
+	/// if let Object::Node(node) = object.as_ref() {
+	///   assert_eq!(node.id().unwrap().as_str(), "https://example.org/dir/node");
+	/// }
+	/// # }
+	/// ```
+	#[inline(always)]
+	pub fn document_url(&self) -> Iri {
+		self.base_url.as_iri()
+	}
+
 	/// Consume the remote document and return the inner document.
 	#[inline(always)]
 	pub fn into_document(self) -> D {
@@ -453,6 +1689,99 @@ impl<D> RemoteDocument<D> {
 	}
 }
 
+/// An expanded document is a document.
+///
+/// This allows an expanded document to be compacted, flattened
+/// or converted into RDF through the [`Document`] methods
+/// without being serialized back into JSON and expanded again.
+///
+/// Expanded documents also implement [`Compact`](crate::Compact),
+/// whose `compact` and `compact_with` methods take different parameters.
+/// Use the fully qualified syntax if both traits are in scope.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, Document, NoLoader};
+/// # use iref::IriBuf;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let doc = json!({
+///   "@context": { "name": "https://schema.org/name" },
+///   "name": "Alice"
+/// });
+///
+/// let loader = NoLoader::<Value>::new();
+/// let expanded = doc.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+///
+/// let context = json!({ "@vocab": "https://schema.org/" });
+/// let compacted = Document::<IriBuf>::compact_with_context(&expanded, &context, &loader)
+///   .await
+///   .unwrap();
+///
+/// assert_eq!(compacted, json!({
+///   "@context": { "@vocab": "https://schema.org/" },
+///   "name": "Alice"
+/// }));
+/// # }
+/// ```
+impl<J: JsonHash + JsonClone, T: Id> Document<T> for ExpandedDocument<J, T> {
+	type Json = J;
+
+	/// Returns `None`.
+	#[inline(always)]
+	fn base_url(&self) -> Option<Iri> {
+		None
+	}
+
+	/// Returns a copy of this document.
+	///
+	/// The document is already expanded:
+	/// the base URL, context, loader and options are ignored.
+	#[inline(always)]
+	fn expand_with<'a, C: ContextMut<T>, L: Loader>(
+		&'a self,
+		_base_url: Option<Iri>,
+		_context: &'a C,
+		_loader: &'a L,
+		_options: expansion::Options,
+	) -> BoxFuture<'a, ExpansionResult<T, J>>
+	where
+		J: expansion::JsonExpand,
+		C: MaybeSend + MaybeSync,
+		C::LocalContext: From<L::Output> + From<J>,
+		L: MaybeSend + MaybeSync,
+		L::Output: Into<J>,
+		T: 'a + MaybeSend + MaybeSync,
+	{
+		let document = self.clone();
+		async move { Ok(document) }.boxed()
+	}
+
+	/// Borrows this document.
+	///
+	/// The document is already expanded:
+	/// the base URL, context, loader and options are ignored.
+	#[inline(always)]
+	fn expand_borrowed_with<'a, C: ContextMut<T>, L: Loader>(
+		&'a self,
+		_base_url: Option<Iri>,
+		_context: &'a C,
+		_loader: &'a L,
+		_options: expansion::Options,
+	) -> BoxFuture<'a, BorrowedExpansionResult<'a, T, J>>
+	where
+		J: expansion::JsonExpand,
+		C: MaybeSend + MaybeSync,
+		C::LocalContext: From<L::Output> + From<J>,
+		L: MaybeSend + MaybeSync,
+		L::Output: Into<J>,
+		T: 'a + MaybeSend + MaybeSync,
+	{
+		async move { Ok(Mown::Borrowed(self)) }.boxed()
+	}
+}
+
 /// A Remote document is a document.
 impl<T: Id, D: Document<T>> Document<T> for RemoteDocument<D> {
 	type Json = D::Json;
@@ -463,21 +1792,47 @@ impl<T: Id, D: Document<T>> Document<T> for RemoteDocument<D> {
 	}
 
 	#[inline(always)]
-	fn expand_with<'a, C: 'a + ContextMut<T> + Send + Sync, L: 'a + Loader + Send + Sync>(
+	fn expand_with<
+		'a,
+		C: 'a + ContextMut<T> + MaybeSend + MaybeSync,
+		L: 'a + Loader + MaybeSend + MaybeSync,
+	>(
 		&'a self,
 		base_url: Option<Iri>,
 		context: &'a C,
-		loader: &'a mut L,
+		loader: &'a L,
 		options: expansion::Options,
 	) -> BoxFuture<'a, ExpansionResult<T, Self::Json>>
 	where
 		D::Json: expansion::JsonExpand,
 		C::LocalContext: From<L::Output> + From<Self::Json>,
 		L::Output: Into<Self::Json>,
-		T: 'a + Send + Sync,
+		T: 'a + MaybeSend + MaybeSync,
 	{
 		self.doc.expand_with(base_url, context, loader, options)
 	}
+
+	#[inline(always)]
+	fn expand_borrowed_with<
+		'a,
+		C: 'a + ContextMut<T> + MaybeSend + MaybeSync,
+		L: 'a + Loader + MaybeSend + MaybeSync,
+	>(
+		&'a self,
+		base_url: Option<Iri>,
+		context: &'a C,
+		loader: &'a L,
+		options: expansion::Options,
+	) -> BoxFuture<'a, BorrowedExpansionResult<'a, T, Self::Json>>
+	where
+		D::Json: expansion::JsonExpand,
+		C::LocalContext: From<L::Output> + From<Self::Json>,
+		L::Output: Into<Self::Json>,
+		T: 'a + MaybeSend + MaybeSync,
+	{
+		self.doc
+			.expand_borrowed_with(base_url, context, loader, options)
+	}
 }
 
 impl<D> Deref for RemoteDocument<D> {
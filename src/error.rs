@@ -70,6 +70,11 @@ impl From<ErrorCode> for Error {
 /// Error code.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub enum ErrorCode {
+	/// The operation has been cancelled, or its deadline has been reached.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	/// See [`CancellableLoader`](crate::CancellableLoader).
+	Cancelled,
+
 	/// Two properties which expand to the same keyword have been detected.
 	/// This might occur if a keyword and an alias thereof are used at the same time.
 	CollidingKeywords,
@@ -77,12 +82,54 @@ pub enum ErrorCode {
 	/// Multiple conflicting indexes have been found for the same node.
 	ConflictingIndexes,
 
+	/// Two contexts being merged define the same term, or the same context setting,
+	/// differently.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	ConflictingDefinitions,
+
 	/// maximum number of `@context` URLs exceeded.
 	ContextOverflow,
 
 	/// A cycle in IRI mappings has been detected.
 	CyclicIriMapping,
 
+	/// Some input data would be silently dropped, in safe mode.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	/// See [`expansion::Options::safe`](crate::expansion::Options::safe).
+	DataLoss,
+
+	/// A loaded document exceeds the maximum document size.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	DocumentTooLarge,
+
+	/// A warning has been escalated into an error.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	/// See [`WarningPolicy`](crate::WarningPolicy).
+	EscalatedWarning,
+
+	/// A limit set in the expansion options has been exceeded.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	/// See [`expansion::Options::max_depth`](crate::expansion::Options::max_depth)
+	/// and [`expansion::Options::max_objects`](crate::expansion::Options::max_objects).
+	ExpansionLimitExceeded,
+
+	/// A document was requested from an origin forbidden by the loader policy.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	ForbiddenOrigin,
+
+	/// An `@import` entry was found while imports are disabled or too deeply nested.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	ForbiddenImport,
+
+	/// A loaded document does not match the integrity pinned for its IRI.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	IntegrityCheckFailed,
+
+	/// An `@annotation` entry was found where annotations are not allowed,
+	/// or its value is not a valid annotation object.
+	/// Note: this error is defined by JSON-LD-star, not the JSON-LD API specification.
+	InvalidAnnotation,
+
 	/// An `@id` entry was encountered whose value was not a string.
 	InvalidIdValue,
 
@@ -132,6 +179,12 @@ pub enum ErrorCode {
 	/// The value of the default language is not a string or null and thus invalid.
 	InvalidDefaultLanguage,
 
+	/// A JSON event stream does not describe a valid JSON document,
+	/// or a document that cannot be expanded in streaming mode.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	/// See [`StreamExpander`](crate::expansion::StreamExpander).
+	InvalidEventStream,
+
 	/// A local context contains a term that has an invalid or missing IRI mapping.
 	InvalidIriMapping,
 
@@ -218,9 +271,17 @@ pub enum ErrorCode {
 	/// A keyword redefinition has been detected.
 	KeywordRedefinition,
 
+	/// The maximum number of loaded documents has been exceeded.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	LoadLimitExceeded,
+
 	/// The document could not be loaded or parsed as JSON.
 	LoadingDocumentFailed,
 
+	/// The document could not be loaded in time.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	LoadingDocumentTimeout,
+
 	/// There was a problem encountered loading a remote context.
 	LoadingRemoteContextFailed,
 
@@ -234,6 +295,10 @@ pub enum ErrorCode {
 
 	/// An attempt was made to redefine a protected term.
 	ProtectedTermRedefinition,
+
+	/// The maximum number of redirections has been exceeded while loading a document.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	TooManyRedirects,
 }
 
 impl ErrorCode {
@@ -242,12 +307,22 @@ impl ErrorCode {
 		use ErrorCode::*;
 
 		match self {
+			Cancelled => "cancelled",
 			CollidingKeywords => "colliding keywords",
 			ConflictingIndexes => "conflicting indexes",
+			ConflictingDefinitions => "conflicting definitions",
 			ContextOverflow => "context overflow",
 			CyclicIriMapping => "cyclic IRI mapping",
+			DataLoss => "data loss",
+			DocumentTooLarge => "document too large",
+			EscalatedWarning => "escalated warning",
+			ExpansionLimitExceeded => "expansion limit exceeded",
+			ForbiddenOrigin => "forbidden origin",
+			ForbiddenImport => "forbidden import",
+			IntegrityCheckFailed => "integrity check failed",
 			InvalidIdValue => "invalid @id value",
 			InvalidImportValue => "invalid @import value",
+			InvalidAnnotation => "invalid annotation",
 			InvalidIncludedValue => "invalid @included value",
 			InvalidIndexValue => "invalid @index value",
 			InvalidNestValue => "invalid @nest value",
@@ -262,6 +337,7 @@ impl ErrorCode {
 			InvalidContextEntry => "invalid context entry",
 			InvalidContextNullification => "invalid context nullification",
 			InvalidDefaultLanguage => "invalid default language",
+			InvalidEventStream => "invalid event stream",
 			InvalidIriMapping => "invalid IRI mapping",
 			InvalidJsonLiteral => "invalid JSON literal",
 			InvalidKeywordAlias => "invalid keyword alias",
@@ -287,14 +363,28 @@ impl ErrorCode {
 			IriConfusedWithPrefix => "IRI confused with prefix",
 			KeyExpansionFailed => "key expansion failed",
 			KeywordRedefinition => "keyword redefinition",
+			LoadLimitExceeded => "load limit exceeded",
 			LoadingDocumentFailed => "loading document failed",
+			LoadingDocumentTimeout => "loading document timeout",
 			LoadingRemoteContextFailed => "loading remote context failed",
 			MultipleContextLinkHeaders => "multiple context link headers",
 			ProcessingModeConflict => "processing mode conflict",
 			ProtectedTermRedefinition => "protected term redefinition",
+			TooManyRedirects => "too many redirects",
 		}
 	}
 
+	/// Checks if this error code reports that a loading limit has been exceeded.
+	///
+	/// Those errors are not turned into `LoadingRemoteContextFailed` errors
+	/// when loading remote contexts.
+	pub fn is_limit_exceeded(&self) -> bool {
+		matches!(
+			self,
+			Self::DocumentTooLarge | Self::LoadLimitExceeded | Self::TooManyRedirects
+		)
+	}
+
 	/// Turns this error code into an actual located error attached with the given `metadata`.
 	pub fn located<M>(self, source: Option<loader::Id>, metadata: M) -> Loc<Error, M> {
 		Error::from(self).located(source, metadata)
@@ -307,12 +397,22 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 	fn try_from(name: &'a str) -> Result<ErrorCode, ()> {
 		use ErrorCode::*;
 		match name {
+			"cancelled" => Ok(Cancelled),
 			"colliding keywords" => Ok(CollidingKeywords),
 			"conflicting indexes" => Ok(ConflictingIndexes),
+			"conflicting definitions" => Ok(ConflictingDefinitions),
 			"context overflow" => Ok(ContextOverflow),
 			"cyclic IRI mapping" => Ok(CyclicIriMapping),
+			"data loss" => Ok(DataLoss),
+			"document too large" => Ok(DocumentTooLarge),
+			"escalated warning" => Ok(EscalatedWarning),
+			"expansion limit exceeded" => Ok(ExpansionLimitExceeded),
+			"forbidden origin" => Ok(ForbiddenOrigin),
+			"forbidden import" => Ok(ForbiddenImport),
+			"integrity check failed" => Ok(IntegrityCheckFailed),
 			"invalid @id value" => Ok(InvalidIdValue),
 			"invalid @import value" => Ok(InvalidImportValue),
+			"invalid annotation" => Ok(InvalidAnnotation),
 			"invalid @included value" => Ok(InvalidIncludedValue),
 			"invalid @index value" => Ok(InvalidIndexValue),
 			"invalid @nest value" => Ok(InvalidNestValue),
@@ -327,6 +427,7 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 			"invalid context entry" => Ok(InvalidContextEntry),
 			"invalid context nullification" => Ok(InvalidContextNullification),
 			"invalid default language" => Ok(InvalidDefaultLanguage),
+			"invalid event stream" => Ok(InvalidEventStream),
 			"invalid IRI mapping" => Ok(InvalidIriMapping),
 			"invalid JSON literal" => Ok(InvalidJsonLiteral),
 			"invalid keyword alias" => Ok(InvalidKeywordAlias),
@@ -352,11 +453,14 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 			"IRI confused with prefix" => Ok(IriConfusedWithPrefix),
 			"key expansion failed" => Ok(KeyExpansionFailed),
 			"keyword redefinition" => Ok(KeywordRedefinition),
+			"load limit exceeded" => Ok(LoadLimitExceeded),
 			"loading document failed" => Ok(LoadingDocumentFailed),
+			"loading document timeout" => Ok(LoadingDocumentTimeout),
 			"loading remote context failed" => Ok(LoadingRemoteContextFailed),
 			"multiple context link headers" => Ok(MultipleContextLinkHeaders),
 			"processing mode conflict" => Ok(ProcessingModeConflict),
 			"protected term redefinition" => Ok(ProtectedTermRedefinition),
+			"too many redirects" => Ok(TooManyRedirects),
 			_ => Err(()),
 		}
 	}
@@ -1,28 +1,30 @@
-use super::{expand_element, ActiveProperty, Expanded, JsonExpand, Options};
+use super::{expand_element, ActiveProperty, Expanded, JsonExpand, ObjectCount, Options};
+use crate::future::{MaybeSend, MaybeSync};
 use crate::{
 	context::{Loader, TermDefinition},
 	object::*,
 	syntax::ContainerType,
-	ContextMut, Error, Id, Loc, Warning,
+	ContextMut, Error, Id, Loc, SendWarningHandler,
 };
 use cc_traits::Iter;
 use iref::Iri;
 
 pub async fn expand_array<
 	J: JsonExpand,
-	T: Id + Send + Sync,
-	C: ContextMut<T> + Send + Sync,
-	L: Loader + Send + Sync,
+	T: Id + MaybeSend + MaybeSync,
+	C: ContextMut<T> + MaybeSend + MaybeSync,
+	L: Loader + MaybeSend + MaybeSync,
 >(
 	active_context: &C,
 	active_property: ActiveProperty<'_, J>,
 	active_property_definition: Option<&TermDefinition<T, C>>,
 	element: &J::Array,
 	base_url: Option<Iri<'_>>,
-	loader: &mut L,
+	loader: &L,
 	options: Options,
 	from_map: bool,
-	warnings: &mut Vec<Loc<Warning, J::MetaData>>,
+	warnings: &mut dyn SendWarningHandler<J::MetaData>,
+	count: &mut ObjectCount,
 ) -> Result<Expanded<J, T>, Loc<Error, J::MetaData>>
 where
 	C::LocalContext: From<L::Output> + From<J>,
@@ -51,9 +53,10 @@ where
 				&*item,
 				base_url,
 				loader,
-				options,
+				options.clone(),
 				from_map,
 				warnings,
+				count,
 			)
 			.await?,
 		);
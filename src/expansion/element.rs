@@ -1,22 +1,41 @@
 use super::{
-	expand_array, expand_iri, expand_literal, expand_node, expand_value, ActiveProperty, Entry,
-	Expanded, ExpandedEntry, JsonExpand, LiteralValue, Options,
+	expand_array, expand_iri, expand_iri_with_resolution, expand_literal, expand_node,
+	expand_value_object, ActiveProperty, Entry, Expanded, ExpandedEntry, JsonExpand, LiteralValue,
+	ObjectCount, Options,
 };
+use crate::future::{BoxFuture, FutureExt, MaybeSend, MaybeSync};
 use crate::util::as_array;
 use crate::{
 	context::{ContextMut, Loader, Local, ProcessingOptions},
+	loader,
 	object::*,
-	syntax::{Keyword, Term},
-	Error, ErrorCode, Id, Indexed, Loc, Reference, Warning,
+	syntax::{is_keyword_like, Keyword, Term},
+	Error, ErrorCode, ExtensionAction, Id, Indexed, Loc, Reference, SendWarningHandler, Warning,
 };
 use cc_traits::{CollectionRef, Get, KeyedRef, Len, MapIter};
-use futures::future::{BoxFuture, FutureExt};
-use generic_json::{Json, Key, ValueRef};
+use generic_json::{Json, JsonClone, JsonHash, Key, ValueRef};
 use iref::Iri;
 use mown::Mown;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 
 pub type ElementExpansionResult<T, J> = Result<Expanded<J, T>, Loc<Error, <J as Json>::MetaData>>;
 
+/// Attaches the given annotation nodes to an expanded object.
+#[cfg(feature = "rdf-star")]
+fn annotated<J: generic_json::JsonHash, T: Id>(
+	mut object: Indexed<Object<J, T>>,
+	annotation: Vec<Indexed<Object<J, T>>>,
+) -> Indexed<Object<J, T>> {
+	object.set_annotation(annotation);
+	object
+}
+
+/// Checks if the given key has the form of a keyword without being a keyword.
+fn is_extension_keyword(key: &str) -> bool {
+	is_keyword_like(key) && Keyword::try_from(key).is_err()
+}
+
 /// Expand an element.
 ///
 /// See <https://www.w3.org/TR/json-ld11-api/#expansion-algorithm>.
@@ -24,21 +43,22 @@ pub type ElementExpansionResult<T, J> = Result<Expanded<J, T>, Loc<Error, <J as
 pub fn expand_element<
 	'a,
 	J: JsonExpand,
-	T: 'a + Id + Send + Sync,
-	C: ContextMut<T> + Send + Sync,
-	L: Loader + Send + Sync,
+	T: 'a + Id + MaybeSend + MaybeSync,
+	C: ContextMut<T> + MaybeSend + MaybeSync,
+	L: Loader + MaybeSend + MaybeSync,
 >(
 	active_context: &'a C,
 	active_property: ActiveProperty<'a, J>,
 	element: &'a J,
 	base_url: Option<Iri<'a>>,
-	loader: &'a mut L,
+	loader: &'a L,
 	options: Options,
 	from_map: bool,
-	warnings: &'a mut Vec<Loc<Warning, J::MetaData>>,
+	warnings: &'a mut dyn SendWarningHandler<J::MetaData>,
+	count: &'a mut ObjectCount,
 ) -> BoxFuture<'a, ElementExpansionResult<T, J>>
 where
-	C::LocalContext: From<L::Output> + From<J> + Send + Sync,
+	C::LocalContext: From<L::Output> + From<J> + MaybeSend + MaybeSync,
 	L::Output: Into<J>,
 {
 	let source = loader.id_opt(base_url);
@@ -48,6 +68,12 @@ where
 			return Ok(Expanded::Null);
 		}
 
+		let element_metadata = element.metadata();
+		let options = match element.as_value_ref() {
+			ValueRef::Array(_) | ValueRef::Object(_) => options.enter(source, element_metadata)?,
+			_ => options,
+		};
+
 		let active_property_definition = active_context.get_opt(active_property.id());
 
 		// If `active_property` has a term definition in `active_context` with a local context,
@@ -63,6 +89,8 @@ where
 			None
 		};
 
+		// Original element, kept when it is an invalid value.
+		let input = element;
 		match element.as_value_ref() {
 			ValueRef::Null => unreachable!(),
 			ValueRef::Array(element) => {
@@ -76,6 +104,7 @@ where
 					options,
 					from_map,
 					warnings,
+					count,
 				)
 				.await
 			}
@@ -91,10 +120,30 @@ where
 					entries.sort()
 				}
 
+				// Report duplicate keys, for JSON implementations exposing them.
+				if entries.len() > 1 {
+					let mut keys = HashSet::with_capacity(entries.len());
+					for Entry(key, _) in &entries {
+						let name: &str = key.as_ref();
+						if !keys.insert(name) {
+							warnings.handle(Loc::new(
+								Warning::DuplicateKey(name.to_string()),
+								source,
+								key.metadata().clone(),
+							));
+						}
+					}
+				}
+
 				let mut value_entry1 = None;
 				let mut id_entry = None;
 
 				for Entry(key, value) in entries.iter() {
+					if is_extension_keyword(key.as_ref()) {
+						// Handled with the other entries.
+						continue;
+					}
+
 					match expand_iri(
 						source,
 						active_context,
@@ -134,7 +183,8 @@ where
 				// definition for `active_property`, in `active_context` and `true` for
 				// `override_protected`.
 				if let Some(property_scoped_context) = property_scoped_context {
-					let options: ProcessingOptions = options.into();
+					let options: ProcessingOptions = options.clone().into();
+					count.add_context();
 					active_context = Mown::Owned(
 						property_scoped_context
 							.process_with(
@@ -155,9 +205,15 @@ where
 				// of the Context Processing algorithm, passing `active_context`, the value of the
 				// `@context` entry as `local_context` and `base_url`.
 				if let Some(local_context) = element.get("@context") {
+					count.add_context();
 					active_context = Mown::Owned(
 						local_context
-							.process_with(active_context.as_ref(), loader, base_url, options.into())
+							.process_with(
+								active_context.as_ref(),
+								loader,
+								base_url,
+								options.clone().into(),
+							)
 							.await?
 							.into_inner(),
 					);
@@ -165,6 +221,11 @@ where
 
 				let mut type_entries: Vec<Entry<J>> = Vec::new();
 				for entry @ Entry(key, _) in entries.iter() {
+					if is_extension_keyword(key.as_ref()) {
+						// Handled with the other entries.
+						continue;
+					}
+
 					let expanded_key = expand_iri(
 						source,
 						active_context.as_ref(),
@@ -214,7 +275,8 @@ where
 								// definition for value in `active_context`, and `false` for `propagate`.
 								let base_url =
 									term_definition.base_url.as_ref().map(|url| url.as_iri());
-								let options: ProcessingOptions = options.into();
+								let options: ProcessingOptions = options.clone().into();
+								count.add_context();
 								active_context = Mown::Owned(
 									local_context
 										.process_with(
@@ -262,20 +324,66 @@ where
 				let mut list_entry = None;
 				let mut set_entry = None;
 				let mut value_entry = None;
+				#[cfg(feature = "rdf-star")]
+				let mut annotation_entry = None;
 				for Entry(key, value) in entries {
 					if key.is_empty() {
-						warnings.push(Loc::new(Warning::EmptyTerm, source, key.metadata().clone()));
+						warnings.handle(Loc::new(
+							Warning::EmptyTerm,
+							source,
+							key.metadata().clone(),
+						));
 					}
 
-					let expanded_key = expand_iri(
-						source,
-						active_context.as_ref(),
-						key.as_ref(),
-						key.metadata(),
-						false,
-						true,
-						warnings,
-					);
+					let name: &str = key.as_ref();
+					#[cfg(feature = "rdf-star")]
+					if options.rdf_star && name == crate::syntax::ANNOTATION {
+						annotation_entry = Some((key, value));
+						continue;
+					}
+
+					let expanded_key = if is_extension_keyword(name) {
+						match options.extensions.action(name) {
+							ExtensionAction::Preserve => {
+								Term::Ref(Reference::Invalid(name.to_string()))
+							}
+							ExtensionAction::Drop => {
+								warnings.handle(Loc::new(
+									Warning::KeywordLikeValue(name.to_string()),
+									source,
+									key.metadata().clone(),
+								));
+								Term::Null
+							}
+							ExtensionAction::Ignore => Term::Null,
+						}
+					} else {
+						let (expanded_key, resolution) = expand_iri_with_resolution(
+							source,
+							active_context.as_ref(),
+							name,
+							key.metadata(),
+							false,
+							true,
+							warnings,
+						);
+
+						if options.explain_keys {
+							let expanded = match &expanded_key {
+								Term::Null => None,
+								term => Some(term.as_str().to_string()),
+							};
+
+							warnings.handle(Loc::new(
+								Warning::ExpandedKey(name.to_string(), expanded, resolution),
+								source,
+								key.metadata().clone(),
+							))
+						}
+
+						expanded_key
+					};
+					trace_event!(key = name, expanded = expanded_key.as_str(), "expanded key");
 					match &expanded_key {
 						Term::Keyword(Keyword::Value) => value_entry = Some(value.clone()),
 						Term::Keyword(Keyword::List)
@@ -285,7 +393,7 @@ where
 						}
 						Term::Keyword(Keyword::Set) => set_entry = Some(value.clone()),
 						Term::Ref(Reference::Blank(id)) => {
-							warnings.push(Loc::new(
+							warnings.handle(Loc::new(
 								Warning::BlankNodeIdProperty(id.clone()),
 								source,
 								key.metadata().clone(),
@@ -301,6 +409,50 @@ where
 					))
 				}
 
+				#[cfg(feature = "rdf-star")]
+				let annotation = match annotation_entry {
+					Some((key, value)) => {
+						// Annotations are only allowed on node and value objects
+						// that are property values.
+						if active_property.is_none()
+							|| active_property == Some("@graph")
+							|| list_entry.is_some()
+							|| set_entry.is_some()
+						{
+							return Err(ErrorCode::InvalidAnnotation
+								.located(source, key.metadata().clone()));
+						}
+
+						let expanded = expand_element(
+							active_context.as_ref(),
+							ActiveProperty::Some(crate::syntax::ANNOTATION, key.metadata()),
+							&*value,
+							base_url,
+							loader,
+							options.clone(),
+							false,
+							warnings,
+							count,
+						)
+						.await?;
+
+						// Annotations must be node objects without identifier.
+						let mut annotation = Vec::new();
+						for item in expanded {
+							match item.inner() {
+								Object::Node(node) if node.id().is_none() => annotation.push(item),
+								_ => {
+									return Err(ErrorCode::InvalidAnnotation
+										.located(source, value.metadata().clone()))
+								}
+							}
+						}
+
+						annotation
+					}
+					None => Vec::new(),
+				};
+
 				if let Some(list_entry) = list_entry {
 					// List objects.
 					let mut index = None;
@@ -335,14 +487,16 @@ where
 								&*item,
 								base_url,
 								loader,
-								options,
+								options.clone(),
 								false,
 								warnings,
+								count,
 							)
 							.await?,
 						)
 					}
 
+					count.add(&options, source, element_metadata)?;
 					Ok(Expanded::Object(Indexed::new(Object::List(result), index)))
 				} else if let Some(set_entry) = set_entry {
 					// Set objects.
@@ -372,39 +526,69 @@ where
 						options,
 						false,
 						warnings,
+						count,
 					)
 					.await
 				} else if let Some(value_entry) = value_entry {
 					// Value objects.
-					if let Some(value) = expand_value(
+					let value = expand_value_object(
 						source,
 						input_type,
 						type_scoped_context,
 						expanded_entries,
 						&*value_entry,
+						options.strict_language_tags,
 						warnings,
 					)
-					.map_err(|e| e.located(source, value_entry.metadata().clone()))?
-					{
+					.and_then(|value| match value {
+						Some(value) => Ok(Some(coerce(value, &options)?)),
+						None => Ok(None),
+					});
+
+					let value = match value {
+						Err(e) if options.keep_invalid_values => Some(invalid_value(
+							input,
+							e,
+							source,
+							value_entry.metadata(),
+							warnings,
+						)),
+						result => {
+							result.map_err(|e| e.located(source, value_entry.metadata().clone()))?
+						}
+					};
+
+					if let Some(value) = value {
+						count.add(&options, source, element_metadata)?;
+						#[cfg(feature = "rdf-star")]
+						let value = annotated(value, annotation);
 						Ok(Expanded::Object(value))
 					} else {
 						Ok(Expanded::Null)
 					}
 				} else {
 					// Node objects.
-					if let Some(result) = expand_node(
+					if let Some(mut result) = expand_node(
 						active_context.as_ref(),
 						type_scoped_context,
 						active_property,
 						expanded_entries,
 						base_url,
 						loader,
-						options,
+						options.clone(),
 						warnings,
+						count,
 					)
 					.await?
 					{
-						Ok(result.cast::<Object<J, T>>().into())
+						count.add(&options, source, element_metadata)?;
+						if options.source_metadata {
+							result.set_metadata(Some(element_metadata.clone()))
+						}
+						let result = result.cast::<Object<J, T>>();
+						#[cfg(feature = "rdf-star")]
+						let result = annotated(result, annotation);
+						Ok(result.into())
 					} else {
 						Ok(Expanded::Null)
 					}
@@ -438,8 +622,9 @@ where
 									.map(|base_url| base_url.as_iri())
 							});
 
+					count.add_context();
 					let result = property_scoped_context
-						.process_with(active_context, loader, base_url, options.into())
+						.process_with(active_context, loader, base_url, options.clone().into())
 						.await
 						.map_err(|e| e.with_metadata(active_property.metadata().unwrap().clone()))?
 						.into_inner();
@@ -450,18 +635,57 @@ where
 
 				// Return the result of the Value Expansion algorithm, passing the `active_context`,
 				// `active_property`, and `element` as value.
-				return Ok(Expanded::Object(
-					expand_literal(
-						source,
-						active_context.as_ref(),
-						active_property,
-						LiteralValue::Given(element),
-						warnings,
-					)
-					.map_err(|e| e.located(source, element.metadata().clone()))?,
-				));
+				count.add(&options, source, element_metadata)?;
+				let value = expand_literal(
+					source,
+					active_context.as_ref(),
+					active_property,
+					LiteralValue::Given(element),
+					warnings,
+				)
+				.and_then(|value| coerce(value, &options));
+
+				let value = match value {
+					Err(e) if options.keep_invalid_values => {
+						invalid_value(element, e, source, element.metadata(), warnings)
+					}
+					result => result.map_err(|e| e.located(source, element.metadata().clone()))?,
+				};
+
+				return Ok(Expanded::Object(value));
 			}
 		}
 	}
 	.boxed()
 }
+
+/// Applies the [literal coercion hooks](Options::coercions) to the given expanded value.
+fn coerce<J: JsonHash, T: Id>(
+	mut value: Indexed<Object<J, T>>,
+	options: &Options,
+) -> Result<Indexed<Object<J, T>>, Error> {
+	if let Object::Value(v) = &mut *value {
+		options.coercions.coerce_value(v)?
+	}
+
+	Ok(value)
+}
+
+/// Keeps the given value that could not be expanded as an invalid value,
+/// emitting a [`Warning::InvalidValue`] warning.
+///
+/// See [`Options::keep_invalid_values`].
+fn invalid_value<J: JsonHash + JsonClone, T: Id>(
+	element: &J,
+	error: Error,
+	source: Option<loader::Id>,
+	metadata: &J::MetaData,
+	warnings: &mut dyn SendWarningHandler<J::MetaData>,
+) -> Indexed<Object<J, T>> {
+	warnings.handle(Loc::new(
+		Warning::InvalidValue(error.code()),
+		source,
+		metadata.clone(),
+	));
+	Object::Value(Value::Invalid(element.clone())).into()
+}
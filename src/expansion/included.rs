@@ -0,0 +1,64 @@
+use crate::{Id, Indexed, Node, Object};
+use generic_json::JsonHash;
+
+/// Moves the nodes included by the given objects (`@included` entries),
+/// at any depth, next to them.
+///
+/// Nodes included inside a graph are moved into this graph.
+pub(crate) fn flatten_included<J: JsonHash, T: Id>(
+	objects: impl IntoIterator<Item = Indexed<Object<J, T>>>,
+) -> Vec<Indexed<Object<J, T>>> {
+	let mut result = Vec::new();
+	let mut included = Vec::new();
+
+	for mut object in objects {
+		flatten_included_in_object(&mut object, &mut included);
+		result.push(object)
+	}
+
+	result.extend(included);
+	result
+}
+
+fn flatten_included_in_object<J: JsonHash, T: Id>(
+	object: &mut Object<J, T>,
+	included: &mut Vec<Indexed<Object<J, T>>>,
+) {
+	match object {
+		Object::Value(_) => (),
+		Object::List(items) => {
+			for item in items {
+				flatten_included_in_object(item, included)
+			}
+		}
+		Object::Node(node) => flatten_included_in_node(node, included),
+	}
+}
+
+fn flatten_included_in_node<J: JsonHash, T: Id>(
+	node: &mut Node<J, T>,
+	included: &mut Vec<Indexed<Object<J, T>>>,
+) {
+	if let Some(graph) = node.graph.take() {
+		node.graph = Some(flatten_included(graph).into_iter().collect())
+	}
+
+	if let Some(nodes) = node.included.take() {
+		for mut included_node in nodes {
+			flatten_included_in_node(&mut included_node, included);
+			included.push(included_node.cast())
+		}
+	}
+
+	for (_, values) in &mut node.properties {
+		for value in values {
+			flatten_included_in_object(value, included)
+		}
+	}
+
+	for (_, nodes) in &mut node.reverse_properties {
+		for reverse_node in nodes {
+			flatten_included_in_node(reverse_node, included)
+		}
+	}
+}
@@ -1,7 +1,7 @@
 use crate::{
 	loader,
 	syntax::{is_keyword_like, Keyword, Term},
-	BlankId, Context, Id, Loc, Reference, Warning,
+	BlankId, Context, Id, IriResolution, Loc, Reference, SendWarningHandler, Warning,
 };
 use iref::{Iri, IriRef};
 use std::convert::TryFrom;
@@ -14,20 +14,43 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 	metadata: &M,
 	document_relative: bool,
 	vocab: bool,
-	warnings: &mut Vec<Loc<Warning, M>>,
+	warnings: &mut dyn SendWarningHandler<M>,
 ) -> Term<T> {
+	expand_iri_with_resolution(
+		source,
+		active_context,
+		value,
+		metadata,
+		document_relative,
+		vocab,
+		warnings,
+	)
+	.0
+}
+
+/// Expands the given IRI as [`expand_iri`] does,
+/// also returning how the IRI has been resolved.
+pub(crate) fn expand_iri_with_resolution<T: Id, C: Context<T>, M: Clone>(
+	source: Option<loader::Id>,
+	active_context: &C,
+	value: &str,
+	metadata: &M,
+	document_relative: bool,
+	vocab: bool,
+	warnings: &mut dyn SendWarningHandler<M>,
+) -> (Term<T>, IriResolution) {
 	if let Ok(keyword) = Keyword::try_from(value) {
-		Term::Keyword(keyword)
+		(Term::Keyword(keyword), IriResolution::Keyword)
 	} else {
 		// If value has the form of a keyword, a processor SHOULD generate a warning and return
 		// null.
 		if is_keyword_like(value) {
-			warnings.push(Loc::new(
+			warnings.handle(Loc::new(
 				Warning::KeywordLikeValue(value.to_string()),
 				source,
 				metadata.clone(),
 			));
-			return Term::Null;
+			return (Term::Null, IriResolution::KeywordLike);
 		}
 
 		if let Some(term_definition) = active_context.get(value) {
@@ -35,7 +58,7 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 			// is a keyword, return that keyword.
 			if let Some(value) = &term_definition.value {
 				if value.is_keyword() {
-					return value.clone();
+					return (value.clone(), IriResolution::Term);
 				}
 			}
 
@@ -43,9 +66,12 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 			// associated IRI mapping.
 			if vocab {
 				if let Some(mapped_value) = &term_definition.value {
-					return mapped_value.clone();
+					return (mapped_value.clone(), IriResolution::Term);
 				} else {
-					return invalid(value.to_string(), source, metadata, warnings);
+					return (
+						invalid(value.to_string(), source, metadata, warnings),
+						IriResolution::Term,
+					);
 				}
 			}
 		}
@@ -61,14 +87,17 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 				// If prefix is underscore (_) or suffix begins with double-forward-slash (//),
 				// return value as it is already an IRI or a blank node identifier.
 				if prefix == "_" {
-					return Term::from(BlankId::new(suffix));
+					return (Term::from(BlankId::new(suffix)), IriResolution::BlankNode);
 				}
 
 				if suffix.starts_with("//") {
 					if let Ok(iri) = Iri::new(value) {
-						return Term::from(T::from_iri(iri));
+						return (Term::from(T::from_iri(iri)), IriResolution::Absolute);
 					} else {
-						return invalid(value.to_string(), source, metadata, warnings);
+						return (
+							invalid(value.to_string(), source, metadata, warnings),
+							IriResolution::Unresolved,
+						);
 					}
 				}
 
@@ -81,12 +110,13 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 							let mut result = mapping.as_str().to_string();
 							result.push_str(suffix);
 
+							let resolution = IriResolution::CompactIri(prefix.to_string());
 							if let Ok(result) = Iri::new(&result) {
-								return Term::from(T::from_iri(result));
+								return (Term::from(T::from_iri(result)), resolution);
 							} else if let Ok(blank) = BlankId::try_from(result.as_ref()) {
-								return Term::from(blank);
+								return (Term::from(blank), resolution);
 							} else {
-								return Reference::Invalid(result).into();
+								return (Reference::Invalid(result).into(), resolution);
 							}
 						}
 					}
@@ -94,7 +124,7 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 
 				// If value has the form of an IRI, return value.
 				if let Ok(result) = Iri::new(value) {
-					return Term::from(T::from_iri(result));
+					return (Term::from(T::from_iri(result)), IriResolution::Absolute);
 				}
 			}
 		}
@@ -108,14 +138,19 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 					result.push_str(value);
 
 					if let Ok(result) = Iri::new(&result) {
-						return Term::from(T::from_iri(result));
+						return (Term::from(T::from_iri(result)), IriResolution::Vocabulary);
 					} else if let Ok(blank) = BlankId::try_from(result.as_ref()) {
-						return Term::from(blank);
+						return (Term::from(blank), IriResolution::Vocabulary);
 					} else {
-						return Reference::Invalid(result).into();
+						return (Reference::Invalid(result).into(), IriResolution::Vocabulary);
 					}
 				}
-				Some(_) => return invalid(value.to_string(), source, metadata, warnings),
+				Some(_) => {
+					return (
+						invalid(value.to_string(), source, metadata, warnings),
+						IriResolution::Unresolved,
+					)
+				}
 				None => (),
 			}
 		}
@@ -130,17 +165,24 @@ pub fn expand_iri<T: Id, C: Context<T>, M: Clone>(
 			if let Ok(iri_ref) = IriRef::new(value) {
 				if let Some(base_iri) = active_context.base_iri() {
 					let value = iri_ref.resolved(base_iri);
-					return Term::from(T::from_iri(value.as_iri()));
-				} else {
-					return invalid(value.to_string(), source, metadata, warnings);
+					return (
+						Term::from(T::from_iri(value.as_iri())),
+						IriResolution::BaseIri,
+					);
 				}
-			} else {
-				return invalid(value.to_string(), source, metadata, warnings);
 			}
+
+			return (
+				invalid(value.to_string(), source, metadata, warnings),
+				IriResolution::Unresolved,
+			);
 		}
 
 		// Return value as is.
-		invalid(value.to_string(), source, metadata, warnings)
+		(
+			invalid(value.to_string(), source, metadata, warnings),
+			IriResolution::Unresolved,
+		)
 	}
 }
 
@@ -149,9 +191,9 @@ fn invalid<T: Id, M: Clone>(
 	value: String,
 	source: Option<loader::Id>,
 	metadata: &M,
-	warnings: &mut Vec<Loc<Warning, M>>,
+	warnings: &mut dyn SendWarningHandler<M>,
 ) -> Term<T> {
-	warnings.push(Loc::new(
+	warnings.handle(Loc::new(
 		Warning::MalformedIri(value.clone()),
 		source,
 		metadata.clone(),
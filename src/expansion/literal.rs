@@ -1,7 +1,7 @@
 use super::{expand_iri, node_id_of_term, ActiveProperty};
 use crate::{
-	loader, object::*, syntax::Type, Context, Error, ErrorCode, Id, Indexed, LangString, Loc,
-	Warning,
+	loader, object::*, syntax::Type, Context, Error, ErrorCode, Id, Indexed, LangString,
+	SendWarningHandler,
 };
 use generic_json::{Json, JsonClone, JsonHash, ValueRef};
 
@@ -40,7 +40,7 @@ pub fn expand_literal<J: JsonHash + JsonClone, T: Id, C: Context<T>>(
 	active_context: &C,
 	active_property: ActiveProperty<J>,
 	value: LiteralValue<J>,
-	warnings: &mut Vec<Loc<Warning, J::MetaData>>,
+	warnings: &mut dyn SendWarningHandler<J::MetaData>,
 ) -> Result<Indexed<Object<J, T>>, Error> {
 	let active_property_definition = active_context.get_opt(active_property.id());
 
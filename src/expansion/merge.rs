@@ -0,0 +1,99 @@
+use super::NodeMerging;
+use crate::{ErrorCode, Id, Indexed, Node, Object, Reference};
+use generic_json::JsonHash;
+use std::collections::{HashMap, HashSet};
+
+/// Merges the node objects of the given list sharing the same identifier.
+///
+/// With the [`NodeMerging::All`] mode, nodes are also merged inside every
+/// nested list of objects.
+pub(crate) fn merge_objects<J: JsonHash, T: Id>(
+	objects: impl IntoIterator<Item = Indexed<Object<J, T>>>,
+	mode: NodeMerging,
+) -> Result<Vec<Indexed<Object<J, T>>>, ErrorCode> {
+	let recursive = mode == NodeMerging::All;
+	let mut result: Vec<Indexed<Object<J, T>>> = Vec::new();
+	let mut positions: HashMap<Reference<T>, usize> = HashMap::new();
+
+	for object in objects {
+		match object.id().and_then(|id| positions.get(id).cloned()) {
+			Some(i) => {
+				let (object, index) = object.into_parts();
+				let target = &mut result[i];
+				match (target.index(), index) {
+					(Some(a), Some(b)) if a != b => return Err(ErrorCode::ConflictingIndexes),
+					(None, Some(b)) => target.set_index(Some(b)),
+					_ => (),
+				}
+
+				if let (Object::Node(target), Object::Node(node)) = (&mut **target, object) {
+					target.merge(node)
+				}
+			}
+			None => {
+				if let Some(id) = object.id() {
+					positions.insert(id.clone(), result.len());
+				}
+
+				result.push(object)
+			}
+		}
+	}
+
+	if recursive {
+		result = result
+			.into_iter()
+			.map(merge_inside)
+			.collect::<Result<_, _>>()?;
+	}
+
+	Ok(result)
+}
+
+/// Merges the nodes sharing the same identifier in the given list of nodes.
+fn merge_nodes<J: JsonHash, T: Id>(
+	nodes: impl IntoIterator<Item = Indexed<Node<J, T>>>,
+) -> Result<Vec<Indexed<Node<J, T>>>, ErrorCode> {
+	Ok(
+		merge_objects(nodes.into_iter().map(Indexed::cast), NodeMerging::All)?
+			.into_iter()
+			.filter_map(|object| object.try_cast().ok())
+			.collect(),
+	)
+}
+
+/// Recursively merges the nodes sharing the same identifier inside the given object.
+fn merge_inside<J: JsonHash, T: Id>(
+	object: Indexed<Object<J, T>>,
+) -> Result<Indexed<Object<J, T>>, ErrorCode> {
+	let (object, index) = object.into_parts();
+	let object = match object {
+		Object::Value(value) => Object::Value(value),
+		Object::List(items) => Object::List(merge_objects(items, NodeMerging::All)?),
+		Object::Node(mut node) => {
+			if let Some(graph) = node.graph.take() {
+				node.graph = Some(
+					merge_objects(graph, NodeMerging::All)?
+						.into_iter()
+						.collect(),
+				)
+			}
+
+			if let Some(included) = node.included.take() {
+				node.included = Some(merge_nodes(included)?.into_iter().collect::<HashSet<_>>())
+			}
+
+			for (_, values) in &mut node.properties {
+				*values = merge_objects(std::mem::take(values), NodeMerging::All)?
+			}
+
+			for (_, nodes) in &mut node.reverse_properties {
+				*nodes = merge_nodes(std::mem::take(nodes))?
+			}
+
+			Object::Node(node)
+		}
+	};
+
+	Ok(Indexed::new(object, index))
+}
@@ -1,36 +1,52 @@
 //! Expansion algorithm and related types.
+use crate::future::{JsonMaybeSendSync, MaybeSend, MaybeSync};
 use crate::{
-	context::{Loader, ProcessingOptions},
-	ContextMut, Error, Id, Indexed, Loc, Object, ProcessingMode, Warning,
+	context::{Loader, Local, ProcessingOptions},
+	loader,
+	stats::CountingLoader,
+	Context, ContextMut, Error, ErrorCode, ExtensionKeywords, Id, Indexed, LiteralCoercions, Loc,
+	Object, ProcessingMode, Stats, StrictLanguageTags, Warning, WarningHandler,
 };
 use cc_traits::{CollectionRef, KeyedRef};
 use derivative::Derivative;
-use generic_json::{Json, JsonClone, JsonHash, JsonLft, JsonSendSync};
-use iref::IriBuf;
-use std::cmp::{Ord, Ordering};
-use std::collections::HashSet;
+use generic_json::{Json, JsonClone, JsonHash, JsonLft, ValueRef};
+use indexmap::IndexSet;
+use iref::{Iri, IriBuf};
+use mown::Mown;
+use std::{
+	any::Any,
+	cmp::{Ord, Ordering},
+	sync::Arc,
+	time::Instant,
+};
 
 mod array;
 mod element;
 mod expanded;
+mod included;
 mod iri;
 mod literal;
+mod merge;
 mod node;
+mod stream;
 mod value;
 
 use array::*;
 use element::*;
 use expanded::*;
+use included::*;
 pub(crate) use iri::*;
 use literal::*;
+use merge::*;
 use node::*;
+pub use stream::*;
 use value::*;
 
 /// JSON document that can be expanded.
-pub trait JsonExpand = JsonSendSync + JsonHash + JsonClone + JsonLft<'static>;
+pub trait JsonExpand = JsonMaybeSendSync + JsonHash + JsonClone + JsonLft<'static>;
 
 /// Expansion options.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 pub struct Options {
 	/// Sets the processing mode.
 	pub processing_mode: ProcessingMode,
@@ -43,6 +59,732 @@ pub struct Options {
 	/// If set to true, input document entries are processed lexicographically.
 	/// If false, order is not considered in processing.
 	pub ordered: bool,
+
+	/// Load the remote contexts referenced by a context array concurrently.
+	///
+	/// See [`ProcessingOptions::concurrent_context_loading`].
+	pub concurrent_context_loading: bool,
+
+	/// Handling of entries whose key is a non-standard keyword.
+	///
+	/// By default, such entries are dropped with a warning.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, ExtensionAction, ExtensionKeywords, JsonContext, NoLoader};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "@annotation": { "https://example.org/source": "import" }
+	/// });
+	///
+	/// let options = expansion::Options {
+	///   extensions: ExtensionKeywords::new(&[("@annotation", ExtensionAction::Preserve)]),
+	///   ..Default::default()
+	/// };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// assert!(expanded.warnings().is_empty());
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([{
+	///   "@id": "https://example.org/alice",
+	///   "@annotation": [{ "https://example.org/source": [{ "@value": "import" }] }]
+	/// }]));
+	/// # }
+	/// ```
+	pub extensions: ExtensionKeywords,
+
+	/// Context applied on top of the initial active context, before processing
+	/// the `@context` entries of the document (`expandContext` option of the JSON-LD API).
+	///
+	/// It is either the IRI of a remote context, loaded using the document loader,
+	/// or an inline context value (see [`ExpandContext`]).
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, expansion::ExpandContext, Document, StaticLoader};
+	/// # use json_ld::util::AsJson;
+	/// # use iref::{Iri, IriBuf};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let loader = StaticLoader::<Value>::default().with(
+	///   Iri::new("https://example.org/context.jsonld").unwrap(),
+	///   r#"{ "@context": { "name": "https://schema.org/name" } }"#
+	/// );
+	///
+	/// let options = expansion::Options {
+	///   expand_context: Some(ExpandContext::Iri(IriBuf::new("https://example.org/context.jsonld").unwrap())),
+	///   ..Default::default()
+	/// };
+	/// let expanded = json!({ "name": "Alice" })
+	///   .expand_with(None, &context::Json::<Value>::new(None), &loader, options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([{ "https://schema.org/name": [{ "@value": "Alice" }] }]));
+	/// # }
+	/// ```
+	pub expand_context: Option<ExpandContext>,
+
+	/// Safe mode.
+	///
+	/// If set to true, input data that would otherwise be silently dropped
+	/// or left unprocessed makes the expansion fail with a
+	/// [`ErrorCode::DataLoss`] error located on the offending value.
+	/// This includes keys that do not expand to an IRI, keyword-like terms and values,
+	/// relative IRIs that cannot be resolved, malformed language tags,
+	/// and top-level values.
+	///
+	/// Default is `false`.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, ErrorCode, NoLoader};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "name": "Alice"
+	/// });
+	///
+	/// let context = context::Json::<Value>::new(None);
+	/// let loader = NoLoader::<Value>::new();
+	/// let expanded = doc.expand_with(None, &context, &loader, expansion::Options::default()).await;
+	/// assert!(expanded.is_ok());
+	///
+	/// let options = expansion::Options { safe: true, ..Default::default() };
+	/// let error = doc.expand_with(None, &context, &loader, options).await.err().unwrap();
+	/// assert_eq!(error.code(), ErrorCode::DataLoss);
+	/// # }
+	/// ```
+	pub safe: bool,
+
+	/// Report every key dropped during expansion.
+	///
+	/// Each dropped key is reported with a [`Warning::DroppedKey`] warning
+	/// located on the key, giving the reason why it has been dropped.
+	///
+	/// Default is `false`.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, DropReason, NoLoader, Warning};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@context": { "nickname": null },
+	///   "@id": "https://example.org/alice",
+	///   "name": "Alice",
+	///   "nickname": "Al"
+	/// });
+	///
+	/// let options = expansion::Options { report_dropped_keys: true, ordered: true, ..Default::default() };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let dropped: Vec<_> = expanded
+	///   .warnings()
+	///   .iter()
+	///   .map(|w| w.value().clone())
+	///   .filter(|w| matches!(w, Warning::DroppedKey(_, _)))
+	///   .collect();
+	/// assert_eq!(dropped, vec![
+	///   Warning::DroppedKey("name".to_string(), DropReason::UndefinedTerm),
+	///   Warning::DroppedKey("nickname".to_string(), DropReason::NullTerm)
+	/// ]);
+	/// # }
+	/// ```
+	pub report_dropped_keys: bool,
+
+	/// Explain how every key is expanded.
+	///
+	/// Each key is reported with a [`Warning::ExpandedKey`] warning
+	/// located on the key, giving the IRI it expands to (if any) and
+	/// how it has been resolved: keyword, term definition, compact IRI,
+	/// vocabulary mapping, etc.
+	/// Dropped keys are also reported, as with the
+	/// [`report_dropped_keys`](Self::report_dropped_keys) option.
+	///
+	/// Default is `false`.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, IriResolution, NoLoader, Warning};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@context": {
+	///     "@vocab": "https://example.org/vocab#",
+	///     "schema": "https://schema.org/",
+	///     "nick": "schema:alternateName"
+	///   },
+	///   "@id": "https://example.org/alice",
+	///   "name": "Alice",
+	///   "nick": "Al",
+	///   "schema:age": 42
+	/// });
+	///
+	/// let options = expansion::Options { explain_keys: true, ordered: true, ..Default::default() };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let explained: Vec<_> = expanded
+	///   .warnings()
+	///   .iter()
+	///   .filter_map(|w| match w.value() {
+	///     Warning::ExpandedKey(key, Some(iri), resolution) => Some((key.as_str(), iri.as_str(), resolution.clone())),
+	///     _ => None,
+	///   })
+	///   .collect();
+	/// assert_eq!(explained, vec![
+	///   ("@context", "@context", IriResolution::Keyword),
+	///   ("@id", "@id", IriResolution::Keyword),
+	///   ("name", "https://example.org/vocab#name", IriResolution::Vocabulary),
+	///   ("nick", "https://schema.org/alternateName", IriResolution::Term),
+	///   ("schema:age", "https://schema.org/age", IriResolution::CompactIri("schema".to_string())),
+	/// ]);
+	/// # }
+	/// ```
+	pub explain_keys: bool,
+
+	/// Collapse identical warnings.
+	///
+	/// When set, the warnings collected by
+	/// [`Document::expand_with`](crate::Document::expand_with)
+	/// are deduplicated: a warning emitted many times
+	/// (for instance for a malformed language tag used throughout the document)
+	/// is only kept once, at its first location,
+	/// as a [`Warning::Repeated`] warning counting its occurrences.
+	/// Use the [`DeduplicateWarnings`](crate::DeduplicateWarnings) handler to
+	/// deduplicate the warnings of [`expand`].
+	///
+	/// Default is `false`.
+	pub deduplicate_warnings: bool,
+
+	/// Collect processing statistics.
+	///
+	/// When set, [`Document::expand_with`](crate::Document::expand_with)
+	/// uses [`expand_with_stats`] and the statistics are given by
+	/// [`ExpandedDocument::stats`](crate::ExpandedDocument::stats).
+	///
+	/// Default is `false`.
+	pub collect_stats: bool,
+
+	/// Maximum nesting depth of arrays and objects in the expanded document.
+	///
+	/// Deeper documents make the expansion fail with an
+	/// [`ErrorCode::ExpansionLimitExceeded`] error.
+	/// Default is `None` (no limit).
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, ErrorCode, NoLoader};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "https://example.org/knows": { "https://example.org/knows": { "https://example.org/name": "Alice" } }
+	/// });
+	///
+	/// let context = context::Json::<Value>::new(None);
+	/// let loader = NoLoader::<Value>::new();
+	/// let options = expansion::Options { max_depth: Some(3), ..Default::default() };
+	/// assert!(doc.expand_with(None, &context, &loader, options).await.is_ok());
+	///
+	/// let options = expansion::Options { max_depth: Some(2), ..Default::default() };
+	/// let error = doc.expand_with(None, &context, &loader, options).await.err().unwrap();
+	/// assert_eq!(error.code(), ErrorCode::ExpansionLimitExceeded);
+	/// # }
+	/// ```
+	pub max_depth: Option<usize>,
+
+	/// Maximum number of node, value and list objects produced by the expansion algorithm.
+	///
+	/// Exceeding it makes the expansion fail with an
+	/// [`ErrorCode::ExpansionLimitExceeded`] error.
+	/// Default is `None` (no limit).
+	pub max_objects: Option<usize>,
+
+	/// Attach the metadata of the input JSON objects to the expanded node objects.
+	///
+	/// When set, every node object produced from a JSON object of the input document
+	/// carries the metadata of this object (see [`Node::metadata`](crate::Node::metadata)),
+	/// so that errors detected after expansion can point to the original source location.
+	/// The metadata is ignored when comparing or hashing nodes.
+	/// Default is `false`.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, NoLoader};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "https://example.org/name": "Alice"
+	/// });
+	///
+	/// let context = context::Json::<Value>::new(None);
+	/// let loader = NoLoader::<Value>::new();
+	/// let options = expansion::Options { source_metadata: true, ..Default::default() };
+	/// let expanded = doc.expand_with(None, &context, &loader, options).await.unwrap();
+	/// let node = expanded.iter().next().unwrap().as_node().unwrap();
+	/// assert!(node.metadata().is_some());
+	///
+	/// let expanded = doc.expand_with(None, &context, &loader, Default::default()).await.unwrap();
+	/// let node = expanded.iter().next().unwrap().as_node().unwrap();
+	/// assert!(node.metadata().is_none());
+	/// # }
+	/// ```
+	pub source_metadata: bool,
+
+	/// Merging of the node objects sharing the same identifier.
+	///
+	/// Default is [`NodeMerging::None`].
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, NoLoader};
+	/// # use json_ld::expansion::NodeMerging;
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!([
+	///   { "@id": "https://example.org/alice", "https://example.org/name": "Alice" },
+	///   { "@id": "https://example.org/alice", "@type": "https://example.org/Person" }
+	/// ]);
+	///
+	/// let options = expansion::Options { merge_nodes: NodeMerging::TopLevel, ..Default::default() };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([{
+	///   "@id": "https://example.org/alice",
+	///   "@type": ["https://example.org/Person"],
+	///   "https://example.org/name": [{ "@value": "Alice" }]
+	/// }]));
+	/// # }
+	/// ```
+	pub merge_nodes: NodeMerging,
+
+	/// Move the included nodes (`@included` entries) next to the top-level nodes.
+	///
+	/// If set to true, the nodes included by any node of the expanded document
+	/// are removed from their `@included` entry and added to the top-level nodes
+	/// (or to the enclosing graph, for nodes included inside a graph).
+	/// If false, they are kept nested, and can be accessed with
+	/// [`Node::included_nodes`](crate::Node::included_nodes).
+	/// Default is `false`.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, NoLoader};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "@included": [
+	///     { "@id": "https://example.org/bob", "https://example.org/name": "Bob" }
+	///   ]
+	/// });
+	///
+	/// let options = expansion::Options { flatten_included: true, ..Default::default() };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([
+	///   { "@id": "https://example.org/alice" },
+	///   { "@id": "https://example.org/bob", "https://example.org/name": [{ "@value": "Bob" }] }
+	/// ]));
+	/// # }
+	/// ```
+	pub flatten_included: bool,
+
+	/// Validation of the language tags of the document and its contexts.
+	///
+	/// In the [`Reject`](StrictLanguageTags::Reject) mode, a malformed language tag
+	/// in the document raises an [`ErrorCode::InvalidLanguageTaggedString`] error.
+	/// See [`ProcessingOptions::strict_language_tags`] for the language tags of contexts.
+	/// Default is [`StrictLanguageTags::Disabled`].
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, ErrorCode, NoLoader, StrictLanguageTags};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let context = context::Json::<Value>::new(None);
+	/// let loader = NoLoader::<Value>::new();
+	///
+	/// let doc = json!({
+	///   "https://example.org/name": { "@value": "Alice", "@language": "EN-us" }
+	/// });
+	/// let options = expansion::Options {
+	///   strict_language_tags: StrictLanguageTags::Warn,
+	///   ..Default::default()
+	/// };
+	/// let expanded = doc.expand_with(None, &context, &loader, options).await.unwrap();
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json[0]["https://example.org/name"][0]["@language"], "en-US");
+	///
+	/// let doc = json!({
+	///   "https://example.org/name": { "@value": "Alice", "@language": "not a tag" }
+	/// });
+	/// let options = expansion::Options {
+	///   strict_language_tags: StrictLanguageTags::Reject,
+	///   ..Default::default()
+	/// };
+	/// let error = doc.expand_with(None, &context, &loader, options).await.err().unwrap();
+	/// assert_eq!(error.code(), ErrorCode::InvalidLanguageTaggedString);
+	/// # }
+	/// ```
+	pub strict_language_tags: StrictLanguageTags,
+
+	/// Literal coercion hooks.
+	///
+	/// Typed string literals are passed to the hook registered for their datatype,
+	/// which may replace or reject their lexical form.
+	/// A rejected literal is an [`ErrorCode::InvalidTypedValue`] error.
+	/// By default, no hook is registered.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Coercion, Document, ErrorCode, LiteralCoercions, NoLoader};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// fn date_time(value: &str) -> Coercion {
+	///   match value.strip_suffix("+00:00") {
+	///     Some(utc) => Coercion::Replace(format!("{}Z", utc)),
+	///     None if value.contains('T') => Coercion::Keep,
+	///     None => Coercion::Invalid,
+	///   }
+	/// }
+	///
+	/// const XSD_DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+	/// let options = expansion::Options {
+	///   coercions: LiteralCoercions::new(&[(XSD_DATE_TIME, date_time)]),
+	///   ..Default::default()
+	/// };
+	/// let context = context::Json::<Value>::new(None);
+	/// let loader = NoLoader::<Value>::new();
+	///
+	/// let doc = json!({
+	///   "https://example.org/date": { "@value": "2021-01-01T00:00:00+00:00", "@type": XSD_DATE_TIME }
+	/// });
+	/// let expanded = doc.expand_with(None, &context, &loader, options.clone()).await.unwrap();
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json[0]["https://example.org/date"][0]["@value"], "2021-01-01T00:00:00Z");
+	///
+	/// let doc = json!({
+	///   "https://example.org/date": { "@value": "yesterday", "@type": XSD_DATE_TIME }
+	/// });
+	/// let error = doc.expand_with(None, &context, &loader, options).await.err().unwrap();
+	/// assert_eq!(error.code(), ErrorCode::InvalidTypedValue);
+	/// # }
+	/// ```
+	pub coercions: LiteralCoercions,
+
+	/// Keep the values that cannot be expanded.
+	///
+	/// By default, a value object or literal that fails validation
+	/// (such as an invalid `@type`, `@language` or `@direction` entry,
+	/// or a literal rejected by a [coercion hook](Self::coercions))
+	/// is an error.
+	/// If this option is set, such values are instead kept as is
+	/// using the [`Value::Invalid`](crate::object::Value::Invalid) variant,
+	/// and a [`Warning::InvalidValue`] warning is emitted,
+	/// so that lossy inputs can still be triaged downstream.
+	/// Invalid values are written back unchanged by the compaction algorithm,
+	/// and dropped during RDF serialization.
+	/// Default is `false`.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, ErrorCode, NoLoader, Warning};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "https://example.org/name": { "@value": "Alice", "@direction": "up" }
+	/// });
+	/// let context = context::Json::<Value>::new(None);
+	/// let loader = NoLoader::<Value>::new();
+	///
+	/// let error = doc.expand::<context::Json<Value>, _>(&loader).await.err().unwrap();
+	/// assert_eq!(error.code(), ErrorCode::InvalidBaseDirection);
+	///
+	/// let options = expansion::Options { keep_invalid_values: true, ..Default::default() };
+	/// let mut warnings = Vec::new();
+	/// let expanded = expansion::expand(&context, &doc, None, &loader, options, &mut warnings)
+	///   .await
+	///   .unwrap();
+	/// assert_eq!(*warnings[0], Warning::InvalidValue(ErrorCode::InvalidBaseDirection));
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json[0]["https://example.org/name"][0], json!({ "@value": "Alice", "@direction": "up" }));
+	/// # }
+	/// ```
+	pub keep_invalid_values: bool,
+
+	/// Enables the experimental JSON-LD-star `@annotation` syntax
+	/// (`rdfstar` option of the JSON-LD-star API).
+	///
+	/// Annotation objects found on node and value objects are expanded and
+	/// attached to the expanded object (see [`Indexed::annotation`]).
+	/// An annotation on a top-level object, a list or a set is an
+	/// [`ErrorCode::InvalidAnnotation`] error.
+	/// Embedded nodes (`@id` entries whose value is a node object)
+	/// are not supported yet.
+	/// Default is `false`.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, NoLoader};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "https://example.org/knows": {
+	///     "@id": "https://example.org/bob",
+	///     "@annotation": { "https://example.org/certainty": 0.8 }
+	///   }
+	/// });
+	///
+	/// let options = expansion::Options { rdf_star: true, ..Default::default() };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([{
+	///   "@id": "https://example.org/alice",
+	///   "https://example.org/knows": [{
+	///     "@id": "https://example.org/bob",
+	///     "@annotation": [{ "https://example.org/certainty": [{ "@value": 0.8 }] }]
+	///   }]
+	/// }]));
+	/// # }
+	/// ```
+	#[cfg(feature = "rdf-star")]
+	pub rdf_star: bool,
+}
+
+impl Options {
+	/// Returns the options used to expand the content of a nested array or object.
+	///
+	/// Fails if the maximum nesting depth is reached.
+	pub(crate) fn enter<M: Clone>(
+		self,
+		source: Option<loader::Id>,
+		metadata: &M,
+	) -> Result<Self, Loc<Error, M>> {
+		match self.max_depth {
+			Some(0) => Err(ErrorCode::ExpansionLimitExceeded.located(source, metadata.clone())),
+			Some(depth) => Ok(Self {
+				max_depth: Some(depth - 1),
+				..self
+			}),
+			None => Ok(self),
+		}
+	}
+}
+
+/// Context applied before expanding a document.
+///
+/// See [`Options::expand_context`].
+#[derive(Clone)]
+pub enum ExpandContext {
+	/// Remote context, loaded from the given IRI using the document loader
+	/// and processed with the IRI as base URL.
+	Iri(IriBuf),
+
+	/// Inline context value, processed with the base URL of the document.
+	Inline(InlineContext),
+}
+
+impl ExpandContext {
+	/// Creates an inline expansion context from the given context value.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, expansion::ExpandContext, Document, NoLoader};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let options = expansion::Options {
+	///   expand_context: Some(ExpandContext::inline(json!({ "name": "https://schema.org/name" }))),
+	///   ..Default::default()
+	/// };
+	/// let expanded = json!({ "name": "Alice" })
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([{ "https://schema.org/name": [{ "@value": "Alice" }] }]));
+	/// # }
+	/// ```
+	pub fn inline<J: Json + MaybeSend + MaybeSync + 'static>(context: J) -> Self {
+		Self::Inline(InlineContext::new(context))
+	}
+}
+
+/// Inline context value of an [`ExpandContext`].
+///
+/// The value must have the JSON type of the expanded document,
+/// otherwise expansion fails with an [`ErrorCode::InvalidLocalContext`] error.
+#[derive(Clone)]
+pub struct InlineContext(Arc<dyn AnyContext>);
+
+impl InlineContext {
+	/// Wraps the given context value.
+	pub fn new<J: Json + MaybeSend + MaybeSync + 'static>(context: J) -> Self {
+		Self(Arc::new(context))
+	}
+
+	/// Returns the context value, if it has the given JSON type.
+	pub fn get<J: Json + 'static>(&self) -> Option<&J> {
+		self.0.as_any().downcast_ref()
+	}
+}
+
+/// Context value of any JSON type.
+trait AnyContext: MaybeSend + MaybeSync {
+	fn as_any(&self) -> &dyn Any;
+}
+
+impl<J: Json + MaybeSend + MaybeSync + 'static> AnyContext for J {
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+/// Processes the [`expand_context`](Options::expand_context) option on top of the given active context.
+///
+/// Returns `None` if the option is not set.
+pub(crate) async fn process_expand_context<J: JsonExpand, T: Id, C: ContextMut<T>, L: Loader>(
+	active_context: &C,
+	base_url: Option<Iri<'_>>,
+	loader: &L,
+	options: &Options,
+	metadata: &J::MetaData,
+	count: &mut ObjectCount,
+) -> Result<Option<C>, Loc<Error, J::MetaData>>
+where
+	T: MaybeSend + MaybeSync,
+	C: MaybeSend + MaybeSync,
+	C::LocalContext: From<L::Output> + From<J>,
+	L: MaybeSend + MaybeSync,
+	L::Output: Into<J>,
+{
+	let processing_options = ProcessingOptions::from(options.clone());
+	let processed = match &options.expand_context {
+		Some(ExpandContext::Iri(url)) => {
+			let expand_context = loader
+				.load_context(url.as_iri())
+				.await
+				.map_err(|e| e.located(loader.id_opt(base_url), metadata.clone()))?
+				.cast::<J>();
+			expand_context
+				.context()
+				.process_with(
+					active_context,
+					loader,
+					Some(expand_context.url()),
+					processing_options,
+				)
+				.await?
+				.into_inner()
+		}
+		Some(ExpandContext::Inline(context)) => match context.get::<J>() {
+			Some(context) => context
+				.process_with(active_context, loader, base_url, processing_options)
+				.await?
+				.into_inner(),
+			None => {
+				return Err(ErrorCode::InvalidLocalContext
+					.located(loader.id_opt(base_url), metadata.clone()))
+			}
+		},
+		None => return Ok(None),
+	};
+
+	count.add_context();
+	Ok(Some(processed))
+}
+
+/// Number of objects produced by the expansion algorithm.
+///
+/// Checked against the [`Options::max_objects`] limit.
+/// Also counts the processed contexts and dropped keys,
+/// reported by [`expand_with_stats`].
+#[derive(Default)]
+pub(crate) struct ObjectCount {
+	objects: usize,
+	contexts: usize,
+	dropped_keys: usize,
+}
+
+impl ObjectCount {
+	/// Counts a newly produced object.
+	pub(crate) fn add<M: Clone>(
+		&mut self,
+		options: &Options,
+		source: Option<loader::Id>,
+		metadata: &M,
+	) -> Result<(), Loc<Error, M>> {
+		self.objects += 1;
+		match options.max_objects {
+			Some(max) if self.objects > max => {
+				Err(ErrorCode::ExpansionLimitExceeded.located(source, metadata.clone()))
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Counts a processed local context.
+	#[inline(always)]
+	pub(crate) fn add_context(&mut self) {
+		self.contexts += 1
+	}
+
+	/// Counts a dropped key.
+	#[inline(always)]
+	pub(crate) fn add_dropped_key(&mut self) {
+		self.dropped_keys += 1
+	}
 }
 
 /// Key expansion policy.
@@ -58,7 +800,7 @@ pub struct Options {
 /// expanded document, or to forbid them completely by raising an error.
 /// You can define your preferred policy using one of this type variant
 /// with the [`Options::policy`] field.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum Policy {
 	/// Relaxed policy.
 	///
@@ -83,6 +825,49 @@ pub enum Policy {
 	/// Every key that cannot be expanded into an IRI or a blank node identifier
 	/// will raise an error.
 	Strictest,
+
+	/// Custom policy.
+	///
+	/// The given function is called on every key that cannot be expanded into an
+	/// IRI or a blank node identifier, with the key metadata,
+	/// and decides what to do with the key.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, NoLoader};
+	/// # use json_ld::expansion::{KeyAction, Policy};
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// fn keep_ex(key: &str, _metadata: &dyn std::any::Any) -> KeyAction {
+	///   if key.starts_with("ex:") {
+	///     KeyAction::Keep
+	///   } else {
+	///     KeyAction::Drop
+	///   }
+	/// }
+	///
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "ex:name": "Alice",
+	///   "nickname": "Al"
+	/// });
+	///
+	/// let options = expansion::Options { policy: Policy::Custom(keep_ex), ..Default::default() };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([{
+	///   "@id": "https://example.org/alice",
+	///   "ex:name": [{ "@value": "Alice" }]
+	/// }]));
+	/// # }
+	/// ```
+	Custom(fn(&str, &dyn Any) -> KeyAction),
 }
 
 impl Policy {
@@ -90,18 +875,117 @@ impl Policy {
 	pub fn is_strict(&self) -> bool {
 		matches!(self, Self::Strict | Self::Strictest)
 	}
+
+	/// Returns the action to take on a key that cannot be expanded into an
+	/// IRI or a blank node identifier.
+	pub fn undefined_key_action<M: Any>(&self, key: &str, metadata: &M) -> KeyAction {
+		match self {
+			Self::Relaxed => KeyAction::Keep,
+			Self::Standard if key.contains(':') => KeyAction::Keep,
+			Self::Standard => KeyAction::Drop,
+			Self::Strict if key.contains(':') => KeyAction::Keep,
+			Self::Strict | Self::Strictest => KeyAction::Error,
+			Self::Custom(f) => f(key, metadata),
+		}
+	}
+}
+
+/// Action taken on a key that cannot be expanded into an
+/// IRI or a blank node identifier.
+///
+/// See [`Policy::Custom`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyAction {
+	/// Keep the key in the expanded document
+	/// using the [`Reference::Invalid`](crate::Reference::Invalid) variant.
+	Keep,
+
+	/// Drop the key.
+	Drop,
+
+	/// Raise a [`KeyExpansionFailed`](crate::ErrorCode::KeyExpansionFailed) error.
+	Error,
+}
+
+impl PartialEq for Policy {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Relaxed, Self::Relaxed)
+			| (Self::Standard, Self::Standard)
+			| (Self::Strict, Self::Strict)
+			| (Self::Strictest, Self::Strictest) => true,
+			(Self::Custom(a), Self::Custom(b)) => std::ptr::fn_addr_eq(*a, *b),
+			_ => false,
+		}
+	}
 }
 
+impl Eq for Policy {}
+
 impl Default for Policy {
 	fn default() -> Self {
 		Self::Standard
 	}
 }
 
+/// Merging of the node objects sharing the same identifier during expansion.
+///
+/// See [`Options::merge_nodes`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum NodeMerging {
+	/// Nodes are not merged.
+	#[default]
+	None,
+
+	/// Top-level nodes sharing the same identifier are merged into a single node.
+	TopLevel,
+
+	/// Nodes sharing the same identifier are merged at every level,
+	/// with the other nodes of the same list of objects
+	/// (top-level nodes, graph, included nodes or property values).
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, expansion, Document, NoLoader};
+	/// # use json_ld::expansion::NodeMerging;
+	/// # use json_ld::util::AsJson;
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "https://example.org/knows": [
+	///     { "@id": "https://example.org/bob", "https://example.org/name": "Bob" },
+	///     { "@id": "https://example.org/bob", "https://example.org/age": 42 }
+	///   ]
+	/// });
+	///
+	/// let options = expansion::Options { merge_nodes: NodeMerging::All, ..Default::default() };
+	/// let expanded = doc
+	///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+	///   .await
+	///   .unwrap();
+	///
+	/// let json: Value = expanded.as_json();
+	/// assert_eq!(json, json!([{
+	///   "@id": "https://example.org/alice",
+	///   "https://example.org/knows": [{
+	///     "@id": "https://example.org/bob",
+	///     "https://example.org/name": [{ "@value": "Bob" }],
+	///     "https://example.org/age": [{ "@value": 42 }]
+	///   }]
+	/// }]));
+	/// # }
+	/// ```
+	All,
+}
+
 impl From<Options> for ProcessingOptions {
 	fn from(options: Options) -> ProcessingOptions {
 		ProcessingOptions {
 			processing_mode: options.processing_mode,
+			concurrent_context_loading: options.concurrent_context_loading,
+			strict_language_tags: options.strict_language_tags,
 			..Default::default()
 		}
 	}
@@ -112,6 +996,9 @@ impl From<crate::compaction::Options> for Options {
 		Options {
 			processing_mode: options.processing_mode,
 			ordered: options.ordered,
+			extensions: options.extensions,
+			#[cfg(feature = "rdf-star")]
+			rdf_star: options.rdf_star,
 			..Options::default()
 		}
 	}
@@ -199,8 +1086,55 @@ fn filter_top_level_item<J: JsonHash, T: Id>(item: &Indexed<Object<J, T>>) -> bo
 	!matches!(item.inner(), Object::Value(_))
 }
 
+/// Warning handler used in safe mode.
+///
+/// Forwards every warning to the inner handler,
+/// and remembers the first one reporting some data loss.
+struct SafeWarnings<'w, W, M> {
+	inner: &'w mut W,
+	enabled: bool,
+	loss: Option<Loc<Warning, M>>,
+}
+
+impl<'w, W, M> SafeWarnings<'w, W, M> {
+	fn new(inner: &'w mut W, enabled: bool) -> Self {
+		Self {
+			inner,
+			enabled,
+			loss: None,
+		}
+	}
+
+	/// Fails with a [`ErrorCode::DataLoss`] error if some data loss has been reported.
+	fn check(&mut self) -> Result<(), Loc<Error, M>> {
+		match self.loss.take() {
+			Some(warning) => {
+				let (warning, source, metadata) = warning.into_parts();
+				Err(Error::with_source(ErrorCode::DataLoss, warning).located(source, metadata))
+			}
+			None => Ok(()),
+		}
+	}
+}
+
+impl<'w, W: WarningHandler<M>, M: Clone> WarningHandler<M> for SafeWarnings<'w, W, M> {
+	fn handle(&mut self, warning: Loc<Warning, M>) {
+		if self.enabled && self.loss.is_none() && warning.value().is_data_loss() {
+			self.loss = Some(warning.clone())
+		}
+
+		self.inner.handle(warning)
+	}
+}
+
+/// Active property of the expansion algorithm.
+///
+/// Term of the entry whose value is being expanded, if any.
 pub enum ActiveProperty<'a, J: Json> {
+	/// No active property, as for the top-level element of a document.
 	None,
+
+	/// Active property term, with its metadata.
 	Some(&'a str, &'a J::MetaData),
 }
 
@@ -254,45 +1188,351 @@ impl<'a, 's, J: Json> PartialEq<Option<&'s str>> for ActiveProperty<'a, J> {
 /// Note that you probably do not want to use this function directly,
 /// but instead use the [`Document::expand`](crate::Document::expand) method, implemented for
 /// every JSON type implementing the [`generic_json::Json`] trait.
-pub async fn expand<'a, J: JsonExpand, T: Id, C: ContextMut<T>, L: Loader>(
+///
+/// Warnings are given to the `warnings` handler as they are emitted.
+pub async fn expand<
+	'a,
+	J: JsonExpand,
+	T: Id,
+	C: ContextMut<T>,
+	L: Loader,
+	W: WarningHandler<J::MetaData> + MaybeSend,
+>(
 	active_context: &'a C,
 	document: &'a J,
 	base_url: Option<IriBuf>,
-	loader: &'a mut L,
+	loader: &'a L,
 	options: Options,
-	warnings: &mut Vec<Loc<Warning, J::MetaData>>,
-) -> Result<HashSet<Indexed<Object<J, T>>>, Loc<Error, J::MetaData>>
+	warnings: &mut W,
+) -> Result<IndexSet<Indexed<Object<J, T>>>, Loc<Error, J::MetaData>>
 where
-	T: Send + Sync,
-	C: Send + Sync,
+	T: MaybeSend + MaybeSync,
+	C: MaybeSend + MaybeSync,
 	C::LocalContext: From<L::Output> + From<J>,
-	L: Send + Sync,
+	L: MaybeSend + MaybeSync,
 	L::Output: Into<J>,
 {
-	let base_url = base_url.as_ref().map(|url| url.as_iri());
-	let expanded = expand_element(
+	expand_counted(
 		active_context,
-		ActiveProperty::None,
 		document,
 		base_url,
 		loader,
 		options,
-		false,
 		warnings,
+		&mut ObjectCount::default(),
+	)
+	.await
+}
+
+/// Expand the given JSON-LD document, as [`expand`] does,
+/// also returning processing statistics.
+///
+/// See [`Stats`] for the collected statistics.
+pub async fn expand_with_stats<
+	'a,
+	J: JsonExpand,
+	T: Id,
+	C: ContextMut<T>,
+	L: Loader,
+	W: WarningHandler<J::MetaData> + MaybeSend,
+>(
+	active_context: &'a C,
+	document: &'a J,
+	base_url: Option<IriBuf>,
+	loader: &'a L,
+	options: Options,
+	warnings: &mut W,
+) -> Result<(IndexSet<Indexed<Object<J, T>>>, Stats), Loc<Error, J::MetaData>>
+where
+	T: MaybeSend + MaybeSync,
+	C: MaybeSend + MaybeSync,
+	C::LocalContext: From<L::Output> + From<J>,
+	L: MaybeSend + MaybeSync,
+	L::Output: Into<J>,
+{
+	let start = Instant::now();
+	let loader = CountingLoader::new(loader);
+	let mut count = ObjectCount::default();
+	let objects = expand_counted(
+		active_context,
+		document,
+		base_url,
+		&loader,
+		options,
+		warnings,
+		&mut count,
 	)
 	.await?;
-	if expanded.len() == 1 {
+
+	let mut stats = Stats {
+		dropped_keys: count.dropped_keys,
+		contexts_processed: count.contexts,
+		remote_loads: loader.loads(),
+		expansion_time: start.elapsed(),
+		..Stats::default()
+	};
+	stats.count_objects(&objects);
+
+	Ok((objects, stats))
+}
+
+#[cfg_attr(
+	feature = "tracing",
+	tracing::instrument(level = "debug", skip_all, fields(base_url = ?base_url))
+)]
+async fn expand_counted<
+	'a,
+	J: JsonExpand,
+	T: Id,
+	C: ContextMut<T>,
+	L: Loader,
+	W: WarningHandler<J::MetaData> + MaybeSend,
+>(
+	active_context: &'a C,
+	document: &'a J,
+	base_url: Option<IriBuf>,
+	loader: &'a L,
+	options: Options,
+	warnings: &mut W,
+	count: &mut ObjectCount,
+) -> Result<IndexSet<Indexed<Object<J, T>>>, Loc<Error, J::MetaData>>
+where
+	T: MaybeSend + MaybeSync,
+	C: MaybeSend + MaybeSync,
+	C::LocalContext: From<L::Output> + From<J>,
+	L: MaybeSend + MaybeSync,
+	L::Output: Into<J>,
+{
+	let base_url = base_url.as_ref().map(|url| url.as_iri());
+
+	let active_context = match process_expand_context(
+		active_context,
+		base_url,
+		loader,
+		&options,
+		document.metadata(),
+		count,
+	)
+	.await?
+	{
+		Some(processed) => Mown::Owned(processed),
+		None => Mown::Borrowed(active_context),
+	};
+	let options = Options {
+		expand_context: None,
+		..options
+	};
+
+	let mut warnings = SafeWarnings::new(warnings, options.safe);
+	let expanded = expand_element(
+		active_context.as_ref(),
+		ActiveProperty::None,
+		document,
+		base_url,
+		loader,
+		options.clone(),
+		false,
+		&mut warnings,
+		count,
+	)
+	.await;
+	warnings.check()?;
+	let expanded = expanded?;
+
+	let finish = |objects: Vec<Indexed<Object<J, T>>>| {
+		let objects = if options.flatten_included {
+			flatten_included(objects)
+		} else {
+			objects
+		};
+
+		match options.merge_nodes {
+			NodeMerging::None => Ok(objects.into_iter().collect()),
+			mode => merge_objects(objects, mode)
+				.map(|objects| objects.into_iter().collect())
+				.map_err(|e| e.located(loader.id_opt(base_url), document.metadata().clone())),
+		}
+	};
+
+	let objects: Vec<_> = if expanded.len() == 1 {
 		match expanded.into_iter().next().unwrap().into_unnamed_graph() {
-			Ok(graph) => Ok(graph),
-			Err(obj) => {
-				let mut set = HashSet::new();
-				if filter_top_level_item(&obj) {
-					set.insert(obj);
-				}
-				Ok(set)
-			}
+			Ok(graph) => return finish(graph.into_iter().collect()),
+			Err(obj) => vec![obj],
 		}
 	} else {
-		Ok(expanded.into_iter().filter(filter_top_level_item).collect())
+		expanded.into_iter().collect()
+	};
+
+	if options.safe && !objects.iter().all(filter_top_level_item) {
+		return Err(
+			ErrorCode::DataLoss.located(loader.id_opt(base_url), document.metadata().clone())
+		);
+	}
+
+	finish(objects.into_iter().filter(filter_top_level_item).collect())
+}
+
+/// Expand a fragment of a JSON-LD document.
+///
+/// Expands `element` against the given active context,
+/// as if it was the value of an entry with the given active property term.
+/// This allows an application to expand a single subtree of a document,
+/// without expanding the whole document.
+///
+/// Contrarily to [`expand`], free-floating values and
+/// `@graph`-only objects are not removed from the result.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Local}, expansion::{self, ActiveProperty}, NoLoader};
+/// # use json_ld::util::AsJson;
+/// # use iref::IriBuf;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let loader = NoLoader::<Value>::new();
+/// let local_context = json!({
+///   "name": "https://schema.org/name",
+///   "homepage": { "@id": "https://schema.org/url", "@type": "@id" }
+/// });
+/// let context = Local::<IriBuf>::process_with(
+///   &local_context,
+///   &context::Json::<Value>::new(None),
+///   &loader,
+///   None,
+///   Default::default(),
+/// )
+/// .await
+/// .unwrap()
+/// .into_inner();
+///
+/// let mut warnings = Vec::new();
+/// let expanded = expansion::expand_fragment(
+///   &context,
+///   ActiveProperty::Some("homepage", &()),
+///   &json!("https://example.org/alice"),
+///   None,
+///   &loader,
+///   Default::default(),
+///   &mut warnings,
+/// )
+/// .await
+/// .unwrap();
+///
+/// let json: Vec<Value> = expanded.iter().map(|object| object.as_json()).collect();
+/// assert_eq!(json, vec![json!({ "@id": "https://example.org/alice" })]);
+/// # }
+/// ```
+pub async fn expand_fragment<
+	'a,
+	J: JsonExpand,
+	T: Id,
+	C: ContextMut<T>,
+	L: Loader,
+	W: WarningHandler<J::MetaData> + MaybeSend,
+>(
+	active_context: &'a C,
+	active_property: ActiveProperty<'a, J>,
+	element: &'a J,
+	base_url: Option<Iri<'a>>,
+	loader: &'a L,
+	options: Options,
+	warnings: &mut W,
+) -> Result<Vec<Indexed<Object<J, T>>>, Loc<Error, J::MetaData>>
+where
+	T: MaybeSend + MaybeSync,
+	C: MaybeSend + MaybeSync,
+	C::LocalContext: From<L::Output> + From<J>,
+	L: MaybeSend + MaybeSync,
+	L::Output: Into<J>,
+{
+	let mut warnings = SafeWarnings::new(warnings, options.safe);
+	let expanded = expand_element(
+		active_context,
+		active_property,
+		element,
+		base_url,
+		loader,
+		options,
+		false,
+		&mut warnings,
+		&mut ObjectCount::default(),
+	)
+	.await;
+	warnings.check()?;
+	Ok(expanded?.into_iter().collect())
+}
+
+/// Result of the [`expand_value`] function.
+pub type ValueExpansionResult<J, T> =
+	Result<Indexed<Object<J, T>>, Loc<Error, <J as Json>::MetaData>>;
+
+/// Expand a single value.
+///
+/// Applies the [Value Expansion algorithm](https://www.w3.org/TR/json-ld11-api/#value-expansion)
+/// to the given literal value (`null`, boolean, number or string),
+/// as if it was the value of an entry with the given active property term.
+/// Type and language coercion rules of the active context are applied.
+///
+/// The result is a value object, unless the active property has a type mapping
+/// of `@id` or `@vocab`, in which case it is a node reference.
+/// Fails with an [`ErrorCode::InvalidValueObjectValue`] error if the value is
+/// an array or an object.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Local}, expansion::{self, ActiveProperty}, NoLoader};
+/// # use json_ld::util::AsJson;
+/// # use iref::IriBuf;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let local_context = json!({
+///   "@language": "en",
+///   "name": "https://schema.org/name",
+///   "age": { "@id": "https://schema.org/age", "@type": "http://www.w3.org/2001/XMLSchema#integer" }
+/// });
+/// let context = Local::<IriBuf>::process_with(
+///   &local_context,
+///   &context::Json::<Value>::new(None),
+///   &NoLoader::<Value>::new(),
+///   None,
+///   Default::default(),
+/// )
+/// .await
+/// .unwrap()
+/// .into_inner();
+///
+/// let mut warnings = Vec::new();
+/// let age = expansion::expand_value(&context, ActiveProperty::Some("age", &()), &json!("42"), &mut warnings).unwrap();
+/// let json: Value = age.as_json();
+/// assert_eq!(json, json!({ "@value": "42", "@type": "http://www.w3.org/2001/XMLSchema#integer" }));
+///
+/// let name = expansion::expand_value(&context, ActiveProperty::Some("name", &()), &json!("Alice"), &mut warnings).unwrap();
+/// let json: Value = name.as_json();
+/// assert_eq!(json, json!({ "@value": "Alice", "@language": "en" }));
+/// # }
+/// ```
+pub fn expand_value<J: JsonHash + JsonClone, T: Id, C: Context<T>, W>(
+	active_context: &C,
+	active_property: ActiveProperty<J>,
+	value: &J,
+	warnings: &mut W,
+) -> ValueExpansionResult<J, T>
+where
+	W: WarningHandler<J::MetaData> + MaybeSend,
+{
+	match value.as_value_ref() {
+		ValueRef::Array(_) | ValueRef::Object(_) => {
+			Err(ErrorCode::InvalidValueObjectValue.located(None, value.metadata().clone()))
+		}
+		_ => expand_literal(
+			None,
+			active_context,
+			active_property,
+			LiteralValue::Given(value),
+			warnings,
+		)
+		.map_err(|e| e.located(None, value.metadata().clone())),
 	}
 }
@@ -1,21 +1,23 @@
 use super::{
 	expand_element, expand_iri, expand_literal, filter_top_level_item, ActiveProperty, Entry,
-	Expanded, ExpandedEntry, JsonExpand, LiteralValue, Options, Policy,
+	Expanded, ExpandedEntry, JsonExpand, KeyAction, LiteralValue, ObjectCount, Options, Policy,
 };
+use crate::future::{BoxFuture, FutureExt, MaybeSend, MaybeSync};
 use crate::util::as_array;
 use crate::{
-	context::{ContextMut, Loader, Local, ProcessingOptions},
+	context::{Context, ContextMut, Loader, Local, ProcessingOptions},
+	loader,
 	object::*,
-	syntax::{Container, ContainerType, Keyword, Term, Type},
-	Error, ErrorCode, Id, Indexed, LangString, Loc, ProcessingMode, Reference, Warning,
+	syntax::{is_keyword_like, Container, ContainerType, Keyword, Term, Type},
+	DropReason, Error, ErrorCode, Id, Indexed, LangString, Loc, ProcessingMode, Reference,
+	SendWarningHandler, Warning,
 };
 use cc_traits::{Len, MapIter};
-use futures::future::{BoxFuture, FutureExt};
 use generic_json::{Json, Key, ValueRef};
+use indexmap::IndexSet;
 use iref::Iri;
-use langtag::LanguageTagBuf;
 use mown::Mown;
-use std::{collections::HashSet, convert::TryInto};
+use std::convert::TryInto;
 
 /// Convert a term to a node id, if possible.
 /// Return `None` if the term is `null`.
@@ -31,18 +33,19 @@ pub fn node_id_of_term<T: Id>(term: Term<T>) -> Option<Reference<T>> {
 pub(crate) async fn expand_node<
 	'a,
 	J: JsonExpand,
-	T: 'a + Id + Send + Sync,
-	C: ContextMut<T> + Send + Sync,
-	L: Loader + Send + Sync,
+	T: 'a + Id + MaybeSend + MaybeSync,
+	C: ContextMut<T> + MaybeSend + MaybeSync,
+	L: Loader + MaybeSend + MaybeSync,
 >(
 	active_context: &'a C,
 	type_scoped_context: &'a C,
 	active_property: ActiveProperty<'a, J>,
 	expanded_entries: Vec<ExpandedEntry<'a, J, Term<T>>>,
 	base_url: Option<Iri<'a>>,
-	loader: &'a mut L,
+	loader: &'a L,
 	options: Options,
-	warnings: &'a mut Vec<Loc<Warning, J::MetaData>>,
+	warnings: &'a mut dyn SendWarningHandler<J::MetaData>,
+	count: &'a mut ObjectCount,
 ) -> Result<Option<Indexed<Node<J, T>>>, Loc<Error, J::MetaData>>
 where
 	C::LocalContext: From<L::Output> + From<J>,
@@ -63,6 +66,7 @@ where
 		loader,
 		options,
 		warnings,
+		count,
 	)
 	.await?;
 
@@ -112,9 +116,9 @@ type NodeEntriesExpensionResult<J, T> =
 fn expand_node_entries<
 	'a,
 	J: JsonExpand,
-	T: 'a + Id + Send + Sync,
-	C: ContextMut<T> + Send + Sync,
-	L: Loader + Send + Sync,
+	T: 'a + Id + MaybeSend + MaybeSync,
+	C: ContextMut<T> + MaybeSend + MaybeSync,
+	L: Loader + MaybeSend + MaybeSync,
 >(
 	mut result: Indexed<Node<J, T>>,
 	mut has_value_object_entries: bool,
@@ -123,12 +127,13 @@ fn expand_node_entries<
 	active_property: ActiveProperty<'a, J>,
 	expanded_entries: Vec<ExpandedEntry<'a, J, Term<T>>>,
 	base_url: Option<Iri<'a>>,
-	loader: &'a mut L,
+	loader: &'a L,
 	options: Options,
-	warnings: &'a mut Vec<Loc<Warning, J::MetaData>>,
+	warnings: &'a mut dyn SendWarningHandler<J::MetaData>,
+	count: &'a mut ObjectCount,
 ) -> BoxFuture<'a, NodeEntriesExpensionResult<J, T>>
 where
-	C::LocalContext: From<L::Output> + From<J> + Send + Sync,
+	C::LocalContext: From<L::Output> + From<J> + MaybeSend + MaybeSync,
 	L::Output: Into<J>,
 {
 	let source = loader.id_opt(base_url);
@@ -136,8 +141,24 @@ where
 		// For each `key` and `value` in `element`, ordered lexicographically by key
 		// if `ordered` is `true`:
 		for ExpandedEntry(key, expanded_key, value) in expanded_entries {
+			// Keyword-like properties are preserved extension keywords.
+			let key_action = match &expanded_key {
+				Term::Ref(Reference::Invalid(prop)) if !is_keyword_like(prop) => {
+					options.policy.undefined_key_action(prop, key.metadata())
+				}
+				_ => KeyAction::Keep,
+			};
+
 			match expanded_key {
-				Term::Null => (),
+				Term::Null => report_dropped_key(
+					options.clone(),
+					count,
+					source,
+					key.as_ref(),
+					key.metadata(),
+					null_key_drop_reason(key.as_ref()),
+					warnings,
+				),
 
 				// If key is @context, continue to the next key.
 				Term::Keyword(Keyword::Context) => (),
@@ -239,9 +260,10 @@ where
 								&*value,
 								base_url,
 								loader,
-								options,
+								options.clone(),
 								false,
 								warnings,
+								count,
 							)
 							.await?;
 							result.graph = Some(
@@ -269,9 +291,10 @@ where
 								&*value,
 								base_url,
 								loader,
-								options,
+								options.clone(),
 								false,
 								warnings,
+								count,
 							)
 							.await?;
 							let mut expanded_nodes = Vec::new();
@@ -324,7 +347,7 @@ where
 								}
 
 								for Entry(reverse_key, reverse_value) in reverse_entries {
-									match expand_iri(
+									let reverse_expanded_key = expand_iri(
 										source,
 										active_context,
 										reverse_key.as_ref(),
@@ -332,20 +355,26 @@ where
 										false,
 										true,
 										warnings,
-									) {
+									);
+
+									let reverse_key_action = match &reverse_expanded_key {
+										Term::Ref(Reference::Invalid(prop)) => options
+											.policy
+											.undefined_key_action(prop, reverse_key.metadata()),
+										_ => KeyAction::Keep,
+									};
+
+									match reverse_expanded_key {
 										Term::Keyword(_) => {
 											return Err(ErrorCode::InvalidReversePropertyMap
 												.located(source, reverse_key.metadata().clone()))
 										}
-										Term::Ref(Reference::Invalid(_))
-											if options.policy == Policy::Strictest =>
-										{
+										Term::Ref(_) if reverse_key_action == KeyAction::Error => {
 											return Err(ErrorCode::KeyExpansionFailed
 												.located(source, reverse_key.metadata().clone()))
 										}
 										Term::Ref(reverse_prop)
-											if reverse_prop.as_str().contains(':')
-												|| options.policy == Policy::Relaxed =>
+											if reverse_key_action == KeyAction::Keep =>
 										{
 											let reverse_expanded_value = expand_element(
 												active_context,
@@ -356,9 +385,10 @@ where
 												&*reverse_value,
 												base_url,
 												loader,
-												options,
+												options.clone(),
 												false,
 												warnings,
+												count,
 											)
 											.await?;
 
@@ -401,14 +431,37 @@ where
 												)
 											}
 										}
-										_ => {
-											if options.policy.is_strict() {
-												return Err(ErrorCode::KeyExpansionFailed.located(
+										Term::Null => report_dropped_key(
+											options.clone(),
+											count,
+											source,
+											reverse_key.as_ref(),
+											reverse_key.metadata(),
+											null_key_drop_reason(reverse_key.as_ref()),
+											warnings,
+										),
+										Term::Ref(_) => {
+											if options.safe {
+												return Err(ErrorCode::DataLoss.located(
 													source,
 													reverse_key.metadata().clone(),
 												));
 											}
+
 											// otherwise the key is just dropped.
+											report_dropped_key(
+												options.clone(),
+												count,
+												source,
+												reverse_key.as_ref(),
+												reverse_key.metadata(),
+												undefined_key_drop_reason(
+													active_context,
+													reverse_key.as_ref(),
+													options.policy,
+												),
+												warnings,
+											)
 										}
 									}
 								}
@@ -441,7 +494,8 @@ where
 								// Step 8 again.
 								let active_context = match property_scoped_context {
 									Some(property_scoped_context) => {
-										let options: ProcessingOptions = options.into();
+										let options: ProcessingOptions = options.clone().into();
+										count.add_context();
 										Mown::Owned(
 											property_scoped_context
 												.process_with(
@@ -461,6 +515,7 @@ where
 								};
 
 								// Steps 13 and 14 again.
+								let nested_metadata = nested_value.metadata();
 								if let Some(nested_value) = nested_value.as_object() {
 									let mut nested_entries: Vec<Entry<J>> = Vec::new();
 
@@ -486,6 +541,8 @@ where
 											ExpandedEntry(key, expanded_key, value)
 										});
 
+									let nested_options =
+										options.clone().enter(source, nested_metadata)?;
 									let (new_result, new_has_value_object_entries) =
 										expand_node_entries(
 											result,
@@ -496,8 +553,9 @@ where
 											nested_expanded_entries.collect(),
 											base_url,
 											loader,
-											options,
+											nested_options,
 											warnings,
+											count,
 										)
 										.await?;
 
@@ -518,15 +576,13 @@ where
 					}
 				}
 
-				Term::Ref(Reference::Invalid(_)) if options.policy == Policy::Strictest => {
+				Term::Ref(_) if key_action == KeyAction::Error => {
 					return Err(
 						ErrorCode::KeyExpansionFailed.located(source, key.metadata().clone())
 					)
 				}
 
-				Term::Ref(prop)
-					if prop.as_str().contains(':') || options.policy == Policy::Relaxed =>
-				{
+				Term::Ref(prop) if key_action == KeyAction::Keep => {
 					let mut container_mapping = Mown::Owned(Container::new());
 
 					let key_definition = active_context.get(key.as_ref());
@@ -608,10 +664,14 @@ where
 												{
 													None
 												} else {
-													match LanguageTagBuf::parse_copy(language) {
+													match options.strict_language_tags.parse(language) {
 														Ok(lang) => Some(lang.into()),
+														Err(_) if options.strict_language_tags.rejects() => {
+															return Err(ErrorCode::InvalidLanguageTaggedString
+																.located(source, language_metadata.clone()))
+														}
 														Err(err) => {
-															warnings.push(Loc::new(
+															warnings.handle(Loc::new(
 																Warning::MalformedLanguageTag(
 																	language.to_string().clone(),
 																	err,
@@ -734,13 +794,14 @@ where
 													.base_url
 													.as_ref()
 													.map(|url| url.as_iri());
+												count.add_context();
 												map_context = Mown::Owned(
 													local_context
 														.process_with(
 															map_context.as_ref(),
 															loader,
 															base_url,
-															options.into(),
+															options.clone().into(),
 														)
 														.await
 														.map_err(|e| {
@@ -788,9 +849,10 @@ where
 										&*index_value,
 										base_url,
 										loader,
-										options,
+										options.clone(),
 										true,
 										warnings,
+										count,
 									)
 									.await?;
 									// For each item in index value:
@@ -804,7 +866,7 @@ where
 											&& !item.is_graph()
 										{
 											let mut node = Node::new();
-											let mut graph = HashSet::new();
+											let mut graph = IndexSet::new();
 											graph.insert(item);
 											node.graph = Some(graph);
 											item = Object::Node(node).into();
@@ -945,9 +1007,10 @@ where
 									&*value,
 									base_url,
 									loader,
-									options,
+									options.clone(),
 									false,
 									warnings,
+									count,
 								)
 								.await?
 							}
@@ -980,7 +1043,7 @@ where
 								.into_iter()
 								.map(|ev| {
 									let mut node = Node::new();
-									let mut graph = HashSet::new();
+									let mut graph = IndexSet::new();
 									graph.insert(ev);
 									node.graph = Some(graph);
 									Object::Node(node).into()
@@ -1016,12 +1079,20 @@ where
 				}
 
 				Term::Ref(_) => {
-					if options.policy.is_strict() {
-						return Err(
-							ErrorCode::KeyExpansionFailed.located(source, key.metadata().clone())
-						);
+					if options.safe {
+						return Err(ErrorCode::DataLoss.located(source, key.metadata().clone()));
 					}
-					// non-keyword properties that does not include a ':' are skipped.
+
+					// otherwise the key is dropped.
+					report_dropped_key(
+						options.clone(),
+						count,
+						source,
+						key.as_ref(),
+						key.metadata(),
+						undefined_key_drop_reason(active_context, key.as_ref(), options.policy),
+						warnings,
+					)
 				}
 			}
 		}
@@ -1030,3 +1101,48 @@ where
 	}
 	.boxed()
 }
+
+/// Counts a dropped key, and reports it if requested by the `report_dropped_keys`
+/// or `explain_keys` options.
+fn report_dropped_key<M: Clone>(
+	options: Options,
+	count: &mut ObjectCount,
+	source: Option<loader::Id>,
+	key: &str,
+	metadata: &M,
+	reason: DropReason,
+	warnings: &mut dyn SendWarningHandler<M>,
+) {
+	count.add_dropped_key();
+	if options.report_dropped_keys || options.explain_keys {
+		warnings.handle(Loc::new(
+			Warning::DroppedKey(key.to_string(), reason),
+			source,
+			metadata.clone(),
+		))
+	}
+}
+
+/// Reason why a key expanded into `null` is dropped.
+fn null_key_drop_reason(key: &str) -> DropReason {
+	if is_keyword_like(key) {
+		DropReason::KeywordLike
+	} else {
+		DropReason::NullTerm
+	}
+}
+
+/// Reason why a key that cannot be expanded into an IRI is dropped.
+fn undefined_key_drop_reason<T: Id, C: Context<T>>(
+	active_context: &C,
+	key: &str,
+	policy: Policy,
+) -> DropReason {
+	match policy {
+		Policy::Custom(_) => DropReason::Policy,
+		_ => match active_context.get(key) {
+			Some(definition) if definition.value.is_none() => DropReason::NullTerm,
+			_ => DropReason::UndefinedTerm,
+		},
+	}
+}
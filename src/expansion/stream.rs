@@ -0,0 +1,519 @@
+use super::{
+	expand, expand_element, expand_iri, filter_top_level_item, process_expand_context,
+	ActiveProperty, JsonExpand, ObjectCount, Options, SafeWarnings,
+};
+use crate::future::{MaybeSend, MaybeSync};
+use crate::{
+	context::{ContextMut, Loader, Local},
+	loader,
+	syntax::{Keyword, Term},
+	Error, ErrorCode, Id, Indexed, Loc, Object, Warning,
+};
+use generic_json::{Json, JsonBuild, Key};
+use iref::IriBuf;
+use mown::Mown;
+use std::marker::PhantomData;
+
+/// Result of a [`StreamExpander`] step.
+pub type StreamExpansionResult<J, T> =
+	Result<Vec<Indexed<Object<J, T>>>, Loc<Error, <J as Json>::MetaData>>;
+
+/// JSON parsing event.
+///
+/// A JSON document is described by a sequence of events,
+/// such as the ones emitted by a streaming JSON parser.
+/// Consumed by the [`StreamExpander`].
+pub enum JsonEvent<J: Json> {
+	/// Beginning of an array.
+	BeginArray(J::MetaData),
+
+	/// End of the current array.
+	EndArray(J::MetaData),
+
+	/// Beginning of an object.
+	BeginObject(J::MetaData),
+
+	/// Key of the next entry of the current object.
+	Key(J::Key),
+
+	/// End of the current object.
+	EndObject(J::MetaData),
+
+	/// Complete value.
+	///
+	/// Usually a literal value (`null`, boolean, number or string),
+	/// but small arrays and objects may also be given at once.
+	Value(J),
+}
+
+impl<J: Json> JsonEvent<J> {
+	/// Returns the metadata attached to this event.
+	pub fn metadata(&self) -> &J::MetaData {
+		match self {
+			Self::BeginArray(m) | Self::EndArray(m) | Self::BeginObject(m) | Self::EndObject(m) => {
+				m
+			}
+			Self::Key(key) => key.metadata(),
+			Self::Value(value) => value.metadata(),
+		}
+	}
+}
+
+/// Partially built JSON value.
+enum Frame<J: Json> {
+	Array(Vec<J>, J::MetaData),
+	Object(Vec<(J::Key, J)>, Option<J::Key>, J::MetaData),
+}
+
+/// Position of the expander in the document.
+enum State<J: Json> {
+	/// No event received yet.
+	Start,
+
+	/// Inside the top-level array.
+	Array,
+
+	/// Inside the top-level object.
+	///
+	/// Holds the entries read so far, the key of the entry being read,
+	/// and whether or not the items of this entry can be streamed.
+	Object {
+		entries: Vec<(J::Key, J)>,
+		key: Option<J::Key>,
+		stream_graph: bool,
+		metadata: J::MetaData,
+	},
+
+	/// Inside the `@graph` array of the top-level object.
+	Graph(J::Key),
+
+	/// After the `@graph` array of the top-level object.
+	AfterGraph,
+
+	/// The document is complete.
+	Done,
+}
+
+/// Streaming expander.
+///
+/// Expands a JSON-LD document given as a sequence of [`JsonEvent`]s,
+/// without building the whole document in memory.
+/// Expanded top-level objects are returned by [`push`](Self::push) as soon as they are complete.
+///
+/// Only the current top-level item is built in memory. Top-level items are:
+///   - the items of the top-level array, or
+///   - the items of the `@graph` array of the top-level object, if the `@graph` entry
+///     is only preceded by a `@context` entry.
+///     In this case, no other entry may follow the `@graph` entry,
+///     otherwise an [`ErrorCode::InvalidEventStream`] error is raised.
+///
+/// Any other top-level object is expanded as a whole once complete.
+/// Items of a top-level array are returned as they are expanded,
+/// even if the array contains a single object with only a `@graph` entry.
+/// Since objects are returned as they are expanded, the result may contain
+/// duplicates that [`expand`] would have merged.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, expansion::{JsonEvent, StreamExpander}, NoLoader, Object};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let context = context::Json::<Value>::new(None);
+/// let loader = NoLoader::<Value>::new();
+/// let mut expander = StreamExpander::new(&context, None, &loader, Default::default());
+///
+/// let events = vec![
+///   JsonEvent::BeginObject(()),
+///   JsonEvent::Key("@context".to_string()),
+///   JsonEvent::Value(json!({ "name": "https://schema.org/name" })),
+///   JsonEvent::Key("@graph".to_string()),
+///   JsonEvent::BeginArray(()),
+///   JsonEvent::BeginObject(()),
+///   JsonEvent::Key("name".to_string()),
+///   JsonEvent::Value(json!("Alice")),
+///   JsonEvent::EndObject(()),
+///   JsonEvent::BeginObject(()),
+///   JsonEvent::Key("name".to_string()),
+///   JsonEvent::Value(json!("Bob")),
+///   JsonEvent::EndObject(()),
+///   JsonEvent::EndArray(()),
+///   JsonEvent::EndObject(()),
+/// ];
+///
+/// let mut count = 0;
+/// for event in events {
+///   for object in expander.push(event).await.unwrap() {
+///     assert!(matches!(object.inner(), Object::Node(_)));
+///     count += 1
+///   }
+/// }
+///
+/// assert!(expander.finish().unwrap().is_empty());
+/// assert_eq!(count, 2);
+/// # }
+/// ```
+pub struct StreamExpander<'a, J: JsonExpand, T: Id, C, L> {
+	active_context: Mown<'a, C>,
+	context: Option<C>,
+	base_url: Option<IriBuf>,
+	loader: &'a L,
+	options: Options,
+	state: State<J>,
+	stack: Vec<Frame<J>>,
+	metadata: Option<J::MetaData>,
+	warnings: Vec<Loc<Warning, J::MetaData>>,
+	objects: ObjectCount,
+	t: PhantomData<T>,
+}
+
+impl<'a, J: JsonExpand + JsonBuild, T: Id, C: ContextMut<T>, L: Loader>
+	StreamExpander<'a, J, T, C, L>
+where
+	T: MaybeSend + MaybeSync,
+	C: MaybeSend + MaybeSync,
+	C::LocalContext: From<L::Output> + From<J>,
+	L: MaybeSend + MaybeSync,
+	L::Output: Into<J>,
+{
+	/// Creates a new streaming expander using the given initial context,
+	/// base URL, document loader and options.
+	pub fn new(
+		active_context: &'a C,
+		base_url: Option<IriBuf>,
+		loader: &'a L,
+		options: Options,
+	) -> Self {
+		Self {
+			active_context: Mown::Borrowed(active_context),
+			context: None,
+			base_url,
+			loader,
+			options,
+			state: State::Start,
+			stack: Vec::new(),
+			metadata: None,
+			warnings: Vec::new(),
+			objects: ObjectCount::default(),
+			t: PhantomData,
+		}
+	}
+
+	/// Returns the warnings emitted so far.
+	pub fn warnings(&self) -> &[Loc<Warning, J::MetaData>] {
+		&self.warnings
+	}
+
+	/// Returns the warnings emitted so far, and clears them.
+	pub fn take_warnings(&mut self) -> Vec<Loc<Warning, J::MetaData>> {
+		std::mem::take(&mut self.warnings)
+	}
+
+	fn source(&self) -> Option<loader::Id> {
+		self.loader
+			.id_opt(self.base_url.as_ref().map(|url| url.as_iri()))
+	}
+
+	fn invalid(&self, metadata: &J::MetaData) -> Loc<Error, J::MetaData> {
+		ErrorCode::InvalidEventStream.located(self.source(), metadata.clone())
+	}
+
+	/// Feeds the next event of the document.
+	///
+	/// Returns the expanded top-level objects completed by this event.
+	pub async fn push(&mut self, event: JsonEvent<J>) -> StreamExpansionResult<J, T> {
+		self.metadata = Some(event.metadata().clone());
+
+		if let State::Start = self.state {
+			let base_url = self.base_url.as_ref().map(|url| url.as_iri());
+			if let Some(processed) = process_expand_context(
+				self.active_context.as_ref(),
+				base_url,
+				self.loader,
+				&self.options,
+				event.metadata(),
+				&mut self.objects,
+			)
+			.await?
+			{
+				self.active_context = Mown::Owned(processed);
+				self.options.expand_context = None;
+			}
+
+			match event {
+				JsonEvent::BeginArray(_) => {
+					self.state = State::Array;
+					return Ok(Vec::new());
+				}
+				JsonEvent::BeginObject(metadata) => {
+					self.state = State::Object {
+						entries: Vec::new(),
+						key: None,
+						stream_graph: false,
+						metadata,
+					};
+					return Ok(Vec::new());
+				}
+				JsonEvent::Value(value) => return self.complete(value).await,
+				event => return Err(self.invalid(event.metadata())),
+			}
+		}
+
+		if self.stack.is_empty() {
+			match (&mut self.state, event) {
+				(State::Array, JsonEvent::EndArray(_)) => {
+					self.state = State::Done;
+					return Ok(Vec::new());
+				}
+				(State::Graph(_), JsonEvent::EndArray(_)) => {
+					self.state = State::AfterGraph;
+					return Ok(Vec::new());
+				}
+				(State::AfterGraph, JsonEvent::EndObject(_)) => {
+					self.state = State::Done;
+					return Ok(Vec::new());
+				}
+				(
+					State::Object {
+						key: key @ None, ..
+					},
+					JsonEvent::Key(k),
+				) => {
+					*key = Some(k);
+					self.start_entry();
+					return Ok(Vec::new());
+				}
+				(
+					State::Object {
+						key: Some(_),
+						stream_graph: true,
+						..
+					},
+					JsonEvent::BeginArray(_),
+				) => {
+					if let State::Object { key: Some(key), .. } =
+						std::mem::replace(&mut self.state, State::Start)
+					{
+						self.state = State::Graph(key)
+					}
+
+					return Ok(Vec::new());
+				}
+				(State::Object { key: None, .. }, JsonEvent::EndObject(_)) => {
+					if let State::Object {
+						entries, metadata, ..
+					} = std::mem::replace(&mut self.state, State::Start)
+					{
+						let document = J::object(entries.into_iter().collect(), metadata);
+						return self.complete(document).await;
+					}
+
+					unreachable!()
+				}
+				(State::Array, event)
+				| (State::Graph(_), event)
+				| (State::Object { key: Some(_), .. }, event) => return self.build(event).await,
+				(_, event) => return Err(self.invalid(event.metadata())),
+			}
+		}
+
+		self.build(event).await
+	}
+
+	/// Checks if the entry being read in the top-level object is a `@graph` entry
+	/// whose items can be streamed.
+	fn start_entry(&mut self) {
+		let source = self.source();
+		let active_context = self.active_context.as_ref();
+		let context = self.context.as_ref().unwrap_or(active_context);
+		if let State::Object {
+			entries,
+			key: Some(key),
+			stream_graph,
+			..
+		} = &mut self.state
+		{
+			*stream_graph = entries.iter().all(|(k, _)| &**k == "@context")
+				&& expand_iri(
+					source,
+					context,
+					key,
+					key.metadata(),
+					false,
+					true,
+					&mut Vec::new(),
+				) == Term::Keyword(Keyword::Graph)
+		}
+	}
+
+	/// Builds the current top-level item.
+	async fn build(&mut self, event: JsonEvent<J>) -> StreamExpansionResult<J, T> {
+		let value = match event {
+			JsonEvent::BeginArray(metadata) => {
+				self.stack.push(Frame::Array(Vec::new(), metadata));
+				None
+			}
+			JsonEvent::BeginObject(metadata) => {
+				self.stack.push(Frame::Object(Vec::new(), None, metadata));
+				None
+			}
+			JsonEvent::Key(key) => match self.stack.last_mut() {
+				Some(Frame::Object(_, pending @ None, _)) => {
+					*pending = Some(key);
+					None
+				}
+				_ => return Err(self.invalid(key.metadata())),
+			},
+			JsonEvent::EndArray(end) => match self.stack.pop() {
+				Some(Frame::Array(items, metadata)) => {
+					Some(J::array(items.into_iter().collect(), metadata))
+				}
+				_ => return Err(self.invalid(&end)),
+			},
+			JsonEvent::EndObject(end) => match self.stack.pop() {
+				Some(Frame::Object(entries, None, metadata)) => {
+					Some(J::object(entries.into_iter().collect(), metadata))
+				}
+				_ => return Err(self.invalid(&end)),
+			},
+			JsonEvent::Value(value) => Some(value),
+		};
+
+		match value {
+			Some(value) => match self.stack.last_mut() {
+				Some(Frame::Array(items, _)) => {
+					items.push(value);
+					Ok(Vec::new())
+				}
+				Some(Frame::Object(entries, key, _)) => match key.take() {
+					Some(key) => {
+						entries.push((key, value));
+						Ok(Vec::new())
+					}
+					None => Err(self.invalid(value.metadata())),
+				},
+				None => self.complete(value).await,
+			},
+			None => Ok(Vec::new()),
+		}
+	}
+
+	/// Handles a complete top-level item.
+	async fn complete(&mut self, value: J) -> StreamExpansionResult<J, T> {
+		let base_url = self.base_url.as_ref().map(|url| url.as_iri());
+		let options = self.options.clone();
+
+		match &mut self.state {
+			State::Start | State::Object { key: None, .. } => {
+				self.state = State::Done;
+				let objects = expand(
+					self.active_context.as_ref(),
+					&value,
+					self.base_url.clone(),
+					self.loader,
+					options,
+					&mut self.warnings,
+				)
+				.await?;
+				Ok(objects.into_iter().collect())
+			}
+			State::Object {
+				entries,
+				key: pending @ Some(_),
+				..
+			} => {
+				let key = pending.take().unwrap();
+				if &*key == "@context" {
+					let processed = value
+						.process_with(
+							self.active_context.as_ref(),
+							self.loader,
+							base_url,
+							options.into(),
+						)
+						.await?
+						.into_inner();
+					self.context = Some(processed)
+				}
+
+				entries.push((key, value));
+				Ok(Vec::new())
+			}
+			State::Array => {
+				let mut warnings = SafeWarnings::new(&mut self.warnings, options.safe);
+				let expanded = expand_element(
+					self.active_context.as_ref(),
+					ActiveProperty::None,
+					&value,
+					base_url,
+					self.loader,
+					options,
+					false,
+					&mut warnings,
+					&mut self.objects,
+				)
+				.await;
+				warnings.check()?;
+
+				let result = expanded?.into_iter().collect();
+				self.filter(result, value.metadata())
+			}
+			State::Graph(key) => {
+				let active_context = self.active_context.as_ref();
+				let context = self.context.as_ref().unwrap_or(active_context);
+				let mut warnings = SafeWarnings::new(&mut self.warnings, options.safe);
+				let expanded = expand_element(
+					context,
+					ActiveProperty::Some("@graph", key.metadata()),
+					&value,
+					base_url,
+					self.loader,
+					options,
+					false,
+					&mut warnings,
+					&mut self.objects,
+				)
+				.await;
+				warnings.check()?;
+
+				let result = expanded?.into_iter().collect();
+				self.filter(result, value.metadata())
+			}
+			State::AfterGraph | State::Done => Err(self.invalid(value.metadata())),
+		}
+	}
+
+	/// Removes the top-level values from the given objects.
+	///
+	/// Fails in safe mode if some value is removed.
+	fn filter(
+		&self,
+		objects: Vec<Indexed<Object<J, T>>>,
+		metadata: &J::MetaData,
+	) -> StreamExpansionResult<J, T> {
+		if self.options.safe && !objects.iter().all(filter_top_level_item) {
+			return Err(ErrorCode::DataLoss.located(self.source(), metadata.clone()));
+		}
+
+		Ok(objects.into_iter().filter(filter_top_level_item).collect())
+	}
+
+	/// Ends the document.
+	///
+	/// Returns the last expanded top-level objects,
+	/// or an [`ErrorCode::InvalidEventStream`] error if the document is incomplete.
+	///
+	/// All the top-level objects are returned by [`push`](Self::push),
+	/// so the returned list is always empty on success.
+	pub fn finish(mut self) -> StreamExpansionResult<J, T>
+	where
+		J::MetaData: Default,
+	{
+		if !matches!(self.state, State::Done) || !self.stack.is_empty() {
+			let metadata = self.metadata.take().unwrap_or_default();
+			return Err(self.invalid(&metadata));
+		}
+
+		Ok(Vec::new())
+	}
+}
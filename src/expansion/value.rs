@@ -3,19 +3,20 @@ use crate::{
 	loader,
 	object::*,
 	syntax::{Keyword, Term},
-	ContextMut, Direction, Error, ErrorCode, Id, Indexed, LangString, Loc, Reference, Warning,
+	ContextMut, Direction, Error, ErrorCode, Id, Indexed, LangString, Loc, Reference,
+	SendWarningHandler, StrictLanguageTags, Warning,
 };
 use generic_json::{JsonClone, JsonHash, ValueRef};
-use langtag::LanguageTagBuf;
 use std::convert::TryFrom;
 
-pub(crate) fn expand_value<'e, J: JsonHash + JsonClone, T: Id, C: ContextMut<T>>(
+pub(crate) fn expand_value_object<'e, J: JsonHash + JsonClone, T: Id, C: ContextMut<T>>(
 	source: Option<loader::Id>,
 	input_type: Option<Term<T>>,
 	type_scoped_context: &C,
 	expanded_entries: Vec<ExpandedEntry<'e, J, Term<T>>>,
 	value_entry: &J,
-	warnings: &mut Vec<Loc<Warning, J::MetaData>>,
+	strict_language_tags: StrictLanguageTags,
+	warnings: &mut dyn SendWarningHandler<J::MetaData>,
 ) -> Result<Option<Indexed<Object<J, T>>>, Error>
 where
 	J::Object: 'e,
@@ -156,10 +157,13 @@ where
 			let lang = match language {
 				Some(language) => {
 					let (language, language_metadata) = language;
-					match LanguageTagBuf::parse_copy(language.as_str()) {
+					match strict_language_tags.parse(language.as_str()) {
 						Ok(lang) => Some(lang.into()),
+						Err(_) if strict_language_tags.rejects() => {
+							return Err(ErrorCode::InvalidLanguageTaggedString.into())
+						}
 						Err(err) => {
-							warnings.push(Loc::new(
+							warnings.handle(Loc::new(
 								Warning::MalformedLanguageTag(language.to_string(), err),
 								source,
 								language_metadata,
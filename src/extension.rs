@@ -0,0 +1,114 @@
+/// Action taken on an entry whose key is a non-standard keyword.
+///
+/// See [`ExtensionKeywords`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ExtensionAction {
+	/// Keep the entry.
+	///
+	/// During expansion, the entry is kept in the node object as a property
+	/// with a [`Reference::Invalid`](crate::Reference::Invalid) key
+	/// equal to the keyword, and its value is expanded like any other property value.
+	/// During compaction, the entry is written back unchanged.
+	///
+	/// Extension keywords are only preserved in node objects:
+	/// list, set and value objects still reject them.
+	Preserve,
+
+	/// Drop the entry, emitting a [`Warning::KeywordLikeValue`](crate::Warning::KeywordLikeValue)
+	/// warning during expansion.
+	///
+	/// This is the standard behavior.
+	Drop,
+
+	/// Silently drop the entry.
+	Ignore,
+}
+
+/// Registry of non-standard keywords.
+///
+/// By default, the expansion algorithm drops any entry whose key
+/// has the form of a keyword (`@` followed by letters)
+/// without being a JSON-LD keyword,
+/// such as `@annotation` or vendor extensions.
+/// This registry defines what to do with such keywords during expansion
+/// (see [`expansion::Options::extensions`](crate::expansion::Options::extensions))
+/// and compaction (see [`compaction::Options::extensions`](crate::compaction::Options::extensions)).
+///
+/// The action for a keyword is the one it has been registered with,
+/// or else the one returned by the handler (if any),
+/// or else [`ExtensionAction::Drop`].
+///
+/// The registry is built from `'static` data so that it can be
+/// shared by copy with the processing options.
+///
+/// # Example
+/// ```
+/// # use json_ld::{ExtensionAction, ExtensionKeywords};
+/// fn vendor(keyword: &str) -> ExtensionAction {
+///   if keyword.starts_with("@acme") {
+///     ExtensionAction::Preserve
+///   } else {
+///     ExtensionAction::Drop
+///   }
+/// }
+///
+/// const EXTENSIONS: ExtensionKeywords = ExtensionKeywords::new(&[
+///   ("@annotation", ExtensionAction::Preserve),
+///   ("@internal", ExtensionAction::Ignore),
+/// ])
+/// .with_handler(vendor);
+///
+/// assert_eq!(EXTENSIONS.action("@annotation"), ExtensionAction::Preserve);
+/// assert_eq!(EXTENSIONS.action("@internal"), ExtensionAction::Ignore);
+/// assert_eq!(EXTENSIONS.action("@acmeRevision"), ExtensionAction::Preserve);
+/// assert_eq!(EXTENSIONS.action("@unknown"), ExtensionAction::Drop);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ExtensionKeywords {
+	keywords: &'static [(&'static str, ExtensionAction)],
+	handler: Option<fn(&str) -> ExtensionAction>,
+}
+
+impl ExtensionKeywords {
+	/// Creates a new registry from the given keywords and associated actions.
+	#[inline(always)]
+	pub const fn new(keywords: &'static [(&'static str, ExtensionAction)]) -> Self {
+		Self {
+			keywords,
+			handler: None,
+		}
+	}
+
+	/// Sets the handler deciding the action for keywords that are not registered.
+	#[inline(always)]
+	pub const fn with_handler(self, handler: fn(&str) -> ExtensionAction) -> Self {
+		Self {
+			keywords: self.keywords,
+			handler: Some(handler),
+		}
+	}
+
+	/// Returns the action to take on the given non-standard keyword.
+	pub fn action(&self, keyword: &str) -> ExtensionAction {
+		match self.keywords.iter().find(|(k, _)| *k == keyword) {
+			Some((_, action)) => *action,
+			None => match self.handler {
+				Some(handler) => handler(keyword),
+				None => ExtensionAction::Drop,
+			},
+		}
+	}
+
+	/// Checks if entries with the given non-standard keyword are preserved.
+	#[inline(always)]
+	pub fn preserves(&self, keyword: &str) -> bool {
+		self.action(keyword) == ExtensionAction::Preserve
+	}
+}
+
+impl Default for ExtensionKeywords {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new(&[])
+	}
+}
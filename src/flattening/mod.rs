@@ -0,0 +1,200 @@
+//! Flattening algorithm and related types.
+use crate::{BlankId, BlankIdGenerator, Error, Id, Indexed, Node, Object, Reference};
+use generic_json::JsonHash;
+use std::collections::HashMap;
+
+mod node_map;
+
+pub use node_map::*;
+
+/// Result of the flattening algorithm.
+///
+/// List of every (top-level) node object of the flattened document.
+/// Named graphs are represented as node objects with a `@graph` field.
+pub type FlattenedDocument<J, T> = Vec<Indexed<Node<J, T>>>;
+
+/// Blank node identifier generator.
+///
+/// Implements the [Generate Blank Node Identifier](https://www.w3.org/TR/json-ld11-api/#generate-blank-node-identifier)
+/// algorithm.
+/// Fresh identifiers are of the form `_:{prefix}{n}` where `n` is a counter
+/// (the default prefix is `b`).
+/// The generator remembers the identifier associated to every relabeled blank node
+/// so that each occurrence of the same blank node is relabeled the same way.
+pub struct Generator {
+	prefix: String,
+	map: HashMap<BlankId, BlankId>,
+	count: usize,
+}
+
+impl Default for Generator {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Generator {
+	/// Creates a new generator producing identifiers of the form `_:b{n}`.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::with_prefix("b")
+	}
+
+	/// Creates a new generator producing identifiers of the form `_:{prefix}{n}`.
+	#[inline(always)]
+	pub fn with_prefix(prefix: &str) -> Self {
+		Self {
+			prefix: prefix.to_string(),
+			map: HashMap::new(),
+			count: 0,
+		}
+	}
+
+	/// Creates a new generator that does not relabel the given blank node identifiers.
+	///
+	/// Fresh identifiers are of the form `_:b{n}` (with as many `_` appended to
+	/// the `b` prefix as needed) and never clash with the preserved identifiers.
+	pub(crate) fn preserving<I: IntoIterator<Item = BlankId>>(ids: I) -> Self {
+		let map: HashMap<_, _> = ids.into_iter().map(|id| (id.clone(), id)).collect();
+
+		let mut prefix = "b".to_string();
+		while map.keys().any(|id| id.name().starts_with(&prefix)) {
+			prefix.push('_')
+		}
+
+		Self {
+			prefix,
+			map,
+			count: 0,
+		}
+	}
+
+	/// Generate a blank node identifier.
+	///
+	/// If `identifier` is `None`, a fresh identifier is returned.
+	/// Otherwise, the identifier previously associated to `identifier` is returned,
+	/// or a fresh identifier is associated to it.
+	pub fn generate(&mut self, identifier: Option<&BlankId>) -> BlankId {
+		match identifier {
+			Some(identifier) => match self.map.get(identifier) {
+				Some(id) => id.clone(),
+				None => {
+					let id = self.next();
+					self.map.insert(identifier.clone(), id.clone());
+					id
+				}
+			},
+			None => self.next(),
+		}
+	}
+
+	/// Relabel the given reference if it is a blank node identifier.
+	#[inline(always)]
+	pub fn relabel<T: Id>(&mut self, r: Reference<T>) -> Reference<T> {
+		match r {
+			Reference::Blank(id) => Reference::Blank(self.generate(Some(&id))),
+			r => r,
+		}
+	}
+
+	fn next(&mut self) -> BlankId {
+		let id = BlankId::new(&format!("{}{}", self.prefix, self.count));
+		self.count += 1;
+		id
+	}
+}
+
+impl BlankIdGenerator for Generator {
+	#[inline(always)]
+	fn next_blank_id(&mut self) -> BlankId {
+		self.next()
+	}
+}
+
+/// Flatten the given expanded objects.
+///
+/// Implements the [Flattening](https://www.w3.org/TR/json-ld11-api/#flattening-algorithm)
+/// algorithm, without the final compaction step.
+/// Blank node identifiers are relabeled using the given `generator`.
+/// If `ordered` is `true`, the nodes are sorted by identifier.
+pub fn flatten<J: JsonHash, T: Id, O: IntoIterator<Item = Indexed<Object<J, T>>>>(
+	objects: O,
+	generator: &mut Generator,
+	ordered: bool,
+) -> Result<FlattenedDocument<J, T>, Error> {
+	let mut node_map = NodeMap::new();
+	for object in objects {
+		node_map.extend_with(generator, object)?;
+	}
+
+	Ok(node_map.flatten(ordered))
+}
+
+/// Merge the given expanded documents into a single node map.
+///
+/// Nodes with the same identifier are merged together.
+/// Blank node identifiers are relabeled using the given `generator`.
+/// Since blank node identifiers are local to each document,
+/// the blank nodes of different documents are never merged,
+/// even if they share the same identifier.
+pub fn merge<J: JsonHash, T: Id, D, O>(
+	documents: D,
+	generator: &mut Generator,
+) -> Result<NodeMap<J, T>, Error>
+where
+	D: IntoIterator<Item = O>,
+	O: IntoIterator<Item = Indexed<Object<J, T>>>,
+{
+	let mut node_map = NodeMap::new();
+	for document in documents {
+		generator.map.clear();
+		for object in document {
+			node_map.extend_with(generator, object)?;
+		}
+	}
+
+	Ok(node_map)
+}
+
+impl<J: JsonHash, T: Id> NodeMap<J, T> {
+	/// Turns this node map into a flattened document.
+	///
+	/// Each named graph is attached to the node of the default graph
+	/// with the same identifier.
+	/// Nodes with no other field than `@id` are omitted.
+	/// If `ordered` is `true`, the nodes of every graph are sorted by identifier.
+	pub fn flatten(self, ordered: bool) -> FlattenedDocument<J, T> {
+		let (mut default_graph, named_graphs) = self.into_parts();
+
+		for (graph_id, graph) in named_graphs {
+			let mut graph_nodes: Vec<_> = graph
+				.into_nodes()
+				.filter(|node| !is_reference_only(node))
+				.collect();
+
+			if ordered {
+				graph_nodes.sort_by(|a, b| a.id().unwrap().as_str().cmp(b.id().unwrap().as_str()))
+			}
+
+			let entry = default_graph.declare_node(graph_id, None).unwrap();
+			entry.set_graph(Some(graph_nodes.into_iter().map(Indexed::cast).collect()))
+		}
+
+		let mut nodes: Vec<_> = default_graph
+			.into_nodes()
+			.filter(|node| !is_reference_only(node))
+			.collect();
+
+		if ordered {
+			nodes.sort_by(|a, b| a.id().unwrap().as_str().cmp(b.id().unwrap().as_str()))
+		}
+
+		nodes
+	}
+}
+
+/// Checks if the only field of the given node is `@id`.
+fn is_reference_only<J: JsonHash, T: Id>(node: &Indexed<Node<J, T>>) -> bool {
+	node.index().is_none() && node.is_empty()
+}
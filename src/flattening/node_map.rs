@@ -0,0 +1,331 @@
+use super::Generator;
+use crate::{BlankIdGenerator, Error, ErrorCode, Id, Indexed, Node, Object, Reference, Relabeling};
+use generic_json::JsonHash;
+use std::collections::HashMap;
+
+/// Named graphs of a [`NodeMap`], indexed by their identifier.
+pub type NamedGraphs<J, T> = HashMap<Reference<T>, NodeMapGraph<J, T>>;
+
+/// Node map.
+///
+/// Result of the *Node Map Generation* algorithm.
+/// It maps each graph of a document (the default graph and every named graph)
+/// to the set of nodes it contains, indexed by their identifier.
+pub struct NodeMap<J: JsonHash, T: Id> {
+	graphs: NamedGraphs<J, T>,
+	default_graph: NodeMapGraph<J, T>,
+}
+
+impl<J: JsonHash, T: Id> Default for NodeMap<J, T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<J: JsonHash, T: Id> NodeMap<J, T> {
+	/// Creates a new empty node map.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			graphs: HashMap::new(),
+			default_graph: NodeMapGraph::new(),
+		}
+	}
+
+	/// Returns a reference to the default graph.
+	#[inline(always)]
+	pub fn default_graph(&self) -> &NodeMapGraph<J, T> {
+		&self.default_graph
+	}
+
+	/// Returns a reference to the given graph.
+	///
+	/// If `id` is `None`, the default graph is returned.
+	#[inline(always)]
+	pub fn graph(&self, id: Option<&Reference<T>>) -> Option<&NodeMapGraph<J, T>> {
+		match id {
+			Some(id) => self.graphs.get(id),
+			None => Some(&self.default_graph),
+		}
+	}
+
+	/// Returns an iterator over the named graphs of the node map.
+	#[inline(always)]
+	pub fn graphs(&self) -> std::collections::hash_map::Iter<Reference<T>, NodeMapGraph<J, T>> {
+		self.graphs.iter()
+	}
+
+	/// Turns this node map into its default graph and named graphs.
+	#[inline(always)]
+	pub fn into_parts(self) -> (NodeMapGraph<J, T>, NamedGraphs<J, T>) {
+		(self.default_graph, self.graphs)
+	}
+
+	/// Relabel every blank node of the node map using the given `generator`.
+	///
+	/// Each blank node identifier is replaced by a fresh identifier,
+	/// the same for every occurrence of the identifier in the node map,
+	/// including graph names.
+	pub fn relabel_blank_nodes<G: BlankIdGenerator>(self, generator: G) -> Self {
+		let mut relabeling = Relabeling::new(generator);
+		let default_graph = self.default_graph.relabel_with(&mut relabeling);
+		let graphs = self
+			.graphs
+			.into_iter()
+			.map(|(id, graph)| (relabeling.relabel(id), graph.relabel_with(&mut relabeling)))
+			.collect();
+
+		Self {
+			graphs,
+			default_graph,
+		}
+	}
+
+	/// Returns a mutable reference to the given graph,
+	/// creating it if it does not already exist.
+	fn graph_mut(&mut self, id: Option<&Reference<T>>) -> &mut NodeMapGraph<J, T> {
+		match id {
+			Some(id) => {
+				if !self.graphs.contains_key(id) {
+					self.graphs.insert(id.clone(), NodeMapGraph::new());
+				}
+
+				self.graphs.get_mut(id).unwrap()
+			}
+			None => &mut self.default_graph,
+		}
+	}
+
+	/// Add the given top-level object to the node map.
+	///
+	/// Blank node identifiers are relabeled using the given `generator`.
+	#[inline(always)]
+	pub fn extend_with(
+		&mut self,
+		generator: &mut Generator,
+		element: Indexed<Object<J, T>>,
+	) -> Result<(), Error> {
+		self.extend_node_map(generator, element, None)?;
+		Ok(())
+	}
+
+	/// Add the given object to the node map, in the given active graph.
+	///
+	/// Returns the flattened version of the object,
+	/// where every node object is replaced by a reference to the node.
+	fn extend_node_map(
+		&mut self,
+		generator: &mut Generator,
+		element: Indexed<Object<J, T>>,
+		active_graph: Option<&Reference<T>>,
+	) -> Result<Indexed<Object<J, T>>, Error> {
+		let (object, index) = element.into_parts();
+		match object {
+			Object::Value(value) => Ok(Indexed::new(Object::Value(value), index)),
+			Object::List(items) => {
+				let mut flat_items = Vec::with_capacity(items.len());
+				for item in items {
+					flat_items.push(self.extend_node_map(generator, item, active_graph)?)
+				}
+
+				Ok(Indexed::new(Object::List(flat_items), index))
+			}
+			Object::Node(node) => {
+				let id = self.extend_node_map_from_node(
+					generator,
+					Indexed::new(node, index),
+					active_graph,
+				)?;
+				Ok(Object::Node(Node::with_id(id)).into())
+			}
+		}
+	}
+
+	/// Add the given node object to the node map, in the given active graph.
+	///
+	/// Returns the (possibly generated) identifier of the node.
+	fn extend_node_map_from_node(
+		&mut self,
+		generator: &mut Generator,
+		element: Indexed<Node<J, T>>,
+		active_graph: Option<&Reference<T>>,
+	) -> Result<Reference<T>, Error> {
+		let (node, index) = element.into_parts();
+
+		let id = match node.id {
+			Some(Reference::Blank(id)) => Reference::Blank(generator.generate(Some(&id))),
+			Some(id) => id,
+			None => Reference::Blank(generator.generate(None)),
+		};
+
+		let flat_node = self
+			.graph_mut(active_graph)
+			.declare_node(id.clone(), index)?;
+
+		for ty in node.types {
+			let ty = generator.relabel(ty);
+			if !flat_node.types.contains(&ty) {
+				flat_node.types.push(ty)
+			}
+		}
+
+		// Reverse properties are turned into forward properties
+		// of the referenced nodes.
+		for (property, reverse_nodes) in node.reverse_properties {
+			let property = generator.relabel(property);
+			for reverse_node in reverse_nodes {
+				let subject_id =
+					self.extend_node_map_from_node(generator, reverse_node, active_graph)?;
+				let subject = self.graph_mut(active_graph).get_mut(&subject_id).unwrap();
+				subject.insert_unique(
+					property.clone(),
+					Object::Node(Node::with_id(id.clone())).into(),
+				)
+			}
+		}
+
+		if let Some(graph) = node.graph {
+			self.graph_mut(Some(&id));
+			for object in graph {
+				self.extend_node_map(generator, object, Some(&id))?;
+			}
+		}
+
+		if let Some(included) = node.included {
+			for included_node in included {
+				self.extend_node_map_from_node(generator, included_node, active_graph)?;
+			}
+		}
+
+		for (property, objects) in node.properties {
+			let property = generator.relabel(property);
+
+			let mut flat_objects = Vec::with_capacity(objects.len());
+			for object in objects {
+				flat_objects.push(self.extend_node_map(generator, object, active_graph)?)
+			}
+
+			let flat_node = self.graph_mut(active_graph).get_mut(&id).unwrap();
+			flat_node.insert_all(property.clone(), std::iter::empty());
+			for object in flat_objects {
+				if object.is_list() {
+					flat_node.insert(property.clone(), object)
+				} else {
+					flat_node.insert_unique(property.clone(), object)
+				}
+			}
+		}
+
+		Ok(id)
+	}
+}
+
+/// Graph of a [`NodeMap`].
+///
+/// Maps each node identifier to its flattened node object.
+pub struct NodeMapGraph<J: JsonHash, T: Id> {
+	nodes: HashMap<Reference<T>, Indexed<Node<J, T>>>,
+}
+
+impl<J: JsonHash, T: Id> Default for NodeMapGraph<J, T> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<J: JsonHash, T: Id> NodeMapGraph<J, T> {
+	/// Creates a new empty graph.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			nodes: HashMap::new(),
+		}
+	}
+
+	/// Returns the number of nodes in the graph.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	/// Checks if the graph is empty.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+
+	/// Checks if the graph contains a node with the given identifier.
+	#[inline(always)]
+	pub fn contains(&self, id: &Reference<T>) -> bool {
+		self.nodes.contains_key(id)
+	}
+
+	/// Get the node with the given identifier.
+	#[inline(always)]
+	pub fn get(&self, id: &Reference<T>) -> Option<&Indexed<Node<J, T>>> {
+		self.nodes.get(id)
+	}
+
+	/// Get a mutable reference to the node with the given identifier.
+	#[inline(always)]
+	pub fn get_mut(&mut self, id: &Reference<T>) -> Option<&mut Indexed<Node<J, T>>> {
+		self.nodes.get_mut(id)
+	}
+
+	/// Returns an iterator over the nodes of the graph.
+	#[inline(always)]
+	pub fn nodes(&self) -> std::collections::hash_map::Values<Reference<T>, Indexed<Node<J, T>>> {
+		self.nodes.values()
+	}
+
+	/// Turns the graph into its nodes.
+	#[inline(always)]
+	pub fn into_nodes(
+		self,
+	) -> std::collections::hash_map::IntoValues<Reference<T>, Indexed<Node<J, T>>> {
+		self.nodes.into_values()
+	}
+
+	fn relabel_with<G: BlankIdGenerator>(self, relabeling: &mut Relabeling<G>) -> Self {
+		let nodes = self
+			.nodes
+			.into_iter()
+			.map(|(id, node)| {
+				(
+					relabeling.relabel(id),
+					node.map_references(&mut |r| relabeling.relabel(r)),
+				)
+			})
+			.collect();
+
+		Self { nodes }
+	}
+
+	/// Get the node with the given identifier, creating it if it does not exist.
+	///
+	/// If an `index` is given, it must match the index of the node, if any.
+	/// Otherwise a [`ErrorCode::ConflictingIndexes`] error is returned.
+	pub(crate) fn declare_node(
+		&mut self,
+		id: Reference<T>,
+		index: Option<String>,
+	) -> Result<&mut Indexed<Node<J, T>>, Error> {
+		let node = self
+			.nodes
+			.entry(id)
+			.or_insert_with_key(|id| Indexed::new(Node::with_id(id.clone()), None));
+
+		if let Some(index) = index {
+			match node.index() {
+				Some(current_index) if current_index != index => {
+					return Err(ErrorCode::ConflictingIndexes.into())
+				}
+				_ => node.set_index(Some(index)),
+			}
+		}
+
+		Ok(node)
+	}
+}
@@ -0,0 +1,90 @@
+//! Boxed futures and thread-safety bounds.
+//!
+//! By default, the futures returned by the processing algorithms
+//! (expansion, compaction, context processing, document loading, etc.)
+//! are `Send`, and the types they manipulate (JSON values, contexts, loaders, etc.)
+//! must be `Send + Sync`.
+//! This allows them to be used with multi-threaded executors such as `tokio`.
+//!
+//! Enabling the `local-futures` feature relaxes these bounds:
+//! the algorithms then return non-`Send` futures ([`futures::future::LocalBoxFuture`])
+//! and accept types that are neither `Send` nor `Sync`, such as `Rc`-based JSON values.
+//! This is useful in WASM or with single-threaded executors.
+//!
+//! The [`MaybeSend`] and [`MaybeSync`] traits are used in place of
+//! `Send` and `Sync` wherever those bounds are relaxed by the feature.
+//! HTTP [backends](crate::http::Backend) are always required to be `Send + Sync`.
+use futures::Future;
+
+#[cfg(not(feature = "local-futures"))]
+pub use futures::future::BoxFuture;
+
+#[cfg(feature = "local-futures")]
+pub use futures::future::LocalBoxFuture as BoxFuture;
+
+/// `Send` bound, unless the `local-futures` feature is enabled.
+#[cfg(not(feature = "local-futures"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(feature = "local-futures"))]
+impl<T: ?Sized + Send> MaybeSend for T {}
+
+/// `Send` bound, unless the `local-futures` feature is enabled.
+#[cfg(feature = "local-futures")]
+pub trait MaybeSend {}
+
+#[cfg(feature = "local-futures")]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// `Sync` bound, unless the `local-futures` feature is enabled.
+#[cfg(not(feature = "local-futures"))]
+pub trait MaybeSync: Sync {}
+
+#[cfg(not(feature = "local-futures"))]
+impl<T: ?Sized + Sync> MaybeSync for T {}
+
+/// `Sync` bound, unless the `local-futures` feature is enabled.
+#[cfg(feature = "local-futures")]
+pub trait MaybeSync {}
+
+#[cfg(feature = "local-futures")]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// JSON value that is `Send + Sync`, along with its components
+/// (see [`generic_json::JsonSendSync`]), unless the `local-futures` feature is enabled.
+#[cfg(not(feature = "local-futures"))]
+pub trait JsonMaybeSendSync = generic_json::JsonSendSync;
+
+/// JSON value that is `Send + Sync`, along with its components
+/// (see [`generic_json::JsonSendSync`]), unless the `local-futures` feature is enabled.
+#[cfg(feature = "local-futures")]
+pub trait JsonMaybeSendSync = generic_json::Json;
+
+/// Extension trait to box futures into [`BoxFuture`].
+pub trait FutureExt: Future {
+	/// Wraps the future into a [`BoxFuture`].
+	///
+	/// Equivalent to [`futures::FutureExt::boxed`],
+	/// or to [`futures::FutureExt::boxed_local`] if the `local-futures` feature is enabled.
+	fn boxed<'a>(self) -> BoxFuture<'a, Self::Output>
+	where
+		Self: 'a + Sized + MaybeSend;
+}
+
+impl<F: Future> FutureExt for F {
+	#[inline(always)]
+	fn boxed<'a>(self) -> BoxFuture<'a, Self::Output>
+	where
+		Self: 'a + Sized + MaybeSend,
+	{
+		#[cfg(not(feature = "local-futures"))]
+		{
+			futures::FutureExt::boxed(self)
+		}
+
+		#[cfg(feature = "local-futures")]
+		{
+			futures::FutureExt::boxed_local(self)
+		}
+	}
+}
@@ -0,0 +1,242 @@
+use super::Headers;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Maximum heuristic freshness lifetime.
+const MAX_HEURISTIC_LIFETIME: Duration = Duration::from_secs(24 * 3600);
+
+/// HTTP caching directives and validators of a response.
+///
+/// The freshness lifetime of a response is computed following
+/// [RFC 9111](https://www.rfc-editor.org/rfc/rfc9111#section-4.2):
+/// it is given by the `max-age` directive, or else by the `Expires` header,
+/// or else heuristically from the `Last-Modified` header.
+/// Responses without any of these headers are stale as soon as they are received.
+///
+/// ```
+/// # use json_ld::http::{CacheInfo, Headers};
+/// # use std::time::{Duration, Instant};
+/// let mut headers = Headers::new();
+/// headers.append("Date", "Sun, 06 Nov 1994 08:49:37 GMT");
+/// headers.append("Expires", "Sun, 06 Nov 1994 09:49:37 GMT");
+/// headers.append("ETag", "\"v1\"");
+///
+/// let info = CacheInfo::from_headers(&headers);
+/// assert_eq!(info.expires, Some(Duration::from_secs(3600)));
+/// assert_eq!(info.freshness_lifetime(), Duration::from_secs(3600));
+/// assert!(info.is_revalidable());
+///
+/// let fetched = Instant::now();
+/// assert!(info.is_fresh(fetched, fetched + Duration::from_secs(60)));
+/// assert!(!info.is_fresh(fetched, fetched + Duration::from_secs(3600)));
+///
+/// // Without freshness information, responses are immediately stale.
+/// let info = CacheInfo::from_headers(&Headers::new());
+/// assert!(!info.is_fresh(fetched, fetched));
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct CacheInfo {
+	/// `max-age` directive of the `Cache-Control` header, if any.
+	pub max_age: Option<Duration>,
+
+	/// Whether the `Cache-Control` header has the `no-cache` directive.
+	pub no_cache: bool,
+
+	/// Whether the `Cache-Control` header has the `no-store` directive.
+	pub no_store: bool,
+
+	/// Freshness lifetime given by the `Expires` header, if any.
+	///
+	/// It is relative to the `Date` header, or to the time the headers are parsed
+	/// if there is no `Date` header.
+	/// An invalid `Expires` header gives a zero lifetime.
+	pub expires: Option<Duration>,
+
+	/// Heuristic freshness lifetime, if any.
+	///
+	/// It is a tenth of the time elapsed between the `Last-Modified` header and
+	/// the `Date` header (or the time the headers are parsed), and at most one day.
+	pub heuristic_lifetime: Option<Duration>,
+
+	/// Value of the `ETag` header, if any.
+	pub etag: Option<String>,
+
+	/// Value of the `Last-Modified` header, if any.
+	pub last_modified: Option<String>,
+}
+
+impl CacheInfo {
+	/// Extracts the caching information from the given response headers.
+	pub fn from_headers(headers: &Headers) -> Self {
+		let mut info = Self::default();
+
+		for value in headers.get_all("cache-control") {
+			for directive in value.split(',') {
+				let (name, arg) = match directive.split_once('=') {
+					Some((name, arg)) => (name.trim(), Some(arg.trim().trim_matches('"'))),
+					None => (directive.trim(), None),
+				};
+
+				if name.eq_ignore_ascii_case("max-age") {
+					info.max_age = arg
+						.and_then(|arg| arg.parse().ok())
+						.map(Duration::from_secs)
+				} else if name.eq_ignore_ascii_case("no-cache") {
+					info.no_cache = true
+				} else if name.eq_ignore_ascii_case("no-store") {
+					info.no_store = true
+				}
+			}
+		}
+
+		let date = headers
+			.get("date")
+			.and_then(parse_http_date)
+			.unwrap_or_else(|| {
+				SystemTime::now()
+					.duration_since(UNIX_EPOCH)
+					.unwrap_or_default()
+			});
+
+		info.expires = headers
+			.get("expires")
+			.map(|expires| match parse_http_date(expires) {
+				Some(expires) => expires.checked_sub(date).unwrap_or_default(),
+				None => Duration::default(),
+			});
+
+		info.etag = headers.get("etag").map(str::to_string);
+		info.last_modified = headers.get("last-modified").map(str::to_string);
+		info.heuristic_lifetime =
+			info.last_modified
+				.as_deref()
+				.and_then(parse_http_date)
+				.map(|last_modified| {
+					let age = date.checked_sub(last_modified).unwrap_or_default();
+					std::cmp::min(age / 10, MAX_HEURISTIC_LIFETIME)
+				});
+		info
+	}
+
+	/// Returns the freshness lifetime of the response.
+	///
+	/// This is the `max-age` directive, or else the lifetime given by the `Expires` header,
+	/// or else the heuristic lifetime computed from the `Last-Modified` header.
+	/// Without any of these, the freshness lifetime is zero.
+	///
+	/// ```
+	/// # use json_ld::http::{CacheInfo, Headers};
+	/// # use std::time::Duration;
+	/// let mut headers = Headers::new();
+	/// headers.append("Date", "Sun, 06 Nov 1994 08:49:37 GMT");
+	/// headers.append("Last-Modified", "Tue, 01 Nov 1994 08:49:37 GMT");
+	/// let info = CacheInfo::from_headers(&headers);
+	/// assert_eq!(info.freshness_lifetime(), Duration::from_secs(12 * 3600));
+	///
+	/// headers.append("Cache-Control", "public, max-age=60");
+	/// let info = CacheInfo::from_headers(&headers);
+	/// assert_eq!(info.freshness_lifetime(), Duration::from_secs(60));
+	///
+	/// let mut headers = Headers::new();
+	/// headers.append("Date", "Thu, 29 Feb 2024 00:00:00 GMT");
+	/// headers.append("Last-Modified", "Fri, 01 Jan 2021 00:00:00 GMT");
+	/// headers.append("Expires", "Fri, 01 Mar 2024 00:00:00 GMT");
+	/// let info = CacheInfo::from_headers(&headers);
+	/// assert_eq!(info.freshness_lifetime(), Duration::from_secs(24 * 3600));
+	/// assert_eq!(info.heuristic_lifetime, Some(Duration::from_secs(24 * 3600)));
+	///
+	/// // Invalid dates in the `Expires` header mean that the response is already expired.
+	/// let mut headers = Headers::new();
+	/// headers.append("Expires", "0");
+	/// let info = CacheInfo::from_headers(&headers);
+	/// assert_eq!(info.freshness_lifetime(), Duration::from_secs(0));
+	/// ```
+	pub fn freshness_lifetime(&self) -> Duration {
+		self.max_age
+			.or(self.expires)
+			.or(self.heuristic_lifetime)
+			.unwrap_or_default()
+	}
+
+	/// Checks if a response fetched at the given time is still fresh.
+	///
+	/// Responses with the `no-cache` or `no-store` directive are never fresh.
+	/// Otherwise, the response is fresh as long as its age is less than its
+	/// [freshness lifetime](Self::freshness_lifetime).
+	pub fn is_fresh(&self, fetched: Instant, now: Instant) -> bool {
+		if self.no_cache || self.no_store {
+			return false;
+		}
+
+		now.saturating_duration_since(fetched) < self.freshness_lifetime()
+	}
+
+	/// Checks if the response can be revalidated with a conditional request,
+	/// using its `ETag` or `Last-Modified` header.
+	pub fn is_revalidable(&self) -> bool {
+		!self.no_store && (self.etag.is_some() || self.last_modified.is_some())
+	}
+}
+
+/// Parses an HTTP date in the preferred `IMF-fixdate` format
+/// (for instance `Sun, 06 Nov 1994 08:49:37 GMT`),
+/// and returns the time elapsed since the UNIX epoch.
+fn parse_http_date(date: &str) -> Option<Duration> {
+	const MONTHS: [&str; 12] = [
+		"Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+	];
+
+	let (_, date) = date.trim().split_once(", ")?;
+	let mut parts = date.split(' ');
+	let day: u64 = parts.next()?.parse().ok()?;
+	let month = parts.next()?;
+	let month = MONTHS.iter().position(|m| *m == month)? as u64 + 1;
+	let year: u64 = parts.next()?.parse().ok()?;
+	let mut time = parts.next()?.split(':');
+	let hours: u64 = time.next()?.parse().ok()?;
+	let minutes: u64 = time.next()?.parse().ok()?;
+	let seconds: u64 = time.next()?.parse().ok()?;
+	if parts.next()? != "GMT"
+		|| parts.next().is_some()
+		|| time.next().is_some()
+		|| year < 1970
+		|| !(1..=31).contains(&day)
+		|| hours > 23
+		|| minutes > 59
+		|| seconds > 60
+	{
+		return None;
+	}
+
+	// Number of days since the epoch, counting years from March
+	// so that leap days are at the end of the year.
+	let (year, month) = if month <= 2 {
+		(year - 1, month + 9)
+	} else {
+		(year, month - 3)
+	};
+	let era = year / 400;
+	let year_of_era = year % 400;
+	let day_of_year = (153 * month + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	let days = (era * 146097 + day_of_era).checked_sub(719468)?;
+
+	Some(Duration::from_secs(
+		days * 86400 + hours * 3600 + minutes * 60 + seconds,
+	))
+}
+
+/// Cache metrics of a [`Loader`](super::Loader).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Metrics {
+	/// Number of documents served from the cache without any request.
+	pub hits: usize,
+
+	/// Number of documents downloaded.
+	pub downloads: usize,
+
+	/// Number of conditional requests sent to revalidate a stale document.
+	pub revalidations: usize,
+
+	/// Number of revalidated documents that were not modified.
+	pub not_modified: usize,
+}
@@ -0,0 +1,179 @@
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::fmt;
+use std::str::FromStr;
+
+/// Hash algorithm of an [`Integrity`] pin.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Algorithm {
+	Sha256,
+	Sha384,
+	Sha512,
+}
+
+impl Algorithm {
+	/// Name of the algorithm, as used in integrity metadata.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Sha256 => "sha256",
+			Self::Sha384 => "sha384",
+			Self::Sha512 => "sha512",
+		}
+	}
+
+	/// Computes the digest of the given bytes.
+	pub fn digest(&self, bytes: &[u8]) -> Vec<u8> {
+		match self {
+			Self::Sha256 => Sha256::digest(bytes).to_vec(),
+			Self::Sha384 => Sha384::digest(bytes).to_vec(),
+			Self::Sha512 => Sha512::digest(bytes).to_vec(),
+		}
+	}
+}
+
+/// Expected content hash of a document.
+///
+/// Integrity pins use the format of the
+/// [Subresource Integrity](https://www.w3.org/TR/SRI/) metadata:
+/// the name of the hash algorithm (`sha256`, `sha384` or `sha512`),
+/// followed by `-` and the base64 encoded digest of the document bytes.
+///
+/// # Example
+/// ```
+/// use json_ld::http::Integrity;
+///
+/// let integrity: Integrity = "sha256-RBNvo1WzZ4oRRq0W9+hknpT7T8If536DEMBg9hyq/4o=".parse().unwrap();
+/// assert!(integrity.matches(b"{}"));
+/// assert!(!integrity.matches(b"{ }"));
+/// assert_eq!(Integrity::sha256(b"{}"), integrity);
+/// assert_eq!(integrity.to_string(), "sha256-RBNvo1WzZ4oRRq0W9+hknpT7T8If536DEMBg9hyq/4o=");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Integrity {
+	algorithm: Algorithm,
+	digest: Vec<u8>,
+}
+
+impl Integrity {
+	/// Creates a new integrity pin from a hash algorithm and the expected digest.
+	#[inline(always)]
+	pub fn new(algorithm: Algorithm, digest: Vec<u8>) -> Self {
+		Self { algorithm, digest }
+	}
+
+	/// Computes the `sha256` integrity pin of the given bytes.
+	#[inline(always)]
+	pub fn sha256(bytes: &[u8]) -> Self {
+		Self::compute(Algorithm::Sha256, bytes)
+	}
+
+	/// Computes the integrity pin of the given bytes with the given algorithm.
+	#[inline(always)]
+	pub fn compute(algorithm: Algorithm, bytes: &[u8]) -> Self {
+		Self::new(algorithm, algorithm.digest(bytes))
+	}
+
+	/// Hash algorithm.
+	#[inline(always)]
+	pub fn algorithm(&self) -> Algorithm {
+		self.algorithm
+	}
+
+	/// Expected digest.
+	#[inline(always)]
+	pub fn digest(&self) -> &[u8] {
+		&self.digest
+	}
+
+	/// Checks if the given bytes match the pin.
+	#[inline(always)]
+	pub fn matches(&self, bytes: &[u8]) -> bool {
+		self.algorithm.digest(bytes) == self.digest
+	}
+}
+
+/// Invalid integrity metadata.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidIntegrity;
+
+impl fmt::Display for InvalidIntegrity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid integrity metadata")
+	}
+}
+
+impl std::error::Error for InvalidIntegrity {}
+
+impl FromStr for Integrity {
+	type Err = InvalidIntegrity;
+
+	fn from_str(s: &str) -> Result<Self, InvalidIntegrity> {
+		let (algorithm, digest) = s.trim().split_once('-').ok_or(InvalidIntegrity)?;
+		let algorithm = match algorithm {
+			"sha256" => Algorithm::Sha256,
+			"sha384" => Algorithm::Sha384,
+			"sha512" => Algorithm::Sha512,
+			_ => return Err(InvalidIntegrity),
+		};
+
+		let digest = base64_decode(digest).ok_or(InvalidIntegrity)?;
+		if digest.len() != algorithm.digest(&[]).len() {
+			return Err(InvalidIntegrity);
+		}
+
+		Ok(Self::new(algorithm, digest))
+	}
+}
+
+impl fmt::Display for Integrity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{}-{}",
+			self.algorithm.as_str(),
+			base64_encode(&self.digest)
+		)
+	}
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the given bytes in base64, with padding.
+fn base64_encode(bytes: &[u8]) -> String {
+	let mut result = String::new();
+	for chunk in bytes.chunks(3) {
+		let n = chunk
+			.iter()
+			.enumerate()
+			.fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+		for i in 0..4 {
+			if i <= chunk.len() {
+				result.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char)
+			} else {
+				result.push('=')
+			}
+		}
+	}
+
+	result
+}
+
+/// Decodes the given base64 string, with optional padding.
+fn base64_decode(s: &str) -> Option<Vec<u8>> {
+	let s = s.trim_end_matches('=');
+	let mut result = Vec::new();
+	let mut n = 0u32;
+	let mut bits = 0;
+	for c in s.bytes() {
+		let value = BASE64_ALPHABET.iter().position(|a| *a == c)? as u32;
+		n = n << 6 | value;
+		bits += 6;
+		if bits >= 8 {
+			bits -= 8;
+			result.push((n >> bits) as u8);
+			n &= (1 << bits) - 1;
+		}
+	}
+
+	Some(result)
+}
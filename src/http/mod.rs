@@ -0,0 +1,1135 @@
+//! HTTP document and context loading, independent of the HTTP client
+//!
+//! The [`Loader`] defined in this module fetches remote documents
+//! through a [`Backend`] sending the actual HTTP requests.
+//! Backends are provided by the `reqwest-loader` feature (see the `reqwest` module)
+//! and the `hyper-loader` feature (see the `hyper` module),
+//! and other HTTP clients can be used by implementing the [`Backend`] trait.
+
+use crate::future::{self, FutureExt, MaybeSend, MaybeSync};
+use crate::{loader, Error, ErrorCode, RemoteDocument};
+use futures::future::{BoxFuture, Either};
+use futures::stream::{BoxStream, StreamExt};
+use generic_json::Json;
+use iref::{Iri, IriBuf};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::iter::FromIterator;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod cache;
+mod integrity;
+
+pub use cache::*;
+pub use integrity::*;
+
+/// HTTP headers.
+///
+/// Header names are case insensitive.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Headers(Vec<(String, String)>);
+
+impl Headers {
+	/// Creates an empty list of headers.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Appends a header.
+	#[inline(always)]
+	pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+		self.0.push((name.into(), value.into()))
+	}
+
+	/// Returns the value of the first header with the given name, if any.
+	#[inline(always)]
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value)
+	}
+
+	/// Returns the values of all the headers with the given name.
+	#[inline(always)]
+	pub fn get_all<'a>(&'a self, name: &'a str) -> impl 'a + Iterator<Item = &'a str> {
+		self.iter()
+			.filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value)
+	}
+
+	/// Returns an iterator over the headers names and values.
+	#[inline(always)]
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+		self.0
+			.iter()
+			.map(|(name, value)| (name.as_str(), value.as_str()))
+	}
+}
+
+impl FromIterator<(String, String)> for Headers {
+	#[inline(always)]
+	fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+		Self(iter.into_iter().collect())
+	}
+}
+
+/// HTTP response returned by a [`Backend`].
+pub struct Response {
+	/// Status code.
+	pub status: u16,
+
+	/// Headers.
+	pub headers: Headers,
+
+	/// Body, as a stream of chunks.
+	pub body: BoxStream<'static, Result<Vec<u8>, RequestError>>,
+}
+
+impl Response {
+	/// Returns the value of the `Content-Length` header, if any.
+	#[inline(always)]
+	pub fn content_length(&self) -> Option<u64> {
+		self.headers
+			.get("content-length")
+			.and_then(|len| len.trim().parse().ok())
+	}
+}
+
+/// Kind of [`RequestError`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RequestErrorKind {
+	/// The connection to the server failed.
+	///
+	/// Such requests are retried according to the [`Retry`] policy.
+	Connect,
+
+	/// The request timed out.
+	///
+	/// Such requests are retried according to the [`Retry`] policy.
+	Timeout,
+
+	/// Any other error.
+	Other,
+}
+
+/// Error raised by a [`Backend`] while sending a request or reading a response.
+#[derive(Debug)]
+pub struct RequestError {
+	kind: RequestErrorKind,
+	source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl RequestError {
+	/// Creates a new error of the given kind, caused by the given error.
+	#[inline(always)]
+	pub fn new<E: Into<Box<dyn std::error::Error + Send + Sync>>>(
+		kind: RequestErrorKind,
+		source: E,
+	) -> Self {
+		Self {
+			kind,
+			source: Some(source.into()),
+		}
+	}
+
+	/// Creates a new timeout error, without source.
+	#[inline(always)]
+	pub fn timeout() -> Self {
+		Self {
+			kind: RequestErrorKind::Timeout,
+			source: None,
+		}
+	}
+
+	/// Returns the kind of error.
+	#[inline(always)]
+	pub fn kind(&self) -> RequestErrorKind {
+		self.kind
+	}
+
+	/// Checks if the connection to the server failed.
+	#[inline(always)]
+	pub fn is_connect(&self) -> bool {
+		self.kind == RequestErrorKind::Connect
+	}
+
+	/// Checks if the request timed out.
+	#[inline(always)]
+	pub fn is_timeout(&self) -> bool {
+		self.kind == RequestErrorKind::Timeout
+	}
+}
+
+impl fmt::Display for RequestError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.kind {
+			RequestErrorKind::Connect => write!(f, "connection failed")?,
+			RequestErrorKind::Timeout => write!(f, "request timed out")?,
+			RequestErrorKind::Other => write!(f, "request failed")?,
+		}
+
+		match &self.source {
+			Some(source) => write!(f, ": {}", source),
+			None => Ok(()),
+		}
+	}
+}
+
+impl std::error::Error for RequestError {
+	#[inline(always)]
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		self.source
+			.as_ref()
+			.map(|e| e.as_ref() as &(dyn std::error::Error + 'static))
+	}
+}
+
+/// HTTP client used by a [`Loader`] to send requests.
+pub trait Backend: Send + Sync {
+	/// Sends a `GET` request to the given URL with the given headers.
+	///
+	/// Redirections must not be followed:
+	/// they are handled by the loader, according to its [`Limits`].
+	fn get<'a>(
+		&'a self,
+		url: Iri<'a>,
+		headers: &'a Headers,
+	) -> BoxFuture<'a, Result<Response, RequestError>>;
+
+	/// Returns a future completing after the given duration.
+	///
+	/// Used to wait between retries and to implement timeouts.
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Value of the `Accept` header sent with every request.
+pub const ACCEPT: &str = "application/ld+json, application/json;q=0.9, */*;q=0.1";
+
+/// Returns the value of the `Accept` header requesting the given profile.
+///
+/// Without profile, this is [`ACCEPT`].
+/// Otherwise, `application/ld+json` documents with the given profile are preferred.
+///
+/// ```
+/// use json_ld::{loader::Profile, http::accept};
+/// assert_eq!(
+///   accept(Some(Profile::Context)),
+///   "application/ld+json;profile=\"http://www.w3.org/ns/json-ld#context\", application/ld+json;q=0.9, application/json;q=0.8, */*;q=0.1"
+/// );
+/// ```
+pub fn accept(profile: Option<loader::Profile>) -> String {
+	match profile {
+		Some(profile) => format!(
+			"application/ld+json;profile=\"{}\", application/ld+json;q=0.9, application/json;q=0.8, */*;q=0.1",
+			profile.iri()
+		),
+		None => ACCEPT.to_string(),
+	}
+}
+
+/// Loading limits.
+///
+/// Protects the loader against documents linking to
+/// endless redirections or huge resources.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Limits {
+	/// Maximum number of redirections followed for each document.
+	///
+	/// Following an `alternate` link counts as a redirection.
+	/// Exceeding this limit results in a `TooManyRedirects` error.
+	pub max_redirects: usize,
+
+	/// Maximum size of a document, in bytes, if any.
+	///
+	/// Exceeding this limit results in a `DocumentTooLarge` error.
+	pub max_document_size: Option<u64>,
+}
+
+impl Default for Limits {
+	/// Follows at most 10 redirections, without limiting the document size.
+	#[inline(always)]
+	fn default() -> Self {
+		Self {
+			max_redirects: 10,
+			max_document_size: None,
+		}
+	}
+}
+
+/// Retry policy.
+///
+/// Requests failing because of a connection error, a timeout,
+/// or a response status listed in `statuses`, are retried
+/// with an exponential backoff.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Retry {
+	/// Maximum number of attempts for each request, including the first one.
+	///
+	/// Requests are not retried if this is `1` or less.
+	pub max_attempts: usize,
+
+	/// Delay before the first retry.
+	pub initial_backoff: Duration,
+
+	/// Factor applied to the delay after each retry.
+	pub multiplier: u32,
+
+	/// Maximum delay between two attempts.
+	pub max_backoff: Duration,
+
+	/// Response statuses triggering a retry.
+	pub statuses: Vec<u16>,
+}
+
+impl Retry {
+	/// Checks if a response with the given status should be retried.
+	#[inline(always)]
+	pub fn is_retryable_status(&self, status: u16) -> bool {
+		self.statuses.contains(&status)
+	}
+}
+
+impl Default for Retry {
+	/// No retry.
+	///
+	/// Once enabled by setting `max_attempts`, waits 100 milliseconds before the first retry,
+	/// doubling the delay after each retry up to 10 seconds,
+	/// and retries on the `408`, `429`, `500`, `502`, `503` and `504` statuses.
+	#[inline(always)]
+	fn default() -> Self {
+		Self {
+			max_attempts: 1,
+			initial_backoff: Duration::from_millis(100),
+			multiplier: 2,
+			max_backoff: Duration::from_secs(10),
+			statuses: vec![408, 429, 500, 502, 503, 504],
+		}
+	}
+}
+
+/// Remote loading options.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Options {
+	/// Loading limits.
+	pub limits: Limits,
+
+	/// Retry policy.
+	pub retry: Retry,
+
+	/// Timeout of each request, if any.
+	///
+	/// The timeout applies to each attempt, from the start of the request
+	/// until the response body has been read.
+	/// Exceeding it results in a `LoadingDocumentTimeout` error,
+	/// whose source is a [`Timeout`] error.
+	pub timeout: Option<Duration>,
+
+	/// Integrity pins, by IRI.
+	///
+	/// The bytes of a document loaded from one of these IRIs
+	/// must match the associated [`Integrity`],
+	/// otherwise an `IntegrityCheckFailed` error is returned.
+	/// Pins apply to the requested IRI, whatever redirections are followed.
+	pub integrity: HashMap<IriBuf, Integrity>,
+}
+
+/// Timeout error.
+///
+/// Source of the `LoadingDocumentTimeout` errors,
+/// giving the IRI of the document that could not be loaded in time.
+#[derive(Debug)]
+pub struct Timeout {
+	url: IriBuf,
+	source: RequestError,
+}
+
+impl Timeout {
+	/// IRI of the document that could not be loaded in time.
+	#[inline(always)]
+	pub fn url(&self) -> Iri {
+		self.url.as_iri()
+	}
+}
+
+impl fmt::Display for Timeout {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "loading `{}' timed out", self.url)
+	}
+}
+
+impl std::error::Error for Timeout {
+	#[inline(always)]
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.source)
+	}
+}
+
+/// Turns an error raised while requesting the given URL into a loading error.
+fn request_error(url: &IriBuf, e: RequestError) -> Error {
+	if e.is_timeout() {
+		Error::with_source(
+			ErrorCode::LoadingDocumentTimeout,
+			Timeout {
+				url: url.clone(),
+				source: e,
+			},
+		)
+	} else {
+		Error::with_source(ErrorCode::LoadingDocumentFailed, e)
+	}
+}
+
+/// `rel` value of `Link` headers pointing to the context of a JSON document.
+pub const CONTEXT_REL: &str = "http://www.w3.org/ns/json-ld#context";
+
+/// Returns the essence of the given media type, without parameters, in lower case.
+pub fn media_type_essence(ty: &str) -> String {
+	ty.split(';').next().unwrap().trim().to_ascii_lowercase()
+}
+
+/// Checks if the given media type (with optional parameters) is a JSON media type.
+///
+/// JSON media types are `application/json`, `application/ld+json`
+/// and any other media type with the `+json` suffix.
+pub fn is_json_media_type(ty: &str) -> bool {
+	let essence = media_type_essence(ty);
+	essence == "application/json" || essence.ends_with("+json")
+}
+
+/// Returns the value of the given parameter of a media type, if any.
+fn media_type_parameter(ty: &str, name: &str) -> Option<String> {
+	ty.split(';').skip(1).find_map(|param| {
+		let (key, value) = param.split_once('=')?;
+		if key.trim().eq_ignore_ascii_case(name) {
+			Some(value.trim().trim_matches('"').to_string())
+		} else {
+			None
+		}
+	})
+}
+
+/// Link of a `Link` HTTP header.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Link {
+	/// Target of the link.
+	pub target: String,
+
+	/// Value of the `rel` parameter.
+	pub rel: Option<String>,
+
+	/// Value of the `type` parameter.
+	pub ty: Option<String>,
+}
+
+impl Link {
+	/// Checks if the link has the given relation type.
+	///
+	/// The `rel` parameter may contain multiple space separated relation types.
+	pub fn has_rel(&self, rel: &str) -> bool {
+		match &self.rel {
+			Some(rels) => rels.split_whitespace().any(|r| r.eq_ignore_ascii_case(rel)),
+			None => false,
+		}
+	}
+}
+
+/// Parses the value of a `Link` HTTP header.
+///
+/// Invalid links are ignored.
+///
+/// # Example
+/// ```
+/// use json_ld::http::parse_link_header;
+///
+/// let links = parse_link_header(
+///   r#"<https://example.com/doc.jsonld>; rel="alternate"; type="application/ld+json", </style.css>; rel=stylesheet"#
+/// );
+///
+/// assert_eq!(links.len(), 2);
+/// assert_eq!(links[0].target, "https://example.com/doc.jsonld");
+/// assert!(links[0].has_rel("alternate"));
+/// assert_eq!(links[0].ty.as_deref(), Some("application/ld+json"));
+/// assert!(links[1].has_rel("stylesheet"));
+/// ```
+pub fn parse_link_header(value: &str) -> Vec<Link> {
+	let mut links = Vec::new();
+	let mut chars = value.chars().peekable();
+
+	loop {
+		// Skip to the target.
+		while let Some(c) = chars.peek() {
+			if *c == '<' {
+				break;
+			}
+
+			chars.next();
+		}
+
+		if chars.next().is_none() {
+			break;
+		}
+
+		let target: String = chars.by_ref().take_while(|c| *c != '>').collect();
+		let mut link = Link {
+			target,
+			rel: None,
+			ty: None,
+		};
+
+		// Parameters, until the next link.
+		let mut params = String::new();
+		let mut quoted = false;
+		while let Some(c) = chars.peek() {
+			match c {
+				'"' => quoted = !quoted,
+				',' if !quoted => break,
+				_ => (),
+			}
+
+			params.push(*c);
+			chars.next();
+		}
+
+		for param in params.split(';') {
+			if let Some((key, value)) = param.split_once('=') {
+				let value = value.trim().trim_matches('"').to_string();
+				match key.trim().to_ascii_lowercase().as_str() {
+					"rel" => link.rel = Some(value),
+					"type" => link.ty = Some(value),
+					_ => (),
+				}
+			}
+		}
+
+		links.push(link)
+	}
+
+	links
+}
+
+/// Resolves the target of the given link against the given base URL.
+fn resolve_link(link: &Link, base_url: &IriBuf) -> Result<IriBuf, Error> {
+	match iref::IriRef::new(&link.target) {
+		Ok(target) => Ok(target.resolved(base_url.as_iri())),
+		Err(_) => Err(ErrorCode::LoadingDocumentFailed.into()),
+	}
+}
+/// Loads the remote JSON-LD document behind the given URL using the given backend.
+///
+/// Redirections are followed, and the base URL of the returned document is its
+/// final URL.
+/// If the document is not a JSON document, an `alternate` link to an
+/// `application/ld+json` document given by the `Link` HTTP header is followed.
+/// If the document is a JSON document, but not a JSON-LD document,
+/// the context linked by the `Link` HTTP header, if any,
+/// is given by [`RemoteDocument::context_url`].
+/// If a `profile` is given, it is requested using the `Accept` header
+/// (see [`accept`]), and the profile returned by the server
+/// is given by [`RemoteDocument::profile`].
+/// The number of redirections and the size of the document
+/// are bounded by the given limits, and failed requests are retried
+/// according to the given retry policy (see [`Options`]).
+pub async fn load_remote_json_ld_document<B: Backend, J, P>(
+	backend: &B,
+	url: Iri<'_>,
+	source: loader::Id,
+	profile: Option<loader::Profile>,
+	options: &Options,
+	parser: &P,
+) -> Result<RemoteDocument<J>, Error>
+where
+	P: Send + Sync + Fn(&str) -> Result<J, Error>,
+{
+	match fetch(backend, url, source, profile, options, None, parser).await? {
+		Fetched::Document(doc, _) => Ok(*doc),
+		Fetched::NotModified(_) => Err(ErrorCode::LoadingDocumentFailed.into()),
+	}
+}
+
+/// Result of a fetch.
+enum Fetched<J> {
+	/// The document was downloaded.
+	Document(Box<RemoteDocument<J>>, CacheInfo),
+
+	/// The document was not modified since it was cached.
+	NotModified(CacheInfo),
+}
+
+/// Fetches the remote JSON-LD document behind the given URL,
+/// as [`load_remote_json_ld_document`] does.
+///
+/// If `validators` are given, a conditional request is sent
+/// using its `ETag` and `Last-Modified` values.
+async fn fetch<B: Backend, J, P>(
+	backend: &B,
+	url: Iri<'_>,
+	source: loader::Id,
+	profile: Option<loader::Profile>,
+	options: &Options,
+	validators: Option<&CacheInfo>,
+	parser: &P,
+) -> Result<Fetched<J>, Error>
+where
+	P: Send + Sync + Fn(&str) -> Result<J, Error>,
+{
+	let limits = &options.limits;
+	let integrity = options.integrity.get(&IriBuf::from(url));
+	let mut url = IriBuf::from(url);
+	let mut redirects = 0;
+	let mut alternate_followed = false;
+	let accept = accept(profile);
+
+	loop {
+		log::info!("loading remote document `{}'", url);
+		let mut headers = Headers::new();
+		headers.append("accept", accept.as_str());
+		if let Some(validators) = validators.filter(|_| !alternate_followed) {
+			if let Some(etag) = &validators.etag {
+				headers.append("if-none-match", etag.as_str())
+			}
+
+			if let Some(last_modified) = &validators.last_modified {
+				headers.append("if-modified-since", last_modified.as_str())
+			}
+		}
+
+		let (mut response, deadline) = send(backend, &url, &headers, options).await?;
+		match response.status {
+			304 if validators.is_some() => {
+				return Ok(Fetched::NotModified(CacheInfo::from_headers(
+					&response.headers,
+				)))
+			}
+			301 | 302 | 303 | 307 | 308 => {
+				let location = match response.headers.get("location") {
+					Some(location) => location,
+					None => return Err(ErrorCode::LoadingDocumentFailed.into()),
+				};
+
+				if redirects >= limits.max_redirects {
+					return Err(ErrorCode::TooManyRedirects.into());
+				}
+
+				url = match iref::IriRef::new(location) {
+					Ok(location) => location.resolved(url.as_iri()),
+					Err(_) => return Err(ErrorCode::LoadingDocumentFailed.into()),
+				};
+				redirects += 1;
+				continue;
+			}
+			status if !(200..300).contains(&status) => {
+				return Err(Error::with_source(
+					ErrorCode::LoadingDocumentFailed,
+					RequestError::new(RequestErrorKind::Other, format!("HTTP status {}", status)),
+				))
+			}
+			_ => (),
+		}
+
+		let content_type = response.headers.get("content-type").map(str::to_string);
+		let links: Vec<Link> = response
+			.headers
+			.get_all("link")
+			.flat_map(parse_link_header)
+			.collect();
+
+		match content_type {
+			Some(content_type) if is_json_media_type(&content_type) => {
+				let essence = media_type_essence(&content_type);
+				let context_url = if essence == "application/ld+json" {
+					None
+				} else {
+					let mut context_links = links.iter().filter(|link| link.has_rel(CONTEXT_REL));
+					match (context_links.next(), context_links.next()) {
+						(Some(_), Some(_)) => {
+							return Err(ErrorCode::MultipleContextLinkHeaders.into())
+						}
+						(Some(link), None) => Some(resolve_link(link, &url)?),
+						_ => None,
+					}
+				};
+
+				let profile = media_type_parameter(&content_type, "profile");
+				let cache_info = CacheInfo::from_headers(&response.headers);
+				let body = read_body(backend, &url, &mut response, options, deadline).await?;
+				if let Some(integrity) = integrity {
+					if !integrity.matches(body.as_bytes()) {
+						return Err(ErrorCode::IntegrityCheckFailed.into());
+					}
+				}
+
+				let doc = (*parser)(body.as_str())?;
+				return Ok(Fetched::Document(
+					Box::new(
+						RemoteDocument::new(doc, url, source)
+							.with_content_type(Some(essence))
+							.with_context_url(context_url)
+							.with_profile(profile),
+					),
+					cache_info,
+				));
+			}
+			_ => {
+				let alternate = links.iter().find(|link| {
+					link.has_rel("alternate")
+						&& link
+							.ty
+							.as_deref()
+							.map(|ty| media_type_essence(ty) == "application/ld+json")
+							.unwrap_or(false)
+				});
+
+				match alternate {
+					Some(_) if !alternate_followed && redirects >= limits.max_redirects => {
+						return Err(ErrorCode::TooManyRedirects.into())
+					}
+					Some(link) if !alternate_followed => {
+						url = resolve_link(link, &url)?;
+						redirects += 1;
+						alternate_followed = true
+					}
+					_ => return Err(ErrorCode::LoadingDocumentFailed.into()),
+				}
+			}
+		}
+	}
+}
+
+/// Waits for the given future, giving up at the given deadline, if any.
+async fn with_deadline<B: Backend, F: Future>(
+	backend: &B,
+	deadline: Option<Instant>,
+	future: F,
+) -> Option<F::Output> {
+	match deadline {
+		Some(deadline) => {
+			let timeout = deadline.saturating_duration_since(Instant::now());
+			match futures::future::select(Box::pin(future), backend.sleep(timeout)).await {
+				Either::Left((output, _)) => Some(output),
+				Either::Right(_) => None,
+			}
+		}
+		None => Some(future.await),
+	}
+}
+
+/// Sends a `GET` request to the given URL,
+/// retrying according to the retry policy of the given options.
+///
+/// Returns the response along with the deadline of the successful attempt,
+/// if the options define a timeout.
+/// The response body must be read before this deadline.
+async fn send<B: Backend>(
+	backend: &B,
+	url: &IriBuf,
+	headers: &Headers,
+	options: &Options,
+) -> Result<(Response, Option<Instant>), Error> {
+	let retry = &options.retry;
+	let mut backoff = retry.initial_backoff;
+	let mut attempt = 1;
+	loop {
+		let request = instrumented!(
+			backend.get(url.as_iri(), headers),
+			"fetch",
+			url = %url,
+			attempt
+		);
+		let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+		let result = with_deadline(backend, deadline, request)
+			.await
+			.unwrap_or_else(|| Err(RequestError::timeout()));
+
+		let retryable = match &result {
+			Ok(response) => retry.is_retryable_status(response.status),
+			Err(e) => e.is_connect() || e.is_timeout(),
+		};
+
+		if !retryable || attempt >= retry.max_attempts {
+			return result
+				.map(|response| (response, deadline))
+				.map_err(|e| request_error(url, e));
+		}
+
+		match result {
+			Ok(response) => log::warn!(
+				"request failed with status {}, retrying in {:?}",
+				response.status,
+				backoff
+			),
+			Err(e) => log::warn!("request failed ({}), retrying in {:?}", e, backoff),
+		}
+
+		backend.sleep(backoff).await;
+		backoff = match backoff.checked_mul(retry.multiplier) {
+			Some(next_backoff) => std::cmp::min(next_backoff, retry.max_backoff),
+			None => retry.max_backoff,
+		};
+		attempt += 1
+	}
+}
+
+/// Reads the body of the given response as text,
+/// failing with a `DocumentTooLarge` error if it exceeds the maximum document size,
+/// or with a `LoadingDocumentTimeout` error if it is not read before the given deadline.
+async fn read_body<B: Backend>(
+	backend: &B,
+	url: &IriBuf,
+	response: &mut Response,
+	options: &Options,
+	deadline: Option<Instant>,
+) -> Result<String, Error> {
+	let max_size = options.limits.max_document_size;
+	if let (Some(max_size), Some(len)) = (max_size, response.content_length()) {
+		if len > max_size {
+			return Err(ErrorCode::DocumentTooLarge.into());
+		}
+	}
+
+	let read = async move {
+		let mut body = Vec::new();
+		while let Some(chunk) = response.body.next().await {
+			let chunk = chunk.map_err(|e| request_error(url, e))?;
+			if let Some(max_size) = max_size {
+				if (body.len() + chunk.len()) as u64 > max_size {
+					return Err(ErrorCode::DocumentTooLarge.into());
+				}
+			}
+
+			body.extend_from_slice(&chunk)
+		}
+
+		String::from_utf8(body).map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
+	};
+
+	match with_deadline(backend, deadline, read).await {
+		Some(result) => result,
+		None => Err(request_error(url, RequestError::timeout())),
+	}
+}
+
+/// Remote document loader.
+///
+/// Fetches remote documents and contexts over HTTP(S) using the backend `B`,
+/// with the `Accept: application/ld+json` header,
+/// following redirections.
+/// The body of the response is parsed using the given parser function.
+/// The number of redirections and the size of the documents
+/// can be bounded using [`Limits`],
+/// failed requests can be retried using a [`Retry`] policy,
+/// and requests can be given a timeout.
+///
+/// Loaded documents are cached following the `Cache-Control`, `Expires`
+/// and `Last-Modified` headers of the response (see [`CacheInfo`]):
+/// documents are fetched again once their freshness lifetime has passed,
+/// or every time with the `no-cache` or `no-store` directives.
+/// Documents without any of these headers are fetched again every time.
+/// Stale documents with an `ETag` or `Last-Modified` header are revalidated
+/// with a conditional request, so they are not downloaded again if not modified.
+/// The caching behavior can be observed with [`Loader::metrics`],
+/// and every request can be reported to a [`loader::Observer`].
+pub struct Loader<B, J> {
+	backend: B,
+	parser: loader::Parser<J>,
+	options: Options,
+	state: Mutex<State<J>>,
+	observer: Option<Arc<dyn loader::Observer>>,
+}
+
+/// Cached documents and cache metrics of a [`Loader`].
+struct State<J> {
+	namespace: HashMap<IriBuf, loader::Id>,
+	cache: Vec<CacheEntry<J>>,
+	metrics: Metrics,
+}
+
+/// Cached document.
+struct CacheEntry<J> {
+	doc: RemoteDocument<J>,
+	info: CacheInfo,
+	fetched: Instant,
+}
+
+impl<B: Backend + Default, J: Clone + MaybeSend> Loader<B, J> {
+	/// Creates a new loader using the given function to parse the fetched documents.
+	#[inline(always)]
+	pub fn new<E: 'static + std::error::Error>(
+		parser: impl 'static + Send + Sync + Fn(&str) -> Result<J, E>,
+	) -> Self {
+		Self::with_backend(B::default(), parser)
+	}
+}
+
+impl<B: Backend, J: Clone + MaybeSend> Loader<B, J> {
+	/// Creates a new loader sending requests with the given backend,
+	/// and using the given function to parse the fetched documents.
+	pub fn with_backend<E: 'static + std::error::Error>(
+		backend: B,
+		parser: impl 'static + Send + Sync + Fn(&str) -> Result<J, E>,
+	) -> Self {
+		Self {
+			backend,
+			options: Options::default(),
+			state: Mutex::new(State {
+				namespace: HashMap::new(),
+				cache: Vec::new(),
+				metrics: Metrics::default(),
+			}),
+			observer: None,
+			parser: Box::new(move |s| {
+				parser(s).map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
+			}),
+		}
+	}
+
+	/// Returns the backend used to send requests.
+	#[inline(always)]
+	pub fn backend(&self) -> &B {
+		&self.backend
+	}
+
+	/// Sets the loading options.
+	#[inline(always)]
+	pub fn with_options(mut self, options: Options) -> Self {
+		self.options = options;
+		self
+	}
+
+	/// Sets the loading limits.
+	#[inline(always)]
+	pub fn with_limits(mut self, limits: Limits) -> Self {
+		self.options.limits = limits;
+		self
+	}
+
+	/// Sets the retry policy.
+	#[inline(always)]
+	pub fn with_retry(mut self, retry: Retry) -> Self {
+		self.options.retry = retry;
+		self
+	}
+
+	/// Sets the timeout of each request.
+	#[inline(always)]
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.options.timeout = Some(timeout);
+		self
+	}
+
+	/// Sets the maximum number of redirections followed for each document.
+	#[inline(always)]
+	pub fn with_max_redirects(mut self, max_redirects: usize) -> Self {
+		self.options.limits.max_redirects = max_redirects;
+		self
+	}
+
+	/// Sets the maximum size of the loaded documents, in bytes.
+	#[inline(always)]
+	pub fn with_max_document_size(mut self, max_document_size: u64) -> Self {
+		self.options.limits.max_document_size = Some(max_document_size);
+		self
+	}
+
+	/// Sets the observer notified of every request sent by the loader,
+	/// and of every document served from the cache.
+	///
+	/// Revalidated documents that have not been modified
+	/// are reported as responses.
+	#[inline(always)]
+	pub fn with_observer(mut self, observer: impl 'static + loader::Observer) -> Self {
+		self.observer = Some(Arc::new(observer));
+		self
+	}
+
+	/// Pins the expected integrity of the document behind the given IRI.
+	///
+	/// See [`Options::integrity`].
+	#[inline(always)]
+	pub fn with_integrity(mut self, url: Iri, integrity: Integrity) -> Self {
+		self.options.integrity.insert(url.into(), integrity);
+		self
+	}
+
+	/// Returns the loading options.
+	#[inline(always)]
+	pub fn options(&self) -> &Options {
+		&self.options
+	}
+
+	/// Returns the loading limits.
+	#[inline(always)]
+	pub fn limits(&self) -> &Limits {
+		&self.options.limits
+	}
+
+	/// Returns the cache metrics.
+	#[inline(always)]
+	pub fn metrics(&self) -> Metrics {
+		self.state.lock().unwrap().metrics
+	}
+
+	/// Loads the document behind the given IRI.
+	///
+	/// The base URL of the returned document is its final URL, after redirections.
+	/// If the document has been loaded through redirections,
+	/// the original IRI is also associated to the document identifier.
+	pub async fn load(&self, url: Iri<'_>) -> Result<RemoteDocument<J>, Error> {
+		self.load_with_profile(url, None).await
+	}
+
+	/// Loads the document behind the given IRI, requesting the given profile.
+	///
+	/// Documents are cached by IRI regardless of their profile:
+	/// the profile is only requested when the document is fetched.
+	pub async fn load_with_profile(
+		&self,
+		url: Iri<'_>,
+		profile: Option<loader::Profile>,
+	) -> Result<RemoteDocument<J>, Error> {
+		let url = IriBuf::from(url);
+		let now = Instant::now();
+		let revalidated = {
+			let mut state = self.state.lock().unwrap();
+			match state.namespace.get(&url).cloned() {
+				Some(id) => {
+					let entry = &state.cache[id.unwrap()];
+					if entry.info.is_fresh(entry.fetched, now) {
+						let doc = entry.doc.clone();
+						state.metrics.hits += 1;
+						if let Some(observer) = &self.observer {
+							observer.on_cache_hit(url.as_iri())
+						}
+
+						return Ok(doc);
+					}
+
+					let revalidated = Some(entry.info.clone())
+						.filter(CacheInfo::is_revalidable)
+						.map(|info| (id, info));
+					if revalidated.is_some() {
+						state.metrics.revalidations += 1
+					}
+
+					revalidated
+				}
+				None => None,
+			}
+		};
+
+		if let Some(observer) = &self.observer {
+			observer.on_request(url.as_iri())
+		}
+
+		let result = fetch(
+			&self.backend,
+			url.as_iri(),
+			loader::Id::new(0),
+			profile,
+			&self.options,
+			revalidated.as_ref().map(|(_, info)| info),
+			&self.parser,
+		)
+		.await;
+
+		if let Some(observer) = &self.observer {
+			match &result {
+				Ok(_) => observer.on_response(url.as_iri(), now.elapsed()),
+				Err(e) => observer.on_error(url.as_iri(), e, now.elapsed()),
+			}
+		}
+
+		let fetched = result?;
+		let mut state = self.state.lock().unwrap();
+		match (fetched, revalidated) {
+			(Fetched::NotModified(info), Some((id, _))) => {
+				state.metrics.not_modified += 1;
+				let entry = &mut state.cache[id.unwrap()];
+				entry.info.max_age = info.max_age;
+				entry.info.no_cache = info.no_cache;
+				entry.info.no_store = info.no_store;
+				entry.fetched = now;
+				Ok(entry.doc.clone())
+			}
+			(Fetched::NotModified(_), None) => Err(ErrorCode::LoadingDocumentFailed.into()),
+			(Fetched::Document(doc, info), _) => {
+				let mut doc = *doc;
+				let id = match state.namespace.get(&url) {
+					Some(id) => *id,
+					None => loader::Id::new(state.cache.len()),
+				};
+
+				doc.source = id;
+				state.metrics.downloads += 1;
+				state.namespace.insert(url, id);
+				state.namespace.insert(doc.base_url.clone(), id);
+				let entry = CacheEntry {
+					doc: doc.clone(),
+					info,
+					fetched: now,
+				};
+
+				if id.unwrap() < state.cache.len() {
+					state.cache[id.unwrap()] = entry
+				} else {
+					state.cache.push(entry)
+				}
+
+				Ok(doc)
+			}
+		}
+	}
+}
+
+impl<B: Backend + Default, J: Clone + MaybeSend + FromStr> Default for Loader<B, J>
+where
+	J::Err: 'static + std::error::Error,
+{
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new(|s| J::from_str(s))
+	}
+}
+
+impl<B: Backend, J: Json + Clone + MaybeSend + MaybeSync> crate::Loader for Loader<B, J> {
+	type Document = J;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<loader::Id> {
+		self.state
+			.lock()
+			.unwrap()
+			.namespace
+			.get(&IriBuf::from(iri))
+			.cloned()
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: loader::Id) -> Option<IriBuf> {
+		self.state
+			.lock()
+			.unwrap()
+			.cache
+			.get(id.unwrap())
+			.map(|entry| entry.doc.base_url.clone())
+	}
+
+	#[inline(always)]
+	fn load<'a>(&'a self, url: Iri<'_>) -> future::BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
+		let url: IriBuf = url.into();
+		async move { Loader::load(self, url.as_iri()).await }.boxed()
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: loader::Profile,
+	) -> future::BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
+		let url: IriBuf = url.into();
+		async move { Loader::load_with_profile(self, url.as_iri(), Some(profile)).await }.boxed()
+	}
+}
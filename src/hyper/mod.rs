@@ -0,0 +1,111 @@
+//! Simple document and context loader based on [`hyper`](https://crates.io/crates/hyper)
+//!
+//! This module is enabled by the `hyper-loader` feature.
+//! The loading logic is provided by the [`http`](crate::http) module,
+//! this module only provides the [`Client`] backend sending the requests.
+//! Note that `hyper` requires the
+//! [`tokio`](https://crates.io/crates/tokio) runtime to work.
+
+use crate::http::{self, Backend, Headers, RequestError, RequestErrorKind, Response};
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::StreamExt;
+use hyper::body::HttpBody;
+use iref::Iri;
+use std::time::Duration;
+
+/// Connector used by the client, supporting HTTP and HTTPS.
+pub type Connector = hyper_tls::HttpsConnector<hyper::client::HttpConnector>;
+
+/// HTTP backend based on a `hyper` client.
+#[derive(Clone)]
+pub struct Client(hyper::Client<Connector>);
+
+impl Client {
+	/// Creates a new client.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self(hyper::Client::builder().build(Connector::new()))
+	}
+}
+
+impl Default for Client {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl From<hyper::Client<Connector>> for Client {
+	#[inline(always)]
+	fn from(client: hyper::Client<Connector>) -> Self {
+		Self(client)
+	}
+}
+
+/// Turns a `hyper` error into a request error.
+fn request_error(e: hyper::Error) -> RequestError {
+	let kind = if e.is_timeout() {
+		RequestErrorKind::Timeout
+	} else if e.is_connect() {
+		RequestErrorKind::Connect
+	} else {
+		RequestErrorKind::Other
+	};
+
+	RequestError::new(kind, e)
+}
+
+impl Backend for Client {
+	fn get<'a>(
+		&'a self,
+		url: Iri<'a>,
+		headers: &'a Headers,
+	) -> BoxFuture<'a, Result<Response, RequestError>> {
+		async move {
+			let mut request = hyper::Request::get(url.as_str());
+			for (name, value) in headers.iter() {
+				request = request.header(name, value)
+			}
+
+			let request = request
+				.body(hyper::Body::empty())
+				.map_err(|e| RequestError::new(RequestErrorKind::Other, e))?;
+			let response = self.0.request(request).await.map_err(request_error)?;
+			let (parts, body) = response.into_parts();
+			let headers = parts
+				.headers
+				.iter()
+				.filter_map(|(name, value)| {
+					Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+				})
+				.collect();
+
+			let body = futures::stream::unfold(Some(body), |body| async move {
+				let mut body = body?;
+				match body.data().await {
+					Some(Ok(chunk)) => Some((Ok(chunk.to_vec()), Some(body))),
+					Some(Err(e)) => Some((Err(request_error(e)), None)),
+					None => None,
+				}
+			})
+			.boxed();
+
+			Ok(Response {
+				status: parts.status.as_u16(),
+				headers,
+				body,
+			})
+		}
+		.boxed()
+	}
+
+	#[inline(always)]
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+		tokio::time::sleep(duration).boxed()
+	}
+}
+
+/// Remote document loader based on `hyper`.
+///
+/// See [`http::Loader`].
+pub type Loader<J> = http::Loader<Client, J>;
@@ -19,13 +19,24 @@ pub struct Indexed<T> {
 
 	/// Value.
 	value: T,
+
+	/// Annotation nodes (`@annotation` field).
+	///
+	/// Only available with the experimental `rdf-star` feature.
+	#[cfg(feature = "rdf-star")]
+	annotation: Vec<Indexed<T>>,
 }
 
 impl<T> Indexed<T> {
 	/// Create a new (maybe) indexed value.
 	#[inline(always)]
 	pub fn new(value: T, index: Option<String>) -> Indexed<T> {
-		Indexed { value, index }
+		Indexed {
+			value,
+			index,
+			#[cfg(feature = "rdf-star")]
+			annotation: Vec::new(),
+		}
 	}
 
 	/// Get a reference to the inner value.
@@ -55,6 +66,30 @@ impl<T> Indexed<T> {
 		self.index = index
 	}
 
+	/// Get the annotation nodes of the value (`@annotation` field).
+	///
+	/// Annotations are statements about the statement
+	/// relating this value to its subject, as defined by JSON-LD-star.
+	#[cfg(feature = "rdf-star")]
+	#[inline(always)]
+	pub fn annotation(&self) -> &[Indexed<T>] {
+		&self.annotation
+	}
+
+	/// Get a mutable reference to the annotation nodes of the value.
+	#[cfg(feature = "rdf-star")]
+	#[inline(always)]
+	pub fn annotation_mut(&mut self) -> &mut Vec<Indexed<T>> {
+		&mut self.annotation
+	}
+
+	/// Set the annotation nodes of the value.
+	#[cfg(feature = "rdf-star")]
+	#[inline(always)]
+	pub fn set_annotation(&mut self, annotation: Vec<Indexed<T>>) {
+		self.annotation = annotation
+	}
+
 	/// Turn this indexed value into its components: inner value and index.
 	#[inline(always)]
 	pub fn into_parts(self) -> (T, Option<String>) {
@@ -64,14 +99,30 @@ impl<T> Indexed<T> {
 	/// Cast the inner value.
 	#[inline(always)]
 	pub fn cast<U: From<T>>(self) -> Indexed<U> {
-		Indexed::new(self.value.into(), self.index)
+		Indexed {
+			value: self.value.into(),
+			index: self.index,
+			#[cfg(feature = "rdf-star")]
+			annotation: self.annotation.into_iter().map(Indexed::cast).collect(),
+		}
 	}
 
 	/// Try to cast the inner value.
+	///
+	/// Annotations that cannot be cast are dropped.
 	#[inline(always)]
 	pub fn try_cast<U: TryFrom<T>>(self) -> Result<Indexed<U>, Indexed<U::Error>> {
 		match self.value.try_into() {
-			Ok(value) => Ok(Indexed::new(value, self.index)),
+			Ok(value) => Ok(Indexed {
+				value,
+				index: self.index,
+				#[cfg(feature = "rdf-star")]
+				annotation: self
+					.annotation
+					.into_iter()
+					.filter_map(|a| a.try_cast().ok())
+					.collect(),
+			}),
 			Err(e) => Err(Indexed::new(e, self.index)),
 		}
 	}
@@ -81,13 +132,20 @@ impl<T: Hash> Hash for Indexed<T> {
 	#[inline(always)]
 	fn hash<H: Hasher>(&self, h: &mut H) {
 		self.value.hash(h);
-		self.index.hash(h)
+		self.index.hash(h);
+		#[cfg(feature = "rdf-star")]
+		self.annotation.hash(h)
 	}
 }
 
 impl<T: PartialEq> PartialEq for Indexed<T> {
 	#[inline(always)]
 	fn eq(&self, other: &Self) -> bool {
+		#[cfg(feature = "rdf-star")]
+		if self.annotation != other.annotation {
+			return false;
+		}
+
 		self.index == other.index && self.value == other.value
 	}
 }
@@ -97,7 +155,12 @@ impl<T: Eq> Eq for Indexed<T> {}
 impl<T: Clone> Clone for Indexed<T> {
 	#[inline(always)]
 	fn clone(&self) -> Self {
-		Indexed::new(self.value.clone(), self.index.clone())
+		Indexed {
+			value: self.value.clone(),
+			index: self.index.clone(),
+			#[cfg(feature = "rdf-star")]
+			annotation: self.annotation.clone(),
+		}
 	}
 }
 
@@ -149,6 +212,14 @@ impl<J: JsonClone, K: JsonFrom<J>, T: AsJson<J, K>> AsJson<J, K> for Indexed<T>
 					index.as_json_with(meta(None)),
 				);
 			}
+
+			#[cfg(feature = "rdf-star")]
+			if !self.annotation.is_empty() {
+				obj.insert(
+					K::new_key(crate::syntax::ANNOTATION, meta(None)),
+					self.annotation.as_json_with(meta.clone()),
+				);
+			}
 		}
 
 		json
@@ -0,0 +1,37 @@
+//! Optional instrumentation with the `tracing` crate.
+//!
+//! Spans and events are only emitted when the `tracing` feature is enabled.
+//! Otherwise the macros of this module expand to the instrumented code alone.
+
+/// Instruments the given future with a `debug` level span.
+#[cfg(feature = "tracing")]
+macro_rules! instrumented {
+	($future:expr, $($span:tt)+) => {{
+		let span = tracing::debug_span!($($span)+);
+		tracing::Instrument::instrument($future, span)
+	}};
+}
+
+/// Instruments the given future with a `debug` level span.
+#[cfg(not(feature = "tracing"))]
+macro_rules! instrumented {
+	($future:expr, $($span:tt)+) => {
+		$future
+	};
+}
+
+/// Emits a `trace` level event.
+#[cfg(feature = "tracing")]
+macro_rules! trace_event {
+	($($event:tt)+) => {
+		tracing::trace!($($event)+)
+	};
+}
+
+/// Emits a `trace` level event.
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event {
+	($($event:tt)+) => {
+		()
+	};
+}
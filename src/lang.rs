@@ -4,6 +4,89 @@ use generic_json::{Json, JsonBuild};
 use langtag::{LanguageTag, LanguageTagBuf};
 use std::fmt;
 
+/// Validation of the language tags found in documents and contexts.
+///
+/// By default language tags are lenient: a tag that is not well-formed according
+/// to section 2.2.9 of [BCP47](https://www.rfc-editor.org/rfc/rfc5646) is kept as is,
+/// and reported with a [`Warning::MalformedLanguageTag`](crate::Warning::MalformedLanguageTag).
+/// The strict modes also canonicalize the case of well-formed tags, as recommended by BCP47
+/// (e.g. `en-us` becomes `en-US`).
+///
+/// # Example
+/// ```
+/// # use json_ld::StrictLanguageTags;
+/// let tag = StrictLanguageTags::Warn.parse("EN-latn-us").unwrap();
+/// assert_eq!(tag.as_str(), "en-Latn-US");
+///
+/// let tag = StrictLanguageTags::Disabled.parse("EN-latn-us").unwrap();
+/// assert_eq!(tag.as_str(), "EN-latn-us");
+///
+/// assert!(StrictLanguageTags::Reject.parse("not a tag").is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum StrictLanguageTags {
+	/// Malformed tags are kept with a warning, and tags are not canonicalized.
+	#[default]
+	Disabled,
+
+	/// Malformed tags are kept with a warning, and well-formed tags are canonicalized.
+	Warn,
+
+	/// Malformed tags raise an error, and well-formed tags are canonicalized.
+	Reject,
+}
+
+impl StrictLanguageTags {
+	/// Checks if malformed language tags raise an error.
+	#[inline(always)]
+	pub fn rejects(&self) -> bool {
+		*self == Self::Reject
+	}
+
+	/// Parses the given language tag, canonicalizing its case in the strict modes.
+	pub fn parse(&self, tag: &str) -> Result<LanguageTagBuf, langtag::Error> {
+		match self {
+			Self::Disabled => LanguageTagBuf::parse_copy(tag),
+			Self::Warn | Self::Reject => LanguageTagBuf::parse_copy(&canonical_case(tag)),
+		}
+	}
+}
+
+/// Returns the given language tag with the case recommended by section 2.1.1 of BCP47.
+///
+/// Subtags are lowercase, except two-letter and four-letter subtags
+/// that are not the first subtag and do not follow a singleton,
+/// which are respectively uppercase and titlecase.
+fn canonical_case(tag: &str) -> String {
+	let mut result = String::with_capacity(tag.len());
+	let mut after_singleton = false;
+	for (i, subtag) in tag.split('-').enumerate() {
+		if i > 0 {
+			result.push('-')
+		}
+
+		let special = i > 0 && !after_singleton;
+		match subtag.len() {
+			2 if special => result.push_str(&subtag.to_ascii_uppercase()),
+			4 if special => {
+				let mut chars = subtag.chars();
+				if let Some(c) = chars.next() {
+					result.push(c.to_ascii_uppercase());
+				}
+				result.push_str(&chars.as_str().to_ascii_lowercase())
+			}
+			len => {
+				result.push_str(&subtag.to_ascii_lowercase());
+				if len == 1 {
+					after_singleton = true
+				}
+			}
+		}
+	}
+
+	result
+}
+
 /// Language tag buffer that may not be well-formed.
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum LenientLanguageTagBuf {
@@ -49,10 +49,10 @@
 //!   "#).unwrap();
 //!
 //!   // JSON document loader.
-//!   let mut loader = NoLoader::<Value>::new();
+//!   let loader = NoLoader::<Value>::new();
 //!
 //!   // Expansion.
-//!   let expanded_doc = doc.expand::<context::Json<Value>, _>(&mut loader).await?;
+//!   let expanded_doc = doc.expand::<context::Json<Value>, _>(&loader).await?;
 //!
 //!   // Reference to the `name` property.
 //!   let name_property = Reference::Id(IriBuf::new("http://xmlns.com/foaf/0.1/name").unwrap());
@@ -76,10 +76,21 @@
 //!     document expansion will not require external resources.
 //!   - `FsLoader` to load remote resources from the file system through a
 //!     mount point system.
+//!   - `StaticLoader` to load documents embedded in the binary. Copies of
+//!     the schema.org, ActivityStreams and W3C credentials contexts are
+//!     provided by the `schema-org-context`, `activitystreams-context` and
+//!     `credentials-context` features.
 //!   - `reqwest::Loader` provided by the `reqwest-loader` feature that uses the
 //!     [`reqwest`](https://crates.io/crates/reqwest) crate to load remote documents.
-//!   Note that `reqwest` requires the
-//!   [`tokio`](https://crates.io/crates/tokio) runtime to work.
+//!   - `hyper::Loader` provided by the `hyper-loader` feature that uses the
+//!     [`hyper`](https://crates.io/crates/hyper) crate to load remote documents.
+//!   - `surf::Loader` provided by the `surf-loader` feature that uses the
+//!     [`surf`](https://crates.io/crates/surf) crate to load remote documents.
+//!   Note that `reqwest` and `hyper` require the
+//!   [`tokio`](https://crates.io/crates/tokio) runtime to work,
+//!   whereas `surf` does not.
+//!   Other HTTP clients can be used with the `http::Loader` by implementing
+//!   the `http::Backend` trait.
 //!
 //! ### Compaction
 //!
@@ -109,13 +120,13 @@
 //!   "#).unwrap();
 //!
 //!   // JSON document loader.
-//!   let mut loader = NoLoader::<Value>::new();
+//!   let loader = NoLoader::<Value>::new();
 //!
 //!   // Process the context.
-//!   let processed_context = context.process::<context::Json<Value>, _>(&mut loader, None).await?;
+//!   let processed_context = context.process::<context::Json<Value>, _>(&loader, None).await?;
 //!
 //!   // Compact the input document.
-//!   let output = input.compact(&processed_context, &mut loader).await.unwrap();
+//!   let output = input.compact(&processed_context, &loader).await.unwrap();
 //!   println!("{}", serde_json::to_string_pretty(&output).unwrap());
 //!
 //!   Ok(())
@@ -124,7 +135,9 @@
 //!
 //! ### Flattening
 //!
-//! Flattening is not yet implemented, but will be in the future.
+//! The `Document` trait also provides a `Document::flatten` function to flatten a document.
+//! If a context is given, the flattened document is then compacted with it.
+//! The underlying node map generation algorithm is exposed by the `flattening` module.
 //!
 //! ## Custom identifiers
 //!
@@ -166,7 +179,29 @@
 //!
 //! ## RDF Serialization/Deserialization
 //!
-//! This is not directly handled by this crate.
+//! The `Document::to_rdf` function converts a document into RDF quads.
+//! Conversely, the `rdf::from_rdf` function builds an expanded document from RDF quads.
+//! The underlying algorithms and RDF data types are provided by the `rdf` module.
+//! Quads can be serialized into canonical N-Quads with the `rdf::nquads` module.
+//! N-Quads (and N-Triples) documents can be parsed with the same module
+//! when the `nquads-parser` feature is enabled.
+//!
+//! ## Instrumentation
+//!
+//! When the `tracing` feature is enabled, the processing algorithms are
+//! instrumented with [`tracing`](https://crates.io/crates/tracing) spans and events:
+//! a span for each processed remote context, loaded context, HTTP request,
+//! expanded document and compacted node,
+//! and an event for each key expanded.
+//!
+//! ## Single-threaded executors
+//!
+//! By default, the processing algorithms return `Send` futures and require
+//! the JSON values, contexts and loaders to be `Send + Sync`.
+//! When the `local-futures` feature is enabled, these bounds are relaxed
+//! and non-`Send` futures are returned instead, so that `Rc`-based JSON types
+//! can be used with single-threaded executors or in WASM.
+//! See the [`future`] module for more details.
 #![allow(clippy::derive_hash_xor_eq)]
 #![feature(generic_associated_types)]
 #![feature(trait_alias)]
@@ -174,13 +209,23 @@
 extern crate iref;
 extern crate log;
 
+#[macro_use]
+mod instrument;
+
 mod blank;
+mod coercion;
 pub mod compaction;
 pub mod context;
+pub mod dataset;
+pub mod diff;
 mod direction;
 mod document;
 mod error;
 pub mod expansion;
+mod extension;
+pub mod flattening;
+pub mod future;
+pub mod http;
 mod id;
 mod indexed;
 mod lang;
@@ -189,30 +234,54 @@ mod loc;
 mod mode;
 mod null;
 pub mod object;
+pub mod rdf;
 mod reference;
+pub mod skolemization;
+mod stats;
 pub mod syntax;
 pub mod util;
-mod vocab;
+pub mod vocab;
 mod warning;
 
+#[cfg(feature = "hyper-loader")]
+pub mod hyper;
 #[cfg(feature = "reqwest-loader")]
 pub mod reqwest;
+#[cfg(feature = "surf-loader")]
+pub mod surf;
+
+#[doc(hidden)]
+pub use iref::Iri as __Iri;
 
 pub use blank::*;
+pub use coercion::*;
 pub use compaction::Compact;
 pub use direction::*;
 pub use document::*;
 pub use error::*;
+pub use extension::*;
 pub use id::*;
 pub use indexed::*;
 pub use lang::*;
-pub use loader::{FsLoader, Loader, NoLoader};
+pub use loader::{
+	CachingLoader, CancellableLoader, CancellationToken, ChainLoader, DiskCachingLoader, DynLoader,
+	FixtureLoader, FixtureMode, FsLoader, LimitedLoader, Loader, MockLoader, NoLoader,
+	ObservedLoader, PolicyLoader, RecordingLoader, StaticLoader,
+};
+
+#[cfg(feature = "hyper-loader")]
+pub use self::hyper::Loader as HyperLoader;
+#[cfg(feature = "reqwest-loader")]
+pub use self::reqwest::Loader as ReqwestLoader;
+#[cfg(feature = "surf-loader")]
+pub use self::surf::Loader as SurfLoader;
 pub use loc::Loc;
 pub use mode::*;
 pub use null::*;
 pub use reference::*;
-pub use vocab::*;
+pub use stats::Stats;
+pub use vocab::{Lexicon, Vocab};
 pub use warning::*;
 
-pub use context::{Context, ContextMut, ContextMutProxy, JsonContext};
+pub use context::{Context, ContextBuilder, ContextMut, ContextMutProxy, JsonContext};
 pub use object::{Node, Nodes, Object, Objects, Value};
@@ -1,11 +1,18 @@
+use crate::future::{BoxFuture, FutureExt, MaybeSend, MaybeSync};
 use crate::{Error, ErrorCode, RemoteDocument};
-use futures::future::{BoxFuture, FutureExt};
+use futures::future;
 use generic_json::Json;
 use iref::{Iri, IriBuf};
-use std::collections::HashMap;
+use once_cell::sync::OnceCell;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{self, AtomicBool, AtomicUsize};
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::time::{Duration, Instant};
 use std::{marker::PhantomData, str::FromStr};
 
 /// Identifier reference.
@@ -28,10 +35,112 @@ impl From<Id> for usize {
 	}
 }
 
+/// JSON-LD profile.
+///
+/// Profile IRIs can be given as the `profile` parameter of the
+/// `application/ld+json` media type, to request or describe
+/// a specific form of JSON-LD document.
+///
+/// ```
+/// use json_ld::loader::Profile;
+/// let iri = Profile::Expanded.iri();
+/// assert_eq!(iri, "http://www.w3.org/ns/json-ld#expanded");
+/// assert_eq!(Profile::from_iri(iri), Some(Profile::Expanded));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Profile {
+	/// Expanded document form.
+	Expanded,
+
+	/// Compacted document form.
+	Compacted,
+
+	/// Context document.
+	Context,
+
+	/// Flattened document form.
+	Flattened,
+
+	/// Frame document.
+	Frame,
+
+	/// Framed document form.
+	Framed,
+}
+
+impl Profile {
+	/// All the profiles.
+	pub const ALL: [Self; 6] = [
+		Self::Expanded,
+		Self::Compacted,
+		Self::Context,
+		Self::Flattened,
+		Self::Frame,
+		Self::Framed,
+	];
+
+	/// Returns the IRI of the profile.
+	pub fn iri(&self) -> Iri<'static> {
+		let iri = match self {
+			Self::Expanded => "http://www.w3.org/ns/json-ld#expanded",
+			Self::Compacted => "http://www.w3.org/ns/json-ld#compacted",
+			Self::Context => "http://www.w3.org/ns/json-ld#context",
+			Self::Flattened => "http://www.w3.org/ns/json-ld#flattened",
+			Self::Frame => "http://www.w3.org/ns/json-ld#frame",
+			Self::Framed => "http://www.w3.org/ns/json-ld#framed",
+		};
+
+		Iri::new(iri).unwrap()
+	}
+
+	/// Returns the profile with the given IRI, if any.
+	pub fn from_iri(iri: Iri) -> Option<Self> {
+		match iri.as_str() {
+			"http://www.w3.org/ns/json-ld#expanded" => Some(Self::Expanded),
+			"http://www.w3.org/ns/json-ld#compacted" => Some(Self::Compacted),
+			"http://www.w3.org/ns/json-ld#context" => Some(Self::Context),
+			"http://www.w3.org/ns/json-ld#flattened" => Some(Self::Flattened),
+			"http://www.w3.org/ns/json-ld#frame" => Some(Self::Frame),
+			"http://www.w3.org/ns/json-ld#framed" => Some(Self::Framed),
+			_ => None,
+		}
+	}
+}
+
+/// Document parser function.
+pub(crate) type Parser<J> = Box<dyn 'static + Send + Sync + Fn(&str) -> Result<J, Error>>;
+
 /// JSON document loader.
 ///
 /// Each document is uniquely identified by the loader by a `u32`.
-pub trait Loader {
+///
+/// Loaders are borrowed immutably, so that a single loader can be shared
+/// by many concurrent processing tasks.
+/// Loaders keeping track of the loaded documents, such as caches,
+/// rely on interior mutability.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, Document, FsLoader, Loader};
+/// # use iref::Iri;
+/// # use serde_json::Value;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut loader = FsLoader::<Value>::default();
+/// loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+///
+/// let a = loader.load(Iri::new("https://example.org/tests/0120-in.jsonld").unwrap()).await.unwrap();
+/// let b = loader.load(Iri::new("https://example.org/tests/0122-in.jsonld").unwrap()).await.unwrap();
+///
+/// // Both documents are expanded concurrently with the same loader.
+/// let (a, b) = futures::join!(
+///   a.expand::<context::Json<Value>, _>(&loader),
+///   b.expand::<context::Json<Value>, _>(&loader)
+/// );
+/// assert!(a.is_ok() && b.is_ok());
+/// # }
+/// ```
+pub trait Loader: MaybeSend + MaybeSync {
 	/// The type of documents that can be loaded.
 	type Document: Json;
 
@@ -47,13 +156,255 @@ pub trait Loader {
 	}
 
 	/// Returns the IRI with the given identifier, if any.
-	fn iri(&self, id: Id) -> Option<Iri<'_>>;
+	fn iri(&self, id: Id) -> Option<IriBuf>;
 
 	/// Loads the document behind the given IRI.
 	fn load<'a>(
-		&'a mut self,
+		&'a self,
 		url: Iri<'_>,
 	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>>;
+
+	/// Loads the document behind the given IRI, requesting the given profile.
+	///
+	/// Loaders able to negotiate the representation of the document,
+	/// such as HTTP loaders, should ask for the given profile.
+	/// The profile actually returned is given by [`RemoteDocument::profile`].
+	/// By default, the profile is ignored and the document is loaded with [`Loader::load`].
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		_profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load(url)
+	}
+}
+
+impl<L: ?Sized + Loader> Loader for &L {
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		(**self).id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		(**self).iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		(**self).load(url)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		(**self).load_with_profile(url, profile)
+	}
+}
+
+impl<L: ?Sized + Loader> Loader for Box<L> {
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		(**self).id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		(**self).iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		(**self).load(url)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		(**self).load_with_profile(url, profile)
+	}
+}
+
+impl<L: ?Sized + Loader> Loader for Arc<L> {
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		(**self).id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		(**self).iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		(**self).load(url)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		(**self).load_with_profile(url, profile)
+	}
+}
+
+/// Type-erased loader.
+///
+/// The [`Loader`] trait is object safe, but its implementations all have different types.
+/// This wrapper erases the type of any loader of `J` documents,
+/// so that loaders can be stored, configured and swapped at runtime.
+///
+/// # Example
+/// ```
+/// # use json_ld::{DynLoader, FsLoader, Loader, NoLoader};
+/// # use iref::Iri;
+/// # use serde_json::Value;
+/// # #[async_std::main]
+/// # async fn main() {
+/// # let offline = false;
+/// let loader: DynLoader<Value> = if offline {
+///   DynLoader::new(NoLoader::new())
+/// } else {
+///   let mut fs_loader = FsLoader::default();
+///   fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+///   DynLoader::new(fs_loader)
+/// };
+///
+/// let url = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+/// loader.load(url).await.unwrap();
+/// # }
+/// ```
+pub struct DynLoader<J: Json>(Box<dyn 'static + Loader<Document = J>>);
+
+impl<J: Json> DynLoader<J> {
+	/// Erases the type of the given loader.
+	#[inline(always)]
+	pub fn new<L: 'static + Loader<Document = J>>(loader: L) -> Self {
+		Self(Box::new(loader))
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &(dyn 'static + Loader<Document = J>) {
+		&*self.0
+	}
+
+	/// Consumes the wrapper and returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> Box<dyn 'static + Loader<Document = J>> {
+		self.0
+	}
+}
+
+impl<J: Json> From<Box<dyn 'static + Loader<Document = J>>> for DynLoader<J> {
+	#[inline(always)]
+	fn from(loader: Box<dyn 'static + Loader<Document = J>>) -> Self {
+		Self(loader)
+	}
+}
+
+impl<J: Json> Loader for DynLoader<J> {
+	type Document = J;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.0.id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.0.iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
+		self.0.load(url)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
+		self.0.load_with_profile(url, profile)
+	}
+}
+
+/// Identifiers allocated to the IRIs of the loaded documents.
+#[derive(Default)]
+struct Namespace {
+	ids: HashMap<IriBuf, Id>,
+	iris: Vec<IriBuf>,
+}
+
+impl Namespace {
+	/// Returns the identifier of the given IRI, if any.
+	#[inline(always)]
+	fn id(&self, iri: Iri) -> Option<Id> {
+		self.ids.get(&IriBuf::from(iri)).cloned()
+	}
+
+	/// Returns the IRI with the given identifier, if any.
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.iris.get(id.unwrap()).cloned()
+	}
+
+	/// Returns the identifier of the given IRI, allocating one if necessary.
+	fn allocate(&mut self, iri: IriBuf) -> Id {
+		match self.ids.get(&iri) {
+			Some(id) => *id,
+			None => {
+				let id = Id::new(self.iris.len());
+				self.ids.insert(iri.clone(), id);
+				self.iris.push(iri);
+				id
+			}
+		}
+	}
+
+	/// Associates the given IRI to the given identifier,
+	/// for instance when the document has been loaded through a redirection.
+	#[inline(always)]
+	fn alias(&mut self, iri: IriBuf, id: Id) {
+		self.ids.insert(iri, id);
+	}
+
+	/// Registers the given document loaded from the given IRI.
+	///
+	/// Allocates an identifier for the base URL of the document,
+	/// aliases the requested IRI to it, and sets it as the source of the document.
+	fn register<J>(&mut self, url: IriBuf, doc: &mut RemoteDocument<J>) {
+		let id = self.allocate(doc.base_url.clone());
+		self.alias(url, id);
+		doc.source = id;
+	}
 }
 
 /// Dummy loader.
@@ -78,7 +429,7 @@ impl<J> Default for NoLoader<J> {
 	}
 }
 
-impl<J: Json> Loader for NoLoader<J> {
+impl<J: Json + MaybeSend + MaybeSync> Loader for NoLoader<J> {
 	type Document = J;
 
 	#[inline(always)]
@@ -87,13 +438,13 @@ impl<J: Json> Loader for NoLoader<J> {
 	}
 
 	#[inline(always)]
-	fn iri(&self, _id: Id) -> Option<Iri<'_>> {
+	fn iri(&self, _id: Id) -> Option<IriBuf> {
 		None
 	}
 
 	#[inline(always)]
 	fn load<'a>(
-		&'a mut self,
+		&'a self,
 		_url: Iri<'_>,
 	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
 		async move { Err(ErrorCode::LoadingDocumentFailed.into()) }.boxed()
@@ -104,38 +455,83 @@ impl<J: Json> Loader for NoLoader<J> {
 ///
 /// This is a special JSON-LD document loader that can load document from the file system by
 /// attaching a directory to specific URLs.
+///
+/// Each mount point maps an IRI prefix to a directory.
+/// The document behind an IRI starting with this prefix is loaded from the file
+/// whose path, relative to the directory, is the rest of the IRI.
+/// If multiple mount points match, the longest prefix is tried first.
+///
+/// # Example
+/// ```
+/// # use json_ld::{FsLoader, Loader};
+/// # use iref::Iri;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut loader = FsLoader::<serde_json::Value>::default();
+/// loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+///
+/// let doc = loader
+///   .load(Iri::new("https://example.org/tests/0120-in.jsonld").unwrap())
+///   .await
+///   .unwrap();
+///
+/// let (_, source, base_url) = doc.into_parts();
+/// assert_eq!(base_url, Iri::new("https://example.org/tests/0120-in.jsonld").unwrap());
+/// assert_eq!(loader.iri(source), Some(base_url));
+/// # }
+/// ```
 pub struct FsLoader<J> {
-	namespace: HashMap<IriBuf, Id>,
-	cache: Vec<(J, IriBuf)>,
-	mount_points: HashMap<PathBuf, IriBuf>,
-	parser: Box<dyn 'static + Send + Sync + FnMut(&str) -> Result<J, Error>>,
+	cache: Mutex<FsCache<J>>,
+	mount_points: Vec<(IriBuf, PathBuf)>,
+	parser: Parser<J>,
+}
+
+/// Documents loaded by a [`FsLoader`].
+struct FsCache<J> {
+	namespace: Namespace,
+	documents: Vec<J>,
 }
 
 impl<J> FsLoader<J> {
+	/// Creates a new loader using the given function to parse the loaded documents.
 	pub fn new<E: 'static + std::error::Error>(
-		mut parser: impl 'static + Send + Sync + FnMut(&str) -> Result<J, E>,
+		parser: impl 'static + Send + Sync + Fn(&str) -> Result<J, E>,
 	) -> Self {
 		Self {
-			namespace: HashMap::new(),
-			cache: Vec::new(),
-			mount_points: HashMap::new(),
+			cache: Mutex::new(FsCache {
+				namespace: Namespace::default(),
+				documents: Vec::new(),
+			}),
+			mount_points: Vec::new(),
 			parser: Box::new(move |s| {
 				parser(s).map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
 			}),
 		}
 	}
 
-	#[inline(always)]
+	/// Mounts the given directory on the given IRI prefix.
+	///
+	/// If the prefix is already mounted, the previous directory is replaced.
 	pub fn mount<P: AsRef<Path>>(&mut self, url: Iri, path: P) {
-		self.mount_points.insert(path.as_ref().into(), url.into());
+		let url: IriBuf = url.into();
+		self.mount_points.retain(|(prefix, _)| *prefix != url);
+		self.mount_points.push((url, path.as_ref().into()));
+		self.mount_points
+			.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.as_str().len()))
 	}
 
-	/// Allocate a identifier to the given IRI.
-	fn allocate(&mut self, iri: IriBuf, doc: J) -> Id {
-		let id = Id::new(self.cache.len());
-		self.namespace.insert(iri.clone(), id);
-		self.cache.push((doc, iri));
-		id
+	/// Returns the path of the file associated to the given IRI by each matching mount
+	/// point, longest prefix first.
+	fn filepaths<'a>(&'a self, url: &'a IriBuf) -> impl 'a + Iterator<Item = PathBuf> {
+		self.mount_points.iter().filter_map(move |(prefix, path)| {
+			let (suffix, _, _) = url.as_iri_ref().suffix(prefix.as_iri_ref())?;
+			let mut filepath = path.clone();
+			for seg in suffix.as_path().segments() {
+				filepath.push(seg.as_str())
+			}
+
+			Some(filepath)
+		})
 	}
 }
 
@@ -149,57 +545,2422 @@ where
 	}
 }
 
-impl<J: Json + Clone + Send> Loader for FsLoader<J> {
+impl<J: Json + Clone + MaybeSend + MaybeSync> Loader for FsLoader<J> {
 	type Document = J;
 
 	#[inline(always)]
 	fn id(&self, iri: Iri<'_>) -> Option<Id> {
-		self.namespace.get(&IriBuf::from(iri)).cloned()
+		self.cache.lock().unwrap().namespace.id(iri)
 	}
 
 	#[inline(always)]
-	fn iri(&self, id: Id) -> Option<Iri<'_>> {
-		self.cache.get(id.unwrap()).map(|(_, iri)| iri.as_iri())
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.cache.lock().unwrap().namespace.iri(id)
 	}
 
-	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
+	fn load<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
 		let url: IriBuf = url.into();
 		async move {
-			match self.namespace.get(&url) {
-				Some(id) => Ok(RemoteDocument::new(
-					self.cache[id.unwrap()].0.clone(),
-					url,
-					*id,
-				)),
-				None => {
-					for (path, target_url) in &self.mount_points {
-						let url_ref = url.as_iri_ref();
-						if let Some((suffix, _, _)) = url_ref.suffix(target_url.as_iri_ref()) {
-							let mut filepath = path.clone();
-							for seg in suffix.as_path().segments() {
-								filepath.push(seg.as_str())
-							}
-
-							if let Ok(file) = File::open(filepath) {
-								let mut buf_reader = BufReader::new(file);
-								let mut contents = String::new();
-								if buf_reader.read_to_string(&mut contents).is_ok() {
-									let doc = (*self.parser)(contents.as_str())?;
-									let id = self.allocate(url.clone(), doc.clone());
-									return Ok(RemoteDocument::new(doc, url, id));
-								} else {
-									return Err(ErrorCode::LoadingDocumentFailed.into());
-								}
-							} else {
-								return Err(ErrorCode::LoadingDocumentFailed.into());
-							}
-						}
+			{
+				let cache = self.cache.lock().unwrap();
+				if let Some(id) = cache.namespace.id(url.as_iri()) {
+					let doc = cache.documents[id.unwrap()].clone();
+					return Ok(RemoteDocument::new(doc, url, id));
+				}
+			}
+
+			let contents = self.filepaths(&url).find_map(|filepath| {
+				let mut contents = String::new();
+				let mut buf_reader = BufReader::new(File::open(filepath).ok()?);
+				buf_reader.read_to_string(&mut contents).ok()?;
+				Some(contents)
+			});
+
+			match contents {
+				Some(contents) => {
+					let doc = (*self.parser)(contents.as_str())?;
+					let mut cache = self.cache.lock().unwrap();
+					let id = cache.namespace.allocate(url.clone());
+					if id.unwrap() < cache.documents.len() {
+						cache.documents[id.unwrap()] = doc.clone()
+					} else {
+						cache.documents.push(doc.clone())
 					}
 
-					Err(ErrorCode::LoadingDocumentFailed.into())
+					Ok(RemoteDocument::new(doc, url, id))
 				}
+				None => Err(ErrorCode::LoadingDocumentFailed.into()),
 			}
 		}
 		.boxed()
 	}
 }
+
+/// Caching loader.
+///
+/// Loader wrapper memoizing the documents successfully loaded by the inner loader,
+/// indexed by IRI and requested [`Profile`].
+/// Cached documents can expire after a given time-to-live,
+/// and the number of cached documents can be limited,
+/// in which case the least recently used documents are evicted first.
+///
+/// # Example
+/// ```
+/// # use json_ld::{loader::Profile, CachingLoader, FsLoader, Loader};
+/// # use iref::Iri;
+/// # use std::time::Duration;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut fs_loader = FsLoader::<serde_json::Value>::default();
+/// fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+///
+/// let loader = CachingLoader::new(fs_loader)
+///   .with_ttl(Duration::from_secs(3600))
+///   .with_max_entries(64);
+///
+/// let url = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+/// loader.load(url).await.unwrap();
+/// assert!(loader.is_cached(url));
+///
+/// // Documents loaded with a profile are cached separately.
+/// loader.load_with_profile(url, Profile::Context).await.unwrap();
+/// assert_eq!(loader.len(), 2);
+///
+/// loader.invalidate(url);
+/// assert!(!loader.is_cached(url));
+/// assert!(loader.is_empty());
+/// # }
+/// ```
+pub struct CachingLoader<L: Loader> {
+	inner: L,
+	cache: Mutex<HashMap<CacheKey, CacheEntry<L::Document>>>,
+	ttl: Option<Duration>,
+	max_entries: Option<usize>,
+	observer: Option<Arc<dyn Observer>>,
+}
+
+/// Cached document IRI and requested profile.
+type CacheKey = (IriBuf, Option<Profile>);
+
+struct CacheEntry<J> {
+	doc: RemoteDocument<J>,
+	loaded_at: Instant,
+	last_access: Instant,
+}
+
+impl<L: Loader> CachingLoader<L> {
+	/// Creates a new caching loader wrapping the given loader.
+	///
+	/// By default, cached documents never expire and the cache is unbounded.
+	#[inline(always)]
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			cache: Mutex::new(HashMap::new()),
+			ttl: None,
+			max_entries: None,
+			observer: None,
+		}
+	}
+
+	/// Sets the time-to-live of cached documents.
+	///
+	/// Documents cached for longer are loaded again.
+	#[inline(always)]
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = Some(ttl);
+		self
+	}
+
+	/// Sets the maximum number of cached documents.
+	///
+	/// When the cache is full, the least recently used document is evicted.
+	#[inline(always)]
+	pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+		self.max_entries = Some(max_entries);
+		self
+	}
+
+	/// Sets the observer notified when a document is served from the cache.
+	///
+	/// See [`Observer::on_cache_hit`].
+	#[inline(always)]
+	pub fn with_observer(mut self, observer: impl 'static + Observer) -> Self {
+		self.observer = Some(Arc::new(observer));
+		self
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the inner loader.
+	#[inline(always)]
+	pub fn inner_mut(&mut self) -> &mut L {
+		&mut self.inner
+	}
+
+	/// Returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Returns the number of cached documents, including expired ones.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.cache.lock().unwrap().len()
+	}
+
+	/// Checks if the cache is empty.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.cache.lock().unwrap().is_empty()
+	}
+
+	/// Checks if the document behind the given IRI, loaded without profile,
+	/// is cached and has not expired.
+	#[inline(always)]
+	pub fn is_cached(&self, url: Iri) -> bool {
+		match self.cache.lock().unwrap().get(&(IriBuf::from(url), None)) {
+			Some(entry) => !self.is_expired(entry, Instant::now()),
+			None => false,
+		}
+	}
+
+	/// Removes the document behind the given IRI from the cache,
+	/// whatever profile it was loaded with.
+	///
+	/// Returns `true` if the document was cached.
+	#[inline(always)]
+	pub fn invalidate(&self, url: Iri) -> bool {
+		let mut cache = self.cache.lock().unwrap();
+		let len = cache.len();
+		cache.retain(|(cached_url, _), _| cached_url.as_iri() != url);
+		cache.len() < len
+	}
+
+	/// Removes every document from the cache.
+	#[inline(always)]
+	pub fn clear(&self) {
+		self.cache.lock().unwrap().clear()
+	}
+
+	fn is_expired(&self, entry: &CacheEntry<L::Document>, now: Instant) -> bool {
+		match self.ttl {
+			Some(ttl) => now.duration_since(entry.loaded_at) >= ttl,
+			None => false,
+		}
+	}
+
+	/// Returns the cached document behind the given key, if it has not expired.
+	fn get(&self, key: &CacheKey, now: Instant) -> Option<RemoteDocument<L::Document>>
+	where
+		L::Document: Clone,
+	{
+		let mut cache = self.cache.lock().unwrap();
+		let entry = cache.get_mut(key)?;
+		if self.is_expired(entry, now) {
+			None
+		} else {
+			entry.last_access = now;
+			Some(entry.doc.clone())
+		}
+	}
+
+	/// Adds the given document to the cache,
+	/// evicting expired and least recently used documents if necessary.
+	fn insert(&self, key: CacheKey, doc: RemoteDocument<L::Document>, now: Instant) {
+		let mut cache = self.cache.lock().unwrap();
+		if let Some(max_entries) = self.max_entries {
+			if max_entries == 0 {
+				return;
+			}
+
+			if cache.len() >= max_entries {
+				let ttl = self.ttl;
+				cache.retain(|_, entry| match ttl {
+					Some(ttl) => now.duration_since(entry.loaded_at) < ttl,
+					None => true,
+				});
+			}
+
+			while cache.len() >= max_entries {
+				let lru = cache
+					.iter()
+					.min_by_key(|(_, entry)| entry.last_access)
+					.map(|(key, _)| key.clone())
+					.unwrap();
+				cache.remove(&lru);
+			}
+		}
+
+		cache.insert(
+			key,
+			CacheEntry {
+				doc,
+				loaded_at: now,
+				last_access: now,
+			},
+		);
+	}
+}
+
+impl<L: Loader> Loader for CachingLoader<L>
+where
+	L::Document: Clone + MaybeSend,
+{
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.inner.id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.inner.iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_cached(url, None)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_cached(url, Some(profile))
+	}
+}
+
+impl<L: Loader> CachingLoader<L>
+where
+	L::Document: Clone + MaybeSend,
+{
+	/// Loads the given document from the cache, or with the inner loader,
+	/// requesting the given profile.
+	fn load_cached<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Option<Profile>,
+	) -> BoxFuture<'a, Result<RemoteDocument<L::Document>, Error>> {
+		let key: CacheKey = (url.into(), profile);
+		async move {
+			let now = Instant::now();
+			if let Some(doc) = self.get(&key, now) {
+				if let Some(observer) = &self.observer {
+					observer.on_cache_hit(key.0.as_iri())
+				}
+
+				return Ok(doc);
+			}
+
+			let doc = match profile {
+				Some(profile) => {
+					self.inner
+						.load_with_profile(key.0.as_iri(), profile)
+						.await?
+				}
+				None => self.inner.load(key.0.as_iri()).await?,
+			};
+
+			self.insert(key, doc.clone(), now);
+			Ok(doc)
+		}
+		.boxed()
+	}
+}
+
+/// Chain loader.
+///
+/// Tries to load documents with the first loader,
+/// then with the second loader if the first one fails.
+/// More than two loaders can be chained using [`ChainLoader::chain`],
+/// for instance to try a static cache, then the file system, then the network.
+///
+/// Since each loader allocates its own document identifiers,
+/// the chain loader allocates new identifiers for the loaded documents.
+///
+/// # Example
+/// ```
+/// # use json_ld::{ChainLoader, FsLoader, Loader, NoLoader};
+/// # use iref::Iri;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut fs_loader = FsLoader::<serde_json::Value>::default();
+/// fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+///
+/// let loader = ChainLoader::new(NoLoader::new(), fs_loader);
+/// let url = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+/// let doc = loader.load(url).await.unwrap();
+/// assert_eq!(loader.iri(doc.source()).unwrap(), url);
+/// # }
+/// ```
+pub struct ChainLoader<A, B> {
+	first: A,
+	second: B,
+	namespace: Mutex<Namespace>,
+}
+
+impl<A, B> ChainLoader<A, B> {
+	/// Creates a new chain loader trying `first`, then `second`.
+	#[inline(always)]
+	pub fn new(first: A, second: B) -> Self {
+		Self {
+			first,
+			second,
+			namespace: Mutex::new(Namespace::default()),
+		}
+	}
+
+	/// Appends a loader at the end of the chain.
+	#[inline(always)]
+	pub fn chain<C>(self, next: C) -> ChainLoader<Self, C> {
+		ChainLoader::new(self, next)
+	}
+
+	/// Returns the inner loaders.
+	#[inline(always)]
+	pub fn into_inner(self) -> (A, B) {
+		(self.first, self.second)
+	}
+}
+
+impl<A: Loader, B: Loader<Document = A::Document>> Loader for ChainLoader<A, B>
+where
+	A::Document: MaybeSend,
+{
+	type Document = A::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.namespace.lock().unwrap().id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.namespace.lock().unwrap().iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_chained(url, None)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_chained(url, Some(profile))
+	}
+}
+
+impl<A: Loader, B: Loader<Document = A::Document>> ChainLoader<A, B>
+where
+	A::Document: MaybeSend,
+{
+	/// Loads the given document with the first loader, then with the second loader,
+	/// requesting the given profile.
+	fn load_chained<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Option<Profile>,
+	) -> BoxFuture<'a, Result<RemoteDocument<A::Document>, Error>> {
+		let url: IriBuf = url.into();
+		async move {
+			let first = match profile {
+				Some(profile) => self.first.load_with_profile(url.as_iri(), profile),
+				None => self.first.load(url.as_iri()),
+			};
+
+			let mut doc = match first.await.ok() {
+				Some(doc) => doc,
+				None => match profile {
+					Some(profile) => self.second.load_with_profile(url.as_iri(), profile).await?,
+					None => self.second.load(url.as_iri()).await?,
+				},
+			};
+
+			self.namespace.lock().unwrap().register(url, &mut doc);
+			Ok(doc)
+		}
+		.boxed()
+	}
+}
+
+/// Static loader.
+///
+/// Loads documents from a fixed set of preloaded sources,
+/// typically embedded in the binary with `include_str!`
+/// or [`include_context!`](crate::include_context),
+/// so that common documents and contexts can be loaded without network access.
+/// Sources are parsed on first load.
+///
+/// # Example
+/// ```
+/// # use json_ld::{Loader, StaticLoader};
+/// # use iref::Iri;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut loader = StaticLoader::<serde_json::Value>::default();
+/// loader.insert(
+///   Iri::new("https://example.org/context.jsonld").unwrap(),
+///   r#"{ "@context": { "name": "https://schema.org/name" } }"#
+/// );
+///
+/// let url = Iri::new("https://example.org/context.jsonld").unwrap();
+/// let doc = loader.load(url).await.unwrap();
+/// assert_eq!(loader.iri(doc.source()).unwrap(), url);
+/// # }
+/// ```
+pub struct StaticLoader<J> {
+	namespace: HashMap<IriBuf, Id>,
+	sources: Vec<(IriBuf, &'static str, OnceCell<J>)>,
+	parser: Parser<J>,
+}
+
+impl<J> StaticLoader<J> {
+	/// Creates a new empty loader using the given function to parse the sources.
+	pub fn new<E: 'static + std::error::Error>(
+		parser: impl 'static + Send + Sync + Fn(&str) -> Result<J, E>,
+	) -> Self {
+		Self {
+			namespace: HashMap::new(),
+			sources: Vec::new(),
+			parser: Box::new(move |s| {
+				parser(s).map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
+			}),
+		}
+	}
+
+	/// Associates the given source to the given IRI.
+	///
+	/// If a source is already associated to this IRI, it is replaced.
+	pub fn insert(&mut self, url: Iri, source: &'static str) {
+		let url: IriBuf = url.into();
+		match self.namespace.get(&url) {
+			Some(id) => self.sources[id.unwrap()] = (url, source, OnceCell::new()),
+			None => {
+				let id = Id::new(self.sources.len());
+				self.namespace.insert(url.clone(), id);
+				self.sources.push((url, source, OnceCell::new()))
+			}
+		}
+	}
+
+	/// Associates the given source to the given IRI, and returns the loader.
+	///
+	/// If a source is already associated to this IRI, it is replaced.
+	#[inline(always)]
+	pub fn with(mut self, url: Iri, source: &'static str) -> Self {
+		self.insert(url, source);
+		self
+	}
+
+	/// Checks if a source is associated to the given IRI.
+	#[inline(always)]
+	pub fn contains(&self, url: Iri) -> bool {
+		self.namespace.contains_key(&IriBuf::from(url))
+	}
+
+	/// Registers the embedded copies of well-known contexts enabled by cargo features.
+	///
+	/// | Feature                   | Context IRIs                                                  |
+	/// |---------------------------|---------------------------------------------------------------|
+	/// | `schema-org-context`      | `https://schema.org/`, `http://schema.org/` (with or without trailing slash) and `https://schema.org/docs/jsonldcontext.jsonld` |
+	/// | `activitystreams-context` | `https://www.w3.org/ns/activitystreams` (also over `http`)    |
+	/// | `credentials-context`     | `https://www.w3.org/2018/credentials/v1`                      |
+	///
+	/// Without any of these features, this function does nothing.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::StaticLoader;
+	/// # use iref::Iri;
+	/// let loader = StaticLoader::<serde_json::Value>::default().with_well_known_contexts();
+	///
+	/// let url = Iri::new("https://www.w3.org/ns/activitystreams").unwrap();
+	/// assert_eq!(loader.contains(url), cfg!(feature = "activitystreams-context"));
+	/// ```
+	pub fn insert_well_known_contexts(&mut self) {
+		#[cfg(feature = "schema-org-context")]
+		for iri in &[
+			"https://schema.org/",
+			"https://schema.org",
+			"http://schema.org/",
+			"http://schema.org",
+			"https://schema.org/docs/jsonldcontext.jsonld",
+		] {
+			crate::include_context!(self, iri, "../contexts/schema.org.jsonld");
+		}
+
+		#[cfg(feature = "activitystreams-context")]
+		for iri in &[
+			"https://www.w3.org/ns/activitystreams",
+			"http://www.w3.org/ns/activitystreams",
+		] {
+			crate::include_context!(self, iri, "../contexts/activitystreams.jsonld");
+		}
+
+		#[cfg(feature = "credentials-context")]
+		crate::include_context!(
+			self,
+			"https://www.w3.org/2018/credentials/v1",
+			"../contexts/credentials-v1.jsonld"
+		);
+	}
+
+	/// Registers the embedded copies of well-known contexts enabled by cargo features,
+	/// and returns the loader.
+	///
+	/// See [`insert_well_known_contexts`](Self::insert_well_known_contexts).
+	#[inline(always)]
+	pub fn with_well_known_contexts(mut self) -> Self {
+		self.insert_well_known_contexts();
+		self
+	}
+}
+
+/// Embeds a context file in the binary and registers it in a [`StaticLoader`].
+///
+/// `include_context!(loader, iri, path)` includes the content of the file at `path`
+/// at compile time using [`include_str!`] (the path is relative to the current file),
+/// and associates it to the given canonical `iri` in `loader`.
+/// This way, contexts can be loaded without any file system or network access.
+///
+/// # Panics
+///
+/// Panics if `iri` is not a valid IRI.
+///
+/// # Example
+/// ```
+/// # use json_ld::{include_context, Loader, StaticLoader};
+/// # use iref::Iri;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut loader = StaticLoader::<serde_json::Value>::default();
+/// include_context!(
+///   loader,
+///   "https://example.org/tests/0120-in.jsonld",
+///   concat!(env!("CARGO_MANIFEST_DIR"), "/tests/custom/0120-in.jsonld")
+/// );
+///
+/// let url = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+/// assert!(loader.contains(url));
+/// loader.load(url).await.unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! include_context {
+	($loader:expr, $iri:expr, $path:expr $(,)?) => {
+		$loader.insert(
+			$crate::__Iri::new($iri).expect("invalid context IRI"),
+			include_str!($path),
+		)
+	};
+}
+
+impl<J: FromStr> Default for StaticLoader<J>
+where
+	J::Err: 'static + std::error::Error,
+{
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new(|s| J::from_str(s))
+	}
+}
+
+impl<J: Json + Clone + MaybeSend + MaybeSync> Loader for StaticLoader<J> {
+	type Document = J;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.namespace.get(&IriBuf::from(iri)).cloned()
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.sources.get(id.unwrap()).map(|(iri, _, _)| iri.clone())
+	}
+
+	fn load<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
+		let url: IriBuf = url.into();
+		async move {
+			match self.namespace.get(&url) {
+				Some(id) => {
+					let (_, source, doc) = &self.sources[id.unwrap()];
+					let doc = doc.get_or_try_init(|| (*self.parser)(source))?.clone();
+					Ok(RemoteDocument::new(doc, url, *id))
+				}
+				None => Err(ErrorCode::LoadingDocumentFailed.into()),
+			}
+		}
+		.boxed()
+	}
+}
+
+/// Limited loader.
+///
+/// Loader wrapper limiting the number of documents loaded by the inner loader,
+/// for instance to bound the number of remote contexts fetched while
+/// processing an untrusted document.
+/// Once the limit is reached, every load fails with a `LoadLimitExceeded` error,
+/// until the counter is [reset](LimitedLoader::reset).
+/// Every call to the inner loader is counted, even if it fails.
+///
+/// # Example
+/// ```
+/// # use json_ld::{ErrorCode, FsLoader, LimitedLoader, Loader};
+/// # use iref::Iri;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut fs_loader = FsLoader::<serde_json::Value>::default();
+/// fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+///
+/// let loader = LimitedLoader::new(fs_loader, 1);
+/// let url = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+/// loader.load(url).await.unwrap();
+///
+/// let error = loader.load(url).await.err().unwrap();
+/// assert_eq!(error.code(), ErrorCode::LoadLimitExceeded);
+///
+/// loader.reset();
+/// loader.load(url).await.unwrap();
+/// # }
+/// ```
+pub struct LimitedLoader<L> {
+	inner: L,
+	max_loads: usize,
+	loads: AtomicUsize,
+}
+
+impl<L> LimitedLoader<L> {
+	/// Creates a new loader allowing at most `max_loads` loads with the given inner loader.
+	#[inline(always)]
+	pub fn new(inner: L, max_loads: usize) -> Self {
+		Self {
+			inner,
+			max_loads,
+			loads: AtomicUsize::new(0),
+		}
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the inner loader.
+	#[inline(always)]
+	pub fn inner_mut(&mut self) -> &mut L {
+		&mut self.inner
+	}
+
+	/// Consumes the limited loader and returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Maximum number of loads.
+	#[inline(always)]
+	pub fn max_loads(&self) -> usize {
+		self.max_loads
+	}
+
+	/// Number of loads performed since the creation of the loader, or the last reset.
+	#[inline(always)]
+	pub fn loads(&self) -> usize {
+		self.loads.load(atomic::Ordering::SeqCst)
+	}
+
+	/// Resets the load counter, typically before processing a new document.
+	#[inline(always)]
+	pub fn reset(&self) {
+		self.loads.store(0, atomic::Ordering::SeqCst)
+	}
+
+	/// Counts a new load, unless the limit is reached.
+	///
+	/// Returns `false` if the limit is reached.
+	fn count(&self) -> bool {
+		let max_loads = self.max_loads;
+		self.loads
+			.fetch_update(
+				atomic::Ordering::SeqCst,
+				atomic::Ordering::SeqCst,
+				|loads| {
+					if loads < max_loads {
+						Some(loads + 1)
+					} else {
+						None
+					}
+				},
+			)
+			.is_ok()
+	}
+}
+
+impl<L: Loader> Loader for LimitedLoader<L> {
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.inner.id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.inner.iri(id)
+	}
+
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		if self.count() {
+			self.inner.load(url)
+		} else {
+			async move { Err(ErrorCode::LoadLimitExceeded.into()) }.boxed()
+		}
+	}
+
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		if self.count() {
+			self.inner.load_with_profile(url, profile)
+		} else {
+			async move { Err(ErrorCode::LoadLimitExceeded.into()) }.boxed()
+		}
+	}
+}
+
+/// Cancellation token.
+///
+/// Shared handle used to cancel the operations performed with a [`CancellableLoader`].
+/// Cloning the token gives another handle to the same cancellation state.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+#[derive(Default)]
+struct CancellationState {
+	cancelled: AtomicBool,
+
+	/// Wakers of the pending loads, by registration id.
+	wakers: Mutex<HashMap<usize, Waker>>,
+
+	/// Next registration id.
+	next_id: AtomicUsize,
+}
+
+impl CancellationState {
+	/// Wakes up every pending load.
+	fn wake_all(&self) {
+		for (_, waker) in self.wakers.lock().unwrap().drain() {
+			waker.wake()
+		}
+	}
+}
+
+impl CancellationToken {
+	/// Creates a new token.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Cancels every operation using this token.
+	///
+	/// Pending loads are woken up and fail with a `Cancelled` error.
+	pub fn cancel(&self) {
+		self.0.cancelled.store(true, atomic::Ordering::SeqCst);
+		self.0.wake_all()
+	}
+
+	/// Checks if the token has been cancelled.
+	#[inline(always)]
+	pub fn is_cancelled(&self) -> bool {
+		self.0.cancelled.load(atomic::Ordering::SeqCst)
+	}
+
+	/// Creates a new registration for a pending load.
+	fn registration(&self) -> Registration {
+		Registration {
+			state: self.0.clone(),
+			id: self.0.next_id.fetch_add(1, atomic::Ordering::SeqCst),
+		}
+	}
+}
+
+/// Registration of a pending load to a [`CancellationToken`].
+///
+/// The waker of the load is unregistered when the registration is dropped.
+struct Registration {
+	state: Arc<CancellationState>,
+	id: usize,
+}
+
+impl Registration {
+	/// Registers the given waker, to be woken up when the token is cancelled.
+	fn register(&self, waker: &Waker) {
+		let mut wakers = self.state.wakers.lock().unwrap();
+		match wakers.get_mut(&self.id) {
+			Some(w) if w.will_wake(waker) => (),
+			Some(w) => *w = waker.clone(),
+			None => {
+				wakers.insert(self.id, waker.clone());
+			}
+		}
+	}
+
+	/// Unregisters the waker of the load.
+	fn unregister(&self) {
+		self.state.wakers.lock().unwrap().remove(&self.id);
+	}
+}
+
+impl Drop for Registration {
+	fn drop(&mut self) {
+		self.unregister()
+	}
+}
+
+/// Timer waking up the pending loads of a [`CancellableLoader`] once its deadline is reached.
+///
+/// The timer runs on its own thread, so that it does not depend on any async runtime.
+/// It is stopped when the loader is dropped.
+struct DeadlineTimer {
+	thread: std::thread::Thread,
+	stopped: Arc<AtomicBool>,
+}
+
+impl DeadlineTimer {
+	fn start(state: Arc<CancellationState>, deadline: Instant) -> Self {
+		let stopped = Arc::new(AtomicBool::new(false));
+		let thread_stopped = stopped.clone();
+		let handle = std::thread::spawn(move || {
+			while !thread_stopped.load(atomic::Ordering::SeqCst) {
+				let now = Instant::now();
+				if now >= deadline {
+					state.wake_all();
+					break;
+				}
+
+				std::thread::park_timeout(deadline - now)
+			}
+		});
+
+		Self {
+			thread: handle.thread().clone(),
+			stopped,
+		}
+	}
+}
+
+impl Drop for DeadlineTimer {
+	fn drop(&mut self) {
+		self.stopped.store(true, atomic::Ordering::SeqCst);
+		self.thread.unpark()
+	}
+}
+
+/// Cancellable loader.
+///
+/// Loader wrapper that aborts the loads of the inner loader once a
+/// [`CancellationToken`] is cancelled or a deadline is reached.
+/// Aborted loads fail with a `Cancelled` error, which is propagated as is
+/// by the expansion, compaction and context processing algorithms.
+///
+/// Cancellation is cooperative: it is checked before each load,
+/// and pending loads are interrupted when the token is cancelled
+/// or when the deadline is reached.
+/// Processing that performs no load is not interrupted.
+///
+/// The deadline is watched by a timer thread started by the first load,
+/// so that pending loads are interrupted whatever the async runtime.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, CancellableLoader, CancellationToken, Document, ErrorCode, StaticLoader};
+/// # use iref::Iri;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let loader = StaticLoader::<Value>::default().with(
+///   Iri::new("https://example.org/context.jsonld").unwrap(),
+///   r#"{ "@context": { "name": "https://schema.org/name" } }"#
+/// );
+///
+/// let token = CancellationToken::new();
+/// let loader = CancellableLoader::new(loader).with_token(token.clone());
+/// let doc = json!({ "@context": "https://example.org/context.jsonld", "name": "Alice" });
+/// let context = context::Json::<Value>::new(None);
+/// assert!(doc.expand_with(None, &context, &loader, Default::default()).await.is_ok());
+///
+/// token.cancel();
+/// let error = doc.expand_with(None, &context, &loader, Default::default()).await.err().unwrap();
+/// assert_eq!(error.code(), ErrorCode::Cancelled);
+/// # }
+/// ```
+pub struct CancellableLoader<L> {
+	inner: L,
+	token: CancellationToken,
+	deadline: Option<Instant>,
+	timer: OnceCell<DeadlineTimer>,
+}
+
+impl<L> CancellableLoader<L> {
+	/// Creates a new cancellable loader with a fresh token and no deadline.
+	#[inline(always)]
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			token: CancellationToken::new(),
+			deadline: None,
+			timer: OnceCell::new(),
+		}
+	}
+
+	/// Uses the given cancellation token.
+	#[inline(always)]
+	pub fn with_token(mut self, token: CancellationToken) -> Self {
+		self.token = token;
+		self
+	}
+
+	/// Sets the deadline after which every load fails.
+	#[inline(always)]
+	pub fn with_deadline(mut self, deadline: Instant) -> Self {
+		self.deadline = Some(deadline);
+		self
+	}
+
+	/// Sets the deadline to the given duration from now.
+	#[inline(always)]
+	pub fn with_timeout(self, timeout: Duration) -> Self {
+		self.with_deadline(Instant::now() + timeout)
+	}
+
+	/// Returns the cancellation token.
+	#[inline(always)]
+	pub fn token(&self) -> &CancellationToken {
+		&self.token
+	}
+
+	/// Returns the deadline, if any.
+	#[inline(always)]
+	pub fn deadline(&self) -> Option<Instant> {
+		self.deadline
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the inner loader.
+	#[inline(always)]
+	pub fn inner_mut(&mut self) -> &mut L {
+		&mut self.inner
+	}
+
+	/// Consumes the cancellable loader and returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Checks if the loads must be aborted.
+	fn is_aborted(&self) -> bool {
+		self.token.is_cancelled()
+			|| self
+				.deadline
+				.map(|deadline| Instant::now() >= deadline)
+				.unwrap_or(false)
+	}
+
+	/// Aborts the given load once the token is cancelled or the deadline reached.
+	fn guard<'a, T: 'a>(
+		&'a self,
+		load: BoxFuture<'a, Result<T, Error>>,
+	) -> BoxFuture<'a, Result<T, Error>>
+	where
+		L: MaybeSync,
+	{
+		if self.is_aborted() {
+			return async move { Err(ErrorCode::Cancelled.into()) }.boxed();
+		}
+
+		if let Some(deadline) = self.deadline {
+			self.timer
+				.get_or_init(|| DeadlineTimer::start(self.token.0.clone(), deadline));
+		}
+
+		let mut load = load;
+		let registration = self.token.registration();
+		future::poll_fn(move |cx| {
+			if self.is_aborted() {
+				registration.unregister();
+				return Poll::Ready(Err(ErrorCode::Cancelled.into()));
+			}
+
+			registration.register(cx.waker());
+			let result = futures::FutureExt::poll_unpin(&mut load, cx);
+			if result.is_ready() {
+				registration.unregister()
+			}
+
+			result
+		})
+		.boxed()
+	}
+}
+
+impl<L: Loader> Loader for CancellableLoader<L> {
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.inner.id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.inner.iri(id)
+	}
+
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.guard(self.inner.load(url))
+	}
+
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.guard(self.inner.load_with_profile(url, profile))
+	}
+}
+
+/// Loader observer.
+///
+/// Receives events about the documents loaded by an [`ObservedLoader`],
+/// and about the cache of loaders keeping the loaded documents,
+/// such as the [`CachingLoader`] or the HTTP loaders,
+/// so that applications can export metrics about remote documents usage.
+/// Every method does nothing by default.
+///
+/// Observers are shared by reference counting:
+/// the same observer can be given to many loaders.
+pub trait Observer: MaybeSend + MaybeSync {
+	/// Called when the document behind the given IRI is requested.
+	#[inline(always)]
+	fn on_request(&self, _url: Iri) {}
+
+	/// Called when the document behind the given IRI has been loaded,
+	/// with the time it took to load it.
+	#[inline(always)]
+	fn on_response(&self, _url: Iri, _duration: Duration) {}
+
+	/// Called when the document behind the given IRI is served from a cache.
+	#[inline(always)]
+	fn on_cache_hit(&self, _url: Iri) {}
+
+	/// Called when the document behind the given IRI could not be loaded,
+	/// with the time it took to fail.
+	#[inline(always)]
+	fn on_error(&self, _url: Iri, _error: &Error, _duration: Duration) {}
+}
+
+impl<O: ?Sized + Observer> Observer for Arc<O> {
+	#[inline(always)]
+	fn on_request(&self, url: Iri) {
+		(**self).on_request(url)
+	}
+
+	#[inline(always)]
+	fn on_response(&self, url: Iri, duration: Duration) {
+		(**self).on_response(url, duration)
+	}
+
+	#[inline(always)]
+	fn on_cache_hit(&self, url: Iri) {
+		(**self).on_cache_hit(url)
+	}
+
+	#[inline(always)]
+	fn on_error(&self, url: Iri, error: &Error, duration: Duration) {
+		(**self).on_error(url, error, duration)
+	}
+}
+
+/// Observed loader.
+///
+/// Loader wrapper reporting every load of the inner loader to an [`Observer`]:
+/// [`Observer::on_request`] is called before each load,
+/// followed by either [`Observer::on_response`] or [`Observer::on_error`].
+///
+/// # Example
+/// ```
+/// # use json_ld::{loader::Observer, FsLoader, Loader, ObservedLoader};
+/// # use iref::Iri;
+/// # use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+/// # use std::time::Duration;
+/// # #[async_std::main]
+/// # async fn main() {
+/// #[derive(Default)]
+/// struct Counter {
+///   responses: AtomicUsize,
+///   errors: AtomicUsize,
+/// }
+///
+/// impl Observer for Counter {
+///   fn on_response(&self, _url: Iri, _duration: Duration) {
+///     self.responses.fetch_add(1, Ordering::SeqCst);
+///   }
+///
+///   fn on_error(&self, _url: Iri, _error: &json_ld::Error, _duration: Duration) {
+///     self.errors.fetch_add(1, Ordering::SeqCst);
+///   }
+/// }
+///
+/// let mut fs_loader = FsLoader::<serde_json::Value>::default();
+/// fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+///
+/// let counter = Arc::new(Counter::default());
+/// let loader = ObservedLoader::new(fs_loader, counter.clone());
+/// loader.load(Iri::new("https://example.org/tests/0120-in.jsonld").unwrap()).await.unwrap();
+/// assert!(loader.load(Iri::new("https://example.org/missing.jsonld").unwrap()).await.is_err());
+///
+/// assert_eq!(counter.responses.load(Ordering::SeqCst), 1);
+/// assert_eq!(counter.errors.load(Ordering::SeqCst), 1);
+/// # }
+/// ```
+pub struct ObservedLoader<L, O> {
+	inner: L,
+	observer: O,
+}
+
+impl<L, O> ObservedLoader<L, O> {
+	/// Creates a new loader reporting the loads of the given inner loader to the given observer.
+	#[inline(always)]
+	pub fn new(inner: L, observer: O) -> Self {
+		Self { inner, observer }
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the inner loader.
+	#[inline(always)]
+	pub fn inner_mut(&mut self) -> &mut L {
+		&mut self.inner
+	}
+
+	/// Returns the observer.
+	#[inline(always)]
+	pub fn observer(&self) -> &O {
+		&self.observer
+	}
+
+	/// Consumes the observed loader and returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+}
+
+impl<L: Loader, O: Observer> ObservedLoader<L, O> {
+	/// Loads the given document with the inner loader,
+	/// requesting the given profile, and reports the result.
+	fn load_observed<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Option<Profile>,
+	) -> BoxFuture<'a, Result<RemoteDocument<L::Document>, Error>> {
+		let url: IriBuf = url.into();
+		async move {
+			self.observer.on_request(url.as_iri());
+			let start = Instant::now();
+			let result = match profile {
+				Some(profile) => self.inner.load_with_profile(url.as_iri(), profile).await,
+				None => self.inner.load(url.as_iri()).await,
+			};
+
+			match &result {
+				Ok(_) => self.observer.on_response(url.as_iri(), start.elapsed()),
+				Err(e) => self.observer.on_error(url.as_iri(), e, start.elapsed()),
+			}
+
+			result
+		}
+		.boxed()
+	}
+}
+
+impl<L: Loader, O: Observer> Loader for ObservedLoader<L, O> {
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.inner.id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.inner.iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_observed(url, None)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_observed(url, Some(profile))
+	}
+}
+
+/// Origin of an IRI.
+///
+/// The origin of an IRI is given by its scheme, host and port.
+/// The scheme and host are compared in lower case,
+/// and the default port of the `http` and `https` schemes is omitted.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Origin {
+	scheme: String,
+	host: String,
+	port: Option<String>,
+}
+
+impl Origin {
+	/// Returns the origin of the given IRI, if it has an authority.
+	pub fn new(iri: Iri) -> Option<Self> {
+		let authority = iri.authority()?;
+		let scheme = iri.scheme().as_str().to_ascii_lowercase();
+		let port = authority
+			.port()
+			.map(|port| port.as_str().to_string())
+			.filter(|port| {
+				!port.is_empty()
+					&& !matches!(
+						(scheme.as_str(), port.as_str()),
+						("http", "80") | ("https", "443")
+					)
+			});
+
+		Some(Self {
+			host: authority.host().as_str().to_ascii_lowercase(),
+			scheme,
+			port,
+		})
+	}
+
+	/// Scheme of the origin, in lower case.
+	#[inline(always)]
+	pub fn scheme(&self) -> &str {
+		&self.scheme
+	}
+
+	/// Host of the origin, in lower case.
+	#[inline(always)]
+	pub fn host(&self) -> &str {
+		&self.host
+	}
+
+	/// Explicit port of the origin, if any.
+	#[inline(always)]
+	pub fn port(&self) -> Option<&str> {
+		self.port.as_deref()
+	}
+}
+
+/// Policy loader.
+///
+/// Loader wrapper restricting the origins documents can be loaded from,
+/// using either an allow-list or a deny-list of origins (see [`Origin`]).
+/// Loading a document from a forbidden origin fails with a `ForbiddenOrigin` error,
+/// which is preserved when loading remote contexts.
+/// The final URL of the loaded document, which differs from the requested IRI
+/// when the inner loader follows redirections or `alternate` links,
+/// must also come from an allowed origin.
+/// IRIs without authority never match the list.
+///
+/// # Example
+/// ```
+/// # use json_ld::{ErrorCode, FsLoader, Loader, PolicyLoader};
+/// # use iref::Iri;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut fs_loader = FsLoader::<serde_json::Value>::default();
+/// fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+/// fs_loader.mount(Iri::new("https://example.com/tests/").unwrap(), "tests/custom");
+///
+/// let loader = PolicyLoader::allow_list(fs_loader)
+///   .with_origin(Iri::new("https://example.org/").unwrap());
+///
+/// let allowed = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+/// loader.load(allowed).await.unwrap();
+///
+/// let forbidden = Iri::new("https://example.com/tests/0120-in.jsonld").unwrap();
+/// let error = loader.load(forbidden).await.err().unwrap();
+/// assert_eq!(error.code(), ErrorCode::ForbiddenOrigin);
+/// # }
+/// ```
+pub struct PolicyLoader<L> {
+	inner: L,
+	deny: bool,
+	origins: HashSet<Origin>,
+}
+
+impl<L> PolicyLoader<L> {
+	/// Creates a new loader only allowing the origins added to the list.
+	#[inline(always)]
+	pub fn allow_list(inner: L) -> Self {
+		Self {
+			inner,
+			deny: false,
+			origins: HashSet::new(),
+		}
+	}
+
+	/// Creates a new loader allowing every origin except the ones added to the list.
+	#[inline(always)]
+	pub fn deny_list(inner: L) -> Self {
+		Self {
+			inner,
+			deny: true,
+			origins: HashSet::new(),
+		}
+	}
+
+	/// Adds the origin of the given IRI to the list.
+	#[inline(always)]
+	pub fn with_origin(mut self, iri: Iri) -> Self {
+		self.insert_origin(iri);
+		self
+	}
+
+	/// Adds the origin of the given IRI to the list.
+	///
+	/// Returns `false` if the IRI has no authority, in which case it is ignored.
+	pub fn insert_origin(&mut self, iri: Iri) -> bool {
+		match Origin::new(iri) {
+			Some(origin) => {
+				self.origins.insert(origin);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Removes the origin of the given IRI from the list.
+	pub fn remove_origin(&mut self, iri: Iri) -> bool {
+		match Origin::new(iri) {
+			Some(origin) => self.origins.remove(&origin),
+			None => false,
+		}
+	}
+
+	/// Checks if documents can be loaded from the given IRI.
+	pub fn is_allowed(&self, iri: Iri) -> bool {
+		let listed = match Origin::new(iri) {
+			Some(origin) => self.origins.contains(&origin),
+			None => false,
+		};
+
+		listed != self.deny
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the inner loader.
+	#[inline(always)]
+	pub fn inner_mut(&mut self) -> &mut L {
+		&mut self.inner
+	}
+
+	/// Consumes the policy loader and returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Checks that the given loaded document comes from an allowed origin.
+	fn check<J>(&self, doc: RemoteDocument<J>) -> Result<RemoteDocument<J>, Error> {
+		if self.is_allowed(doc.base_url.as_iri()) {
+			Ok(doc)
+		} else {
+			Err(ErrorCode::ForbiddenOrigin.into())
+		}
+	}
+}
+
+impl<L: Loader> Loader for PolicyLoader<L> {
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.inner.id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.inner.iri(id)
+	}
+
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		if self.is_allowed(url) {
+			let load = self.inner.load(url);
+			async move { self.check(load.await?) }.boxed()
+		} else {
+			async move { Err(ErrorCode::ForbiddenOrigin.into()) }.boxed()
+		}
+	}
+
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		if self.is_allowed(url) {
+			let load = self.inner.load_with_profile(url, profile);
+			async move { self.check(load.await?) }.boxed()
+		} else {
+			async move { Err(ErrorCode::ForbiddenOrigin.into()) }.boxed()
+		}
+	}
+}
+
+/// Recording loader.
+///
+/// Offline loader that does not load anything, like [`NoLoader`],
+/// but records every IRI it has been asked to load.
+/// This can be used to find out which documents and contexts
+/// a set of documents depends on, for instance to pre-cache them.
+///
+/// Raises a `LoadingDocumentFailed` error at every attempt to load a resource.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, Document, RecordingLoader};
+/// # use iref::Iri;
+/// # use serde_json::Value;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let doc: Value = serde_json::from_str(r#"{
+///   "@context": "https://example.com/context.jsonld",
+///   "name": "Alice"
+/// }"#).unwrap();
+///
+/// let loader = RecordingLoader::<Value>::new();
+/// assert!(doc.expand::<context::Json<Value>, _>(&loader).await.is_err());
+/// assert_eq!(loader.requested(), [Iri::new("https://example.com/context.jsonld").unwrap()]);
+/// # }
+/// ```
+pub struct RecordingLoader<J> {
+	requested: Mutex<Vec<IriBuf>>,
+	document: PhantomData<J>,
+}
+
+impl<J> RecordingLoader<J> {
+	/// Creates a new recording loader.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			requested: Mutex::new(Vec::new()),
+			document: PhantomData,
+		}
+	}
+
+	/// Returns the requested IRIs, in the order of their first request.
+	#[inline(always)]
+	pub fn requested(&self) -> Vec<IriBuf> {
+		self.requested.lock().unwrap().clone()
+	}
+
+	/// Checks if the given IRI has been requested.
+	#[inline(always)]
+	pub fn is_requested(&self, iri: Iri) -> bool {
+		self.requested
+			.lock()
+			.unwrap()
+			.iter()
+			.any(|requested| *requested == iri)
+	}
+
+	/// Forgets the requested IRIs.
+	#[inline(always)]
+	pub fn clear(&self) {
+		self.requested.lock().unwrap().clear()
+	}
+
+	/// Consumes the loader and returns the requested IRIs,
+	/// in the order of their first request.
+	#[inline(always)]
+	pub fn into_requested(self) -> Vec<IriBuf> {
+		self.requested.into_inner().unwrap()
+	}
+}
+
+impl<J> Default for RecordingLoader<J> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<J: Json + MaybeSend + MaybeSync> Loader for RecordingLoader<J> {
+	type Document = J;
+
+	#[inline(always)]
+	fn id(&self, _iri: Iri<'_>) -> Option<Id> {
+		None
+	}
+
+	#[inline(always)]
+	fn iri(&self, _id: Id) -> Option<IriBuf> {
+		None
+	}
+
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		let mut requested = self.requested.lock().unwrap();
+		if !requested.iter().any(|requested| *requested == url) {
+			requested.push(url.into())
+		}
+
+		async move { Err(ErrorCode::LoadingDocumentFailed.into()) }.boxed()
+	}
+}
+
+/// Mock loader.
+///
+/// Test-oriented loader serving documents, or errors,
+/// registered for specific IRIs with a builder API.
+/// Every request is recorded, so that tests can check which documents have been loaded,
+/// and that no document has been requested without being registered first.
+/// Loading an unregistered IRI raises a `LoadingDocumentFailed` error.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, Document, ErrorCode, Loader, MockLoader};
+/// # use iref::Iri;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let context = Iri::new("https://example.org/context.jsonld").unwrap();
+/// let unreachable = Iri::new("https://example.org/unreachable.jsonld").unwrap();
+///
+/// let loader = MockLoader::new()
+///   .with(context, json!({ "@context": { "name": "https://schema.org/name" } }))
+///   .with_error(unreachable, ErrorCode::LoadingDocumentTimeout);
+///
+/// let doc = json!({ "@context": "https://example.org/context.jsonld", "name": "Alice" });
+/// doc.expand::<context::Json<Value>, _>(&loader).await.unwrap();
+/// loader.assert_requested(context);
+/// loader.assert_no_unexpected_requests();
+///
+/// let error = loader.load(unreachable).await.err().unwrap();
+/// assert_eq!(error.code(), ErrorCode::LoadingDocumentTimeout);
+/// # }
+/// ```
+pub struct MockLoader<J> {
+	namespace: HashMap<IriBuf, Id>,
+	responses: Vec<(IriBuf, Result<J, ErrorCode>)>,
+	requested: Mutex<Vec<IriBuf>>,
+}
+
+impl<J> MockLoader<J> {
+	/// Creates a new mock loader, without any registered document.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			namespace: HashMap::new(),
+			responses: Vec::new(),
+			requested: Mutex::new(Vec::new()),
+		}
+	}
+
+	/// Associates the given response to the given IRI.
+	///
+	/// If a response is already associated to this IRI, it is replaced.
+	fn insert(&mut self, url: Iri, response: Result<J, ErrorCode>) {
+		let url: IriBuf = url.into();
+		match self.namespace.get(&url) {
+			Some(id) => self.responses[id.unwrap()] = (url, response),
+			None => {
+				let id = Id::new(self.responses.len());
+				self.namespace.insert(url.clone(), id);
+				self.responses.push((url, response))
+			}
+		}
+	}
+
+	/// Serves the given document for the given IRI.
+	///
+	/// If a document or an error is already registered for this IRI, it is replaced.
+	#[inline(always)]
+	pub fn with(mut self, url: Iri, doc: J) -> Self {
+		self.insert(url, Ok(doc));
+		self
+	}
+
+	/// Raises an error with the given code when loading the given IRI.
+	///
+	/// If a document or an error is already registered for this IRI, it is replaced.
+	#[inline(always)]
+	pub fn with_error(mut self, url: Iri, code: ErrorCode) -> Self {
+		self.insert(url, Err(code));
+		self
+	}
+
+	/// Checks if a document or an error is registered for the given IRI.
+	#[inline(always)]
+	pub fn contains(&self, url: Iri) -> bool {
+		self.namespace.contains_key(&IriBuf::from(url))
+	}
+
+	/// Returns every requested IRI, in the order of the requests.
+	///
+	/// IRIs requested multiple times appear multiple times.
+	#[inline(always)]
+	pub fn requested(&self) -> Vec<IriBuf> {
+		self.requested.lock().unwrap().clone()
+	}
+
+	/// Returns the number of times the given IRI has been requested.
+	#[inline(always)]
+	pub fn request_count(&self, url: Iri) -> usize {
+		self.requested
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|requested| **requested == url)
+			.count()
+	}
+
+	/// Returns the requested IRIs for which no document nor error is registered,
+	/// in the order of their first request.
+	pub fn unexpected(&self) -> Vec<IriBuf> {
+		let mut unexpected: Vec<IriBuf> = Vec::new();
+		for url in self.requested.lock().unwrap().iter() {
+			if !self.namespace.contains_key(url) && !unexpected.iter().any(|u| u == url) {
+				unexpected.push(url.clone())
+			}
+		}
+
+		unexpected
+	}
+
+	/// Forgets the requested IRIs.
+	#[inline(always)]
+	pub fn clear(&self) {
+		self.requested.lock().unwrap().clear()
+	}
+
+	/// Asserts that the given IRI has been requested.
+	///
+	/// # Panics
+	///
+	/// Panics if the given IRI has never been requested.
+	pub fn assert_requested(&self, url: Iri) {
+		if self.request_count(url) == 0 {
+			panic!("`{}' has not been requested", url)
+		}
+	}
+
+	/// Asserts that the given IRI has not been requested.
+	///
+	/// # Panics
+	///
+	/// Panics if the given IRI has been requested.
+	pub fn assert_not_requested(&self, url: Iri) {
+		let count = self.request_count(url);
+		if count > 0 {
+			panic!("`{}' has been requested {} time(s)", url, count)
+		}
+	}
+
+	/// Asserts that only registered IRIs have been requested.
+	///
+	/// # Panics
+	///
+	/// Panics if an IRI for which no document nor error is registered has been requested,
+	/// listing every such IRI.
+	pub fn assert_no_unexpected_requests(&self) {
+		let unexpected = self.unexpected();
+		if !unexpected.is_empty() {
+			let list: Vec<String> = unexpected.iter().map(IriBuf::to_string).collect();
+			panic!("unexpected requests: {}", list.join(", "))
+		}
+	}
+}
+
+impl<J> Default for MockLoader<J> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<J: Json + Clone + MaybeSend + MaybeSync> Loader for MockLoader<J> {
+	type Document = J;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.namespace.get(&IriBuf::from(iri)).cloned()
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.responses.get(id.unwrap()).map(|(iri, _)| iri.clone())
+	}
+
+	fn load<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
+		let url: IriBuf = url.into();
+		self.requested.lock().unwrap().push(url.clone());
+		async move {
+			match self.namespace.get(&url) {
+				Some(id) => match &self.responses[id.unwrap()].1 {
+					Ok(doc) => Ok(RemoteDocument::new(doc.clone(), url, *id)),
+					Err(code) => Err((*code).into()),
+				},
+				None => Err(ErrorCode::LoadingDocumentFailed.into()),
+			}
+		}
+		.boxed()
+	}
+}
+
+/// Mode of a [`FixtureLoader`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FixtureMode {
+	/// Load documents with the inner loader, and save them as fixtures.
+	Record,
+
+	/// Only load documents from the saved fixtures.
+	Replay,
+}
+
+/// Fixture loader.
+///
+/// Loader wrapper recording the documents loaded by the inner loader
+/// into a directory of fixtures, and replaying them.
+/// In [`FixtureMode::Record`] mode, documents are loaded with the inner loader
+/// and saved into the directory, along with their metadata.
+/// In [`FixtureMode::Replay`] mode, the inner loader is never used,
+/// and only documents saved in the directory can be loaded.
+/// This makes tests depending on remote documents deterministic and offline.
+///
+/// Each fixture is a JSON file named after the SHA-256 hash of the IRI of the document
+/// and of the requested profile, if any (see [`FixtureLoader::fixture_path`]),
+/// and storing this IRI.
+///
+/// # Example
+/// ```
+/// # use json_ld::{FixtureLoader, FixtureMode, FsLoader, Loader};
+/// # use iref::Iri;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut fs_loader = FsLoader::<serde_json::Value>::default();
+/// fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+/// let directory = std::env::temp_dir().join("json-ld-fixtures");
+/// let url = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+///
+/// let recorder = FixtureLoader::new(fs_loader, &directory, FixtureMode::Record);
+/// let recorded = recorder.load(url).await.unwrap();
+///
+/// let replayer = FixtureLoader::new(recorder.into_inner(), &directory, FixtureMode::Replay);
+/// let replayed = replayer.load(url).await.unwrap();
+/// assert_eq!(*replayed, *recorded);
+/// # }
+/// ```
+pub struct FixtureLoader<L: Loader> {
+	inner: L,
+	directory: PathBuf,
+	mode: FixtureMode,
+	parser: Parser<L::Document>,
+	namespace: Mutex<Namespace>,
+}
+
+impl<L: Loader> FixtureLoader<L> {
+	/// Creates a new fixture loader on the given directory,
+	/// parsing the fixtures with [`FromStr`].
+	pub fn new<P: Into<PathBuf>>(inner: L, directory: P, mode: FixtureMode) -> Self
+	where
+		L::Document: 'static + FromStr,
+		<L::Document as FromStr>::Err: 'static + std::error::Error,
+	{
+		Self::with_parser(inner, directory, mode, L::Document::from_str)
+	}
+
+	/// Creates a new fixture loader on the given directory,
+	/// using the given function to parse the fixtures.
+	pub fn with_parser<P: Into<PathBuf>, E: 'static + std::error::Error>(
+		inner: L,
+		directory: P,
+		mode: FixtureMode,
+		parser: impl 'static + Send + Sync + Fn(&str) -> Result<L::Document, E>,
+	) -> Self {
+		Self {
+			inner,
+			directory: directory.into(),
+			mode,
+			parser: Box::new(move |s| {
+				parser(s).map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
+			}),
+			namespace: Mutex::new(Namespace::default()),
+		}
+	}
+
+	/// Mode of the loader.
+	#[inline(always)]
+	pub fn mode(&self) -> FixtureMode {
+		self.mode
+	}
+
+	/// Directory of the fixtures.
+	#[inline(always)]
+	pub fn directory(&self) -> &Path {
+		&self.directory
+	}
+
+	/// Returns the path of the fixture of the given IRI.
+	///
+	/// The fixture file is named after the hexadecimal SHA-256 hash of the IRI,
+	/// so that its name length does not depend on the length of the IRI.
+	///
+	/// ```
+	/// # use json_ld::{FixtureLoader, FixtureMode, NoLoader};
+	/// # use iref::IriBuf;
+	/// let loader = FixtureLoader::new(NoLoader::<serde_json::Value>::new(), "fixtures", FixtureMode::Replay);
+	/// let iri = IriBuf::from_string(format!("https://example.org/{}", "a".repeat(1024))).unwrap();
+	/// let path = loader.fixture_path(iri.as_iri());
+	/// assert_eq!(path.file_name().unwrap().len(), 64 + ".json".len());
+	/// ```
+	pub fn fixture_path(&self, iri: Iri) -> PathBuf {
+		document_file_path(&self.directory, iri, None)
+	}
+
+	/// Returns the path of the fixture of the given IRI,
+	/// loaded with the given requested profile.
+	pub fn fixture_path_with_profile(&self, iri: Iri, profile: Profile) -> PathBuf {
+		document_file_path(&self.directory, iri, Some(profile))
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the inner loader.
+	#[inline(always)]
+	pub fn inner_mut(&mut self) -> &mut L {
+		&mut self.inner
+	}
+
+	/// Consumes the fixture loader and returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Loads the given document, requesting the given profile.
+	fn load_fixture<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Option<Profile>,
+	) -> BoxFuture<'a, Result<RemoteDocument<L::Document>, Error>>
+	where
+		L::Document: MaybeSend,
+	{
+		let url: IriBuf = url.into();
+		async move {
+			let mut doc = match self.mode {
+				FixtureMode::Record => {
+					let doc = match profile {
+						Some(profile) => {
+							self.inner.load_with_profile(url.as_iri(), profile).await?
+						}
+						None => self.inner.load(url.as_iri()).await?,
+					};
+
+					write_document_file(&self.directory, url.as_iri(), profile, &doc, None)?;
+					doc
+				}
+				FixtureMode::Replay => {
+					read_document_file(&self.directory, url.as_iri(), profile, &self.parser)?
+						.into_remote_document(Id::new(0))
+				}
+			};
+
+			self.namespace.lock().unwrap().register(url, &mut doc);
+			Ok(doc)
+		}
+		.boxed()
+	}
+}
+
+/// Returns the path of the file storing the document of the given IRI,
+/// loaded with the given requested profile, in the given directory.
+///
+/// The file is named after the hexadecimal SHA-256 hash of the IRI,
+/// followed by the IRI of the profile, if any, separated by a space
+/// (which cannot appear in an IRI).
+/// The IRI itself is stored in the file.
+fn document_file_path(directory: &Path, iri: Iri, profile: Option<Profile>) -> PathBuf {
+	let mut hasher = Sha256::new();
+	hasher.update(iri.as_str().as_bytes());
+	if let Some(profile) = profile {
+		hasher.update(b" ");
+		hasher.update(profile.iri().as_str().as_bytes());
+	}
+
+	let mut name = String::new();
+	for b in hasher.finalize() {
+		name.push_str(&format!("{:02x}", b))
+	}
+
+	name.push_str(".json");
+	directory.join(name)
+}
+
+/// Document stored in a file, with its metadata.
+struct StoredDocument<J> {
+	doc: J,
+	base_url: IriBuf,
+	content_type: Option<String>,
+	context_url: Option<IriBuf>,
+	profile: Option<String>,
+
+	/// Time of the fetch, in seconds since the UNIX epoch, if recorded.
+	fetched_at: Option<u64>,
+}
+
+impl<J> StoredDocument<J> {
+	fn into_remote_document(self, source: Id) -> RemoteDocument<J> {
+		RemoteDocument::new(self.doc, self.base_url, source)
+			.with_content_type(self.content_type)
+			.with_context_url(self.context_url)
+			.with_profile(self.profile)
+	}
+}
+
+/// Stores the given document loaded from the given IRI with the given requested profile,
+/// and its metadata, in the given directory, creating the directory if necessary.
+fn write_document_file<J: Json>(
+	directory: &Path,
+	iri: Iri,
+	requested_profile: Option<Profile>,
+	doc: &RemoteDocument<J>,
+	fetched_at: Option<u64>,
+) -> Result<(), Error> {
+	let context_url = doc.context_url();
+	let mut contents = String::new();
+	contents.push('{');
+	write_document_file_entry(&mut contents, "contentType", doc.content_type());
+	contents.push(',');
+	write_document_file_entry(
+		&mut contents,
+		"contextUrl",
+		context_url.as_ref().map(|url| url.as_str()),
+	);
+	contents.push_str(",\"document\":");
+	contents.push_str(&crate::util::canonical_json::<J>(doc));
+	if let Some(fetched_at) = fetched_at {
+		contents.push_str(&format!(",\"fetchedAt\":{}", fetched_at));
+	}
+	contents.push(',');
+	write_document_file_entry(&mut contents, "iri", Some(iri.as_str()));
+	contents.push(',');
+	write_document_file_entry(&mut contents, "profile", doc.profile());
+	contents.push(',');
+	write_document_file_entry(&mut contents, "url", Some(doc.base_url.as_str()));
+	contents.push('}');
+
+	std::fs::create_dir_all(directory)
+		.and_then(|()| {
+			std::fs::write(
+				document_file_path(directory, iri, requested_profile),
+				contents,
+			)
+		})
+		.map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
+}
+
+/// Writes a `"key":value` entry of a document file, with a string or `null` value.
+fn write_document_file_entry(contents: &mut String, key: &str, value: Option<&str>) {
+	crate::util::write_canonical_string(key, contents);
+	contents.push(':');
+	match value {
+		Some(value) => crate::util::write_canonical_string(value, contents),
+		None => contents.push_str("null"),
+	}
+}
+
+/// Reads a document loaded from the given IRI with the given requested profile,
+/// and its metadata, stored in the given directory.
+fn read_document_file<J: Json>(
+	directory: &Path,
+	iri: Iri,
+	requested_profile: Option<Profile>,
+	parser: &Parser<J>,
+) -> Result<StoredDocument<J>, Error> {
+	let contents = std::fs::read_to_string(document_file_path(directory, iri, requested_profile))
+		.map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))?;
+
+	let mut stored_iri = None;
+	let mut doc = None;
+	let mut base_url = None;
+	let mut content_type = None;
+	let mut context_url = None;
+	let mut profile = None;
+	let mut fetched_at = None;
+	if let generic_json::Value::Object(entries) = (*parser)(&contents)?.into() {
+		for (key, value) in entries {
+			let string = value.as_str().map(str::to_string);
+			match &*key {
+				"document" => doc = Some(value),
+				"iri" => stored_iri = string,
+				"url" => base_url = string.and_then(|s| IriBuf::from_string(s).ok()),
+				"contentType" => content_type = string,
+				"contextUrl" => context_url = string.and_then(|s| IriBuf::from_string(s).ok()),
+				"profile" => profile = string,
+				"fetchedAt" => fetched_at = value.as_u64(),
+				_ => (),
+			}
+		}
+	}
+
+	match (doc, base_url) {
+		(Some(doc), Some(base_url)) if stored_iri.as_deref() == Some(iri.as_str()) => {
+			Ok(StoredDocument {
+				doc,
+				base_url,
+				content_type,
+				context_url,
+				profile,
+				fetched_at,
+			})
+		}
+		_ => Err(ErrorCode::LoadingDocumentFailed.into()),
+	}
+}
+
+impl<L: Loader> Loader for FixtureLoader<L>
+where
+	L::Document: MaybeSend,
+{
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.namespace.lock().unwrap().id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.namespace.lock().unwrap().iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_fixture(url, None)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_fixture(url, Some(profile))
+	}
+}
+
+/// Disk caching loader.
+///
+/// Loader wrapper storing the documents successfully loaded by the inner loader
+/// in a directory, along with their metadata and the time they were fetched,
+/// so that they survive process restarts.
+/// Cached documents are loaded from the directory without using the inner loader,
+/// unless they have expired, given an optional time-to-live.
+/// Cache files use the same format as the [`FixtureLoader`] fixtures.
+///
+/// # Example
+/// ```
+/// # use json_ld::{DiskCachingLoader, FsLoader, Loader};
+/// # use iref::Iri;
+/// # use std::time::Duration;
+/// # #[async_std::main]
+/// # async fn main() {
+/// let mut fs_loader = FsLoader::<serde_json::Value>::default();
+/// fs_loader.mount(Iri::new("https://example.org/tests/").unwrap(), "tests/custom");
+/// let directory = std::env::temp_dir().join("json-ld-cache");
+///
+/// let loader = DiskCachingLoader::new(fs_loader, directory)
+///   .with_ttl(Duration::from_secs(24 * 3600));
+///
+/// let url = Iri::new("https://example.org/tests/0120-in.jsonld").unwrap();
+/// loader.load(url).await.unwrap();
+/// assert!(loader.is_cached(url));
+///
+/// loader.invalidate(url);
+/// assert!(!loader.is_cached(url));
+/// # }
+/// ```
+pub struct DiskCachingLoader<L: Loader> {
+	inner: L,
+	directory: PathBuf,
+	ttl: Option<Duration>,
+	parser: Parser<L::Document>,
+	namespace: Mutex<Namespace>,
+}
+
+impl<L: Loader> DiskCachingLoader<L> {
+	/// Creates a new disk caching loader storing documents in the given directory,
+	/// parsing the cache files with [`FromStr`].
+	pub fn new<P: Into<PathBuf>>(inner: L, directory: P) -> Self
+	where
+		L::Document: 'static + FromStr,
+		<L::Document as FromStr>::Err: 'static + std::error::Error,
+	{
+		Self::with_parser(inner, directory, L::Document::from_str)
+	}
+
+	/// Creates a new disk caching loader storing documents in the given directory,
+	/// using the given function to parse the cache files.
+	pub fn with_parser<P: Into<PathBuf>, E: 'static + std::error::Error>(
+		inner: L,
+		directory: P,
+		parser: impl 'static + Send + Sync + Fn(&str) -> Result<L::Document, E>,
+	) -> Self {
+		Self {
+			inner,
+			directory: directory.into(),
+			ttl: None,
+			parser: Box::new(move |s| {
+				parser(s).map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
+			}),
+			namespace: Mutex::new(Namespace::default()),
+		}
+	}
+
+	/// Sets the time-to-live of cached documents.
+	///
+	/// Documents fetched for longer than `ttl` are fetched again with the inner loader.
+	#[inline(always)]
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = Some(ttl);
+		self
+	}
+
+	/// Directory of the cache.
+	#[inline(always)]
+	pub fn directory(&self) -> &Path {
+		&self.directory
+	}
+
+	/// Returns a reference to the inner loader.
+	#[inline(always)]
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns a mutable reference to the inner loader.
+	#[inline(always)]
+	pub fn inner_mut(&mut self) -> &mut L {
+		&mut self.inner
+	}
+
+	/// Consumes the disk caching loader and returns the inner loader.
+	#[inline(always)]
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Checks if the document behind the given IRI is in the cache directory.
+	///
+	/// The document may have expired.
+	/// Documents loaded with a requested profile are cached separately
+	/// (see [`is_cached_with_profile`](Self::is_cached_with_profile)).
+	#[inline(always)]
+	pub fn is_cached(&self, url: Iri) -> bool {
+		document_file_path(&self.directory, url, None).is_file()
+	}
+
+	/// Checks if the document behind the given IRI,
+	/// loaded with the given requested profile, is in the cache directory.
+	///
+	/// The document may have expired.
+	#[inline(always)]
+	pub fn is_cached_with_profile(&self, url: Iri, profile: Profile) -> bool {
+		document_file_path(&self.directory, url, Some(profile)).is_file()
+	}
+
+	/// Removes the document behind the given IRI from the cache directory,
+	/// whatever the profile requested to load it.
+	///
+	/// Returns `true` if the document was cached.
+	pub fn invalidate(&self, url: Iri) -> bool {
+		let mut removed = false;
+		for profile in std::iter::once(None).chain(Profile::ALL.iter().copied().map(Some)) {
+			removed |=
+				std::fs::remove_file(document_file_path(&self.directory, url, profile)).is_ok()
+		}
+
+		removed
+	}
+
+	/// Loads the cached document behind the given IRI,
+	/// loaded with the given requested profile, if it has not expired.
+	///
+	/// Cache files that cannot be read are ignored.
+	fn load_cached(
+		&self,
+		url: Iri,
+		profile: Option<Profile>,
+	) -> Option<RemoteDocument<L::Document>> {
+		let stored = read_document_file(&self.directory, url, profile, &self.parser).ok()?;
+
+		if let Some(ttl) = self.ttl {
+			let fetched_at = stored.fetched_at?;
+			if now().saturating_sub(fetched_at) >= ttl.as_secs() {
+				return None;
+			}
+		}
+
+		Some(stored.into_remote_document(Id::new(0)))
+	}
+
+	/// Loads the given document from the cache directory, or with the inner loader,
+	/// requesting the given profile.
+	fn load_through_cache<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Option<Profile>,
+	) -> BoxFuture<'a, Result<RemoteDocument<L::Document>, Error>>
+	where
+		L::Document: MaybeSend,
+	{
+		let url: IriBuf = url.into();
+		async move {
+			let mut doc = match self.load_cached(url.as_iri(), profile) {
+				Some(doc) => doc,
+				None => {
+					let doc = match profile {
+						Some(profile) => {
+							self.inner.load_with_profile(url.as_iri(), profile).await?
+						}
+						None => self.inner.load(url.as_iri()).await?,
+					};
+
+					if let Err(e) = write_document_file(
+						&self.directory,
+						url.as_iri(),
+						profile,
+						&doc,
+						Some(now()),
+					) {
+						log::warn!("unable to cache document `{}': {}", url, e)
+					}
+
+					doc
+				}
+			};
+
+			self.namespace.lock().unwrap().register(url, &mut doc);
+			Ok(doc)
+		}
+		.boxed()
+	}
+}
+
+/// Returns the current time, in seconds since the UNIX epoch.
+fn now() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or(0)
+}
+
+impl<L: Loader> Loader for DiskCachingLoader<L>
+where
+	L::Document: MaybeSend,
+{
+	type Document = L::Document;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<Id> {
+		self.namespace.lock().unwrap().id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: Id) -> Option<IriBuf> {
+		self.namespace.lock().unwrap().iri(id)
+	}
+
+	#[inline(always)]
+	fn load<'a>(
+		&'a self,
+		url: Iri<'_>,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_through_cache(url, None)
+	}
+
+	#[inline(always)]
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		self.load_through_cache(url, Some(profile))
+	}
+}
@@ -10,11 +10,12 @@ use crate::{
 	Id, Indexed, Reference,
 };
 use generic_json::{JsonClone, JsonHash};
+use indexmap::IndexSet;
 use iref::{Iri, IriBuf};
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
-pub use node::{Node, Nodes};
+pub use node::{IncludedNodes, Node, Nodes};
 pub use value::{Literal, LiteralString, Value};
 
 pub trait Any<J: JsonHash, T: Id> {
@@ -239,6 +240,33 @@ impl<J: JsonHash, T: Id> Object<J, T> {
 			_ => None,
 		}
 	}
+
+	/// Applies the given function to every node reference of the object, recursively.
+	///
+	/// This includes node identifiers, types, and property and reverse property names.
+	pub(crate) fn map_references<F: FnMut(Reference<T>) -> Reference<T>>(self, f: &mut F) -> Self {
+		match self {
+			Object::Value(value) => Object::Value(value),
+			Object::Node(node) => Object::Node(node.map_references(f)),
+			Object::List(items) => Object::List(
+				items
+					.into_iter()
+					.map(|item| item.map_references(f))
+					.collect(),
+			),
+		}
+	}
+}
+
+impl<J: JsonHash + JsonClone, T: Id> Clone for Object<J, T> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		match self {
+			Self::Value(v) => Self::Value(v.clone()),
+			Self::Node(n) => Self::Node(n.clone()),
+			Self::List(l) => Self::List(l.clone()),
+		}
+	}
 }
 
 impl<J: JsonHash, T: Id> Hash for Object<J, T> {
@@ -274,8 +302,17 @@ impl<J: JsonHash, T: Id> Indexed<Object<J, T>> {
 		object.into_list().map(|list| Indexed::new(list, index))
 	}
 
+	/// Applies the given function to every node reference of the object, recursively.
+	///
+	/// See [`Object::map_references`].
+	#[inline(always)]
+	pub(crate) fn map_references<F: FnMut(Reference<T>) -> Reference<T>>(self, f: &mut F) -> Self {
+		let (object, index) = self.into_parts();
+		Indexed::new(object.map_references(f), index)
+	}
+
 	/// Try to convert this object into an unnamed graph.
-	pub fn into_unnamed_graph(self) -> Result<HashSet<Self>, Self> {
+	pub fn into_unnamed_graph(self) -> Result<IndexSet<Self>, Self> {
 		let (obj, index) = self.into_parts();
 		match obj {
 			Object::Node(n) => match n.into_unnamed_graph() {
@@ -342,6 +379,19 @@ impl<J: JsonHash + JsonClone, K: JsonFrom<J>, T: Id> AsJson<J, K>
 	}
 }
 
+impl<J: JsonHash + JsonClone, K: JsonFrom<J>, T: Id> AsJson<J, K>
+	for IndexSet<Indexed<Object<J, T>>>
+{
+	#[inline(always)]
+	fn as_json_with(&self, meta: impl Clone + Fn(Option<&J::MetaData>) -> K::MetaData) -> K {
+		let array = self
+			.iter()
+			.map(|value| value.as_json_with(meta.clone()))
+			.collect();
+		K::array(array, meta(None))
+	}
+}
+
 /// Iterator through indexed objects.
 pub struct Objects<'a, J: JsonHash, T: Id>(Option<std::slice::Iter<'a, Indexed<Object<J, T>>>>);
 
@@ -5,6 +5,7 @@ use crate::{
 };
 use cc_traits::MapInsert;
 use generic_json::{JsonClone, JsonHash};
+use indexmap::IndexSet;
 use iref::{Iri, IriBuf};
 use std::collections::HashSet;
 use std::convert::TryFrom;
@@ -24,7 +25,6 @@ pub use reverse_properties::ReverseProperties;
 /// (`@included` field).
 // NOTE it may be better to use BTreeSet instead of HashSet to have some ordering?
 //      in which case the Json bound should be lifted.
-#[derive(PartialEq, Eq)]
 pub struct Node<J: JsonHash, T: Id = IriBuf> {
 	/// Identifier.
 	///
@@ -39,7 +39,7 @@ pub struct Node<J: JsonHash, T: Id = IriBuf> {
 	/// Associated graph.
 	///
 	/// This is the `@graph` field.
-	pub(crate) graph: Option<HashSet<Indexed<Object<J, T>>>>,
+	pub(crate) graph: Option<IndexSet<Indexed<Object<J, T>>>>,
 
 	/// Included nodes.
 	///
@@ -55,6 +55,13 @@ pub struct Node<J: JsonHash, T: Id = IriBuf> {
 	///
 	/// This is the `@reverse` field.
 	pub(crate) reverse_properties: ReverseProperties<J, T>,
+
+	/// Metadata of the JSON object this node has been expanded from.
+	///
+	/// Only set when the [`source_metadata`](crate::expansion::Options::source_metadata)
+	/// expansion option is enabled.
+	/// It is not taken into account when comparing or hashing nodes.
+	pub(crate) metadata: Option<J::MetaData>,
 }
 
 impl<J: JsonHash, T: Id> Default for Node<J, T> {
@@ -75,6 +82,7 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 			included: None,
 			properties: Properties::new(),
 			reverse_properties: ReverseProperties::new(),
+			metadata: None,
 		}
 	}
 
@@ -88,6 +96,7 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 			included: None,
 			properties: Properties::new(),
 			reverse_properties: ReverseProperties::new(),
+			metadata: None,
 		}
 	}
 
@@ -124,6 +133,21 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 		self.id.as_ref()
 	}
 
+	/// Get the metadata of the JSON object this node has been expanded from, if any.
+	///
+	/// This is only set when expanding with the
+	/// [`source_metadata`](crate::expansion::Options::source_metadata) option.
+	#[inline(always)]
+	pub fn metadata(&self) -> Option<&J::MetaData> {
+		self.metadata.as_ref()
+	}
+
+	/// Set the metadata attached to this node.
+	#[inline(always)]
+	pub fn set_metadata(&mut self, metadata: Option<J::MetaData>) {
+		self.metadata = metadata
+	}
+
 	/// Get the node's as an IRI if possible.
 	///
 	/// Returns the node's IRI id if any. Returns `None` otherwise.
@@ -200,19 +224,19 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 
 	/// If the node is a graph object, get the graph.
 	#[inline(always)]
-	pub fn graph(&self) -> Option<&HashSet<Indexed<Object<J, T>>>> {
+	pub fn graph(&self) -> Option<&IndexSet<Indexed<Object<J, T>>>> {
 		self.graph.as_ref()
 	}
 
 	/// If the node is a graph object, get the mutable graph.
 	#[inline(always)]
-	pub fn graph_mut(&mut self) -> Option<&mut HashSet<Indexed<Object<J, T>>>> {
+	pub fn graph_mut(&mut self) -> Option<&mut IndexSet<Indexed<Object<J, T>>>> {
 		self.graph.as_mut()
 	}
 
 	/// Set the graph.
 	#[inline(always)]
-	pub fn set_graph(&mut self, graph: Option<HashSet<Indexed<Object<J, T>>>>) {
+	pub fn set_graph(&mut self, graph: Option<IndexSet<Indexed<Object<J, T>>>>) {
 		self.graph = graph
 	}
 
@@ -238,6 +262,40 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 		self.included = included
 	}
 
+	/// Returns an iterator over the nodes included by this node.
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{context, Document, NoLoader};
+	/// # use serde_json::{json, Value};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let doc = json!({
+	///   "@id": "https://example.org/alice",
+	///   "@included": [
+	///     { "@id": "https://example.org/bob", "https://example.org/name": "Bob" }
+	///   ]
+	/// });
+	///
+	/// let expanded = doc.expand::<context::Json<Value>, _>(&NoLoader::<Value>::new()).await.unwrap();
+	/// let alice = expanded.into_iter().next().unwrap().into_indexed_node().unwrap();
+	/// let included: Vec<_> = alice.included_nodes().filter_map(|node| node.as_str()).collect();
+	/// assert_eq!(included, ["https://example.org/bob"]);
+	/// # }
+	/// ```
+	#[inline(always)]
+	pub fn included_nodes(&self) -> IncludedNodes<J, T> {
+		IncludedNodes(self.included.as_ref().map(HashSet::iter))
+	}
+
+	/// Adds the given node to the nodes included by this node.
+	///
+	/// This correspond to adding a node to the `@included` field in the JSON representation.
+	#[inline(always)]
+	pub fn insert_included(&mut self, node: Indexed<Self>) {
+		self.included.get_or_insert_with(HashSet::new).insert(node);
+	}
+
 	/// Returns a reference to the properties of the node.
 	#[inline(always)]
 	pub fn properties(&self) -> &Properties<J, T> {
@@ -277,6 +335,13 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 		self.properties.insert(prop, value)
 	}
 
+	/// Associates the given object to the node through the given property,
+	/// unless it is already associated to it.
+	#[inline(always)]
+	pub fn insert_unique(&mut self, prop: Reference<T>, value: Indexed<Object<J, T>>) {
+		self.properties.insert_unique(prop, value)
+	}
+
 	/// Associates all the given objects to the node through the given property.
 	///
 	/// If there already exists objects associated to the given reverse property,
@@ -310,6 +375,91 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 			.insert_all(reverse_prop, reverse_values)
 	}
 
+	/// Merges the given node into this node.
+	///
+	/// Types, graph and included nodes are unioned,
+	/// and the property and reverse property values of `other` are added to this node
+	/// unless they are already present.
+	/// The identifier and metadata of this node are kept.
+	pub fn merge(&mut self, other: Self) {
+		for ty in other.types {
+			if !self.types.iter().any(|t| *t == ty) {
+				self.types.push(ty)
+			}
+		}
+
+		if let Some(graph) = other.graph {
+			self.graph.get_or_insert_with(IndexSet::new).extend(graph)
+		}
+
+		if let Some(included) = other.included {
+			self.included
+				.get_or_insert_with(HashSet::new)
+				.extend(included)
+		}
+
+		for (prop, values) in other.properties {
+			for value in values {
+				self.properties.insert_unique(prop.clone(), value)
+			}
+		}
+
+		for (prop, nodes) in other.reverse_properties {
+			for node in nodes {
+				if !self
+					.reverse_properties
+					.get(&prop)
+					.any(|other| *other == node)
+				{
+					self.reverse_properties.insert(prop.clone(), node)
+				}
+			}
+		}
+	}
+
+	/// Applies the given function to every node reference of the node, recursively.
+	///
+	/// This includes node identifiers, types, and property and reverse property names.
+	pub(crate) fn map_references<F: FnMut(Reference<T>) -> Reference<T>>(self, f: &mut F) -> Self {
+		let mut result = Self::new();
+		result.metadata = self.metadata;
+		result.id = self.id.map(&mut *f);
+		result.types = self.types.into_iter().map(&mut *f).collect();
+
+		if let Some(graph) = self.graph {
+			result.graph = Some(
+				graph
+					.into_iter()
+					.map(|object| object.map_references(f))
+					.collect(),
+			)
+		}
+
+		if let Some(included) = self.included {
+			result.included = Some(
+				included
+					.into_iter()
+					.map(|node| node.map_references(f))
+					.collect(),
+			)
+		}
+
+		for (prop, objects) in self.properties {
+			let prop = f(prop);
+			result.insert_all(
+				prop,
+				objects.into_iter().map(|object| object.map_references(f)),
+			)
+		}
+
+		for (prop, nodes) in self.reverse_properties {
+			let prop = f(prop);
+			result.insert_all_reverse(prop, nodes.into_iter().map(|node| node.map_references(f)))
+		}
+
+		result
+	}
+
 	/// Tests if the node is an unnamed graph object.
 	///
 	/// Returns `true` is the only field of the object is a `@graph` field.
@@ -329,7 +479,7 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 	/// The unnamed graph is returned as a set of indexed objects.
 	/// Fails and returns itself if the node is *not* an unnamed graph.
 	#[inline(always)]
-	pub fn into_unnamed_graph(self) -> Result<HashSet<Indexed<Object<J, T>>>, Self> {
+	pub fn into_unnamed_graph(self) -> Result<IndexSet<Indexed<Object<J, T>>>, Self> {
 		if self.is_unnamed_graph() {
 			Ok(self.graph.unwrap())
 		} else {
@@ -338,6 +488,17 @@ impl<J: JsonHash, T: Id> Node<J, T> {
 	}
 }
 
+impl<J: JsonHash, T: Id> Indexed<Node<J, T>> {
+	/// Applies the given function to every node reference of the node, recursively.
+	///
+	/// See [`Node::map_references`].
+	#[inline(always)]
+	pub(crate) fn map_references<F: FnMut(Reference<T>) -> Reference<T>>(self, f: &mut F) -> Self {
+		let (node, index) = self.into_parts();
+		Indexed::new(node.map_references(f), index)
+	}
+}
+
 impl<J: JsonHash, T: Id> object::Any<J, T> for Node<J, T> {
 	#[inline(always)]
 	fn as_ref(&self) -> object::Ref<J, T> {
@@ -357,12 +518,41 @@ impl<J: JsonHash, T: Id> TryFrom<Object<J, T>> for Node<J, T> {
 	}
 }
 
+impl<J: JsonHash + JsonClone, T: Id> Clone for Node<J, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		Self {
+			id: self.id.clone(),
+			types: self.types.clone(),
+			graph: self.graph.clone(),
+			included: self.included.clone(),
+			properties: self.properties.clone(),
+			reverse_properties: self.reverse_properties.clone(),
+			metadata: self.metadata.clone(),
+		}
+	}
+}
+
+impl<J: JsonHash, T: Id> PartialEq for Node<J, T> {
+	#[inline]
+	fn eq(&self, other: &Self) -> bool {
+		self.id == other.id
+			&& self.types == other.types
+			&& self.graph == other.graph
+			&& self.included == other.included
+			&& self.properties == other.properties
+			&& self.reverse_properties == other.reverse_properties
+	}
+}
+
+impl<J: JsonHash, T: Id> Eq for Node<J, T> {}
+
 impl<J: JsonHash, T: Id> Hash for Node<J, T> {
 	#[inline]
 	fn hash<H: Hasher>(&self, h: &mut H) {
 		self.id.hash(h);
 		self.types.hash(h);
-		util::hash_set_opt(&self.graph, h);
+		util::hash_index_set_opt(&self.graph, h);
 		util::hash_set_opt(&self.included, h);
 		self.properties.hash(h);
 		self.reverse_properties.hash(h)
@@ -423,7 +613,7 @@ impl<J: JsonHash + JsonClone, K: util::JsonFrom<J>, T: Id> util::AsJson<J, K> fo
 			);
 		}
 
-		K::object(obj, meta(None))
+		K::object(obj, meta(self.metadata.as_ref()))
 	}
 }
 
@@ -461,3 +651,22 @@ impl<'a, J: JsonHash, T: Id> Iterator for Nodes<'a, J, T> {
 		}
 	}
 }
+
+/// Iterator through the nodes included by a node.
+///
+/// It is created by the [`Node::included_nodes`] method.
+pub struct IncludedNodes<'a, J: JsonHash, T: Id>(
+	Option<std::collections::hash_set::Iter<'a, Indexed<Node<J, T>>>>,
+);
+
+impl<'a, J: JsonHash, T: Id> Iterator for IncludedNodes<'a, J, T> {
+	type Item = &'a Indexed<Node<J, T>>;
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<&'a Indexed<Node<J, T>>> {
+		match &mut self.0 {
+			None => None,
+			Some(it) => it.next(),
+		}
+	}
+}
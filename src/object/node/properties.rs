@@ -1,6 +1,6 @@
 use super::Objects;
 use crate::{Id, Indexed, Object, Reference, ToReference};
-use generic_json::JsonHash;
+use generic_json::{JsonClone, JsonHash};
 use std::{
 	borrow::Borrow,
 	collections::HashMap,
@@ -27,11 +27,11 @@ use std::{
 /// )
 /// .unwrap();
 ///
-/// let mut loader = NoLoader::<Value>::new();
+/// let loader = NoLoader::<Value>::new();
 ///
 /// let rt  = tokio::runtime::Runtime::new().unwrap();
 /// let expanded_doc = rt.block_on(doc
-///   .expand::<context::Json<Value>, _>(&mut loader)).unwrap();
+///   .expand::<context::Json<Value>, _>(&loader)).unwrap();
 ///
 /// let node = expanded_doc.into_iter().next().unwrap().into_indexed_node().unwrap();
 ///
@@ -106,6 +106,20 @@ impl<J: JsonHash, T: Id> Properties<J, T> {
 		}
 	}
 
+	/// Associate the given object to the node through the given property,
+	/// unless it is already associated to it.
+	#[inline]
+	pub fn insert_unique(&mut self, prop: Reference<T>, value: Indexed<Object<J, T>>) {
+		if let Some(node_values) = self.0.get_mut(&prop) {
+			if !node_values.contains(&value) {
+				node_values.push(value)
+			}
+		} else {
+			let node_values = vec![value];
+			self.0.insert(prop, node_values);
+		}
+	}
+
 	/// Associate all the given objects to the node through the given property.
 	#[inline(always)]
 	pub fn insert_all<Objects: Iterator<Item = Indexed<Object<J, T>>>>(
@@ -137,6 +151,13 @@ impl<J: JsonHash, T: Id> Properties<J, T> {
 	}
 }
 
+impl<J: JsonHash + JsonClone, T: Id> Clone for Properties<J, T> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
 impl<J: JsonHash, T: Id> Hash for Properties<J, T> {
 	#[inline(always)]
 	fn hash<H: Hasher>(&self, h: &mut H) {
@@ -152,6 +173,18 @@ pub type BindingRef<'a, J, T> = (&'a Reference<T>, &'a [Indexed<Object<J, T>>]);
 /// associating a property to some objects, with a mutable access to the objects.
 pub type BindingMut<'a, J, T> = (&'a Reference<T>, &'a mut Vec<Indexed<Object<J, T>>>);
 
+impl<J: JsonHash, T: Id> IntoIterator for Properties<J, T> {
+	type Item = (Reference<T>, Vec<Indexed<Object<J, T>>>);
+	type IntoIter = IntoIter<J, T>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter {
+			inner: self.0.into_iter(),
+		}
+	}
+}
+
 impl<'a, J: JsonHash, T: Id> IntoIterator for &'a Properties<J, T> {
 	type Item = BindingRef<'a, J, T>;
 	type IntoIter = Iter<'a, J, T>;
@@ -224,3 +257,28 @@ impl<'a, J: JsonHash, T: Id> Iterator for IterMut<'a, J, T> {
 impl<'a, J: JsonHash, T: Id> ExactSizeIterator for IterMut<'a, J, T> {}
 
 impl<'a, J: JsonHash, T: Id> std::iter::FusedIterator for IterMut<'a, J, T> {}
+
+/// Consuming iterator over the properties of a node.
+///
+/// It is created by the `into_iter` function of [`Properties`].
+pub struct IntoIter<J: JsonHash, T: Id> {
+	inner: std::collections::hash_map::IntoIter<Reference<T>, Vec<Indexed<Object<J, T>>>>,
+}
+
+impl<J: JsonHash, T: Id> Iterator for IntoIter<J, T> {
+	type Item = (Reference<T>, Vec<Indexed<Object<J, T>>>);
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next()
+	}
+}
+
+impl<J: JsonHash, T: Id> ExactSizeIterator for IntoIter<J, T> {}
+
+impl<J: JsonHash, T: Id> std::iter::FusedIterator for IntoIter<J, T> {}
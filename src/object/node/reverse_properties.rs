@@ -1,6 +1,6 @@
 use super::{Node, Nodes};
 use crate::{Id, Indexed, Reference, ToReference};
-use generic_json::JsonHash;
+use generic_json::{JsonClone, JsonHash};
 use std::{
 	borrow::Borrow,
 	collections::HashMap,
@@ -103,6 +103,13 @@ impl<J: JsonHash, T: Id> ReverseProperties<J, T> {
 	}
 }
 
+impl<J: JsonHash + JsonClone, T: Id> Clone for ReverseProperties<J, T> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
 impl<J: JsonHash, T: Id> Hash for ReverseProperties<J, T> {
 	#[inline(always)]
 	fn hash<H: Hasher>(&self, h: &mut H) {
@@ -118,6 +125,18 @@ pub type ReverseBindingRef<'a, J, T> = (&'a Reference<T>, &'a [Indexed<Node<J, T
 /// associating a reverse property to some nodes, with a mutable access to the nodes.
 pub type ReverseBindingMut<'a, J, T> = (&'a Reference<T>, &'a mut Vec<Indexed<Node<J, T>>>);
 
+impl<J: JsonHash, T: Id> IntoIterator for ReverseProperties<J, T> {
+	type Item = (Reference<T>, Vec<Indexed<Node<J, T>>>);
+	type IntoIter = IntoIter<J, T>;
+
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter {
+			inner: self.0.into_iter(),
+		}
+	}
+}
+
 impl<'a, J: JsonHash, T: Id> IntoIterator for &'a ReverseProperties<J, T> {
 	type Item = ReverseBindingRef<'a, J, T>;
 	type IntoIter = Iter<'a, J, T>;
@@ -190,3 +209,28 @@ impl<'a, J: JsonHash, T: Id> Iterator for IterMut<'a, J, T> {
 impl<'a, J: JsonHash, T: Id> ExactSizeIterator for IterMut<'a, J, T> {}
 
 impl<'a, J: JsonHash, T: Id> std::iter::FusedIterator for IterMut<'a, J, T> {}
+
+/// Consuming iterator over the reverse properties of a node.
+///
+/// It is created by the `into_iter` function of [`ReverseProperties`].
+pub struct IntoIter<J: JsonHash, T: Id> {
+	inner: std::collections::hash_map::IntoIter<Reference<T>, Vec<Indexed<Node<J, T>>>>,
+}
+
+impl<J: JsonHash, T: Id> Iterator for IntoIter<J, T> {
+	type Item = (Reference<T>, Vec<Indexed<Node<J, T>>>);
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next()
+	}
+}
+
+impl<J: JsonHash, T: Id> ExactSizeIterator for IntoIter<J, T> {}
+
+impl<J: JsonHash, T: Id> std::iter::FusedIterator for IntoIter<J, T> {}
@@ -167,6 +167,14 @@ pub enum Value<J: Json, T: Id = IriBuf> {
 
 	/// JSON literal value.
 	Json(J),
+
+	/// Invalid value object, kept as is.
+	///
+	/// Only produced by the expansion algorithm when the
+	/// [`keep_invalid_values`](crate::expansion::Options::keep_invalid_values)
+	/// option is set.
+	/// It holds the original JSON value, that could not be expanded.
+	Invalid(J),
 }
 
 impl<J: JsonClone, T: Id> Clone for Value<J, T> {
@@ -176,6 +184,7 @@ impl<J: JsonClone, T: Id> Clone for Value<J, T> {
 			Self::Literal(l, t) => Self::Literal(l.clone(), t.clone()),
 			Self::LangString(s) => Self::LangString(s.clone()),
 			Self::Json(j) => Self::Json(j.clone()),
+			Self::Invalid(j) => Self::Invalid(j.clone()),
 		}
 	}
 }
@@ -186,10 +195,16 @@ impl<J: Json, T: Id> Value<J, T> {
 		match self {
 			Value::Literal(lit, _) => lit.as_str(),
 			Value::LangString(str) => Some(str.as_str()),
-			Value::Json(_) => None,
+			Value::Json(_) | Value::Invalid(_) => None,
 		}
 	}
 
+	/// Checks if this value is an [invalid](Value::Invalid) value object.
+	#[inline(always)]
+	pub fn is_invalid(&self) -> bool {
+		matches!(self, Value::Invalid(_))
+	}
+
 	#[inline(always)]
 	pub fn as_bool(&self) -> Option<bool> {
 		match self {
@@ -257,6 +272,7 @@ impl<J: JsonHash, T: Id> Hash for Value<J, T> {
 			}
 			Value::LangString(s) => s.hash(h),
 			Value::Json(json) => crate::util::hash_json(json, h), // TODO replace by the hash function provided by J whenever possible.
+			Value::Invalid(json) => crate::util::hash_json(json, h),
 		}
 	}
 }
@@ -266,6 +282,7 @@ impl<J: JsonClone, K: util::JsonFrom<J>, T: Id> util::AsJson<J, K> for Value<J,
 		let mut obj = K::Object::default();
 
 		match self {
+			Value::Invalid(json) => return json.as_json_with(meta),
 			Value::Literal(lit, ty) => {
 				match lit {
 					Literal::Null => obj.insert(
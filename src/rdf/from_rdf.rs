@@ -0,0 +1,583 @@
+use super::{Literal, Options, Quad, RdfDirection, Value};
+use crate::{
+	object::{self, value::LiteralString},
+	Direction, Error, ErrorCode, ExpandedDocument, Id, Indexed, LangString, LenientLanguageTagBuf,
+	Node, Object, ProcessingMode, Reference,
+};
+use generic_json::JsonHash;
+use langtag::LanguageTagBuf;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+/// Converts the given RDF quads into an expanded JSON-LD document.
+///
+/// Implements the [Serialize RDF as JSON-LD](https://www.w3.org/TR/json-ld11-api/#serialize-rdf-as-json-ld-algorithm)
+/// algorithm.
+/// Well-formed `rdf:first`/`rdf:rest` chains are turned back into list objects.
+///
+/// The `parser` is used to parse `rdf:JSON` literals into JSON values,
+/// and, if the `use_native_types` option is set, to build native numbers.
+///
+/// # Example
+/// ```
+/// use json_ld::{rdf, Reference};
+/// use iref::IriBuf;
+///
+/// let s = Reference::Id(IriBuf::new("https://example.com/s").unwrap());
+/// let p = Reference::Id(IriBuf::new("https://example.com/p").unwrap());
+/// let first = Reference::Id(IriBuf::new(rdf::RDF_FIRST).unwrap());
+/// let rest = Reference::Id(IriBuf::new(rdf::RDF_REST).unwrap());
+/// let nil = Reference::Id(IriBuf::new(rdf::RDF_NIL).unwrap());
+/// let l0 = Reference::Blank(json_ld::BlankId::new("l0"));
+/// let item = |s: &str| {
+///   rdf::Literal::Typed(s.to_string(), IriBuf::new(rdf::XSD_STRING).unwrap()).into()
+/// };
+///
+/// let quads = vec![
+///   rdf::Quad::new(s.clone(), p.clone(), l0.clone().into(), None),
+///   rdf::Quad::new(l0.clone(), first.clone(), item("a"), None),
+///   rdf::Quad::new(l0.clone(), rest.clone(), nil.clone().into(), None),
+/// ];
+///
+/// let doc = rdf::from_rdf::<serde_json::Value, IriBuf, _, _, _>(
+///   quads,
+///   rdf::Options::default(),
+///   |s: &str| serde_json::from_str(s)
+/// ).unwrap();
+///
+/// assert_eq!(doc.len(), 1);
+/// let node = doc.iter().next().unwrap().as_node().unwrap();
+/// let list = node.get_any(&p).unwrap().as_list().unwrap();
+/// assert_eq!(list.len(), 1);
+/// assert_eq!(list[0].as_str(), Some("a"));
+/// ```
+pub fn from_rdf<J, T, Q, P, E>(
+	quads: Q,
+	options: Options,
+	mut parser: P,
+) -> Result<ExpandedDocument<J, T>, Error>
+where
+	J: JsonHash,
+	T: Id,
+	Q: IntoIterator<Item = Quad<T>>,
+	P: FnMut(&str) -> Result<J, E>,
+{
+	let mut graphs: HashMap<Option<Reference<T>>, Graph<J, T>> = HashMap::new();
+	graphs.insert(None, HashMap::new());
+
+	// For each blank node, its usage if it is referenced once, or `None` if it
+	// is referenced more than once.
+	let mut referenced_once: HashMap<Reference<T>, Option<Usage<T>>> = HashMap::new();
+	let mut nil_usages: HashMap<Option<Reference<T>>, Vec<Usage<T>>> = HashMap::new();
+	let mut compound_literal_subjects: HashMap<Option<Reference<T>>, HashSet<Reference<T>>> =
+		HashMap::new();
+
+	for quad in quads {
+		let (subject, predicate, object, graph_name) = quad.into_parts();
+
+		if let Some(graph_name) = &graph_name {
+			declare_node(graphs.get_mut(&None).unwrap(), graph_name);
+		}
+
+		let graph = graphs.entry(graph_name.clone()).or_default();
+		declare_node(graph, &subject);
+
+		if options.rdf_direction == Some(RdfDirection::CompoundLiteral)
+			&& is_iri(&predicate, super::RDF_DIRECTION)
+		{
+			compound_literal_subjects
+				.entry(graph_name.clone())
+				.or_default()
+				.insert(subject.clone());
+		}
+
+		if let Value::Reference(r) = &object {
+			declare_node(graph, r)
+		}
+
+		if !options.use_rdf_type && is_iri(&predicate, super::RDF_TYPE) {
+			if let Value::Reference(ty) = object {
+				let node = graph.get_mut(&subject).unwrap();
+				if !node.types.contains(&ty) {
+					node.types.push(ty)
+				}
+
+				continue;
+			}
+		}
+
+		let is_nil = matches!(&object, Value::Reference(r) if is_iri(r, super::RDF_NIL));
+		let blank = match &object {
+			Value::Reference(r @ Reference::Blank(_)) => Some(r.clone()),
+			_ => None,
+		};
+
+		let value = object_from_rdf(object, options, &mut parser)?;
+		let index = graph
+			.get_mut(&subject)
+			.unwrap()
+			.insert_unique(predicate.clone(), value);
+
+		let usage = Usage {
+			graph: graph_name,
+			subject,
+			property: predicate,
+			index,
+		};
+
+		if is_nil {
+			nil_usages
+				.entry(usage.graph.clone())
+				.or_default()
+				.push(usage)
+		} else if let Some(blank) = blank {
+			referenced_once
+				.entry(blank)
+				.and_modify(|u| *u = None)
+				.or_insert(Some(usage));
+		}
+	}
+
+	for (graph_name, graph) in &mut graphs {
+		if let Some(subjects) = compound_literal_subjects.get(graph_name) {
+			for subject in subjects {
+				decode_compound_literal(graph, graph_name, subject, &referenced_once)?
+			}
+		}
+
+		if let Some(usages) = nil_usages.get(graph_name) {
+			convert_lists(graph, graph_name, usages, &referenced_once)
+		}
+	}
+
+	let mut default_graph = graphs.remove(&None).unwrap();
+	let mut objects = HashSet::new();
+	for (id, entry) in default_graph.drain() {
+		let mut node = entry.into_node(id.clone());
+
+		if let Some(graph) = graphs.remove(&Some(id)) {
+			node.set_graph(Some(
+				graph
+					.into_iter()
+					.map(|(id, entry)| entry.into_node(id))
+					.filter(|node| !node.is_empty())
+					.map(|node| Indexed::new(Object::Node(node), None))
+					.collect(),
+			))
+		}
+
+		if !node.is_empty() {
+			objects.insert(Indexed::new(Object::Node(node), None));
+		}
+	}
+
+	Ok(ExpandedDocument::new(objects, Vec::new()))
+}
+
+/// Node object under construction.
+struct NodeEntry<J: JsonHash, T: Id> {
+	types: Vec<Reference<T>>,
+	properties: HashMap<Reference<T>, Vec<Indexed<Object<J, T>>>>,
+}
+
+impl<J: JsonHash, T: Id> NodeEntry<J, T> {
+	fn new() -> Self {
+		Self {
+			types: Vec::new(),
+			properties: HashMap::new(),
+		}
+	}
+
+	/// Associate the given object to the node through the given property,
+	/// unless it is already associated to it.
+	///
+	/// Returns the position of the object in the property values.
+	fn insert_unique(&mut self, prop: Reference<T>, value: Indexed<Object<J, T>>) -> usize {
+		let values = self.properties.entry(prop).or_default();
+		match values.iter().position(|v| *v == value) {
+			Some(i) => i,
+			None => {
+				values.push(value);
+				values.len() - 1
+			}
+		}
+	}
+
+	fn get_mut(&mut self, prop: &Reference<T>, index: usize) -> Option<&mut Indexed<Object<J, T>>> {
+		self.properties
+			.get_mut(prop)
+			.and_then(|values| values.get_mut(index))
+	}
+
+	fn into_node(self, id: Reference<T>) -> Node<J, T> {
+		let mut node = Node::with_id(id);
+		node.types = self.types;
+		for (prop, values) in self.properties {
+			node.insert_all(prop, values.into_iter())
+		}
+
+		node
+	}
+}
+
+type Graph<J, T> = HashMap<Reference<T>, NodeEntry<J, T>>;
+
+/// Location of an object referencing a node.
+#[derive(Clone)]
+struct Usage<T: Id> {
+	graph: Option<Reference<T>>,
+	subject: Reference<T>,
+	property: Reference<T>,
+	index: usize,
+}
+
+fn declare_node<J: JsonHash, T: Id>(graph: &mut Graph<J, T>, id: &Reference<T>) {
+	if !graph.contains_key(id) {
+		graph.insert(id.clone(), NodeEntry::new());
+	}
+}
+
+fn is_iri<T: Id>(r: &Reference<T>, iri: &str) -> bool {
+	matches!(r, Reference::Id(id) if id.as_iri().as_str() == iri)
+}
+
+/// Returns the only object associated to the given property of the node.
+fn single_value<'a, J: JsonHash, T: Id>(
+	entry: &'a NodeEntry<J, T>,
+	prop: &str,
+) -> Option<&'a Indexed<Object<J, T>>> {
+	let mut values = entry
+		.properties
+		.iter()
+		.filter(|(p, _)| is_iri(p, prop))
+		.flat_map(|(_, values)| values);
+	match (values.next(), values.next()) {
+		(Some(value), None) => Some(value),
+		_ => None,
+	}
+}
+
+/// Replaces the compound literal node `subject` by the value object it represents.
+fn decode_compound_literal<J: JsonHash, T: Id>(
+	graph: &mut Graph<J, T>,
+	graph_name: &Option<Reference<T>>,
+	subject: &Reference<T>,
+	referenced_once: &HashMap<Reference<T>, Option<Usage<T>>>,
+) -> Result<(), Error> {
+	let usage = match referenced_once.get(subject) {
+		Some(Some(usage)) if usage.graph == *graph_name => usage,
+		_ => return Ok(()),
+	};
+
+	let entry = match graph.get(subject) {
+		Some(entry) => entry,
+		None => return Ok(()),
+	};
+
+	let value = match single_value(entry, super::RDF_VALUE).and_then(|v| v.as_str()) {
+		Some(value) => value.to_string(),
+		None => return Ok(()),
+	};
+
+	let language = single_value(entry, super::RDF_LANGUAGE)
+		.and_then(|v| v.as_str())
+		.map(|language| match LanguageTagBuf::parse_copy(language) {
+			Ok(tag) => tag.into(),
+			Err(_) => LenientLanguageTagBuf::from(language.to_string()),
+		});
+
+	let direction = match single_value(entry, super::RDF_DIRECTION).and_then(|v| v.as_str()) {
+		Some(direction) => Some(
+			Direction::try_from(direction)
+				.map_err(|_| Error::from(ErrorCode::InvalidBaseDirection))?,
+		),
+		None => None,
+	};
+
+	let lang_string = match LangString::new(LiteralString::Inferred(value), language, direction) {
+		Ok(lang_string) => object::Value::LangString(lang_string),
+		Err(s) => object::Value::Literal(object::value::Literal::String(s), None),
+	};
+
+	graph.remove(subject);
+	if let Some(object) = graph
+		.get_mut(&usage.subject)
+		.and_then(|entry| entry.get_mut(&usage.property, usage.index))
+	{
+		*object = Indexed::new(Object::Value(lang_string), None)
+	}
+
+	Ok(())
+}
+
+/// Checks if the given node is a well-formed list node.
+///
+/// A well-formed list node is a blank node referenced only once,
+/// with exactly one `rdf:first` and one `rdf:rest` value
+/// and no other property, and optionally the `rdf:List` type.
+fn is_well_formed_list_node<J: JsonHash, T: Id>(
+	graph: &Graph<J, T>,
+	graph_name: &Option<Reference<T>>,
+	id: &Reference<T>,
+	referenced_once: &HashMap<Reference<T>, Option<Usage<T>>>,
+) -> bool {
+	if !matches!(id, Reference::Blank(_)) {
+		return false;
+	}
+
+	if !matches!(referenced_once.get(id), Some(Some(usage)) if usage.graph == *graph_name) {
+		return false;
+	}
+
+	match graph.get(id) {
+		Some(entry) => {
+			entry.types.iter().all(|ty| is_iri(ty, super::RDF_LIST))
+				&& entry.properties.len() == 2
+				&& single_value(entry, super::RDF_FIRST).is_some()
+				&& single_value(entry, super::RDF_REST).is_some()
+		}
+		None => false,
+	}
+}
+
+/// Detected list, with the location of its head and its list nodes (in reverse order).
+struct ListChain<T: Id> {
+	head: Usage<T>,
+	nodes: Vec<Reference<T>>,
+}
+
+/// Turns the `rdf:first`/`rdf:rest` chains ending with the given `rdf:nil` usages
+/// into list objects.
+fn convert_lists<J: JsonHash, T: Id>(
+	graph: &mut Graph<J, T>,
+	graph_name: &Option<Reference<T>>,
+	nil_usages: &[Usage<T>],
+	referenced_once: &HashMap<Reference<T>, Option<Usage<T>>>,
+) {
+	let mut chains = Vec::with_capacity(nil_usages.len());
+	for usage in nil_usages {
+		let mut head = usage.clone();
+		let mut nodes = Vec::new();
+
+		while is_iri(&head.property, super::RDF_REST)
+			&& is_well_formed_list_node(graph, graph_name, &head.subject, referenced_once)
+		{
+			nodes.push(head.subject.clone());
+			head = referenced_once
+				.get(&head.subject)
+				.cloned()
+				.flatten()
+				.unwrap();
+
+			if matches!(head.subject, Reference::Id(_)) {
+				break;
+			}
+		}
+
+		chains.push(ListChain { head, nodes })
+	}
+
+	// Lists may be nested, in which case the head of a list is the `rdf:first`
+	// value of a list node of another list.
+	let heads: HashMap<Reference<T>, usize> = chains
+		.iter()
+		.enumerate()
+		.filter(|(_, chain)| is_iri(&chain.head.property, super::RDF_FIRST))
+		.map(|(i, chain)| (chain.head.subject.clone(), i))
+		.collect();
+
+	let mut built = HashSet::new();
+	for i in 0..chains.len() {
+		if !built.contains(&i) {
+			let list = build_list(graph, &chains, &heads, i, &mut built);
+			let head = &chains[i].head;
+			if let Some(object) = graph
+				.get_mut(&head.subject)
+				.and_then(|entry| entry.get_mut(&head.property, head.index))
+			{
+				let index = object.index().map(ToString::to_string);
+				*object = Indexed::new(Object::List(list), index)
+			}
+		}
+	}
+}
+
+/// Builds the items of the `i`-th detected list,
+/// removing its list nodes from the graph.
+fn build_list<J: JsonHash, T: Id>(
+	graph: &mut Graph<J, T>,
+	chains: &[ListChain<T>],
+	heads: &HashMap<Reference<T>, usize>,
+	i: usize,
+	built: &mut HashSet<usize>,
+) -> Vec<Indexed<Object<J, T>>> {
+	built.insert(i);
+
+	let mut items = Vec::with_capacity(chains[i].nodes.len());
+	for node in chains[i].nodes.iter().rev() {
+		let item = match heads.get(node) {
+			Some(&j) if !built.contains(&j) => Indexed::new(
+				Object::List(build_list(graph, chains, heads, j, built)),
+				None,
+			),
+			_ => {
+				let entry = graph.get_mut(node).unwrap();
+				let first = entry
+					.properties
+					.iter_mut()
+					.find(|(p, _)| is_iri(p, super::RDF_FIRST))
+					.unwrap()
+					.1;
+				first.pop().unwrap()
+			}
+		};
+
+		graph.remove(node);
+		items.push(item)
+	}
+
+	items
+}
+
+/// Converts the given RDF value into an expanded object.
+///
+/// Implements the [RDF to Object Conversion](https://www.w3.org/TR/json-ld11-api/#rdf-to-object-conversion)
+/// algorithm.
+fn object_from_rdf<J: JsonHash, T: Id, P: FnMut(&str) -> Result<J, E>, E>(
+	value: Value<T>,
+	options: Options,
+	parser: &mut P,
+) -> Result<Indexed<Object<J, T>>, Error> {
+	let value = match value {
+		Value::Reference(r) => return Ok(Indexed::new(Object::Node(Node::with_id(r)), None)),
+		Value::Literal(Literal::LangString(s, language)) => {
+			match LangString::new(LiteralString::Inferred(s), Some(language), None) {
+				Ok(lang_string) => object::Value::LangString(lang_string),
+				Err(s) => object::Value::Literal(object::value::Literal::String(s), None),
+			}
+		}
+		Value::Literal(Literal::Typed(s, ty)) => typed_literal_from_rdf(s, ty, options, parser)?,
+	};
+
+	Ok(Indexed::new(Object::Value(value), None))
+}
+
+fn typed_literal_from_rdf<J: JsonHash, T: Id, P: FnMut(&str) -> Result<J, E>, E>(
+	s: String,
+	ty: T,
+	options: Options,
+	parser: &mut P,
+) -> Result<object::Value<J, T>, Error> {
+	use object::value::Literal as JsonLiteral;
+	let datatype = ty.as_iri();
+	let datatype = datatype.as_str();
+
+	if datatype == super::XSD_STRING {
+		return Ok(object::Value::Literal(
+			JsonLiteral::String(LiteralString::Inferred(s)),
+			None,
+		));
+	}
+
+	if options.use_native_types {
+		if datatype == super::XSD_BOOLEAN {
+			match s.as_str() {
+				"true" => return Ok(object::Value::Literal(JsonLiteral::Boolean(true), None)),
+				"false" => return Ok(object::Value::Literal(JsonLiteral::Boolean(false), None)),
+				_ => (),
+			}
+		}
+
+		let number = if datatype == super::XSD_INTEGER {
+			json_integer(&s)
+		} else if datatype == super::XSD_DOUBLE {
+			json_double(&s)
+		} else {
+			None
+		};
+
+		if let Some(number) = number {
+			if let Ok(json) = parser(&number) {
+				if let generic_json::Value::Number(n) = json.into_parts().0 {
+					return Ok(object::Value::Literal(JsonLiteral::Number(n), None));
+				}
+			}
+		}
+	}
+
+	if datatype == super::RDF_JSON && options.processing_mode != ProcessingMode::JsonLd1_0 {
+		return match parser(&s) {
+			Ok(json) => Ok(object::Value::Json(json)),
+			Err(_) => Err(ErrorCode::InvalidJsonLiteral.into()),
+		};
+	}
+
+	if options.rdf_direction == Some(RdfDirection::I18nDatatype) {
+		if let Some(suffix) = datatype.strip_prefix(super::I18N) {
+			if let Some((language, direction)) = suffix.split_once('_') {
+				if let Ok(direction) = Direction::try_from(direction) {
+					let language = if language.is_empty() {
+						None
+					} else {
+						Some(match LanguageTagBuf::parse_copy(language) {
+							Ok(tag) => tag.into(),
+							Err(_) => LenientLanguageTagBuf::from(language.to_string()),
+						})
+					};
+
+					if let Ok(lang_string) = LangString::new(
+						LiteralString::Inferred(s.clone()),
+						language,
+						Some(direction),
+					) {
+						return Ok(object::Value::LangString(lang_string));
+					}
+				}
+			}
+		}
+	}
+
+	Ok(object::Value::Literal(
+		JsonLiteral::String(LiteralString::Inferred(s)),
+		Some(ty),
+	))
+}
+
+/// Returns the JSON representation of the given `xsd:integer` lexical form,
+/// if it is valid.
+fn json_integer(s: &str) -> Option<String> {
+	let (negative, digits) = match s.as_bytes().first() {
+		Some(b'-') => (true, &s[1..]),
+		Some(b'+') => (false, &s[1..]),
+		_ => (false, s),
+	};
+
+	if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+		return None;
+	}
+
+	let digits = digits.trim_start_matches('0');
+	if digits.is_empty() {
+		Some("0".to_string())
+	} else if negative {
+		Some(format!("-{}", digits))
+	} else {
+		Some(digits.to_string())
+	}
+}
+
+/// Returns the JSON representation of the given `xsd:double` lexical form,
+/// if it is valid and finite.
+fn json_double(s: &str) -> Option<String> {
+	if !s
+		.bytes()
+		.all(|c| c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.' | b'e' | b'E'))
+	{
+		return None;
+	}
+
+	match s.parse::<f64>() {
+		Ok(f) if f.is_finite() => Some(format!("{:?}", f)),
+		_ => None,
+	}
+}
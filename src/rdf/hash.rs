@@ -0,0 +1,161 @@
+use super::{nquads, Quad, Value};
+use crate::{BlankId, Id, Reference};
+use sha2::{Digest as _, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// SHA-256 digest.
+pub type Digest = [u8; 32];
+
+/// Computes a content hash of the given RDF dataset.
+///
+/// The hash does not depend on the order of the quads,
+/// duplicate quads, or blank node identifiers:
+/// isomorphic datasets have the same hash.
+///
+/// Blank nodes are first labeled after their neighborhood,
+/// using the first degree hash of the
+/// [RDF Dataset Canonicalization](https://www.w3.org/TR/rdf-canon/#hash-1d-quads)
+/// algorithm, iteratively refined with the labels of the neighboring blank nodes.
+/// The hash is then the SHA-256 digest of the resulting canonical N-Quads document.
+/// Since blank nodes with indistinguishable neighborhoods get the same label,
+/// some highly symmetric datasets that are not isomorphic may have the same hash.
+pub fn hash<T: Id, Q: IntoIterator<Item = Quad<T>>>(quads: Q) -> Digest {
+	let quads: Vec<_> = quads
+		.into_iter()
+		.collect::<HashSet<_>>()
+		.into_iter()
+		.collect();
+
+	let mut mentions: HashMap<&BlankId, Vec<&Quad<T>>> = HashMap::new();
+	for quad in &quads {
+		for id in blank_ids(quad) {
+			let quads = mentions.entry(id).or_default();
+			if !quads.iter().any(|q| std::ptr::eq(*q, quad)) {
+				quads.push(quad)
+			}
+		}
+	}
+
+	// First degree hashes.
+	let mut labels: HashMap<&BlankId, String> = mentions
+		.iter()
+		.map(|(id, quads)| {
+			let label = neighborhood_hash(None, quads, |other| {
+				if other == *id {
+					"a".to_string()
+				} else {
+					"z".to_string()
+				}
+			});
+
+			(*id, label)
+		})
+		.collect();
+
+	// Refinement, until the partition of blank nodes is stable.
+	let mut classes = count_classes(&labels);
+	for _ in 0..mentions.len() {
+		let refined: HashMap<&BlankId, String> = mentions
+			.iter()
+			.map(|(id, quads)| {
+				let label = neighborhood_hash(Some(&labels[id]), quads, |other| {
+					if other == *id {
+						"a".to_string()
+					} else {
+						labels[other].clone()
+					}
+				});
+
+				(*id, label)
+			})
+			.collect();
+
+		let refined_classes = count_classes(&refined);
+		labels = refined;
+		if refined_classes == classes {
+			break;
+		}
+
+		classes = refined_classes
+	}
+
+	let relabeled = quads
+		.iter()
+		.map(|quad| relabel(quad, |id| labels[id].clone()));
+	Sha256::digest(nquads::to_string(relabeled).as_bytes()).into()
+}
+
+/// Returns the number of distinct labels.
+fn count_classes(labels: &HashMap<&BlankId, String>) -> usize {
+	labels.values().collect::<HashSet<_>>().len()
+}
+
+/// Hashes the given quads, relabeling blank nodes with `label`,
+/// prefixed by the `previous` label of the blank node, if any.
+fn neighborhood_hash<T: Id, F: Fn(&BlankId) -> String>(
+	previous: Option<&str>,
+	quads: &[&Quad<T>],
+	label: F,
+) -> String {
+	let mut lines: Vec<_> = quads
+		.iter()
+		.map(|quad| relabel(quad, &label).to_string())
+		.collect();
+	lines.sort_unstable();
+
+	let mut hasher = Sha256::new();
+	if let Some(previous) = previous {
+		hasher.update(previous.as_bytes());
+		hasher.update(b"\n");
+	}
+
+	for line in lines {
+		hasher.update(line.as_bytes());
+		hasher.update(b"\n");
+	}
+
+	let mut result = String::with_capacity(64);
+	for b in hasher.finalize() {
+		write!(result, "{:02x}", b).unwrap()
+	}
+
+	result
+}
+
+/// Returns the blank node identifiers appearing in the given quad.
+fn blank_ids<T: Id>(quad: &Quad<T>) -> impl Iterator<Item = &BlankId> {
+	let object = match quad.object() {
+		Value::Reference(r) => Some(r),
+		Value::Literal(_) => None,
+	};
+
+	std::iter::once(quad.subject())
+		.chain(std::iter::once(quad.predicate()))
+		.chain(object)
+		.chain(quad.graph())
+		.filter_map(|r| match r {
+			Reference::Blank(id) => Some(id),
+			_ => None,
+		})
+}
+
+/// Relabels the blank nodes of the given quad.
+fn relabel<T: Id, F: Fn(&BlankId) -> String>(quad: &Quad<T>, label: F) -> Quad<T> {
+	let relabel_reference = |r: &Reference<T>| match r {
+		Reference::Blank(id) => Reference::Blank(BlankId::new(&label(id))),
+		r => r.clone(),
+	};
+
+	let object = match quad.object() {
+		Value::Reference(r) => Value::Reference(relabel_reference(r)),
+		Value::Literal(l) => Value::Literal(l.clone()),
+	};
+
+	Quad::new(
+		relabel_reference(quad.subject()),
+		relabel_reference(quad.predicate()),
+		object,
+		quad.graph().map(relabel_reference),
+	)
+}
@@ -0,0 +1,241 @@
+//! RDF serialization/deserialization.
+//!
+//! This module provides the data types to represent
+//! [RDF](https://www.w3.org/TR/rdf11-concepts/) quads
+//! and the algorithms to convert an expanded JSON-LD document into RDF quads,
+//! and RDF quads back into an expanded JSON-LD document.
+//! Quads can be serialized into N-Quads using the [`nquads`] module.
+//! A content hash of quads, independent of blank node identifiers,
+//! can be computed with the [`hash`] function.
+use crate::{BlankId, Id, LenientLanguageTagBuf, Reference};
+use iref::{AsIri, Iri, IriBuf};
+
+mod from_rdf;
+mod hash;
+mod to_rdf;
+
+pub mod nquads;
+
+pub use from_rdf::*;
+pub use hash::*;
+pub use to_rdf::*;
+
+/// `rdf:type` IRI.
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// `rdf:first` IRI.
+pub const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+
+/// `rdf:rest` IRI.
+pub const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+
+/// `rdf:nil` IRI.
+pub const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+/// `rdf:List` IRI.
+pub const RDF_LIST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#List";
+
+/// `rdf:value` IRI.
+pub const RDF_VALUE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#value";
+
+/// `rdf:language` IRI.
+pub const RDF_LANGUAGE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#language";
+
+/// `rdf:direction` IRI.
+pub const RDF_DIRECTION: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#direction";
+
+/// `rdf:JSON` IRI.
+pub const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+
+/// `rdf:langString` IRI.
+pub const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+/// `xsd:boolean` IRI.
+pub const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+
+/// `xsd:integer` IRI.
+pub const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+
+/// `xsd:double` IRI.
+pub const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+/// `xsd:string` IRI.
+pub const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// Base IRI of the `i18n` datatypes used to represent base directions.
+pub const I18N: &str = "https://www.w3.org/ns/i18n#";
+
+/// Builds an identifier from one of the IRI constants of this module.
+#[inline(always)]
+pub(crate) fn id<T: Id>(iri: &'static str) -> T {
+	T::from_iri(Iri::new(iri).unwrap())
+}
+
+/// How base directions are represented in RDF.
+///
+/// Corresponds to the `rdfDirection` option of the JSON-LD API.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RdfDirection {
+	/// Language tagged strings with a base direction are represented
+	/// using `https://www.w3.org/ns/i18n#{language}_{direction}` datatypes.
+	I18nDatatype,
+
+	/// Language tagged strings with a base direction are represented
+	/// using a blank node with `rdf:value`, `rdf:language` and `rdf:direction`
+	/// properties.
+	CompoundLiteral,
+}
+
+/// RDF literal.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Literal<T: AsIri = IriBuf> {
+	/// Typed literal.
+	Typed(String, T),
+
+	/// Language tagged string (of type `rdf:langString`).
+	LangString(String, LenientLanguageTagBuf),
+}
+
+impl<T: AsIri> Literal<T> {
+	/// Returns the lexical form of the literal.
+	#[inline(always)]
+	pub fn lexical_form(&self) -> &str {
+		match self {
+			Self::Typed(s, _) => s.as_str(),
+			Self::LangString(s, _) => s.as_str(),
+		}
+	}
+
+	/// Returns the datatype IRI of the literal.
+	#[inline(always)]
+	pub fn datatype(&self) -> Iri {
+		match self {
+			Self::Typed(_, ty) => ty.as_iri(),
+			Self::LangString(_, _) => Iri::new(RDF_LANG_STRING).unwrap(),
+		}
+	}
+
+	/// Returns the language tag of the literal, if it is a language tagged string.
+	#[inline(always)]
+	pub fn language(&self) -> Option<&LenientLanguageTagBuf> {
+		match self {
+			Self::LangString(_, lang) => Some(lang),
+			_ => None,
+		}
+	}
+}
+
+/// RDF quad object.
+///
+/// Either a node reference (IRI or blank node identifier) or a literal.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Value<T: AsIri = IriBuf> {
+	/// Node reference.
+	Reference(Reference<T>),
+
+	/// Literal value.
+	Literal(Literal<T>),
+}
+
+impl<T: AsIri> Value<T> {
+	/// Returns the node reference, if this value is one.
+	#[inline(always)]
+	pub fn as_reference(&self) -> Option<&Reference<T>> {
+		match self {
+			Self::Reference(r) => Some(r),
+			_ => None,
+		}
+	}
+
+	/// Returns the literal, if this value is one.
+	#[inline(always)]
+	pub fn as_literal(&self) -> Option<&Literal<T>> {
+		match self {
+			Self::Literal(l) => Some(l),
+			_ => None,
+		}
+	}
+}
+
+impl<T: AsIri> From<Reference<T>> for Value<T> {
+	#[inline(always)]
+	fn from(r: Reference<T>) -> Self {
+		Self::Reference(r)
+	}
+}
+
+impl<T: AsIri> From<BlankId> for Value<T> {
+	#[inline(always)]
+	fn from(b: BlankId) -> Self {
+		Self::Reference(Reference::Blank(b))
+	}
+}
+
+impl<T: AsIri> From<Literal<T>> for Value<T> {
+	#[inline(always)]
+	fn from(l: Literal<T>) -> Self {
+		Self::Literal(l)
+	}
+}
+
+/// RDF quad.
+///
+/// A triple (subject, predicate, object) with an optional graph name.
+/// If the graph name is `None`, the triple belongs to the default graph.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Quad<T: AsIri = IriBuf> {
+	subject: Reference<T>,
+	predicate: Reference<T>,
+	object: Value<T>,
+	graph: Option<Reference<T>>,
+}
+
+impl<T: AsIri> Quad<T> {
+	/// Creates a new quad.
+	#[inline(always)]
+	pub fn new(
+		subject: Reference<T>,
+		predicate: Reference<T>,
+		object: Value<T>,
+		graph: Option<Reference<T>>,
+	) -> Self {
+		Self {
+			subject,
+			predicate,
+			object,
+			graph,
+		}
+	}
+
+	/// Subject of the quad.
+	#[inline(always)]
+	pub fn subject(&self) -> &Reference<T> {
+		&self.subject
+	}
+
+	/// Predicate of the quad.
+	#[inline(always)]
+	pub fn predicate(&self) -> &Reference<T> {
+		&self.predicate
+	}
+
+	/// Object of the quad.
+	#[inline(always)]
+	pub fn object(&self) -> &Value<T> {
+		&self.object
+	}
+
+	/// Graph name of the quad.
+	///
+	/// Returns `None` if the quad belongs to the default graph.
+	#[inline(always)]
+	pub fn graph(&self) -> Option<&Reference<T>> {
+		self.graph.as_ref()
+	}
+
+	/// Turns this quad into its subject, predicate, object and graph name.
+	#[inline(always)]
+	pub fn into_parts(self) -> (Reference<T>, Reference<T>, Value<T>, Option<Reference<T>>) {
+		(self.subject, self.predicate, self.object, self.graph)
+	}
+}
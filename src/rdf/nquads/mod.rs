@@ -0,0 +1,149 @@
+//! N-Quads serialization.
+//!
+//! This module provides functions to serialize RDF quads into the
+//! [N-Quads](https://www.w3.org/TR/n-quads/) format.
+//! The output is in canonical form, as defined by the
+//! [RDF Dataset Canonicalization](https://www.w3.org/TR/rdf-canon/#canonical-quads)
+//! specification:
+//! one quad per line, no superfluous whitespace, `xsd:string` datatypes omitted,
+//! and lines sorted in code point order without duplicates.
+//!
+//! If the `nquads-parser` feature is enabled, this module also provides an N-Quads
+//! (and N-Triples) parser, whose output can be given to [`from_rdf`](super::from_rdf).
+use super::{Literal, Quad, Value, XSD_STRING};
+use crate::Reference;
+use iref::AsIri;
+use std::fmt;
+use std::io;
+
+#[cfg(feature = "nquads-parser")]
+mod parser;
+
+#[cfg(feature = "nquads-parser")]
+pub use parser::*;
+
+/// Serializes the given quads into a canonical N-Quads document.
+///
+/// # Example
+/// ```
+/// use json_ld::{rdf, Reference};
+/// use iref::IriBuf;
+///
+/// let quad = rdf::Quad::new(
+///   Reference::Id(IriBuf::new("https://example.com/s").unwrap()),
+///   Reference::Id(IriBuf::new("https://example.com/p").unwrap()),
+///   rdf::Literal::Typed("a \"b\"\n".to_string(), IriBuf::new(rdf::XSD_STRING).unwrap()).into(),
+///   None
+/// );
+///
+/// assert_eq!(
+///   rdf::nquads::to_string(vec![quad]),
+///   "<https://example.com/s> <https://example.com/p> \"a \\\"b\\\"\\n\" .\n"
+/// );
+/// ```
+pub fn to_string<T: AsIri, Q: IntoIterator<Item = Quad<T>>>(quads: Q) -> String {
+	let mut result = String::new();
+	for line in canonical_lines(quads) {
+		result.push_str(&line);
+		result.push('\n')
+	}
+
+	result
+}
+
+/// Writes the given quads as a canonical N-Quads document into `output`.
+pub fn write<T: AsIri, Q: IntoIterator<Item = Quad<T>>, W: io::Write>(
+	quads: Q,
+	output: &mut W,
+) -> io::Result<()> {
+	for line in canonical_lines(quads) {
+		writeln!(output, "{}", line)?
+	}
+
+	Ok(())
+}
+
+/// Returns the sorted and deduplicated N-Quads lines of the given quads.
+fn canonical_lines<T: AsIri, Q: IntoIterator<Item = Quad<T>>>(quads: Q) -> Vec<String> {
+	let mut lines: Vec<_> = quads.into_iter().map(|quad| quad.to_string()).collect();
+	lines.sort_unstable();
+	lines.dedup();
+	lines
+}
+
+/// Formats the quad as an N-Quads statement, without the trailing newline.
+impl<T: AsIri> fmt::Display for Quad<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_reference(self.subject(), f)?;
+		f.write_str(" ")?;
+		write_reference(self.predicate(), f)?;
+		f.write_str(" ")?;
+		write!(f, "{}", self.object())?;
+
+		if let Some(graph) = self.graph() {
+			f.write_str(" ")?;
+			write_reference(graph, f)?;
+		}
+
+		f.write_str(" .")
+	}
+}
+
+/// Formats the value as an N-Quads term.
+impl<T: AsIri> fmt::Display for Value<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Reference(r) => write_reference(r, f),
+			Self::Literal(lit) => write!(f, "{}", lit),
+		}
+	}
+}
+
+/// Formats the literal as an N-Quads term.
+impl<T: AsIri> fmt::Display for Literal<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_string(self.lexical_form(), f)?;
+		match self {
+			Self::Typed(_, ty) => {
+				let ty = ty.as_iri();
+				if ty.as_str() != XSD_STRING {
+					write!(f, "^^<{}>", ty)?
+				}
+
+				Ok(())
+			}
+			Self::LangString(_, language) => write!(f, "@{}", language.as_str()),
+		}
+	}
+}
+
+fn write_reference<T: AsIri>(r: &Reference<T>, f: &mut fmt::Formatter) -> fmt::Result {
+	match r {
+		Reference::Blank(id) => write!(f, "{}", id),
+		r => write!(f, "<{}>", r.as_str()),
+	}
+}
+
+/// Writes the given string as a quoted N-Quads string literal.
+///
+/// `"`, `\` and the `\b`, `\t`, `\n`, `\f`, `\r` control characters are escaped
+/// with a backslash. Other control characters are escaped as `\uXXXX`.
+fn write_string(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
+	f.write_str("\"")?;
+
+	for c in s.chars() {
+		match c {
+			'\u{8}' => f.write_str("\\b")?,
+			'\t' => f.write_str("\\t")?,
+			'\n' => f.write_str("\\n")?,
+			'\u{c}' => f.write_str("\\f")?,
+			'\r' => f.write_str("\\r")?,
+			'"' => f.write_str("\\\"")?,
+			'\\' => f.write_str("\\\\")?,
+			'\u{0}'..='\u{1f}' | '\u{7f}' => write!(f, "\\u{:04X}", c as u32)?,
+			c => fmt::Write::write_char(f, c)?,
+		}
+	}
+
+	f.write_str("\"")
+}
@@ -0,0 +1,385 @@
+use crate::{
+	loader,
+	rdf::{Literal, Quad, Value},
+	BlankId, Id, LenientLanguageTagBuf, Loc, Reference,
+};
+use iref::Iri;
+use langtag::LanguageTagBuf;
+use std::fmt;
+
+/// Position in an N-Quads document.
+///
+/// Lines and columns start at 1. Columns are counted in characters.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Position {
+	/// Line number.
+	pub line: usize,
+
+	/// Column number.
+	pub column: usize,
+}
+
+impl fmt::Display for Position {
+	#[inline(always)]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}", self.line, self.column)
+	}
+}
+
+/// N-Quads parse error.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseError {
+	/// Unexpected character.
+	Unexpected(char),
+
+	/// Unexpected end of line.
+	UnexpectedEnd,
+
+	/// Invalid IRI.
+	InvalidIri(String),
+
+	/// Invalid blank node identifier.
+	InvalidBlankId,
+
+	/// Invalid language tag.
+	InvalidLanguageTag,
+
+	/// Invalid escape sequence.
+	InvalidEscape,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Unexpected(c) => write!(f, "unexpected character `{}`", c.escape_debug()),
+			Self::UnexpectedEnd => write!(f, "unexpected end of line"),
+			Self::InvalidIri(iri) => write!(f, "invalid IRI `{}`", iri),
+			Self::InvalidBlankId => write!(f, "invalid blank node identifier"),
+			Self::InvalidLanguageTag => write!(f, "invalid language tag"),
+			Self::InvalidEscape => write!(f, "invalid escape sequence"),
+		}
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses the given N-Quads (or N-Triples) document.
+///
+/// Errors are located in the document using the given `source` and
+/// their [`Position`].
+///
+/// # Example
+/// ```
+/// use json_ld::rdf;
+/// use iref::IriBuf;
+///
+/// let input = "<https://example.com/s> <https://example.com/p> \"value\"@en .\n";
+/// let quads = rdf::nquads::parse::<IriBuf>(input, None).unwrap();
+/// let doc = rdf::from_rdf::<serde_json::Value, IriBuf, _, _, _>(
+///   quads,
+///   rdf::Options::default(),
+///   |s: &str| serde_json::from_str(s)
+/// ).unwrap();
+///
+/// assert_eq!(doc.len(), 1);
+///
+/// let err = rdf::nquads::parse::<IriBuf>("<https://example.com/s> <p> _:o .", None).unwrap_err();
+/// assert_eq!(err.metadata().column, 25);
+/// ```
+pub fn parse<T: Id>(
+	input: &str,
+	source: Option<loader::Id>,
+) -> Result<Vec<Quad<T>>, Loc<ParseError, Position>> {
+	Parser::new(input, source).collect()
+}
+
+/// N-Quads (and N-Triples) parser.
+///
+/// Iterates over the quads of an N-Quads document, one statement per line.
+pub struct Parser<'a, T: Id> {
+	lines: std::iter::Enumerate<std::str::Lines<'a>>,
+	source: Option<loader::Id>,
+	t: std::marker::PhantomData<T>,
+}
+
+impl<'a, T: Id> Parser<'a, T> {
+	/// Creates a new parser for the given input.
+	#[inline(always)]
+	pub fn new(input: &'a str, source: Option<loader::Id>) -> Self {
+		Self {
+			lines: input.lines().enumerate(),
+			source,
+			t: std::marker::PhantomData,
+		}
+	}
+}
+
+impl<'a, T: Id> Iterator for Parser<'a, T> {
+	type Item = Result<Quad<T>, Loc<ParseError, Position>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		for (i, line) in &mut self.lines {
+			let mut cursor = Cursor::new(line, i + 1);
+			match cursor.statement() {
+				Ok(Some(quad)) => return Some(Ok(quad)),
+				Ok(None) => (),
+				Err((e, position)) => return Some(Err(Loc::new(e, self.source, position))),
+			}
+		}
+
+		None
+	}
+}
+
+impl<'a, T: Id> std::iter::FusedIterator for Parser<'a, T> {}
+
+type CursorResult<T> = Result<T, (ParseError, Position)>;
+
+/// Character cursor over a single line.
+struct Cursor<'a> {
+	line: &'a str,
+	offset: usize,
+	position: Position,
+}
+
+impl<'a> Cursor<'a> {
+	fn new(line: &'a str, line_number: usize) -> Self {
+		Self {
+			line,
+			offset: 0,
+			position: Position {
+				line: line_number,
+				column: 1,
+			},
+		}
+	}
+
+	/// Returns the rest of the line.
+	fn rest(&self) -> &'a str {
+		&self.line[self.offset..]
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.rest().chars().next()
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let c = self.peek();
+		if let Some(c) = c {
+			self.offset += c.len_utf8();
+			self.position.column += 1
+		}
+
+		c
+	}
+
+	fn error<U>(&self, e: ParseError) -> CursorResult<U> {
+		Err((e, self.position))
+	}
+
+	/// Consumes the next character, failing if there is none.
+	fn expect_any(&mut self) -> CursorResult<char> {
+		match self.next() {
+			Some(c) => Ok(c),
+			None => self.error(ParseError::UnexpectedEnd),
+		}
+	}
+
+	fn expect(&mut self, expected: char) -> CursorResult<()> {
+		match self.peek() {
+			Some(c) if c == expected => {
+				self.next();
+				Ok(())
+			}
+			Some(c) => self.error(ParseError::Unexpected(c)),
+			None => self.error(ParseError::UnexpectedEnd),
+		}
+	}
+
+	/// Skips whitespaces and comments.
+	fn skip_whitespaces(&mut self) {
+		while let Some(c) = self.peek() {
+			match c {
+				' ' | '\t' | '\r' => {
+					self.next();
+				}
+				'#' => while self.next().is_some() {},
+				_ => break,
+			}
+		}
+	}
+
+	/// Parses a statement, or returns `None` if the line is empty.
+	fn statement<T: Id>(&mut self) -> CursorResult<Option<Quad<T>>> {
+		self.skip_whitespaces();
+		if self.peek().is_none() {
+			return Ok(None);
+		}
+
+		let subject = self.reference()?;
+		self.skip_whitespaces();
+		let predicate = match self.peek() {
+			Some('<') => Reference::Id(self.iri()?),
+			Some(c) => return self.error(ParseError::Unexpected(c)),
+			None => return self.error(ParseError::UnexpectedEnd),
+		};
+		self.skip_whitespaces();
+		let object = match self.peek() {
+			Some('"') => Value::Literal(self.literal()?),
+			_ => Value::Reference(self.reference()?),
+		};
+		self.skip_whitespaces();
+		let graph = match self.peek() {
+			Some('.') => None,
+			_ => Some(self.reference()?),
+		};
+		self.skip_whitespaces();
+		self.expect('.')?;
+		self.skip_whitespaces();
+
+		match self.peek() {
+			Some(c) => self.error(ParseError::Unexpected(c)),
+			None => Ok(Some(Quad::new(subject, predicate, object, graph))),
+		}
+	}
+
+	/// Parses an IRI or blank node identifier.
+	fn reference<T: Id>(&mut self) -> CursorResult<Reference<T>> {
+		match self.peek() {
+			Some('<') => Ok(Reference::Id(self.iri()?)),
+			Some('_') => Ok(Reference::Blank(self.blank_id()?)),
+			Some(c) => self.error(ParseError::Unexpected(c)),
+			None => self.error(ParseError::UnexpectedEnd),
+		}
+	}
+
+	fn iri<T: Id>(&mut self) -> CursorResult<T> {
+		let start = self.position;
+		self.expect('<')?;
+
+		let mut iri = String::new();
+		loop {
+			let position = self.position;
+			match self.expect_any()? {
+				'>' => break,
+				'\\' => match self.expect_any()? {
+					'u' => iri.push(self.unicode_escape(4)?),
+					'U' => iri.push(self.unicode_escape(8)?),
+					_ => return self.error(ParseError::InvalidEscape),
+				},
+				c if c <= ' ' || matches!(c, '<' | '"' | '{' | '}' | '|' | '^' | '`') => {
+					return Err((ParseError::Unexpected(c), position))
+				}
+				c => iri.push(c),
+			}
+		}
+
+		match Iri::new(&iri) {
+			Ok(iri) => Ok(T::from_iri(iri)),
+			Err(_) => Err((ParseError::InvalidIri(iri), start)),
+		}
+	}
+
+	fn blank_id(&mut self) -> CursorResult<BlankId> {
+		self.expect('_')?;
+		self.expect(':')?;
+
+		// A trailing `.` is not part of the label, but the end of the statement.
+		let name = self
+			.rest()
+			.split(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '\u{b7}')))
+			.next()
+			.unwrap()
+			.trim_end_matches('.');
+
+		if name.is_empty() || name.starts_with(['-', '.', '\u{b7}']) {
+			return self.error(ParseError::InvalidBlankId);
+		}
+
+		for _ in name.chars() {
+			self.next();
+		}
+
+		Ok(BlankId::new(name))
+	}
+
+	fn literal<T: Id>(&mut self) -> CursorResult<Literal<T>> {
+		self.expect('"')?;
+
+		let mut value = String::new();
+		loop {
+			match self.expect_any()? {
+				'"' => break,
+				'\\' => {
+					let c = match self.expect_any()? {
+						't' => '\t',
+						'b' => '\u{8}',
+						'n' => '\n',
+						'r' => '\r',
+						'f' => '\u{c}',
+						'"' => '"',
+						'\'' => '\'',
+						'\\' => '\\',
+						'u' => self.unicode_escape(4)?,
+						'U' => self.unicode_escape(8)?,
+						_ => return self.error(ParseError::InvalidEscape),
+					};
+					value.push(c)
+				}
+				'\n' | '\r' => return self.error(ParseError::UnexpectedEnd),
+				c => value.push(c),
+			}
+		}
+
+		match self.peek() {
+			Some('^') => {
+				self.next();
+				self.expect('^')?;
+				Ok(Literal::Typed(value, self.iri()?))
+			}
+			Some('@') => {
+				self.next();
+				let mut tag = String::new();
+				while let Some(c) = self.peek() {
+					if c.is_ascii_alphanumeric() || c == '-' {
+						self.next();
+						tag.push(c)
+					} else {
+						break;
+					}
+				}
+
+				if tag.is_empty() {
+					return self.error(ParseError::InvalidLanguageTag);
+				}
+
+				let tag = match LanguageTagBuf::parse_copy(tag.as_str()) {
+					Ok(tag) => tag.into(),
+					Err(_) => LenientLanguageTagBuf::from(tag),
+				};
+
+				Ok(Literal::LangString(value, tag))
+			}
+			_ => Ok(Literal::Typed(
+				value,
+				T::from_iri(Iri::new(crate::rdf::XSD_STRING).unwrap()),
+			)),
+		}
+	}
+
+	/// Parses the `len` hexadecimal digits of a `\u` or `\U` escape sequence.
+	fn unicode_escape(&mut self, len: usize) -> CursorResult<char> {
+		let mut code = 0;
+		for _ in 0..len {
+			match self.expect_any()?.to_digit(16) {
+				Some(d) => code = code * 16 + d,
+				None => return self.error(ParseError::InvalidEscape),
+			}
+		}
+
+		match std::char::from_u32(code) {
+			Some(c) => Ok(c),
+			None => self.error(ParseError::InvalidEscape),
+		}
+	}
+}
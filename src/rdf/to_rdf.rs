@@ -0,0 +1,403 @@
+use super::{id, Literal, Quad, RdfDirection, Value};
+use crate::{
+	expansion,
+	flattening::{Generator, NodeMap, NodeMapGraph},
+	object::{self, value},
+	util, Coercion, Error, Id, Indexed, LiteralCoercions, Object, ProcessingMode, Reference,
+	Warning,
+};
+use generic_json::{JsonHash, Number};
+
+/// RDF serialization/deserialization options.
+#[derive(Clone, Copy, Default)]
+pub struct Options {
+	/// JSON-LD processing mode.
+	pub processing_mode: ProcessingMode,
+
+	/// Determines how value objects containing a base direction are transformed to
+	/// and from RDF.
+	///
+	/// If `None`, the base direction is ignored.
+	pub rdf_direction: Option<RdfDirection>,
+
+	/// If set to `true`, `xsd:boolean`, `xsd:integer` and `xsd:double` literals
+	/// are converted into native JSON values by [`from_rdf`](super::from_rdf).
+	pub use_native_types: bool,
+
+	/// If set to `true`, `rdf:type` triples are kept as regular properties
+	/// by [`from_rdf`](super::from_rdf) instead of being turned into `@type` entries.
+	pub use_rdf_type: bool,
+
+	/// If set to `true`, triples with a blank node predicate are produced,
+	/// resulting in [generalized RDF](https://www.w3.org/TR/rdf11-concepts/#section-generalized-rdf).
+	///
+	/// Otherwise such triples are dropped,
+	/// and a [`Warning::BlankNodePredicate`] warning is emitted for each of them.
+	pub produce_generalized_rdf: bool,
+
+	/// Literal coercion hooks.
+	///
+	/// The lexical form of every typed literal is passed to the hook
+	/// registered for its datatype, if any, by [`to_rdf`].
+	/// Rejected literals are kept as is,
+	/// and a [`Warning::InvalidLiteral`] warning is emitted for each of them.
+	///
+	/// The hooks are also used during expansion
+	/// when the options are converted into [`expansion::Options`].
+	pub coercions: LiteralCoercions,
+}
+
+impl From<Options> for expansion::Options {
+	fn from(options: Options) -> expansion::Options {
+		expansion::Options {
+			processing_mode: options.processing_mode,
+			coercions: options.coercions,
+			..expansion::Options::default()
+		}
+	}
+}
+
+/// Iterator over the RDF quads of a document.
+///
+/// It is created by the [`to_rdf`] function.
+pub struct Quads<T: Id> {
+	quads: std::vec::IntoIter<Quad<T>>,
+	warnings: Vec<Warning>,
+}
+
+impl<T: Id> Quads<T> {
+	/// Returns the warnings emitted during the conversion.
+	#[inline(always)]
+	pub fn warnings(&self) -> &[Warning] {
+		&self.warnings
+	}
+}
+
+impl<T: Id> Iterator for Quads<T> {
+	type Item = Quad<T>;
+
+	#[inline(always)]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.quads.size_hint()
+	}
+
+	#[inline(always)]
+	fn next(&mut self) -> Option<Quad<T>> {
+		self.quads.next()
+	}
+}
+
+impl<T: Id> ExactSizeIterator for Quads<T> {}
+
+impl<T: Id> std::iter::FusedIterator for Quads<T> {}
+
+/// Converts the given expanded objects into RDF quads.
+///
+/// Implements the [Deserialize JSON-LD to RDF](https://www.w3.org/TR/json-ld11-api/#deserialize-json-ld-to-rdf-algorithm)
+/// algorithm.
+/// Blank node identifiers are relabeled using the given `generator`,
+/// which is also used to generate the blank nodes of lists.
+pub fn to_rdf<J: JsonHash, T: Id, O: IntoIterator<Item = Indexed<Object<J, T>>>>(
+	objects: O,
+	generator: &mut Generator,
+	options: Options,
+) -> Result<Quads<T>, Error> {
+	let mut node_map = NodeMap::new();
+	for object in objects {
+		node_map.extend_with(generator, object)?;
+	}
+
+	Ok(node_map_to_rdf(&node_map, generator, options))
+}
+
+/// Converts the given node map into RDF quads.
+pub fn node_map_to_rdf<J: JsonHash, T: Id>(
+	node_map: &NodeMap<J, T>,
+	generator: &mut Generator,
+	options: Options,
+) -> Quads<T> {
+	let mut quads = Vec::new();
+	let mut warnings = Vec::new();
+
+	graph_to_rdf(
+		node_map.default_graph(),
+		None,
+		generator,
+		options,
+		&mut quads,
+		&mut warnings,
+	);
+
+	let mut graphs: Vec<_> = node_map.graphs().collect();
+	graphs.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+	for (graph_name, graph) in graphs {
+		if graph_name.is_valid() {
+			graph_to_rdf(
+				graph,
+				Some(graph_name),
+				generator,
+				options,
+				&mut quads,
+				&mut warnings,
+			)
+		}
+	}
+
+	Quads {
+		quads: quads.into_iter(),
+		warnings,
+	}
+}
+
+fn graph_to_rdf<J: JsonHash, T: Id>(
+	graph: &NodeMapGraph<J, T>,
+	graph_name: Option<&Reference<T>>,
+	generator: &mut Generator,
+	options: Options,
+	quads: &mut Vec<Quad<T>>,
+	warnings: &mut Vec<Warning>,
+) {
+	let mut nodes: Vec<_> = graph.nodes().collect();
+	nodes.sort_by(|a, b| a.id().unwrap().as_str().cmp(b.id().unwrap().as_str()));
+
+	for node in nodes {
+		let subject = node.id().unwrap();
+		if !subject.is_valid() {
+			continue;
+		}
+
+		for ty in node.types() {
+			if ty.is_valid() {
+				quads.push(Quad::new(
+					subject.clone(),
+					Reference::Id(id(super::RDF_TYPE)),
+					ty.clone().into(),
+					graph_name.cloned(),
+				))
+			}
+		}
+
+		let mut properties: Vec<_> = node.properties().iter().collect();
+		properties.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+		for (property, objects) in properties {
+			match property {
+				Reference::Id(_) => (),
+				// Blank node properties are not allowed in (non-generalized) RDF.
+				Reference::Blank(id) if !options.produce_generalized_rdf => {
+					for _ in objects {
+						warnings.push(Warning::BlankNodePredicate(id.clone()))
+					}
+
+					continue;
+				}
+				Reference::Blank(_) => (),
+				Reference::Invalid(_) => continue,
+			}
+
+			for object in objects {
+				let mut triples = Vec::new();
+				if let Some(value) =
+					object_to_rdf(object, generator, options, &mut triples, warnings)
+				{
+					triples.push((subject.clone(), property.clone(), value))
+				}
+
+				quads.extend(
+					triples
+						.into_iter()
+						.map(|(s, p, o)| Quad::new(s, p, o, graph_name.cloned())),
+				)
+			}
+		}
+	}
+}
+
+type Triple<T> = (Reference<T>, Reference<T>, Value<T>);
+
+/// Converts the given object into an RDF value.
+///
+/// Any additional triple generated in the process (for lists or compound literals)
+/// is pushed into `triples`.
+/// Returns `None` if the object cannot be represented in RDF.
+fn object_to_rdf<J: JsonHash, T: Id>(
+	object: &Indexed<Object<J, T>>,
+	generator: &mut Generator,
+	options: Options,
+	triples: &mut Vec<Triple<T>>,
+	warnings: &mut Vec<Warning>,
+) -> Option<Value<T>> {
+	match object.inner() {
+		Object::Node(node) => match node.id() {
+			Some(r) if r.is_valid() => Some(r.clone().into()),
+			_ => None,
+		},
+		Object::List(items) => Some(list_to_rdf(items, generator, options, triples, warnings)),
+		Object::Value(value) => value_to_rdf(value, generator, options, triples, warnings),
+	}
+}
+
+/// Converts the given value object into an RDF value.
+fn value_to_rdf<J: JsonHash, T: Id>(
+	value: &object::Value<J, T>,
+	generator: &mut Generator,
+	options: Options,
+	triples: &mut Vec<Triple<T>>,
+	warnings: &mut Vec<Warning>,
+) -> Option<Value<T>> {
+	match value {
+		object::Value::Literal(lit, ty) => {
+			let (lexical_form, default_ty) = match lit {
+				value::Literal::Null => return None,
+				value::Literal::Boolean(b) => (b.to_string(), super::XSD_BOOLEAN),
+				value::Literal::Number(n) => {
+					let is_double = ty
+						.as_ref()
+						.map(|ty| ty.as_iri().as_str() == super::XSD_DOUBLE)
+						.unwrap_or(false);
+					match integer_lexical_form(n) {
+						Some(i) if !is_double => (i, super::XSD_INTEGER),
+						_ => (double_lexical_form(n.as_f64_lossy()), super::XSD_DOUBLE),
+					}
+				}
+				value::Literal::String(s) => (s.as_str().to_string(), super::XSD_STRING),
+			};
+
+			let ty = match ty {
+				Some(ty) => ty.clone(),
+				None => id(default_ty),
+			};
+
+			let lexical_form = match options
+				.coercions
+				.coerce(ty.as_iri().as_str(), &lexical_form)
+			{
+				Coercion::Keep => lexical_form,
+				Coercion::Replace(new_form) => new_form,
+				Coercion::Invalid => {
+					warnings.push(Warning::InvalidLiteral(
+						lexical_form.clone(),
+						ty.as_iri().into(),
+					));
+					lexical_form
+				}
+			};
+
+			Some(Literal::Typed(lexical_form, ty).into())
+		}
+		object::Value::LangString(lang_string) => {
+			let s = lang_string.as_str().to_string();
+			match (lang_string.direction(), options.rdf_direction) {
+				(Some(direction), Some(RdfDirection::I18nDatatype)) => {
+					let language = lang_string
+						.language()
+						.map(|l| l.as_str().to_lowercase())
+						.unwrap_or_default();
+					let ty = format!("{}{}_{}", super::I18N, language, direction);
+					let ty = T::from_iri(iref::Iri::new(&ty).ok()?);
+					Some(Literal::Typed(s, ty).into())
+				}
+				(Some(direction), Some(RdfDirection::CompoundLiteral)) => {
+					let node = Reference::Blank(generator.generate(None));
+					triples.push((
+						node.clone(),
+						Reference::Id(id(super::RDF_VALUE)),
+						Literal::Typed(s, id(super::XSD_STRING)).into(),
+					));
+
+					if let Some(language) = lang_string.language() {
+						triples.push((
+							node.clone(),
+							Reference::Id(id(super::RDF_LANGUAGE)),
+							Literal::Typed(language.as_str().to_string(), id(super::XSD_STRING))
+								.into(),
+						))
+					}
+
+					triples.push((
+						node.clone(),
+						Reference::Id(id(super::RDF_DIRECTION)),
+						Literal::Typed(direction.to_string(), id(super::XSD_STRING)).into(),
+					));
+
+					Some(node.into())
+				}
+				_ => match lang_string.language() {
+					Some(language) if language.is_well_formed() => {
+						Some(Literal::LangString(s, language.cloned()).into())
+					}
+					Some(_) => None,
+					None => Some(Literal::Typed(s, id(super::XSD_STRING)).into()),
+				},
+			}
+		}
+		object::Value::Json(json) => {
+			Some(Literal::Typed(util::canonical_json(json), id(super::RDF_JSON)).into())
+		}
+		object::Value::Invalid(_) => None,
+	}
+}
+
+/// Converts the given list into RDF.
+///
+/// Implements the [List to RDF Conversion](https://www.w3.org/TR/json-ld11-api/#list-to-rdf-conversion)
+/// algorithm. Returns the head of the list.
+fn list_to_rdf<J: JsonHash, T: Id>(
+	items: &[Indexed<Object<J, T>>],
+	generator: &mut Generator,
+	options: Options,
+	triples: &mut Vec<Triple<T>>,
+	warnings: &mut Vec<Warning>,
+) -> Value<T> {
+	if items.is_empty() {
+		return Reference::Id(id(super::RDF_NIL)).into();
+	}
+
+	let bnodes: Vec<_> = items.iter().map(|_| generator.generate(None)).collect();
+	for (i, item) in items.iter().enumerate() {
+		let subject = Reference::Blank(bnodes[i].clone());
+
+		let mut embedded_triples = Vec::new();
+		if let Some(value) =
+			object_to_rdf(item, generator, options, &mut embedded_triples, warnings)
+		{
+			triples.push((subject.clone(), Reference::Id(id(super::RDF_FIRST)), value))
+		}
+
+		let rest = match bnodes.get(i + 1) {
+			Some(next) => Reference::Blank(next.clone()),
+			None => Reference::Id(id(super::RDF_NIL)),
+		};
+		triples.push((subject, Reference::Id(id(super::RDF_REST)), rest.into()));
+		triples.extend(embedded_triples)
+	}
+
+	bnodes[0].clone().into()
+}
+
+/// Returns the canonical `xsd:integer` lexical form of the given number,
+/// if it has no fractional part and is less than 10^21.
+fn integer_lexical_form<N: Number>(n: &N) -> Option<String> {
+	if let Some(i) = n.as_i64() {
+		return Some(i.to_string());
+	}
+
+	if let Some(u) = n.as_u64() {
+		return Some(u.to_string());
+	}
+
+	let f = n.as_f64_lossy();
+	if f.fract() == 0.0 && f.abs() < 1e21 {
+		Some(format!("{:.0}", f))
+	} else {
+		None
+	}
+}
+
+/// Returns the canonical `xsd:double` lexical form of the given number.
+fn double_lexical_form(f: f64) -> String {
+	let repr = format!("{:E}", f);
+	match repr.find('E') {
+		Some(i) if !repr[..i].contains('.') => format!("{}.0{}", &repr[..i], &repr[i..]),
+		_ => repr,
+	}
+}
@@ -1,124 +1,140 @@
 //! Simple document and context loader based on [`reqwest`](https://crates.io/crates/reqwest)
+//!
+//! This module is enabled by the `reqwest-loader` feature.
+//! The loading logic is provided by the [`http`](crate::http) module,
+//! this module only provides the [`Client`] backend sending the requests.
 
-use crate::{loader, Error, ErrorCode, RemoteDocument};
+use crate::{
+	http::{self, Backend, Headers, RequestError, RequestErrorKind, Response},
+	loader, Error, ErrorCode, RemoteDocument,
+};
 use futures::future::{BoxFuture, FutureExt};
-use generic_json::Json;
-use iref::{Iri, IriBuf};
-use std::collections::HashMap;
+use futures::stream::StreamExt;
+use iref::Iri;
+use std::time::Duration;
 
-pub fn is_json_media_type(ty: &str) -> bool {
-	ty == "application/json" || ty == "application/ld+json"
-}
-
-pub async fn load_remote_json_ld_document<J, P>(url: Iri<'_>, parser: &mut P) -> Result<J, Error>
-where
-	P: Send + Sync + FnMut(&str) -> Result<J, Error>,
-{
-	log::info!("loading remote document `{}'", url);
-	use reqwest::header::*;
-
-	let client = reqwest::Client::new();
-	let request = client
-		.get(url.as_str())
-		.header(ACCEPT, "application/ld+json, application/json");
-	let response = request.send().await?;
-
-	if response
-		.headers()
-		.get_all(CONTENT_TYPE)
-		.iter()
-		.any(|value| {
-			if let Ok(value) = value.to_str() {
-				is_json_media_type(value)
-			} else {
-				false
-			}
-		}) {
-		let body = response.text().await?;
-		let doc = (*parser)(body.as_str())?;
-		Ok(doc)
-	} else {
-		Err(ErrorCode::LoadingDocumentFailed.into())
-	}
-}
-
-pub struct Loader<J> {
-	namespace: HashMap<IriBuf, loader::Id>,
-	cache: Vec<(J, IriBuf)>,
-	parser: Box<dyn 'static + Send + Sync + FnMut(&str) -> Result<J, Error>>,
-}
+pub use crate::http::{
+	accept, is_json_media_type, media_type_essence, parse_link_header, CacheInfo, Integrity,
+	Limits, Link, Metrics, Options, Retry, Timeout, ACCEPT, CONTEXT_REL,
+};
 
-impl<J: Clone + Send> Loader<J> {
-	pub fn new<E: 'static + std::error::Error>(
-		mut parser: impl 'static + Send + Sync + FnMut(&str) -> Result<J, E>,
-	) -> Self {
-		Self {
-			namespace: HashMap::new(),
-			cache: Vec::new(),
-			parser: Box::new(move |s| {
-				parser(s).map_err(|e| Error::with_source(ErrorCode::LoadingDocumentFailed, e))
-			}),
-		}
-	}
+/// HTTP backend based on a `reqwest` client.
+///
+/// The underlying client never follows redirections,
+/// they are followed by the loader.
+#[derive(Clone)]
+pub struct Client(reqwest::Client);
 
-	/// Allocate a identifier to the given IRI.
-	fn allocate(&mut self, iri: IriBuf, doc: J) -> loader::Id {
-		let id = loader::Id::new(self.cache.len());
-		self.namespace.insert(iri.clone(), id);
-		self.cache.push((doc, iri));
-		id
+impl Client {
+	/// Creates a new client.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self::from_builder(reqwest::Client::builder()).expect("unable to build HTTP client")
 	}
 
-	pub async fn load(&mut self, url: Iri<'_>) -> Result<RemoteDocument<J>, Error> {
-		let url = IriBuf::from(url);
-		match self.namespace.get(&url) {
-			Some(id) => Ok(RemoteDocument::new(
-				self.cache[id.unwrap()].0.clone(),
-				url,
-				*id,
-			)),
-			None => {
-				let doc = load_remote_json_ld_document(url.as_iri(), &mut self.parser).await?;
-				let id = self.allocate(url.clone(), doc.clone());
-				Ok(RemoteDocument::new(doc, url, id))
-			}
-		}
+	/// Creates a new client from the given builder,
+	/// for instance to configure a proxy or TLS settings.
+	///
+	/// The redirect policy of the builder is overridden.
+	#[inline(always)]
+	pub fn from_builder(builder: reqwest::ClientBuilder) -> Result<Self, reqwest::Error> {
+		Ok(Self(
+			builder
+				.redirect(reqwest::redirect::Policy::none())
+				.build()?,
+		))
 	}
 }
 
-impl<J: Json + Clone + Send + Sync> crate::Loader for Loader<J> {
-	type Document = J;
-
+impl Default for Client {
 	#[inline(always)]
-	fn id(&self, iri: Iri<'_>) -> Option<loader::Id> {
-		self.namespace.get(&IriBuf::from(iri)).cloned()
+	fn default() -> Self {
+		Self::new()
 	}
+}
 
-	#[inline(always)]
-	fn iri(&self, id: loader::Id) -> Option<Iri<'_>> {
-		self.cache.get(id.unwrap()).map(|(_, iri)| iri.as_iri())
-	}
+/// Turns a `reqwest` error into a request error.
+fn request_error(e: reqwest::Error) -> RequestError {
+	let kind = if e.is_timeout() {
+		RequestErrorKind::Timeout
+	} else if e.is_connect() {
+		RequestErrorKind::Connect
+	} else {
+		RequestErrorKind::Other
+	};
+
+	RequestError::new(kind, e)
+}
 
-	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<J>, Error>> {
-		let url: IriBuf = url.into();
+impl Backend for Client {
+	fn get<'a>(
+		&'a self,
+		url: Iri<'a>,
+		headers: &'a Headers,
+	) -> BoxFuture<'a, Result<Response, RequestError>> {
 		async move {
-			match self.namespace.get(&url) {
-				Some(id) => Ok(RemoteDocument::new(
-					self.cache[id.unwrap()].0.clone(),
-					url,
-					*id,
-				)),
-				None => {
-					let doc = load_remote_json_ld_document(url.as_iri(), &mut self.parser).await?;
-					let id = self.allocate(url.clone(), doc.clone());
-					Ok(RemoteDocument::new(doc, url, id))
-				}
+			let mut request = self.0.get(url.as_str());
+			for (name, value) in headers.iter() {
+				request = request.header(name, value)
 			}
+
+			let response = request.send().await.map_err(request_error)?;
+			let status = response.status().as_u16();
+			let headers = response
+				.headers()
+				.iter()
+				.filter_map(|(name, value)| {
+					Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+				})
+				.collect();
+
+			let body = futures::stream::unfold(Some(response), |response| async move {
+				let mut response = response?;
+				match response.chunk().await {
+					Ok(Some(chunk)) => Some((Ok(chunk.to_vec()), Some(response))),
+					Ok(None) => None,
+					Err(e) => Some((Err(request_error(e)), None)),
+				}
+			})
+			.boxed();
+
+			Ok(Response {
+				status,
+				headers,
+				body,
+			})
 		}
 		.boxed()
 	}
+
+	#[inline(always)]
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+		tokio::time::sleep(duration).boxed()
+	}
 }
 
+/// Loads the remote JSON-LD document behind the given URL
+/// using a new [`Client`].
+///
+/// See [`http::load_remote_json_ld_document`].
+pub async fn load_remote_json_ld_document<J, P>(
+	url: Iri<'_>,
+	source: loader::Id,
+	profile: Option<loader::Profile>,
+	options: &Options,
+	parser: &P,
+) -> Result<RemoteDocument<J>, Error>
+where
+	P: Send + Sync + Fn(&str) -> Result<J, Error>,
+{
+	http::load_remote_json_ld_document(&Client::new(), url, source, profile, options, parser).await
+}
+
+/// Remote document loader based on `reqwest`.
+///
+/// See [`http::Loader`].
+pub type Loader<J> = http::Loader<Client, J>;
+
 impl From<reqwest::Error> for Error {
 	fn from(e: reqwest::Error) -> Error {
 		Error::with_source(ErrorCode::LoadingDocumentFailed, e)
@@ -0,0 +1,114 @@
+//! Skolemization of blank nodes.
+//!
+//! Skolemization replaces blank node identifiers with globally unique IRIs,
+//! called *Skolem IRIs*, as described in the
+//! [RDF 1.1 Concepts](https://www.w3.org/TR/rdf11-concepts/#section-skolemization)
+//! specification.
+//! Skolem IRIs are of the form `{scheme}://{authority}/.well-known/genid/{name}`
+//! where `name` is the (percent-encoded) name of the blank node identifier.
+//! Deskolemization is the inverse transformation, turning Skolem IRIs back into
+//! blank node identifiers.
+use crate::{BlankId, Id, Indexed, Object, Reference};
+use generic_json::JsonHash;
+use iref::Iri;
+use std::fmt::Write;
+
+/// Path prefix of Skolem IRIs.
+pub const GENID_PATH: &str = "/.well-known/genid/";
+
+/// Returns the Skolem IRI of the given blank node identifier.
+///
+/// The scheme and authority of the Skolem IRI are the ones of `base_iri`.
+pub fn skolem_iri<T: Id>(base_iri: Iri, id: &BlankId) -> T {
+	let mut iri = base_iri.scheme().as_str().to_string();
+	iri.push(':');
+	if let Some(authority) = base_iri.authority() {
+		iri.push_str("//");
+		iri.push_str(authority.as_str())
+	}
+
+	iri.push_str(GENID_PATH);
+	for b in id.name().bytes() {
+		if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+			iri.push(b as char)
+		} else {
+			write!(iri, "%{:02X}", b).unwrap()
+		}
+	}
+
+	T::from_iri(Iri::new(&iri).unwrap())
+}
+
+/// Skolemize the given expanded object.
+///
+/// Every blank node identifier is replaced by its Skolem IRI
+/// (see [`skolem_iri`]).
+pub fn skolemize<J: JsonHash, T: Id>(
+	object: Indexed<Object<J, T>>,
+	base_iri: Iri,
+) -> Indexed<Object<J, T>> {
+	object.map_references(&mut |r| match r {
+		Reference::Blank(id) => Reference::Id(skolem_iri(base_iri, &id)),
+		r => r,
+	})
+}
+
+/// Returns the blank node identifier of the given Skolem IRI.
+///
+/// If `base_iri` is given, the Skolem IRI must have the same scheme and authority.
+/// Returns `None` if `iri` is not a Skolem IRI.
+pub fn blank_id(iri: Iri, base_iri: Option<Iri>) -> Option<BlankId> {
+	if let Some(base_iri) = base_iri {
+		if iri.scheme() != base_iri.scheme() || iri.authority() != base_iri.authority() {
+			return None;
+		}
+	}
+
+	if iri.query().is_some() || iri.fragment().is_some() {
+		return None;
+	}
+
+	let path = iri.path();
+	let encoded_name = path.as_str().strip_prefix(GENID_PATH)?;
+	if encoded_name.is_empty() || encoded_name.contains('/') {
+		return None;
+	}
+
+	let mut name = Vec::with_capacity(encoded_name.len());
+	let mut bytes = encoded_name.bytes();
+	while let Some(b) = bytes.next() {
+		if b == b'%' {
+			let hex = [bytes.next()?, bytes.next()?];
+			let hex = std::str::from_utf8(&hex).ok()?;
+			name.push(u8::from_str_radix(hex, 16).ok()?)
+		} else {
+			name.push(b)
+		}
+	}
+
+	Some(BlankId::new(&String::from_utf8(name).ok()?))
+}
+
+/// Deskolemize the given expanded object.
+///
+/// Every Skolem IRI is replaced by its blank node identifier
+/// (see [`blank_id`]).
+/// Occurrences of the same Skolem IRI are replaced by the same blank node identifier,
+/// preserving co-reference.
+///
+/// If `base_iri` is given, only the Skolem IRIs with the same scheme and
+/// authority are deskolemized.
+/// Otherwise, Skolem IRIs with different authorities but the same name are
+/// replaced by the same blank node identifier.
+pub fn deskolemize<J: JsonHash, T: Id>(
+	object: Indexed<Object<J, T>>,
+	base_iri: Option<Iri>,
+) -> Indexed<Object<J, T>> {
+	object.map_references(&mut |r| match r {
+		Reference::Id(id) => match blank_id(id.as_iri(), base_iri) {
+			Some(blank_id) => Reference::Blank(blank_id),
+			None => Reference::Id(id),
+		},
+		r => r,
+	})
+}
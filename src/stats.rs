@@ -0,0 +1,162 @@
+use crate::future::BoxFuture;
+use crate::{
+	context::{Loader, RemoteContext},
+	Error, Id, Indexed, Node, Object,
+};
+use generic_json::JsonHash;
+use iref::{Iri, IriBuf};
+use std::sync::atomic::{self, AtomicUsize};
+use std::time::Duration;
+
+/// Processing statistics.
+///
+/// Returned by [`expansion::expand_with_stats`](crate::expansion::expand_with_stats),
+/// [`ExpandedDocument::stats`](crate::ExpandedDocument::stats)
+/// (with the [`collect_stats`](crate::expansion::Options::collect_stats) expansion option)
+/// and [`Document::compact_with_stats`](crate::Document::compact_with_stats).
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, expansion, Document, NoLoader};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let doc = json!({
+///   "@context": { "name": "https://schema.org/name", "knows": "https://schema.org/knows" },
+///   "@id": "https://example.org/alice",
+///   "name": "Alice",
+///   "knows": { "@list": [ { "name": "Bob" } ] },
+///   "nickname": "Al"
+/// });
+///
+/// let options = expansion::Options { collect_stats: true, ..Default::default() };
+/// let expanded = doc
+///   .expand_with(None, &context::Json::<Value>::new(None), &NoLoader::<Value>::new(), options)
+///   .await
+///   .unwrap();
+///
+/// let stats = expanded.stats().unwrap();
+/// assert_eq!(stats.nodes, 2);
+/// assert_eq!(stats.values, 2);
+/// assert_eq!(stats.lists, 1);
+/// assert_eq!(stats.dropped_keys, 1);
+/// assert_eq!(stats.contexts_processed, 1);
+/// assert_eq!(stats.remote_loads, 0);
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Stats {
+	/// Number of node objects in the expanded document, at any depth.
+	pub nodes: usize,
+
+	/// Number of value objects in the expanded document, at any depth.
+	pub values: usize,
+
+	/// Number of list objects in the expanded document, at any depth.
+	pub lists: usize,
+
+	/// Number of keys dropped during expansion.
+	pub dropped_keys: usize,
+
+	/// Number of local contexts processed during expansion
+	/// (embedded, property-scoped and type-scoped contexts).
+	///
+	/// Remote contexts referenced by these contexts are not counted.
+	pub contexts_processed: usize,
+
+	/// Number of remote contexts loaded during expansion.
+	pub remote_loads: usize,
+
+	/// Time spent expanding the document.
+	pub expansion_time: Duration,
+
+	/// Time spent compacting the document.
+	///
+	/// Zero if the document has not been compacted.
+	pub compaction_time: Duration,
+}
+
+impl Stats {
+	/// Counts the nodes, values and lists in the given expanded objects.
+	pub(crate) fn count_objects<'a, J: 'a + JsonHash, T: 'a + Id>(
+		&mut self,
+		objects: impl IntoIterator<Item = &'a Indexed<Object<J, T>>>,
+	) {
+		for object in objects {
+			match object.inner() {
+				Object::Value(_) => self.values += 1,
+				Object::List(items) => {
+					self.lists += 1;
+					self.count_objects(items)
+				}
+				Object::Node(node) => self.count_node(node),
+			}
+		}
+	}
+
+	fn count_node<J: JsonHash, T: Id>(&mut self, node: &Node<J, T>) {
+		self.nodes += 1;
+
+		if let Some(graph) = &node.graph {
+			self.count_objects(graph)
+		}
+
+		if let Some(included) = &node.included {
+			for node in included {
+				self.count_node(node)
+			}
+		}
+
+		for (_, values) in &node.properties {
+			self.count_objects(values)
+		}
+
+		for (_, nodes) in &node.reverse_properties {
+			for node in nodes {
+				self.count_node(node)
+			}
+		}
+	}
+}
+
+/// Context loader counting the loaded contexts.
+pub(crate) struct CountingLoader<'a, L> {
+	loader: &'a L,
+	loads: AtomicUsize,
+}
+
+impl<'a, L> CountingLoader<'a, L> {
+	pub(crate) fn new(loader: &'a L) -> Self {
+		Self {
+			loader,
+			loads: AtomicUsize::new(0),
+		}
+	}
+
+	/// Returns the number of contexts loaded so far.
+	pub(crate) fn loads(&self) -> usize {
+		self.loads.load(atomic::Ordering::Relaxed)
+	}
+}
+
+impl<'l, L: Loader> Loader for CountingLoader<'l, L> {
+	type Output = L::Output;
+
+	#[inline(always)]
+	fn id(&self, iri: Iri<'_>) -> Option<crate::loader::Id> {
+		self.loader.id(iri)
+	}
+
+	#[inline(always)]
+	fn iri(&self, id: crate::loader::Id) -> Option<IriBuf> {
+		self.loader.iri(id)
+	}
+
+	fn load_context<'a>(
+		&'a self,
+		url: Iri,
+	) -> BoxFuture<'a, Result<RemoteContext<Self::Output>, Error>> {
+		self.loads.fetch_add(1, atomic::Ordering::Relaxed);
+		self.loader.load_context(url)
+	}
+}
@@ -0,0 +1,127 @@
+//! Simple document and context loader based on [`surf`](https://crates.io/crates/surf)
+//!
+//! This module is enabled by the `surf-loader` feature.
+//! The loading logic is provided by the [`http`](crate::http) module,
+//! this module only provides the [`Client`] backend sending the requests.
+//! Unlike `reqwest` and `hyper`, `surf` does not require the `tokio` runtime:
+//! delays are implemented with [`async-std`](https://crates.io/crates/async-std).
+
+use crate::http::{self, Backend, Headers, RequestError, RequestErrorKind, Response};
+use futures::future::{BoxFuture, FutureExt};
+use futures::io::AsyncReadExt;
+use futures::stream::StreamExt;
+use iref::Iri;
+use std::time::Duration;
+
+/// Size of the buffer used to read response bodies.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// HTTP backend based on a `surf` client.
+///
+/// The client must not follow redirections
+/// (no redirect middleware must be registered),
+/// they are followed by the loader.
+#[derive(Clone)]
+pub struct Client(surf::Client);
+
+impl Client {
+	/// Creates a new client.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self(surf::Client::new())
+	}
+}
+
+impl Default for Client {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl From<surf::Client> for Client {
+	#[inline(always)]
+	fn from(client: surf::Client) -> Self {
+		Self(client)
+	}
+}
+
+/// Returns the kind of request error corresponding to the given I/O error.
+fn io_error_kind(e: &std::io::Error) -> RequestErrorKind {
+	match e.kind() {
+		std::io::ErrorKind::TimedOut => RequestErrorKind::Timeout,
+		std::io::ErrorKind::ConnectionRefused
+		| std::io::ErrorKind::ConnectionReset
+		| std::io::ErrorKind::ConnectionAborted
+		| std::io::ErrorKind::NotConnected
+		| std::io::ErrorKind::AddrNotAvailable => RequestErrorKind::Connect,
+		_ => RequestErrorKind::Other,
+	}
+}
+
+/// Turns a `surf` error into a request error.
+fn request_error(e: surf::Error) -> RequestError {
+	let kind = match e.downcast_ref::<std::io::Error>() {
+		Some(e) => io_error_kind(e),
+		None => RequestErrorKind::Other,
+	};
+
+	RequestError::new(kind, e.into_inner())
+}
+
+impl Backend for Client {
+	fn get<'a>(
+		&'a self,
+		url: Iri<'a>,
+		headers: &'a Headers,
+	) -> BoxFuture<'a, Result<Response, RequestError>> {
+		async move {
+			let mut request = self.0.get(url.as_str());
+			for (name, value) in headers.iter() {
+				request = request.header(name, value)
+			}
+
+			let response = request.await.map_err(request_error)?;
+			let status = response.status() as u16;
+			let headers = response
+				.iter()
+				.flat_map(|(name, values)| {
+					values
+						.iter()
+						.map(move |value| (name.as_str().to_string(), value.as_str().to_string()))
+				})
+				.collect();
+
+			let body = futures::stream::unfold(Some(response), |response| async move {
+				let mut response = response?;
+				let mut chunk = vec![0; CHUNK_SIZE];
+				match response.read(&mut chunk).await {
+					Ok(0) => None,
+					Ok(len) => {
+						chunk.truncate(len);
+						Some((Ok(chunk), Some(response)))
+					}
+					Err(e) => Some((Err(RequestError::new(io_error_kind(&e), e)), None)),
+				}
+			})
+			.boxed();
+
+			Ok(Response {
+				status,
+				headers,
+				body,
+			})
+		}
+		.boxed()
+	}
+
+	#[inline(always)]
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+		async_std::task::sleep(duration).boxed()
+	}
+}
+
+/// Remote document loader based on `surf`.
+///
+/// See [`http::Loader`].
+pub type Loader<J> = http::Loader<Client, J>;
@@ -199,6 +199,14 @@ impl<K: JsonBuild> util::AsAnyJson<K> for Keyword {
 	}
 }
 
+/// The JSON-LD-star `@annotation` keyword (experimental).
+///
+/// It is not part of the JSON-LD 1.1 keywords, hence not a [`Keyword`] variant.
+/// Its entries are only processed when the
+/// [`rdf_star`](crate::expansion::Options::rdf_star) expansion option is enabled.
+#[cfg(feature = "rdf-star")]
+pub const ANNOTATION: &str = "@annotation";
+
 pub fn is_keyword(str: &str) -> bool {
 	Keyword::try_from(str).is_ok()
 }
@@ -1,4 +1,4 @@
-use generic_json::{Json, JsonHash, ValueRef};
+use generic_json::{Json, JsonHash, Number, ValueRef};
 use std::{
 	collections::hash_map::DefaultHasher,
 	hash::{Hash, Hasher},
@@ -98,3 +98,96 @@ pub fn hash_json<J: JsonHash, H: Hasher>(json: &J, hasher: &mut H) {
 		}
 	}
 }
+
+/// Serialize a JSON value into its canonical form.
+///
+/// Follows the [JSON Canonicalization Scheme (JCS)](https://tools.ietf.org/html/rfc8785):
+/// no whitespace, object entries sorted by key (compared as UTF-16 code units) and
+/// numbers formatted as ECMAScript would.
+/// This is notably used to serialize JSON literals into RDF.
+pub fn canonical_json<J: Json>(json: &J) -> String {
+	let mut result = String::new();
+	write_canonical_json(json, &mut result);
+	result
+}
+
+fn write_canonical_json<J: Json>(json: &J, result: &mut String) {
+	use cc_traits::{Iter, MapIter};
+	match json.as_value_ref() {
+		ValueRef::Null => result.push_str("null"),
+		ValueRef::Boolean(b) => result.push_str(if b { "true" } else { "false" }),
+		ValueRef::Number(n) => result.push_str(&canonical_number(n)),
+		ValueRef::String(s) => write_canonical_string(s, result),
+		ValueRef::Array(ary) => {
+			result.push('[');
+			for (i, item) in ary.iter().enumerate() {
+				if i > 0 {
+					result.push(',')
+				}
+
+				write_canonical_json(&*item, result)
+			}
+			result.push(']')
+		}
+		ValueRef::Object(obj) => {
+			let mut entries: Vec<_> = obj.iter().collect();
+			entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+			result.push('{');
+			for (i, (key, value)) in entries.into_iter().enumerate() {
+				if i > 0 {
+					result.push(',')
+				}
+
+				write_canonical_string(&key, result);
+				result.push(':');
+				write_canonical_json(&*value, result)
+			}
+			result.push('}')
+		}
+	}
+}
+
+fn canonical_number<N: Number>(n: &N) -> String {
+	if let Some(i) = n.as_i64() {
+		return i.to_string();
+	}
+
+	if let Some(u) = n.as_u64() {
+		return u.to_string();
+	}
+
+	let f = n.as_f64_lossy();
+	let abs = f.abs();
+	if f == 0.0 {
+		"0".to_string()
+	} else if (1e-6..1e21).contains(&abs) {
+		f.to_string()
+	} else {
+		let repr = format!("{:e}", f);
+		match repr.find('e') {
+			Some(i) if !repr[(i + 1)..].starts_with('-') => {
+				format!("{}e+{}", &repr[..i], &repr[(i + 1)..])
+			}
+			_ => repr,
+		}
+	}
+}
+
+pub(crate) fn write_canonical_string(s: &str, result: &mut String) {
+	result.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\u{08}' => result.push_str("\\b"),
+			'\u{0c}' => result.push_str("\\f"),
+			'\n' => result.push_str("\\n"),
+			'\r' => result.push_str("\\r"),
+			'\t' => result.push_str("\\t"),
+			c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+			c => result.push(c),
+		}
+	}
+	result.push('"')
+}
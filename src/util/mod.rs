@@ -1,4 +1,5 @@
 //! Utility functions.
+use indexmap::IndexSet;
 use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
@@ -34,6 +35,29 @@ pub fn hash_set_opt<T: Hash, H: Hasher>(set_opt: &Option<HashSet<T>>, hasher: &m
 	}
 }
 
+/// Hash an [`IndexSet`].
+///
+/// Just like [`hash_set`], the resulting hash does not depend on the
+/// insertion order of the elements, to stay consistent with the
+/// order-independent equality of [`IndexSet`].
+pub fn hash_index_set<T: Hash, H: Hasher>(set: &IndexSet<T>, hasher: &mut H) {
+	let mut hash = 0;
+	for item in set {
+		let mut h = DefaultHasher::new();
+		item.hash(&mut h);
+		hash = u64::wrapping_add(hash, h.finish());
+	}
+
+	hasher.write_u64(hash);
+}
+
+/// Hash an optional [`IndexSet`].
+pub fn hash_index_set_opt<T: Hash, H: Hasher>(set_opt: &Option<IndexSet<T>>, hasher: &mut H) {
+	if let Some(set) = set_opt.as_ref() {
+		hash_index_set(set, hasher)
+	}
+}
+
 /// Hash a [`HashMap`].
 ///
 /// The standard library does not provide (yet) a `Hash` implementation
@@ -1,9 +1,34 @@
+//! Vocabularies.
+//!
+//! This module provides the [`Vocab`] trait and [`Lexicon`] identifier type,
+//! used to represent a statically known set of IRIs efficiently,
+//! and `enum` types for some well-known vocabularies:
+//! [`Rdf`], [`Xsd`], [`Schema`], [`Foaf`] and [`DcTerms`].
+//! Those types can be used as [`Vocab`] for a [`Lexicon`],
+//! or converted into any identifier, [`Reference`] or [`Term`](crate::syntax::Term).
+//!
+//! # Example
+//! ```
+//! use json_ld::{context, syntax::Type, vocab::{Schema, Xsd}, Context, ContextBuilder};
+//! use iref::AsIri;
+//! use serde_json::Value;
+//!
+//! let context: context::Json<Value> = ContextBuilder::new()
+//!   .term("name", Schema::Name.as_iri())
+//!   .typed_term("created", Schema::DateCreated.as_iri(), Type::Ref(Xsd::DateTime.id()))
+//!   .build();
+//! assert_eq!(context.get("name").unwrap().value, Some(Schema::Name.term()));
+//! ```
 use crate::{Id, Reference, ToReference};
 use iref::{AsIri, Iri, IriBuf};
 use std::convert::TryFrom;
 use std::fmt;
 use std::hash::Hash;
 
+mod well_known;
+
+pub use well_known::*;
+
 /// Vocabulary type.
 ///
 /// Directly using [`IriBuf`] to identify each node
@@ -27,10 +52,7 @@ where
 {
 	#[inline]
 	fn from_iri(iri: Iri) -> Option<Self> {
-		match T::try_from(iri) {
-			Ok(t) => Some(t),
-			Err(_) => None,
-		}
+		T::try_from(iri).ok()
 	}
 }
 
@@ -0,0 +1,352 @@
+use crate::{syntax::Term, Id, Reference};
+use iref::{AsIri, Iri};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Defines a well-known vocabulary `enum` type.
+///
+/// Each variant is given the IRI obtained by concatenating
+/// the vocabulary namespace and the given local name.
+macro_rules! vocabulary {
+	(
+		$(#[$meta:meta])*
+		$name:ident ($prefix:literal = $namespace:literal) {
+			$($(#[$vmeta:meta])* $variant:ident = $local:literal),* $(,)?
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+		pub enum $name {
+			$($(#[$vmeta])* $variant),*
+		}
+
+		impl $name {
+			/// Conventional prefix of the vocabulary.
+			pub const PREFIX: &'static str = $prefix;
+
+			/// Namespace IRI of the vocabulary.
+			pub const NAMESPACE: &'static str = $namespace;
+
+			/// All the variants of the vocabulary.
+			pub const ALL: &'static [$name] = &[$($name::$variant),*];
+
+			/// Returns the IRI of this item, as a string.
+			#[inline(always)]
+			pub const fn as_str(&self) -> &'static str {
+				match self {
+					$($name::$variant => concat!($namespace, $local)),*
+				}
+			}
+
+			/// Returns the compact IRI of this item, using the conventional prefix
+			/// (for instance `rdf:type`).
+			#[inline(always)]
+			pub const fn compact_iri(&self) -> &'static str {
+				match self {
+					$($name::$variant => concat!($prefix, ":", $local)),*
+				}
+			}
+
+			/// Returns the item matching the given IRI, if any.
+			#[inline(always)]
+			pub fn from_iri_str(iri: &str) -> Option<Self> {
+				match iri {
+					$(concat!($namespace, $local) => Some($name::$variant),)*
+					_ => None,
+				}
+			}
+
+			/// Converts this item into an identifier of type `T`.
+			#[inline(always)]
+			pub fn id<T: Id>(&self) -> T {
+				T::from_iri(self.as_iri())
+			}
+
+			/// Converts this item into a node reference.
+			#[inline(always)]
+			pub fn reference<T: Id>(&self) -> Reference<T> {
+				Reference::Id(self.id())
+			}
+
+			/// Converts this item into a term.
+			#[inline(always)]
+			pub fn term<T: Id>(&self) -> Term<T> {
+				Term::Ref(self.reference())
+			}
+		}
+
+		impl AsIri for $name {
+			#[inline(always)]
+			fn as_iri(&self) -> Iri {
+				Iri::new(self.as_str()).unwrap()
+			}
+		}
+
+		impl<'a> TryFrom<Iri<'a>> for $name {
+			type Error = Iri<'a>;
+
+			#[inline(always)]
+			fn try_from(iri: Iri<'a>) -> Result<Self, Iri<'a>> {
+				Self::from_iri_str(iri.as_str()).ok_or(iri)
+			}
+		}
+
+		impl fmt::Display for $name {
+			#[inline(always)]
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				self.as_str().fmt(f)
+			}
+		}
+	};
+}
+
+vocabulary! {
+	/// RDF vocabulary (`rdf:`).
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::{vocab::Rdf, Reference};
+	/// # use iref::IriBuf;
+	/// let ty: Reference<IriBuf> = Rdf::Type.reference();
+	/// assert_eq!(ty.as_str(), "http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+	/// assert_eq!(Rdf::Type.compact_iri(), "rdf:type");
+	/// assert_eq!(Rdf::from_iri_str("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"), Some(Rdf::Nil));
+	/// ```
+	Rdf("rdf" = "http://www.w3.org/1999/02/22-rdf-syntax-ns#") {
+		/// `rdf:type`.
+		Type = "type",
+		/// `rdf:Property`.
+		Property = "Property",
+		/// `rdf:Statement`.
+		Statement = "Statement",
+		/// `rdf:subject`.
+		Subject = "subject",
+		/// `rdf:predicate`.
+		Predicate = "predicate",
+		/// `rdf:object`.
+		Object = "object",
+		/// `rdf:List`.
+		List = "List",
+		/// `rdf:first`.
+		First = "first",
+		/// `rdf:rest`.
+		Rest = "rest",
+		/// `rdf:nil`.
+		Nil = "nil",
+		/// `rdf:value`.
+		Value = "value",
+		/// `rdf:language`.
+		Language = "language",
+		/// `rdf:direction`.
+		Direction = "direction",
+		/// `rdf:CompoundLiteral`.
+		CompoundLiteral = "CompoundLiteral",
+		/// `rdf:JSON`.
+		Json = "JSON",
+		/// `rdf:HTML`.
+		Html = "HTML",
+		/// `rdf:XMLLiteral`.
+		XmlLiteral = "XMLLiteral",
+		/// `rdf:langString`.
+		LangString = "langString",
+	}
+}
+
+vocabulary! {
+	/// XML Schema datatypes (`xsd:`).
+	///
+	/// # Example
+	/// ```
+	/// # use json_ld::vocab::Xsd;
+	/// # use iref::Iri;
+	/// # use std::convert::TryFrom;
+	/// let iri = Iri::new("http://www.w3.org/2001/XMLSchema#integer").unwrap();
+	/// assert_eq!(Xsd::try_from(iri), Ok(Xsd::Integer));
+	/// ```
+	Xsd("xsd" = "http://www.w3.org/2001/XMLSchema#") {
+		/// `xsd:string`.
+		String = "string",
+		/// `xsd:boolean`.
+		Boolean = "boolean",
+		/// `xsd:decimal`.
+		Decimal = "decimal",
+		/// `xsd:integer`.
+		Integer = "integer",
+		/// `xsd:double`.
+		Double = "double",
+		/// `xsd:float`.
+		Float = "float",
+		/// `xsd:long`.
+		Long = "long",
+		/// `xsd:int`.
+		Int = "int",
+		/// `xsd:short`.
+		Short = "short",
+		/// `xsd:byte`.
+		Byte = "byte",
+		/// `xsd:nonNegativeInteger`.
+		NonNegativeInteger = "nonNegativeInteger",
+		/// `xsd:positiveInteger`.
+		PositiveInteger = "positiveInteger",
+		/// `xsd:date`.
+		Date = "date",
+		/// `xsd:time`.
+		Time = "time",
+		/// `xsd:dateTime`.
+		DateTime = "dateTime",
+		/// `xsd:duration`.
+		Duration = "duration",
+		/// `xsd:anyURI`.
+		AnyUri = "anyURI",
+		/// `xsd:language`.
+		Language = "language",
+		/// `xsd:base64Binary`.
+		Base64Binary = "base64Binary",
+		/// `xsd:hexBinary`.
+		HexBinary = "hexBinary",
+	}
+}
+
+vocabulary! {
+	/// Schema.org vocabulary (`schema:`), using the `https` namespace.
+	Schema("schema" = "https://schema.org/") {
+		/// `schema:Thing`.
+		Thing = "Thing",
+		/// `schema:Person`.
+		Person = "Person",
+		/// `schema:Organization`.
+		Organization = "Organization",
+		/// `schema:CreativeWork`.
+		CreativeWork = "CreativeWork",
+		/// `schema:Article`.
+		Article = "Article",
+		/// `schema:Event`.
+		Event = "Event",
+		/// `schema:Place`.
+		Place = "Place",
+		/// `schema:PostalAddress`.
+		PostalAddress = "PostalAddress",
+		/// `schema:ImageObject`.
+		ImageObject = "ImageObject",
+		/// `schema:name`.
+		Name = "name",
+		/// `schema:description`.
+		Description = "description",
+		/// `schema:identifier`.
+		Identifier = "identifier",
+		/// `schema:url`.
+		Url = "url",
+		/// `schema:image`.
+		Image = "image",
+		/// `schema:sameAs`.
+		SameAs = "sameAs",
+		/// `schema:email`.
+		Email = "email",
+		/// `schema:telephone`.
+		Telephone = "telephone",
+		/// `schema:address`.
+		Address = "address",
+		/// `schema:givenName`.
+		GivenName = "givenName",
+		/// `schema:familyName`.
+		FamilyName = "familyName",
+		/// `schema:knows`.
+		Knows = "knows",
+		/// `schema:author`.
+		Author = "author",
+		/// `schema:headline`.
+		Headline = "headline",
+		/// `schema:dateCreated`.
+		DateCreated = "dateCreated",
+		/// `schema:dateModified`.
+		DateModified = "dateModified",
+		/// `schema:datePublished`.
+		DatePublished = "datePublished",
+		/// `schema:startDate`.
+		StartDate = "startDate",
+		/// `schema:endDate`.
+		EndDate = "endDate",
+		/// `schema:location`.
+		Location = "location",
+	}
+}
+
+vocabulary! {
+	/// Friend of a Friend vocabulary (`foaf:`).
+	Foaf("foaf" = "http://xmlns.com/foaf/0.1/") {
+		/// `foaf:Agent`.
+		Agent = "Agent",
+		/// `foaf:Person`.
+		Person = "Person",
+		/// `foaf:Organization`.
+		Organization = "Organization",
+		/// `foaf:Group`.
+		Group = "Group",
+		/// `foaf:Document`.
+		Document = "Document",
+		/// `foaf:Image`.
+		Image = "Image",
+		/// `foaf:OnlineAccount`.
+		OnlineAccount = "OnlineAccount",
+		/// `foaf:name`.
+		Name = "name",
+		/// `foaf:givenName`.
+		GivenName = "givenName",
+		/// `foaf:familyName`.
+		FamilyName = "familyName",
+		/// `foaf:nick`.
+		Nick = "nick",
+		/// `foaf:mbox`.
+		Mbox = "mbox",
+		/// `foaf:homepage`.
+		Homepage = "homepage",
+		/// `foaf:depiction`.
+		Depiction = "depiction",
+		/// `foaf:knows`.
+		Knows = "knows",
+		/// `foaf:member`.
+		Member = "member",
+		/// `foaf:account`.
+		Account = "account",
+	}
+}
+
+vocabulary! {
+	/// DCMI Metadata Terms (`dcterms:`).
+	DcTerms("dcterms" = "http://purl.org/dc/terms/") {
+		/// `dcterms:title`.
+		Title = "title",
+		/// `dcterms:description`.
+		Description = "description",
+		/// `dcterms:identifier`.
+		Identifier = "identifier",
+		/// `dcterms:creator`.
+		Creator = "creator",
+		/// `dcterms:contributor`.
+		Contributor = "contributor",
+		/// `dcterms:publisher`.
+		Publisher = "publisher",
+		/// `dcterms:date`.
+		Date = "date",
+		/// `dcterms:created`.
+		Created = "created",
+		/// `dcterms:modified`.
+		Modified = "modified",
+		/// `dcterms:issued`.
+		Issued = "issued",
+		/// `dcterms:language`.
+		Language = "language",
+		/// `dcterms:license`.
+		License = "license",
+		/// `dcterms:rights`.
+		Rights = "rights",
+		/// `dcterms:subject`.
+		Subject = "subject",
+		/// `dcterms:source`.
+		Source = "source",
+		/// `dcterms:format`.
+		Format = "format",
+		/// `dcterms:type`.
+		Type = "type",
+	}
+}
@@ -1,4 +1,6 @@
-use crate::BlankId;
+use crate::{BlankId, Error, ErrorCode, Loc};
+use iref::IriBuf;
+use std::collections::HashMap;
 use std::fmt;
 
 /// Warning that can occur during JSON-LD documents processing.
@@ -27,6 +29,103 @@ pub enum Warning {
 
 	/// String literal is not an IRI.
 	MalformedIri(String),
+
+	/// Triple with a blank node identifier as predicate.
+	///
+	/// Such triples are only allowed in generalized RDF,
+	/// and are dropped during RDF serialization unless the
+	/// `produce_generalized_rdf` option is set.
+	BlankNodePredicate(BlankId),
+
+	/// Context imported with `@import`.
+	///
+	/// Only emitted when the
+	/// [`report_imports`](crate::context::ProcessingOptions::report_imports)
+	/// processing option is set.
+	ImportedContext(IriBuf),
+
+	/// Term definition skipped because it is invalid.
+	///
+	/// Only emitted when the
+	/// [`skip_invalid_terms`](crate::context::ProcessingOptions::skip_invalid_terms)
+	/// processing option is set.
+	InvalidTermDefinition(String, ErrorCode),
+
+	/// Key dropped during expansion.
+	///
+	/// Only emitted when the
+	/// [`report_dropped_keys`](crate::expansion::Options::report_dropped_keys)
+	/// expansion option is set.
+	DroppedKey(String, DropReason),
+
+	/// Key appearing more than once in the same JSON object.
+	///
+	/// Most JSON implementations only keep one of the duplicate entries while parsing,
+	/// so this warning can only be emitted for implementations exposing every entry.
+	DuplicateKey(String),
+
+	/// Literal whose lexical form has been rejected by a
+	/// [coercion hook](crate::LiteralCoercions) during RDF serialization.
+	///
+	/// The literal is kept as is.
+	InvalidLiteral(String, IriBuf),
+
+	/// Value that could not be expanded, kept as an
+	/// [invalid value](crate::object::Value::Invalid).
+	///
+	/// Only emitted when the
+	/// [`keep_invalid_values`](crate::expansion::Options::keep_invalid_values)
+	/// expansion option is set.
+	InvalidValue(ErrorCode),
+
+	/// Key expanded into the given IRI (or `null`), and how it has been resolved.
+	///
+	/// Only emitted when the
+	/// [`explain_keys`](crate::expansion::Options::explain_keys)
+	/// expansion option is set.
+	ExpandedKey(String, Option<String>, IriResolution),
+
+	/// Warning emitted the given number of times.
+	///
+	/// Only produced when warnings are deduplicated
+	/// (see [`DeduplicateWarnings`]).
+	Repeated(Box<Warning>, usize),
+}
+
+impl Warning {
+	/// Returns the severity of this warning.
+	pub fn severity(&self) -> Severity {
+		match self {
+			Self::Repeated(warning, _) => warning.severity(),
+			Self::BlankNodeIdProperty(_) => Severity::Deprecation,
+			Self::ImportedContext(_) | Self::DroppedKey(_, _) | Self::ExpandedKey(_, _, _) => {
+				Severity::Info
+			}
+			_ => Severity::Warning,
+		}
+	}
+
+	/// Checks if this warning reports some input data being dropped
+	/// or left unprocessed.
+	///
+	/// Such warnings are turned into [`ErrorCode::DataLoss`] errors
+	/// in [safe mode](crate::expansion::Options::safe).
+	pub fn is_data_loss(&self) -> bool {
+		if let Self::Repeated(warning, _) = self {
+			return warning.is_data_loss();
+		}
+
+		matches!(
+			self,
+			Self::KeywordLikeTerm(_)
+				| Self::KeywordLikeValue(_)
+				| Self::MalformedLanguageTag(_, _)
+				| Self::MalformedIri(_)
+				| Self::BlankNodePredicate(_)
+				| Self::InvalidTermDefinition(_, _)
+				| Self::InvalidValue(_)
+		)
+	}
 }
 
 impl fmt::Display for Warning {
@@ -44,6 +143,541 @@ impl fmt::Display for Warning {
 				write!(f, "malformed language tag `{}`: {}", tag, e)
 			}
 			Self::MalformedIri(value) => write!(f, "invalid IRI `{}`", value),
+			Self::BlankNodePredicate(id) => write!(
+				f,
+				"dropped triple with blank node identifier `{}` as predicate",
+				id
+			),
+			Self::ImportedContext(iri) => write!(f, "imported context `{}`", iri),
+			Self::InvalidTermDefinition(term, code) => {
+				write!(f, "skipped definition of term `{}`: {}", term, code)
+			}
+			Self::DroppedKey(key, reason) => write!(f, "dropped key `{}`: {}", key, reason),
+			Self::DuplicateKey(key) => write!(f, "duplicate key `{}`", key),
+			Self::InvalidLiteral(value, ty) => {
+				write!(f, "invalid literal `{}` of type `{}`", value, ty)
+			}
+			Self::InvalidValue(code) => write!(f, "kept invalid value: {}", code),
+			Self::ExpandedKey(key, Some(expanded), resolution) => write!(
+				f,
+				"key `{}` expanded to `{}` ({})",
+				key, expanded, resolution
+			),
+			Self::ExpandedKey(key, None, resolution) => {
+				write!(f, "key `{}` expanded to null ({})", key, resolution)
+			}
+			Self::Repeated(warning, count) => write!(f, "{} ({} times)", warning, count),
+		}
+	}
+}
+
+/// Reason why a key has been dropped during expansion.
+///
+/// See [`Warning::DroppedKey`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DropReason {
+	/// The key is not defined in the context,
+	/// and cannot be expanded using the vocabulary mapping.
+	UndefinedTerm,
+
+	/// The key is defined as `null` in the context.
+	NullTerm,
+
+	/// The key has the form of a keyword.
+	KeywordLike,
+
+	/// The key has been dropped by a
+	/// [`Policy::Custom`](crate::expansion::Policy::Custom) key policy.
+	Policy,
+}
+
+impl DropReason {
+	/// Returns a short description of the reason.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::UndefinedTerm => "undefined term",
+			Self::NullTerm => "term defined as null",
+			Self::KeywordLike => "keyword-like key",
+			Self::Policy => "dropped by the key policy",
+		}
+	}
+}
+
+impl fmt::Display for DropReason {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// How an IRI, term, compact IRI or keyword has been expanded.
+///
+/// See [`Warning::ExpandedKey`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum IriResolution {
+	/// The value is a keyword.
+	Keyword,
+
+	/// The value has the form of a keyword, and is expanded into `null`.
+	KeywordLike,
+
+	/// The value is a term defined in the active context.
+	Term,
+
+	/// The value is a blank node identifier.
+	BlankNode,
+
+	/// The value is an absolute IRI.
+	Absolute,
+
+	/// The value is a compact IRI using the given prefix.
+	CompactIri(String),
+
+	/// The value is expanded using the vocabulary mapping (`@vocab`).
+	Vocabulary,
+
+	/// The value is resolved against the base IRI.
+	BaseIri,
+
+	/// The value cannot be expanded into an IRI.
+	Unresolved,
+}
+
+impl fmt::Display for IriResolution {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Keyword => write!(f, "keyword"),
+			Self::KeywordLike => write!(f, "keyword-like value"),
+			Self::Term => write!(f, "term definition"),
+			Self::BlankNode => write!(f, "blank node identifier"),
+			Self::Absolute => write!(f, "absolute IRI"),
+			Self::CompactIri(prefix) => write!(f, "compact IRI with prefix `{}`", prefix),
+			Self::Vocabulary => write!(f, "vocabulary mapping"),
+			Self::BaseIri => write!(f, "base IRI"),
+			Self::Unresolved => write!(f, "unresolved"),
+		}
+	}
+}
+
+/// Warning handler.
+///
+/// Receives the warnings emitted by the processing algorithms,
+/// as they are emitted.
+///
+/// Implemented by `Vec<Loc<Warning, M>>`, collecting the warnings,
+/// by [`IgnoreWarnings`], [`LogWarnings`] and [`EscalateWarnings`],
+/// and by any `FnMut(Loc<Warning, M>)` function.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Context}, expansion, EscalateWarnings, ErrorCode, NoLoader};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let doc = json!({ "@id": "https://example.org/alice", "@foo": "bar" });
+/// let context = context::Json::<Value>::new(None);
+/// let loader = NoLoader::<Value>::new();
+///
+/// let mut count = 0;
+/// let mut counter = |_| count += 1;
+/// expansion::expand(&context, &doc, None, &loader, Default::default(), &mut counter)
+///   .await
+///   .unwrap();
+/// assert_eq!(count, 1);
+///
+/// let mut escalate = EscalateWarnings::new();
+/// expansion::expand(&context, &doc, None, &loader, Default::default(), &mut escalate)
+///   .await
+///   .unwrap();
+/// assert_eq!(escalate.check().err().unwrap().code(), ErrorCode::EscalatedWarning);
+/// # }
+/// ```
+pub trait WarningHandler<M> {
+	/// Handles the given warning.
+	fn handle(&mut self, warning: Loc<Warning, M>);
+}
+
+/// Warning handler that is `Send`, unless the `local-futures` feature is enabled.
+///
+/// This is the type of warning handler trait objects
+/// passed along the processing algorithms.
+#[cfg(not(feature = "local-futures"))]
+pub trait SendWarningHandler<M> = WarningHandler<M> + Send;
+
+/// Warning handler that is `Send`, unless the `local-futures` feature is enabled.
+///
+/// This is the type of warning handler trait objects
+/// passed along the processing algorithms.
+#[cfg(feature = "local-futures")]
+pub trait SendWarningHandler<M> = WarningHandler<M>;
+
+impl<M> WarningHandler<M> for Vec<Loc<Warning, M>> {
+	#[inline(always)]
+	fn handle(&mut self, warning: Loc<Warning, M>) {
+		self.push(warning)
+	}
+}
+
+impl<M, F: FnMut(Loc<Warning, M>)> WarningHandler<M> for F {
+	#[inline(always)]
+	fn handle(&mut self, warning: Loc<Warning, M>) {
+		self(warning)
+	}
+}
+
+/// Warning handler ignoring every warning.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct IgnoreWarnings;
+
+impl<M> WarningHandler<M> for IgnoreWarnings {
+	#[inline(always)]
+	fn handle(&mut self, _warning: Loc<Warning, M>) {}
+}
+
+/// Warning handler logging every warning with the [`log`] crate.
+///
+/// [`Severity::Info`] warnings are logged at the `info` level,
+/// the others at the `warn` level.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct LogWarnings;
+
+impl<M> WarningHandler<M> for LogWarnings {
+	fn handle(&mut self, warning: Loc<Warning, M>) {
+		match warning.severity() {
+			Severity::Info => log::info!("{}", *warning),
+			_ => log::warn!("{}", *warning),
+		}
+	}
+}
+
+/// Warning handler turning the first warning into an error.
+///
+/// Processing is not interrupted by the warning:
+/// use [`EscalateWarnings::check`] once it is done.
+pub struct EscalateWarnings<M> {
+	first: Option<Loc<Warning, M>>,
+}
+
+impl<M> EscalateWarnings<M> {
+	/// Creates a new handler.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self { first: None }
+	}
+
+	/// Returns the first warning received by the handler, if any.
+	#[inline(always)]
+	pub fn first(&self) -> Option<&Loc<Warning, M>> {
+		self.first.as_ref()
+	}
+
+	/// Returns an [`ErrorCode::EscalatedWarning`] error built from the first warning
+	/// received by the handler, if any.
+	pub fn check(self) -> Result<(), Loc<Error, M>> {
+		match self.first {
+			Some(warning) => {
+				let (warning, source, metadata) = warning.into_parts();
+				Err(Error::with_source(ErrorCode::EscalatedWarning, warning)
+					.located(source, metadata))
+			}
+			None => Ok(()),
 		}
 	}
 }
+
+impl<M> Default for EscalateWarnings<M> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<M> WarningHandler<M> for EscalateWarnings<M> {
+	#[inline(always)]
+	fn handle(&mut self, warning: Loc<Warning, M>) {
+		if self.first.is_none() {
+			self.first = Some(warning)
+		}
+	}
+}
+
+/// Warning handler collapsing identical warnings.
+///
+/// Warnings equal to a previously received warning
+/// (same kind and same subject) are not collected again:
+/// the first one is kept, with its location,
+/// and is turned into a [`Warning::Repeated`] warning counting
+/// its occurrences.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context, expansion, DeduplicateWarnings, NoLoader, Warning};
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let doc = json!([
+///   { "https://example.org/name": { "@value": "Alice", "@language": "not a tag" } },
+///   { "https://example.org/name": { "@value": "Bob", "@language": "not a tag" } },
+///   { "https://example.org/name": { "@value": "Carol", "@language": "not a tag" } }
+/// ]);
+/// let context = context::Json::<Value>::new(None);
+/// let loader = NoLoader::<Value>::new();
+///
+/// let mut warnings = DeduplicateWarnings::new();
+/// expansion::expand(&context, &doc, None, &loader, Default::default(), &mut warnings)
+///   .await
+///   .unwrap();
+///
+/// let warnings = warnings.into_warnings();
+/// assert_eq!(warnings.len(), 1);
+/// assert!(matches!(warnings[0].value(), Warning::Repeated(_, 3)));
+/// # }
+/// ```
+pub struct DeduplicateWarnings<M> {
+	warnings: Vec<(Loc<Warning, M>, usize)>,
+	indexes: HashMap<String, Vec<usize>>,
+}
+
+impl<M> DeduplicateWarnings<M> {
+	/// Creates a new handler.
+	#[inline(always)]
+	pub fn new() -> Self {
+		Self {
+			warnings: Vec::new(),
+			indexes: HashMap::new(),
+		}
+	}
+
+	/// Returns the number of distinct warnings received by the handler.
+	#[inline(always)]
+	pub fn len(&self) -> usize {
+		self.warnings.len()
+	}
+
+	/// Checks if the handler received no warning.
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool {
+		self.warnings.is_empty()
+	}
+
+	/// Returns an iterator over the distinct warnings received by the handler,
+	/// in order of first occurrence, with their number of occurrences.
+	#[inline(always)]
+	pub fn iter(&self) -> impl Iterator<Item = (&Loc<Warning, M>, usize)> {
+		self.warnings
+			.iter()
+			.map(|(warning, count)| (warning, *count))
+	}
+
+	/// Returns the distinct warnings received by the handler,
+	/// in order of first occurrence.
+	///
+	/// Warnings received more than once are wrapped into a
+	/// [`Warning::Repeated`] warning.
+	pub fn into_warnings(self) -> Vec<Loc<Warning, M>> {
+		self.warnings
+			.into_iter()
+			.map(|(warning, count)| {
+				if count > 1 {
+					let (warning, source, metadata) = warning.into_parts();
+					Loc::new(
+						Warning::Repeated(Box::new(warning), count),
+						source,
+						metadata,
+					)
+				} else {
+					warning
+				}
+			})
+			.collect()
+	}
+}
+
+impl<M> Default for DeduplicateWarnings<M> {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<M> WarningHandler<M> for DeduplicateWarnings<M> {
+	fn handle(&mut self, warning: Loc<Warning, M>) {
+		// Warnings are not hashable (some of them hold foreign error types),
+		// so they are indexed by their textual representation.
+		let indexes = self.indexes.entry(warning.value().to_string()).or_default();
+		for &i in indexes.iter() {
+			let (other, count) = &mut self.warnings[i];
+			if **other == *warning {
+				*count += 1;
+				return;
+			}
+		}
+
+		indexes.push(self.warnings.len());
+		self.warnings.push((warning, 1))
+	}
+}
+
+impl std::error::Error for Warning {}
+
+/// Severity of a [`Warning`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+	/// Informative notice, that does not denote any problem in the input.
+	Info,
+
+	/// Possible problem in the input, such as a malformed value that has been dropped or kept as is.
+	Warning,
+
+	/// Use of a feature that is obsolete, and may be removed in a future version of JSON-LD.
+	Deprecation,
+}
+
+/// Action taken on a warning, according to its severity.
+///
+/// See [`WarningPolicy`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WarningAction {
+	/// Collect the warning.
+	///
+	/// This is the default.
+	Collect,
+
+	/// Silently drop the warning.
+	Suppress,
+
+	/// Turn the warning into an [`ErrorCode::EscalatedWarning`] error,
+	/// with the warning as source.
+	Escalate,
+}
+
+impl Default for WarningAction {
+	#[inline(always)]
+	fn default() -> Self {
+		Self::Collect
+	}
+}
+
+/// Defines what to do with warnings of each [`Severity`].
+///
+/// By default, every warning is collected.
+///
+/// # Example
+/// ```
+/// # use json_ld::{context::{self, Local, ProcessingOptions}, ErrorCode, NoLoader, Severity, WarningAction, WarningPolicy};
+/// # use iref::IriBuf;
+/// # use serde_json::{json, Value};
+/// # #[async_std::main]
+/// # async fn main() {
+/// let local_context = json!({ "@language": "not a language tag!", "@foo": "https://example.org/foo" });
+/// let active_context = context::Json::<Value>::new(None);
+/// let loader = NoLoader::<Value>::new();
+///
+/// let options = ProcessingOptions { warning_policy: WarningPolicy::new(), ..Default::default() };
+/// let processed = Local::<IriBuf>::process_with(&local_context, &active_context, &loader, None, options)
+///   .await
+///   .unwrap();
+/// assert_eq!(processed.warnings().len(), 2);
+///
+/// let options = ProcessingOptions {
+///   warning_policy: WarningPolicy::new().with(Severity::Warning, WarningAction::Escalate),
+///   ..Default::default()
+/// };
+/// let error = Local::<IriBuf>::process_with(&local_context, &active_context, &loader, None, options)
+///   .await
+///   .err()
+///   .unwrap();
+/// assert_eq!(error.code(), ErrorCode::EscalatedWarning);
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct WarningPolicy {
+	/// Action taken on [`Severity::Info`] warnings.
+	pub info: WarningAction,
+
+	/// Action taken on [`Severity::Warning`] warnings.
+	pub warning: WarningAction,
+
+	/// Action taken on [`Severity::Deprecation`] warnings.
+	pub deprecation: WarningAction,
+
+	/// Collapse identical warnings into [`Warning::Repeated`] warnings
+	/// (see [`DeduplicateWarnings`]).
+	pub deduplicate: bool,
+}
+
+impl WarningPolicy {
+	/// Creates a new policy collecting every warning.
+	#[inline(always)]
+	pub const fn new() -> Self {
+		Self {
+			info: WarningAction::Collect,
+			warning: WarningAction::Collect,
+			deprecation: WarningAction::Collect,
+			deduplicate: false,
+		}
+	}
+
+	/// Enables the deduplication of identical warnings.
+	#[inline(always)]
+	pub const fn with_deduplication(mut self) -> Self {
+		self.deduplicate = true;
+		self
+	}
+
+	/// Sets the action taken on warnings with the given severity.
+	#[inline(always)]
+	pub const fn with(mut self, severity: Severity, action: WarningAction) -> Self {
+		match severity {
+			Severity::Info => self.info = action,
+			Severity::Warning => self.warning = action,
+			Severity::Deprecation => self.deprecation = action,
+		}
+
+		self
+	}
+
+	/// Returns the action taken on the given warning.
+	#[inline(always)]
+	pub fn action(&self, warning: &Warning) -> WarningAction {
+		match warning.severity() {
+			Severity::Info => self.info,
+			Severity::Warning => self.warning,
+			Severity::Deprecation => self.deprecation,
+		}
+	}
+
+	/// Applies this policy to the given warnings.
+	///
+	/// Suppressed warnings are removed.
+	/// Returns an error built from the first escalated warning, if any.
+	pub fn apply<M>(
+		&self,
+		warnings: Vec<Loc<Warning, M>>,
+	) -> Result<Vec<Loc<Warning, M>>, Loc<Error, M>> {
+		let warnings = if self.deduplicate {
+			let mut deduplicated = DeduplicateWarnings::new();
+			for warning in warnings {
+				deduplicated.handle(warning)
+			}
+
+			deduplicated.into_warnings()
+		} else {
+			warnings
+		};
+
+		let mut collected = Vec::with_capacity(warnings.len());
+		for warning in warnings {
+			match self.action(&warning) {
+				WarningAction::Collect => collected.push(warning),
+				WarningAction::Suppress => (),
+				WarningAction::Escalate => {
+					let (warning, source, metadata) = warning.into_parts();
+					return Err(Error::with_source(ErrorCode::EscalatedWarning, warning)
+						.located(source, metadata));
+				}
+			}
+		}
+
+		Ok(collected)
+	}
+}
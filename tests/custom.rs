@@ -11,8 +11,10 @@ use iref::{Iri, IriBuf};
 use json_ld::{
 	context::{self, Loader as ContextLoader, Local, ProcessingOptions},
 	expansion,
+	future::{BoxFuture, FutureExt},
+	loader::{self, Profile},
 	util::{json_ld_eq, AsJson},
-	Document, FsLoader, Loader, ProcessingMode,
+	Document, Error, FsLoader, Loader, ProcessingMode, RemoteDocument,
 };
 use serde_json::Value;
 
@@ -55,7 +57,7 @@ fn positive_test(options: Options, input_url: Iri, base_url: Iri, output_url: Ir
 			.into_context();
 		input_context = task::block_on(local_context.process_with(
 			&input_context,
-			&mut loader,
+			&loader,
 			Some(base_url),
 			options.into(),
 		))
@@ -63,13 +65,9 @@ fn positive_test(options: Options, input_url: Iri, base_url: Iri, output_url: Ir
 		.into_inner();
 	}
 
-	let result = task::block_on(input.expand_with(
-		Some(base_url),
-		&input_context,
-		&mut loader,
-		options.into(),
-	))
-	.unwrap();
+	let result =
+		task::block_on(input.expand_with(Some(base_url), &input_context, &loader, options.into()))
+			.unwrap();
 
 	let result_json: Value = result.as_json();
 	let success = json_ld_eq(&result_json, &*output);
@@ -270,3 +268,883 @@ fn custom_0125() {
 		output_url,
 	)
 }
+
+#[cfg(feature = "local-futures")]
+#[test]
+fn local_futures() {
+	use json_ld::{Loc, NoLoader, Warning, WarningHandler};
+	use std::{cell::RefCell, rc::Rc};
+
+	struct SharedWarnings(Rc<RefCell<Vec<Warning>>>);
+
+	impl<M> WarningHandler<M> for SharedWarnings {
+		fn handle(&mut self, warning: Loc<Warning, M>) {
+			self.0.borrow_mut().push(warning.unwrap())
+		}
+	}
+
+	let doc: Value = serde_json::json!({
+		"@context": { "name": "https://schema.org/name" },
+		"name": "Alice",
+		"nickname": "Al"
+	});
+
+	let warnings = Rc::new(RefCell::new(Vec::new()));
+	let mut handler = SharedWarnings(warnings.clone());
+	let options = expansion::Options {
+		report_dropped_keys: true,
+		..expansion::Options::default()
+	};
+
+	let context = context::Json::<Value>::new(None);
+	let loader = NoLoader::<Value>::new();
+	let expanded = task::block_on(expansion::expand(
+		&context,
+		&doc,
+		None,
+		&loader,
+		options,
+		&mut handler,
+	))
+	.unwrap();
+
+	assert_eq!(expanded.len(), 1);
+	assert!(warnings
+		.borrow()
+		.iter()
+		.any(|w| matches!(w, Warning::DroppedKey(key, _) if key == "nickname")));
+}
+
+#[cfg(any(
+	feature = "schema-org-context",
+	feature = "activitystreams-context",
+	feature = "credentials-context"
+))]
+fn expand_with_well_known_contexts(doc: Value, property: &str) -> usize {
+	use json_ld::{Object, Reference, StaticLoader};
+
+	let loader = StaticLoader::<Value>::default().with_well_known_contexts();
+	let expanded = task::block_on(doc.expand::<context::Json<Value>, _>(&loader)).unwrap();
+	let property = Reference::Id(IriBuf::new(property).unwrap());
+	expanded
+		.iter()
+		.map(|object| match object.as_ref() {
+			Object::Node(node) => node.get(&property).count(),
+			_ => 0,
+		})
+		.sum()
+}
+
+#[cfg(feature = "schema-org-context")]
+#[test]
+fn schema_org_context() {
+	let doc = serde_json::json!({
+		"@context": "https://schema.org",
+		"@type": "Person",
+		"name": "Alice"
+	});
+
+	assert_eq!(
+		expand_with_well_known_contexts(doc, "http://schema.org/name"),
+		1
+	)
+}
+
+#[cfg(feature = "activitystreams-context")]
+#[test]
+fn activitystreams_context() {
+	let doc = serde_json::json!({
+		"@context": "https://www.w3.org/ns/activitystreams",
+		"type": "Note",
+		"content": "Hello"
+	});
+
+	assert_eq!(
+		expand_with_well_known_contexts(doc, "https://www.w3.org/ns/activitystreams#content"),
+		1
+	)
+}
+
+#[cfg(feature = "credentials-context")]
+#[test]
+fn credentials_context() {
+	let doc = serde_json::json!({
+		"@context": "https://www.w3.org/2018/credentials/v1",
+		"type": "VerifiableCredential",
+		"issuer": "https://example.org/issuer",
+		"issuanceDate": "2021-01-01T00:00:00Z"
+	});
+
+	assert_eq!(
+		expand_with_well_known_contexts(doc, "https://www.w3.org/2018/credentials#issuer"),
+		1
+	)
+}
+
+#[test]
+fn policy_loader_redirection() {
+	use json_ld::{
+		future::{BoxFuture, FutureExt},
+		loader::Id,
+		Error, ErrorCode, PolicyLoader, RemoteDocument,
+	};
+
+	/// Loader redirecting every request to `https://example.com/`.
+	struct RedirectLoader;
+
+	impl Loader for RedirectLoader {
+		type Document = Value;
+
+		fn id(&self, _iri: Iri<'_>) -> Option<Id> {
+			None
+		}
+
+		fn iri(&self, _id: Id) -> Option<IriBuf> {
+			None
+		}
+
+		fn load<'a>(
+			&'a self,
+			_url: Iri<'_>,
+		) -> BoxFuture<'a, Result<RemoteDocument<Value>, Error>> {
+			async move {
+				Ok(RemoteDocument::new(
+					serde_json::json!({}),
+					iri!("https://example.com/").to_owned(),
+					Id::new(0),
+				))
+			}
+			.boxed()
+		}
+	}
+
+	let loader = PolicyLoader::allow_list(RedirectLoader).with_origin(iri!("https://example.org/"));
+	let error = task::block_on(loader.load(iri!("https://example.org/doc.jsonld")))
+		.err()
+		.unwrap();
+	assert_eq!(error.code(), ErrorCode::ForbiddenOrigin);
+
+	let loader = loader.with_origin(iri!("https://example.com/"));
+	task::block_on(loader.load(iri!("https://example.org/doc.jsonld"))).unwrap();
+}
+
+/// Loader returning the requested profile as document.
+struct ProfileLoader;
+
+impl Loader for ProfileLoader {
+	type Document = Value;
+
+	fn id(&self, _iri: Iri<'_>) -> Option<loader::Id> {
+		None
+	}
+
+	fn iri(&self, _id: loader::Id) -> Option<IriBuf> {
+		None
+	}
+
+	fn load<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Value>, Error>> {
+		let doc = RemoteDocument::new(
+			serde_json::json!({ "profile": null }),
+			url.into(),
+			loader::Id::new(0),
+		);
+		async move { Ok(doc) }.boxed()
+	}
+
+	fn load_with_profile<'a>(
+		&'a self,
+		url: Iri<'_>,
+		profile: Profile,
+	) -> BoxFuture<'a, Result<RemoteDocument<Value>, Error>> {
+		let doc = RemoteDocument::new(
+			serde_json::json!({ "profile": profile.iri().as_str() }),
+			url.into(),
+			loader::Id::new(0),
+		);
+		async move { Ok(doc) }.boxed()
+	}
+}
+
+#[test]
+fn disk_caching_loader_long_iri() {
+	use json_ld::{DiskCachingLoader, NoLoader, StaticLoader};
+
+	let url = IriBuf::from_string(format!("https://example.org/{}", "a".repeat(1024))).unwrap();
+	let directory = std::env::temp_dir().join("json-ld-long-iri-cache");
+
+	let mut static_loader = StaticLoader::<Value>::default();
+	static_loader.insert(url.as_iri(), r#"{ "name": "Alice" }"#);
+	let loader = DiskCachingLoader::new(static_loader, &directory);
+	let loaded = task::block_on(loader.load(url.as_iri())).unwrap();
+	assert!(loader.is_cached(url.as_iri()));
+
+	// The document is loaded back from the cache directory.
+	let loader = DiskCachingLoader::new(NoLoader::<Value>::new(), &directory);
+	let cached = task::block_on(loader.load(url.as_iri())).unwrap();
+	assert_eq!(*cached, *loaded);
+	assert_eq!(Loader::iri(&loader, cached.source()), Some(url.clone()));
+
+	assert!(loader.invalidate(url.as_iri()));
+}
+
+#[test]
+fn disk_caching_loader_profiles() {
+	use json_ld::{DiskCachingLoader, NoLoader};
+	use serde_json::json;
+
+	let url = iri!("https://example.org/doc.jsonld");
+	let directory = std::env::temp_dir().join("json-ld-profile-cache");
+	let loader = DiskCachingLoader::new(ProfileLoader, &directory);
+	loader.invalidate(url);
+	task::block_on(loader.load(url)).unwrap();
+	task::block_on(loader.load_with_profile(url, Profile::Context)).unwrap();
+	assert!(loader.is_cached(url));
+	assert!(loader.is_cached_with_profile(url, Profile::Context));
+	assert!(!loader.is_cached_with_profile(url, Profile::Expanded));
+
+	// Each profile is loaded back from its own cache file.
+	let loader = DiskCachingLoader::new(NoLoader::<Value>::new(), &directory);
+	let doc = task::block_on(loader.load(url)).unwrap();
+	assert_eq!(*doc, json!({ "profile": null }));
+	let doc = task::block_on(loader.load_with_profile(url, Profile::Context)).unwrap();
+	assert_eq!(*doc, json!({ "profile": Profile::Context.iri().as_str() }));
+
+	assert!(loader.invalidate(url));
+	assert!(!loader.is_cached(url));
+	assert!(!loader.is_cached_with_profile(url, Profile::Context));
+}
+
+#[test]
+fn fixture_loader_profiles() {
+	use json_ld::{FixtureLoader, FixtureMode, NoLoader};
+	use serde_json::json;
+
+	let url = iri!("https://example.org/doc.jsonld");
+	let directory = std::env::temp_dir().join("json-ld-profile-fixtures");
+	let recorder = FixtureLoader::new(ProfileLoader, &directory, FixtureMode::Record);
+	task::block_on(recorder.load(url)).unwrap();
+	task::block_on(recorder.load_with_profile(url, Profile::Context)).unwrap();
+	assert!(recorder.fixture_path(url).is_file());
+	assert!(recorder
+		.fixture_path_with_profile(url, Profile::Context)
+		.is_file());
+
+	// Each profile is replayed from its own fixture.
+	let replayer = FixtureLoader::new(NoLoader::<Value>::new(), &directory, FixtureMode::Replay);
+	let doc = task::block_on(replayer.load(url)).unwrap();
+	assert_eq!(*doc, json!({ "profile": null }));
+	let doc = task::block_on(replayer.load_with_profile(url, Profile::Context)).unwrap();
+	assert_eq!(*doc, json!({ "profile": Profile::Context.iri().as_str() }));
+	assert!(task::block_on(replayer.load_with_profile(url, Profile::Expanded)).is_err());
+}
+
+#[test]
+fn context_cache_processing_options() {
+	use json_ld::{context::Cache, ErrorCode, MockLoader, Warning};
+	use serde_json::json;
+	use std::sync::Arc;
+
+	let context_url = iri!("https://example.org/context.jsonld");
+	let schema_url = iri!("https://example.org/schema.jsonld");
+	let loader = MockLoader::new()
+		.with(
+			context_url,
+			json!({ "@context": { "@version": 1.1, "@import": "https://example.org/schema.jsonld" } }),
+		)
+		.with(
+			schema_url,
+			json!({ "@context": { "name": "https://schema.org/name" } }),
+		);
+	let local_context = json!("https://example.org/context.jsonld");
+
+	let cache = Arc::new(Cache::new());
+	let active_context = context::Json::<Value>::new(None).with_cache(cache.clone());
+	task::block_on(Local::<IriBuf>::process_with(
+		&local_context,
+		&active_context,
+		&loader,
+		None,
+		ProcessingOptions::default(),
+	))
+	.unwrap();
+	assert_eq!(cache.len(), 1);
+
+	// Forbidding imports is not bypassed by the cached context.
+	let options = ProcessingOptions {
+		allow_import: false,
+		..ProcessingOptions::default()
+	};
+	let error = task::block_on(Local::<IriBuf>::process_with(
+		&local_context,
+		&active_context,
+		&loader,
+		None,
+		options,
+	))
+	.err()
+	.unwrap();
+	assert_eq!(error.code(), ErrorCode::ForbiddenImport);
+
+	// Warnings raised by the remote context are reported, and the context is not cached.
+	let options = ProcessingOptions {
+		report_imports: true,
+		..ProcessingOptions::default()
+	};
+	for _ in 0..2 {
+		let processed = task::block_on(Local::<IriBuf>::process_with(
+			&local_context,
+			&active_context,
+			&loader,
+			None,
+			options,
+		))
+		.unwrap();
+		assert_eq!(
+			*processed.warnings()[0],
+			Warning::ImportedContext(schema_url.into())
+		);
+	}
+
+	assert_eq!(cache.len(), 1);
+	assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn cancellable_loader_deadline() {
+	use json_ld::{
+		future::{BoxFuture, FutureExt},
+		loader::Id,
+		CancellableLoader, Error, ErrorCode, RemoteDocument,
+	};
+	use std::time::{Duration, Instant};
+
+	/// Loader whose loads never complete.
+	struct PendingLoader;
+
+	impl Loader for PendingLoader {
+		type Document = Value;
+
+		fn id(&self, _iri: Iri<'_>) -> Option<Id> {
+			None
+		}
+
+		fn iri(&self, _id: Id) -> Option<IriBuf> {
+			None
+		}
+
+		fn load<'a>(
+			&'a self,
+			_url: Iri<'_>,
+		) -> BoxFuture<'a, Result<RemoteDocument<Value>, Error>> {
+			futures::future::poll_fn(|_| std::task::Poll::Pending).boxed()
+		}
+	}
+
+	// The pending load is woken up by the deadline, without being polled again otherwise.
+	let start = Instant::now();
+	let loader = CancellableLoader::new(PendingLoader).with_timeout(Duration::from_millis(100));
+	let error = task::block_on(loader.load(iri!("https://example.org/doc.jsonld")))
+		.err()
+		.unwrap();
+	assert_eq!(error.code(), ErrorCode::Cancelled);
+	assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn expanded_document_processing() {
+	use json_ld::NoLoader;
+	use serde_json::json;
+
+	let doc = json!({
+		"@context": { "name": "https://schema.org/name", "knows": "https://schema.org/knows" },
+		"@id": "https://example.org/alice",
+		"name": "Alice",
+		"knows": { "name": "Bob" }
+	});
+	let context = json!({ "@vocab": "https://schema.org/" });
+
+	let loader = NoLoader::<Value>::new();
+	let expanded = task::block_on(doc.expand::<context::Json<Value>, _>(&loader)).unwrap();
+
+	// The expanded document is processed as the JSON document it comes from.
+	assert_eq!(
+		task::block_on(Document::<IriBuf>::compact_with_context(
+			&expanded, &context, &loader
+		))
+		.unwrap(),
+		task::block_on(Document::<IriBuf>::compact_with_context(
+			&doc, &context, &loader
+		))
+		.unwrap()
+	);
+
+	// Flattened nodes are not ordered.
+	let processed_context = task::block_on(Local::<IriBuf>::process::<context::Json<Value>, _>(
+		&context, &loader, None,
+	))
+	.unwrap();
+	let nodes = |flattened: Value| {
+		let mut nodes = flattened["@graph"].as_array().unwrap().clone();
+		nodes.sort_by(|a, b| a["@id"].as_str().cmp(&b["@id"].as_str()));
+		nodes
+	};
+	assert_eq!(
+		nodes(
+			task::block_on(Document::<IriBuf>::flatten(
+				&expanded,
+				Some(&processed_context),
+				&loader
+			))
+			.unwrap()
+		),
+		nodes(
+			task::block_on(Document::<IriBuf>::flatten(
+				&doc,
+				Some(&processed_context),
+				&loader
+			))
+			.unwrap()
+		)
+	);
+
+	let quads = |quads: json_ld::rdf::Quads<IriBuf>| {
+		let mut quads: Vec<_> = quads.map(|quad| quad.to_string()).collect();
+		quads.sort();
+		quads
+	};
+	assert_eq!(
+		quads(
+			task::block_on(Document::<IriBuf>::to_rdf::<context::Json<Value>, _>(
+				&expanded, &loader
+			))
+			.unwrap()
+		),
+		quads(
+			task::block_on(Document::<IriBuf>::to_rdf::<context::Json<Value>, _>(
+				&doc, &loader
+			))
+			.unwrap()
+		)
+	);
+}
+
+#[test]
+fn compaction_iri_memo() {
+	use json_ld::{context::Inversible, NoLoader};
+	use serde_json::json;
+
+	let context = json!({
+		"@vocab": "https://schema.org/",
+		"knows": { "@id": "https://schema.org/knows", "@type": "@id" },
+		"knownBy": { "@reverse": "https://schema.org/knows", "@type": "@id" }
+	});
+	let loader = NoLoader::<Value>::new();
+	let processed_context = task::block_on(Local::<IriBuf>::process::<context::Json<Value>, _>(
+		&context, &loader, None,
+	))
+	.unwrap()
+	.into_inner();
+	let processed_context = Inversible::new(processed_context);
+
+	// The same IRI is compacted as a node identifier, a type, a property and a reverse property.
+	let doc = json!({
+		"@id": "https://schema.org/Person",
+		"@type": "https://schema.org/Person",
+		"https://schema.org/knows": { "@id": "https://schema.org/Person" },
+		"@reverse": { "https://schema.org/knows": { "@id": "https://example.org/bob" } }
+	});
+
+	for _ in 0..2 {
+		let mut compacted = task::block_on(doc.compact(&processed_context, &loader)).unwrap();
+		compacted.as_object_mut().unwrap().remove("@context");
+		assert_eq!(
+			compacted,
+			json!({
+				"@id": "https://schema.org/Person",
+				"@type": "Person",
+				"knows": "https://schema.org/Person",
+				"knownBy": "https://example.org/bob"
+			})
+		);
+	}
+}
+
+#[test]
+fn ordered_compaction() {
+	use json_ld::{compaction, NoLoader};
+	use serde_json::json;
+
+	fn keys(value: &Value) -> Vec<&str> {
+		value
+			.as_object()
+			.unwrap()
+			.keys()
+			.map(String::as_str)
+			.collect()
+	}
+
+	let context = json!({
+		"@vocab": "https://schema.org/",
+		"knows": { "@id": "https://schema.org/knows" }
+	});
+	let doc = json!({
+		"https://schema.org/name": "Alice",
+		"https://schema.org/knows": {
+			"https://schema.org/name": "Bob",
+			"https://schema.org/knows": {
+				"https://schema.org/name": "Carol",
+				"https://schema.org/email": "carol@example.org",
+				"@id": "https://example.org/carol"
+			},
+			"https://schema.org/email": "bob@example.org"
+		},
+		"@id": "https://example.org/alice"
+	});
+
+	let options = compaction::Options {
+		ordered: true,
+		..compaction::Options::default()
+	};
+	let compacted = task::block_on(Document::<IriBuf>::compact_with_context_and_options(
+		&doc,
+		&context,
+		&NoLoader::<Value>::new(),
+		options,
+	))
+	.unwrap();
+
+	// Entries are emitted in lexicographical order at every level.
+	assert_eq!(keys(&compacted), ["@context", "@id", "knows", "name"]);
+	assert_eq!(keys(&compacted["@context"]), ["@vocab", "knows"]);
+	assert_eq!(keys(&compacted["knows"]), ["email", "knows", "name"]);
+	assert_eq!(keys(&compacted["knows"]["knows"]), ["@id", "email", "name"]);
+}
+
+#[test]
+fn sibling_remote_contexts() {
+	use json_ld::{context::ContextChain, Context, ErrorCode, MockLoader};
+	use serde_json::json;
+
+	let v1 = iri!("https://example.org/v1.jsonld");
+	let v2 = iri!("https://example.org/v2.jsonld");
+	let loader = MockLoader::new()
+		.with(
+			v1,
+			json!({ "@context": { "name": "https://schema.org/name" } }),
+		)
+		.with(
+			v2,
+			json!({ "@context": [
+				"https://example.org/v1.jsonld",
+				{ "email": "https://schema.org/email" }
+			] }),
+		);
+	let process = |local_context: Value| {
+		task::block_on(Local::<IriBuf>::process_with(
+			&local_context,
+			&context::Json::<Value>::new(None),
+			&loader,
+			None,
+			ProcessingOptions::default(),
+		))
+		.map(|processed| processed.into_inner())
+	};
+
+	// The same context repeated.
+	let processed = process(json!([v1.as_str(), v1.as_str()])).unwrap();
+	assert!(processed.get("name").is_some());
+
+	// Sibling contexts, the second one including the first.
+	let processed = process(json!([v1.as_str(), v2.as_str()])).unwrap();
+	assert!(processed.get("name").is_some());
+	assert!(processed.get("email").is_some());
+
+	// The origin of a term does not list the sibling contexts.
+	let origin = processed.get("email").unwrap().origin().unwrap();
+	assert_eq!(origin.chain().urls(), [v2]);
+
+	// A real cycle.
+	let a = iri!("https://example.org/a.jsonld");
+	let b = iri!("https://example.org/b.jsonld");
+	let loader = MockLoader::new()
+		.with(
+			a,
+			json!({ "@context": [v1.as_str(), "https://example.org/b.jsonld"] }),
+		)
+		.with(b, json!({ "@context": "https://example.org/a.jsonld" }))
+		.with(
+			v1,
+			json!({ "@context": { "name": "https://schema.org/name" } }),
+		);
+	let error = task::block_on(Local::<IriBuf>::process_with(
+		&json!(a.as_str()),
+		&context::Json::<Value>::new(None),
+		&loader,
+		None,
+		ProcessingOptions::default(),
+	))
+	.err()
+	.unwrap()
+	.unwrap();
+	assert_eq!(error.code(), ErrorCode::ContextOverflow);
+	let chain = std::error::Error::source(&error)
+		.unwrap()
+		.downcast_ref::<ContextChain>()
+		.unwrap();
+	assert_eq!(chain.urls(), [a, b, a]);
+	assert!(chain.is_cycle());
+}
+
+#[test]
+fn cyclic_iri_mapping_chain() {
+	use json_ld::{context::ContextChain, ErrorCode, MockLoader};
+	use serde_json::json;
+
+	let a = iri!("https://example.org/a.jsonld");
+	let b = iri!("https://example.org/b.jsonld");
+	let loader = MockLoader::new()
+		.with(a, json!({ "@context": "https://example.org/b.jsonld" }))
+		.with(
+			b,
+			json!({ "@context": { "term1": "term2:foo", "term2": "term1:bar" } }),
+		);
+	let error = task::block_on(Local::<IriBuf>::process_with(
+		&json!(a.as_str()),
+		&context::Json::<Value>::new(None),
+		&loader,
+		None,
+		ProcessingOptions::default(),
+	))
+	.err()
+	.unwrap()
+	.unwrap();
+	assert_eq!(error.code(), ErrorCode::CyclicIriMapping);
+	let chain = std::error::Error::source(&error)
+		.unwrap()
+		.downcast_ref::<ContextChain>()
+		.unwrap();
+	assert_eq!(chain.urls(), [a, b]);
+}
+
+#[test]
+fn remote_context_depth() {
+	use json_ld::{ErrorCode, MockLoader};
+	use serde_json::json;
+
+	let urls: Vec<IriBuf> = (0..40)
+		.map(|i| IriBuf::new(&format!("https://example.org/{}.jsonld", i)).unwrap())
+		.collect();
+	let process = |loader: &MockLoader<Value>, local_context: Value| {
+		task::block_on(Local::<IriBuf>::process_with(
+			&local_context,
+			&context::Json::<Value>::new(None),
+			loader,
+			None,
+			ProcessingOptions::default(),
+		))
+		.map(|_| ())
+		.map_err(|error| error.unwrap().code())
+	};
+
+	// Sibling contexts do not count in the depth.
+	let mut loader = MockLoader::new();
+	for (i, url) in urls.iter().enumerate() {
+		loader = loader.with(
+			url.as_iri(),
+			json!({ "@context": { format!("term{}", i): "https://schema.org/name" } }),
+		);
+	}
+	let siblings: Vec<_> = urls.iter().map(|url| json!(url.as_str())).collect();
+	assert!(process(&loader, Value::Array(siblings)).is_ok());
+
+	// Each context including the next one.
+	let mut loader = MockLoader::new();
+	for (url, next) in urls.iter().zip(&urls[1..]) {
+		loader = loader.with(url.as_iri(), json!({ "@context": next.as_str() }));
+	}
+	loader = loader.with(urls[39].as_iri(), json!({ "@context": {} }));
+	assert_eq!(
+		process(&loader, json!(urls[0].as_str())),
+		Err(ErrorCode::ContextOverflow)
+	);
+}
+
+#[test]
+fn inline_expand_context() {
+	use json_ld::{
+		expansion::{ExpandContext, JsonEvent, StreamExpander},
+		util::AsJson,
+		NoLoader,
+	};
+	use serde_json::json;
+
+	let context = context::Json::<Value>::new(None);
+	let loader = NoLoader::<Value>::new();
+	let options = expansion::Options {
+		expand_context: Some(ExpandContext::inline(
+			json!({ "name": "https://schema.org/name" }),
+		)),
+		..expansion::Options::default()
+	};
+	let expected = json!([{ "https://schema.org/name": [{ "@value": "Alice" }] }]);
+
+	let expanded = task::block_on(json!({ "name": "Alice" }).expand_with(
+		None,
+		&context,
+		&loader,
+		options.clone(),
+	))
+	.unwrap();
+	let json: Value = expanded.as_json();
+	assert_eq!(json, expected);
+
+	// The stream expander applies the context before the first event.
+	let mut expander = StreamExpander::new(&context, None, &loader, options);
+	let mut objects = Vec::new();
+	for event in [
+		JsonEvent::BeginObject(()),
+		JsonEvent::Key("name".to_string()),
+		JsonEvent::Value(json!("Alice")),
+		JsonEvent::EndObject(()),
+	] {
+		objects.extend(task::block_on(expander.push(event)).unwrap());
+	}
+	objects.extend(expander.finish().unwrap());
+	let json: Value = objects.as_json();
+	assert_eq!(json, expected);
+}
+
+#[test]
+fn stream_expansion_events() {
+	use json_ld::{
+		expansion::{JsonEvent, StreamExpander},
+		ErrorCode, NoLoader,
+	};
+	use serde_json::json;
+
+	let context = context::Json::<Value>::new(None);
+	let loader = NoLoader::<Value>::new();
+	let stream = |events: Vec<JsonEvent<Value>>| {
+		let mut expander = StreamExpander::new(&context, None, &loader, Default::default());
+		let mut objects = Vec::new();
+		for event in events {
+			objects.extend(task::block_on(expander.push(event)).map_err(|e| e.code())?);
+		}
+		objects.extend(expander.finish().map_err(|e| e.code())?);
+		let json: Value = objects.as_json();
+		Ok(json)
+	};
+
+	// A `@graph` object in a top-level array is not unwrapped.
+	let graph = json!({
+		"@graph": [{ "@id": "https://example.org/alice", "https://schema.org/name": "Alice" }]
+	});
+	let node = json!({
+		"@id": "https://example.org/alice",
+		"https://schema.org/name": [{ "@value": "Alice" }]
+	});
+	assert_eq!(
+		stream(vec![
+			JsonEvent::BeginArray(()),
+			JsonEvent::Value(graph.clone()),
+			JsonEvent::EndArray(()),
+		]),
+		Ok(json!([{ "@graph": [node] }]))
+	);
+
+	// A top-level `@graph` object is unwrapped.
+	assert_eq!(stream(vec![JsonEvent::Value(graph)]), Ok(json!([node])));
+
+	// Invalid event sequences.
+	for events in vec![
+		vec![JsonEvent::EndArray(())],
+		vec![JsonEvent::Key("@id".to_string())],
+		vec![JsonEvent::BeginArray(()), JsonEvent::EndObject(())],
+		vec![JsonEvent::BeginArray(()), JsonEvent::Key("@id".to_string())],
+		vec![
+			JsonEvent::BeginObject(()),
+			JsonEvent::Value(json!("https://example.org/alice")),
+		],
+		vec![JsonEvent::BeginObject(())],
+		vec![
+			JsonEvent::Value(json!({ "@id": "https://example.org/alice" })),
+			JsonEvent::Value(json!({ "@id": "https://example.org/bob" })),
+		],
+	] {
+		assert_eq!(stream(events), Err(ErrorCode::InvalidEventStream));
+	}
+
+	// No entry may follow a streamed `@graph` entry.
+	assert_eq!(
+		stream(vec![
+			JsonEvent::BeginObject(()),
+			JsonEvent::Key("@graph".to_string()),
+			JsonEvent::BeginArray(()),
+			JsonEvent::Value(json!({ "@id": "https://example.org/alice" })),
+			JsonEvent::EndArray(()),
+			JsonEvent::Key("@id".to_string()),
+			JsonEvent::Value(json!("https://example.org/graph")),
+			JsonEvent::EndObject(()),
+		]),
+		Err(ErrorCode::InvalidEventStream)
+	);
+}
+
+#[test]
+fn ordered_flattening() {
+	use json_ld::{
+		flattening::{self, Generator},
+		NoLoader,
+	};
+	use serde_json::json;
+
+	let doc = json!([
+		{
+			"@id": "https://example.org/g",
+			"@graph": [
+				{ "@id": "https://example.org/c", "https://schema.org/name": "C" },
+				{ "@id": "https://example.org/a", "https://schema.org/name": "A" },
+				{ "@id": "https://example.org/b", "https://schema.org/name": "B" }
+			]
+		},
+		{
+			"@id": "https://example.org/x",
+			"_:p": { "@id": "https://example.org/y" },
+			"@reverse": { "_:p": { "@id": "https://example.org/z" } }
+		}
+	]);
+
+	let loader = NoLoader::<Value>::new();
+	let expanded = task::block_on(doc.expand::<context::Json<Value>, _>(&loader)).unwrap();
+	let flattened: Value = flattening::flatten(expanded, &mut Generator::new(), true)
+		.unwrap()
+		.as_json();
+	let nodes = flattened.as_array().unwrap();
+	let node = |id: &str| nodes.iter().find(|node| node["@id"] == id).unwrap();
+
+	// The nodes of named graphs are ordered too.
+	let graph: Vec<_> = node("https://example.org/g")["@graph"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|node| node["@id"].as_str().unwrap())
+		.collect();
+	assert_eq!(
+		graph,
+		[
+			"https://example.org/a",
+			"https://example.org/b",
+			"https://example.org/c"
+		]
+	);
+
+	// Reverse properties are relabeled like forward properties.
+	let x = node("https://example.org/x").as_object().unwrap();
+	let property = x.keys().find(|key| key.starts_with("_:")).unwrap();
+	assert_ne!(property, "_:p");
+	assert_eq!(x[property][0]["@id"], "https://example.org/y");
+	assert_eq!(
+		node("https://example.org/z")[property.as_str()][0]["@id"],
+		"https://example.org/x"
+	);
+}
@@ -0,0 +1,209 @@
+extern crate async_std;
+extern crate iref;
+extern crate json_ld;
+
+use async_std::task;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::StreamExt;
+use iref::Iri;
+use json_ld::{
+	http::{Backend, Headers, Loader, RequestError, Response, Retry},
+	ErrorCode,
+};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Backend answering requests with a predefined list of statuses,
+/// and recording the delays it is asked to wait.
+///
+/// Once the list of statuses is exhausted, a JSON-LD document is returned.
+#[derive(Default)]
+struct MockBackend {
+	statuses: Mutex<VecDeque<u16>>,
+	requests: Mutex<usize>,
+	sleeps: Mutex<Vec<Duration>>,
+}
+
+impl MockBackend {
+	fn new(statuses: &[u16]) -> Self {
+		Self {
+			statuses: Mutex::new(statuses.iter().cloned().collect()),
+			..Self::default()
+		}
+	}
+
+	fn requests(&self) -> usize {
+		*self.requests.lock().unwrap()
+	}
+
+	fn sleeps(&self) -> Vec<Duration> {
+		self.sleeps.lock().unwrap().clone()
+	}
+}
+
+impl Backend for MockBackend {
+	fn get<'a>(
+		&'a self,
+		_url: Iri<'a>,
+		_headers: &'a Headers,
+	) -> BoxFuture<'a, Result<Response, RequestError>> {
+		*self.requests.lock().unwrap() += 1;
+		let status = self.statuses.lock().unwrap().pop_front().unwrap_or(200);
+		async move {
+			let mut headers = Headers::new();
+			headers.append("content-type", "application/ld+json");
+			Ok(Response {
+				status,
+				headers,
+				body: StreamExt::boxed(futures::stream::iter(vec![Ok(b"{}".to_vec())])),
+			})
+		}
+		.boxed()
+	}
+
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+		self.sleeps.lock().unwrap().push(duration);
+		futures::future::ready(()).boxed()
+	}
+}
+
+fn loader(backend: MockBackend, retry: Retry) -> Loader<MockBackend, Value> {
+	Loader::with_backend(backend, |s| serde_json::from_str::<Value>(s)).with_retry(retry)
+}
+
+#[test]
+fn retry_exponential_backoff() {
+	let loader = loader(
+		MockBackend::new(&[503, 503, 503, 503]),
+		Retry {
+			max_attempts: 4,
+			initial_backoff: Duration::from_millis(100),
+			multiplier: 2,
+			max_backoff: Duration::from_millis(250),
+			..Retry::default()
+		},
+	);
+
+	let url = Iri::new("https://example.org/doc.jsonld").unwrap();
+	let error = task::block_on(loader.load(url)).err().unwrap();
+	assert_eq!(error.code(), ErrorCode::LoadingDocumentFailed);
+	assert_eq!(loader.backend().requests(), 4);
+	assert_eq!(
+		loader.backend().sleeps(),
+		vec![
+			Duration::from_millis(100),
+			Duration::from_millis(200),
+			Duration::from_millis(250)
+		]
+	);
+}
+
+#[test]
+fn retry_until_success() {
+	let loader = loader(
+		MockBackend::new(&[500, 429]),
+		Retry {
+			max_attempts: 3,
+			..Retry::default()
+		},
+	);
+
+	let url = Iri::new("https://example.org/doc.jsonld").unwrap();
+	task::block_on(loader.load(url)).unwrap();
+	assert_eq!(loader.backend().requests(), 3);
+}
+
+#[test]
+fn retry_non_retryable_status() {
+	let loader = loader(
+		MockBackend::new(&[404]),
+		Retry {
+			max_attempts: 3,
+			..Retry::default()
+		},
+	);
+
+	let url = Iri::new("https://example.org/doc.jsonld").unwrap();
+	let error = task::block_on(loader.load(url)).err().unwrap();
+	assert_eq!(error.code(), ErrorCode::LoadingDocumentFailed);
+	assert_eq!(loader.backend().requests(), 1);
+	assert!(loader.backend().sleeps().is_empty());
+}
+
+#[test]
+fn retry_backoff_overflow() {
+	let max_backoff = Duration::from_secs(1);
+	let loader = loader(
+		MockBackend::new(&[503, 503]),
+		Retry {
+			max_attempts: 3,
+			initial_backoff: Duration::MAX / 2,
+			multiplier: 4,
+			max_backoff,
+			..Retry::default()
+		},
+	);
+
+	let url = Iri::new("https://example.org/doc.jsonld").unwrap();
+	task::block_on(loader.load(url)).unwrap();
+	assert_eq!(
+		loader.backend().sleeps(),
+		vec![Duration::MAX / 2, max_backoff]
+	);
+}
+
+/// Backend taking the given delay to send the response headers,
+/// and then the same delay to send the response body.
+struct SlowBackend(Duration);
+
+impl Backend for SlowBackend {
+	fn get<'a>(
+		&'a self,
+		_url: Iri<'a>,
+		_headers: &'a Headers,
+	) -> BoxFuture<'a, Result<Response, RequestError>> {
+		let delay = self.0;
+		async move {
+			task::sleep(delay).await;
+			let mut headers = Headers::new();
+			headers.append("content-type", "application/ld+json");
+			let body = futures::stream::once(async move {
+				task::sleep(delay).await;
+				Ok(b"{}".to_vec())
+			});
+
+			Ok(Response {
+				status: 200,
+				headers,
+				body: StreamExt::boxed(body),
+			})
+		}
+		.boxed()
+	}
+
+	fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+		task::sleep(duration).boxed()
+	}
+}
+
+#[test]
+fn timeout_includes_body() {
+	let url = Iri::new("https://example.org/doc.jsonld").unwrap();
+
+	// Sending the request and reading the body each take less than the timeout,
+	// but not both.
+	let loader = Loader::with_backend(SlowBackend(Duration::from_millis(300)), |s| {
+		serde_json::from_str::<Value>(s)
+	})
+	.with_timeout(Duration::from_millis(500));
+	let error = task::block_on(loader.load(url)).err().unwrap();
+	assert_eq!(error.code(), ErrorCode::LoadingDocumentTimeout);
+
+	let loader = Loader::with_backend(SlowBackend(Duration::from_millis(100)), |s| {
+		serde_json::from_str::<Value>(s)
+	})
+	.with_timeout(Duration::from_millis(500));
+	task::block_on(loader.load(url)).unwrap();
+}
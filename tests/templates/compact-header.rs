@@ -69,7 +69,7 @@ fn positive_test(options: Options, input_url: Iri, base_url: Iri, output_url: Ir
 			.into_context();
 		input_context = task::block_on(local_context.process_with(
 			input_context.as_ref(),
-			&mut loader,
+			&loader,
 			Some(base_url),
 			options.into(),
 		))
@@ -80,7 +80,7 @@ fn positive_test(options: Options, input_url: Iri, base_url: Iri, output_url: Ir
 	let output: Value = task::block_on(input.compact_with(
 		Some(base_url),
 		&input_context,
-		&mut loader,
+		&loader,
 		options.into(),
 		no_metadata,
 		no_metadata,
@@ -117,7 +117,7 @@ fn negative_test(options: Options, input_url: Iri, base_url: Iri, error_code: Er
 			.into_context();
 		input_context = match task::block_on(local_context.process_with(
 			input_context.as_ref(),
-			&mut loader,
+			&loader,
 			Some(base_url),
 			options.into(),
 		)) {{
@@ -132,7 +132,7 @@ fn negative_test(options: Options, input_url: Iri, base_url: Iri, error_code: Er
 	let result: Result<Value, _> = task::block_on(input.compact_with(
 		Some(base_url),
 		&input_context,
-		&mut loader,
+		&loader,
 		options.into(),
 		no_metadata,
 		no_metadata,
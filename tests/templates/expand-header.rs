@@ -55,7 +55,7 @@ fn positive_test(options: Options, input_url: Iri, base_url: Iri, output_url: Ir
 			.into_context();
 		input_context = task::block_on(local_context.process_with(
 			&input_context,
-			&mut loader,
+			&loader,
 			Some(base_url),
 			options.into(),
 		))
@@ -66,7 +66,7 @@ fn positive_test(options: Options, input_url: Iri, base_url: Iri, output_url: Ir
 	let output = task::block_on(input.expand_with(
 		Some(base_url),
 		&input_context,
-		&mut loader,
+		&loader,
 		options.into(),
 	))
 	.unwrap();
@@ -101,7 +101,7 @@ fn negative_test(options: Options, input_url: Iri, base_url: Iri, error_code: Er
 			.into_context();
 		input_context = task::block_on(local_context.process_with(
 			&input_context,
-			&mut loader,
+			&loader,
 			Some(base_url),
 			options.into(),
 		))
@@ -112,7 +112,7 @@ fn negative_test(options: Options, input_url: Iri, base_url: Iri, error_code: Er
 	let result = task::block_on(input.expand_with(
 		Some(base_url),
 		&input_context,
-		&mut loader,
+		&loader,
 		options.into(),
 	));
 